@@ -15,6 +15,7 @@
 from __future__ import annotations
 
 import asyncio
+import base64
 import sys
 from typing import Any, TypeVar
 from uuid import uuid4
@@ -22,6 +23,8 @@ from uuid import uuid4
 from stepflow_py.generated_flow import Flow
 from stepflow_py.generated_protocol import (
     BlobType,
+    Capabilities,
+    ComponentBatchItem,
     EvaluateFlowParams,
     EvaluateFlowResult,
     FlowResultFailed,
@@ -30,12 +33,16 @@ from stepflow_py.generated_protocol import (
     GetBlobResult,
     GetFlowMetadataParams,
     GetFlowMetadataResult,
+    LogLevel,
+    LogMessage,
     Message,
     Method,
     MethodError,
     MethodSuccess,
+    ProgressMessage,
     PutBlobParams,
     PutBlobResult,
+    RequestId,
 )
 from stepflow_py.message_decoder import MessageDecoder
 
@@ -45,6 +52,48 @@ Context API for stepflow components to interact with the runtime.
 
 T = TypeVar("T")
 
+# Maximum number of bytes moved per request when transferring binary blobs.
+BINARY_CHUNK_SIZE = 1024 * 1024
+
+
+BLOB_REF_KEY = "$blob"
+"""Key identifying a blob reference (``{"$blob": "<blob_id>"}``) in step inputs.
+
+Values that merely look like blob references are escaped by the runtime with an
+additional ``$`` (``{"$$blob": ...}``), which is removed when resolving.
+"""
+
+
+def contains_blob_refs(value: Any) -> bool:
+    """Check whether a JSON value contains blob references or escaped values."""
+    if isinstance(value, dict):
+        if _blob_ref_key_escapes(value) is not None:
+            return True
+        return any(contains_blob_refs(v) for v in value.values())
+    if isinstance(value, list):
+        return any(contains_blob_refs(v) for v in value)
+    return False
+
+
+def _blob_ref_key_escapes(value: dict) -> int | None:
+    """Return the number of ``$`` prefixing the key of a blob reference shape."""
+    if len(value) != 1:
+        return None
+    key = next(iter(value))
+    if not isinstance(key, str) or not key.endswith("blob"):
+        return None
+    prefix = key[: -len("blob")]
+    if not prefix or prefix.strip("$"):
+        return None
+    return len(prefix)
+
+
+def _blob_ref_id(value: dict) -> str | None:
+    if _blob_ref_key_escapes(value) != 1:
+        return None
+    blob_id = value.get(BLOB_REF_KEY)
+    return blob_id if isinstance(blob_id, str) else None
+
 
 class StepflowContext:
     """Context for stepflow components to make calls back to the runtime.
@@ -61,6 +110,8 @@ class StepflowContext:
         step_id: str | None = None,
         run_id: str | None = None,
         flow_id: str | None = None,
+        request_id: RequestId | None = None,
+        capabilities: Capabilities | None = None,
     ):
         self._outgoing_queue = outgoing_queue
         self._message_decoder = message_decoder
@@ -68,6 +119,25 @@ class StepflowContext:
         self._step_id = step_id
         self._run_id = run_id
         self._flow_id = flow_id
+        self._request_id = request_id
+        self._capabilities = capabilities or Capabilities()
+
+    def for_item(self, item: ComponentBatchItem) -> StepflowContext:
+        """Create a context for an execution within a batch.
+
+        The context shares this context's connection to the runtime, while log
+        messages and progress are attributed to the step of the item.
+        """
+        return StepflowContext(
+            self._outgoing_queue,
+            self._message_decoder,
+            session_id=self._session_id,
+            step_id=item.step_id,
+            run_id=item.run_id,
+            flow_id=item.flow_id,
+            request_id=item.request_id,
+            capabilities=self._capabilities,
+        )
 
     async def _send_request(
         self, method: Method, params: Any, result_type: type[T]
@@ -119,7 +189,7 @@ class StepflowContext:
         Returns:
             The blob ID (SHA-256 hash) for the stored data
         """
-        params = PutBlobParams(data=data, blob_type=blob_type)
+        params = PutBlobParams(data=data, blob_type=blob_type, run_id=self._run_id)
         response = await self._send_request(Method.blobs_put, params, PutBlobResult)
         return response.blob_id
 
@@ -136,6 +206,105 @@ class StepflowContext:
         response = await self._send_request(Method.blobs_get, params, GetBlobResult)
         return response.data
 
+    async def resolve_blob_refs(self, value: Any) -> Any:
+        """Replace blob references within a JSON value by the blob content.
+
+        Large step results may be passed between steps as blob references
+        (``{"$blob": "<blob_id>"}``). Each referenced blob is fetched once, and
+        escaped values that look like blob references are unescaped.
+
+        Args:
+            value: The JSON value to resolve
+
+        Returns:
+            The value with all blob references replaced
+        """
+        cache: dict[str, Any] = {}
+
+        async def resolve(item: Any) -> Any:
+            if isinstance(item, dict):
+                blob_id = _blob_ref_id(item)
+                if blob_id is not None:
+                    if blob_id not in cache:
+                        # The content may itself hold escaped values.
+                        cache[blob_id] = await resolve(await self.get_blob(blob_id))
+                    return cache[blob_id]
+                if (_blob_ref_key_escapes(item) or 0) > 1:
+                    ((key, inner),) = item.items()
+                    return {key[1:]: await resolve(inner)}
+                return {k: await resolve(v) for k, v in item.items()}
+            if isinstance(item, list):
+                return [await resolve(v) for v in item]
+            return item
+
+        return await resolve(value)
+
+    async def put_binary_blob(
+        self,
+        content: bytes,
+        content_type: str = "application/octet-stream",
+        chunk_size: int = BINARY_CHUNK_SIZE,
+    ) -> str:
+        """Store binary content as a blob and return its content-based ID.
+
+        Content larger than `chunk_size` is uploaded in several requests, each
+        carrying one chunk, so no single message holds the entire payload. The
+        chunks are only held by the runtime until the final request.
+
+        Args:
+            content: The raw bytes to store
+            content_type: The MIME type of the content
+            chunk_size: Maximum number of bytes sent per request
+
+        Returns:
+            The blob ID (SHA-256 hash of the content type and raw bytes)
+        """
+        chunk_ids: list[str] = []
+        offset = 0
+        while len(content) - offset > chunk_size:
+            chunk = content[offset : offset + chunk_size]
+            params = PutBlobParams(data=chunk, blob_type=BlobType.binary, partial=True)
+            response = await self._send_request(
+                Method.blobs_put, params, PutBlobResult
+            )
+            chunk_ids.append(response.blob_id)
+            offset += chunk_size
+
+        # Bytes are base64-encoded in JSON messages, and raw in MessagePack.
+        params = PutBlobParams(
+            data=content[offset:],
+            blob_type=BlobType.binary,
+            content_type=content_type,
+            chunks=chunk_ids or None,
+            run_id=self._run_id,
+        )
+        response = await self._send_request(Method.blobs_put, params, PutBlobResult)
+        return response.blob_id
+
+    async def get_binary_blob(
+        self, blob_id: str, chunk_size: int = BINARY_CHUNK_SIZE
+    ) -> tuple[bytes, str]:
+        """Retrieve binary content by blob ID, reading it in chunks.
+
+        Args:
+            blob_id: The blob ID to retrieve
+            chunk_size: Maximum number of bytes requested at a time
+
+        Returns:
+            The raw bytes and content type of the blob
+        """
+        content = bytearray()
+        while True:
+            params = {"blob_id": blob_id, "offset": len(content), "length": chunk_size}
+            response = await self._send_request(
+                Method.blobs_get, params, GetBlobResult
+            )
+            data = response.data
+            content.extend(data if isinstance(data, bytes) else base64.b64decode(data))
+            if response.size is None or len(content) >= response.size:
+                break
+        return bytes(content), response.content_type or "application/octet-stream"
+
     @property
     def session_id(self) -> str | None:
         """Get the session ID for HTTP mode, or None for STDIO mode."""
@@ -202,6 +371,7 @@ class StepflowContext:
         params = EvaluateFlowParams(
             flow_id=flow_id,
             input=input,
+            request_id=self._request_id,
         )
         evaluate_result = await self._send_request(
             Method.flows_evaluate, params, EvaluateFlowResult
@@ -255,6 +425,60 @@ class StepflowContext:
 
         return result
 
-    def log(self, message):
-        """Log a message."""
+    def log(self, message: str, level: LogLevel = LogLevel.info, **fields: Any):
+        """Log a message.
+
+        The message is written to stderr. If the runtime supports the ``logging``
+        capability, it is also sent to the runtime, which attributes it to the
+        step being executed.
+
+        Args:
+            message: The message to log
+            level: The severity of the message
+            **fields: Structured fields to attach to the message
+        """
         print(f"PYTHON: {message}", file=sys.stderr)
+        if self._capabilities.logging:
+            params = LogMessage(
+                level=level,
+                message=message,
+                fields=fields,
+                request_id=self._request_id,
+            )
+            self._outgoing_queue.put_nowait(
+                {"jsonrpc": "2.0", "method": Method.log.value, "params": params}
+            )
+
+    def report_progress(
+        self,
+        fraction: float | None = None,
+        message: str | None = None,
+        completed: int | None = None,
+        total: int | None = None,
+    ):
+        """Report the progress of the step being executed.
+
+        Progress is sent to the runtime if it supports the ``progress``
+        capability, and replaces any progress previously reported for the step.
+        It is ignored outside of a component execution.
+
+        Args:
+            fraction: Fraction of the work completed, from 0 to 1, if known
+            message: Description of the work being done
+            completed: Number of items completed, if the work is counted in items
+            total: Total number of items, if known
+        """
+        if not self._capabilities.progress or self._request_id is None:
+            return
+        if fraction is None and completed is not None and total:
+            fraction = min(completed / total, 1.0)
+        params = ProgressMessage(
+            fraction=fraction,
+            message=message,
+            completed=completed,
+            total=total,
+            request_id=self._request_id,
+        )
+        self._outgoing_queue.put_nowait(
+            {"jsonrpc": "2.0", "method": Method.progress.value, "params": params}
+        )
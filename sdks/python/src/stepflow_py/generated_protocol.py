@@ -21,7 +21,7 @@ from __future__ import annotations
 from enum import Enum
 from typing import Annotated, Any, ClassVar, Dict, List, Literal
 
-from msgspec import Meta, Struct
+from msgspec import Meta, Struct, field
 
 JsonRpc = Annotated[
     Literal['2.0'], Meta(description='The version of the JSON-RPC protocol.')
@@ -42,10 +42,41 @@ class Method(Enum):
     components_list = 'components/list'
     components_info = 'components/info'
     components_execute = 'components/execute'
+    components_execute_batch = 'components/execute_batch'
     blobs_put = 'blobs/put'
     blobs_get = 'blobs/get'
     flows_evaluate = 'flows/evaluate'
     flows_get_metadata = 'flows/get_metadata'
+    log = 'log'
+    progress = 'progress'
+
+
+class Capabilities(Struct, kw_only=True):
+    streaming: Annotated[
+        bool, Meta(description='Components may stream partial results.')
+    ] = False
+    cancellation: Annotated[
+        bool, Meta(description='Requests in flight may be cancelled.')
+    ] = False
+    batching: Annotated[
+        bool, Meta(description='Several executions may be sent in a single request.')
+    ] = False
+    binary_blobs: Annotated[
+        bool, Meta(description='Binary blobs may be stored and retrieved.')
+    ] = False
+    progress: Annotated[
+        bool, Meta(description='Components may report the progress of an execution.')
+    ] = False
+    logging: Annotated[
+        bool,
+        Meta(description='Component servers may forward log records to the runtime.'),
+    ] = False
+
+
+class Framing(Enum):
+    json = 'json'
+    msgpack = 'msgpack'
+    cbor = 'cbor'
 
 
 class InitializeParams(Struct, kw_only=True):
@@ -56,6 +87,16 @@ class InitializeParams(Struct, kw_only=True):
             ge=0,
         ),
     ]
+    runtime_capabilities: Annotated[
+        Capabilities,
+        Meta(description='Optional features supported by the Stepflow runtime.'),
+    ] = field(default_factory=Capabilities)
+    framings: Annotated[
+        List[Framing],
+        Meta(
+            description='Binary framings the component server may switch to after initialization,\nin order of preference. Only offered over stdio.'
+        ),
+    ] = field(default_factory=list)
 
 
 Component = Annotated[
@@ -95,11 +136,32 @@ class ComponentListParams(Struct, kw_only=True):
 
 class GetBlobParams(Struct, kw_only=True):
     blob_id: Annotated[BlobId, Meta(description='The ID of the blob to retrieve.')]
+    offset: (
+        Annotated[
+            int | None,
+            Meta(
+                description='Byte offset to start reading from (binary blobs only).',
+                ge=0,
+            ),
+        ]
+        | None
+    ) = None
+    length: (
+        Annotated[
+            int | None,
+            Meta(
+                description='Maximum number of bytes to return (binary blobs only).\n\nIf not provided, the content from `offset` to the end of the blob is returned.',
+                ge=0,
+            ),
+        ]
+        | None
+    ) = None
 
 
 class BlobType(Enum):
     flow = 'flow'
     data = 'data'
+    binary = 'binary'
 
 
 class EvaluateFlowParams(Struct, kw_only=True):
@@ -108,6 +170,15 @@ class EvaluateFlowParams(Struct, kw_only=True):
         Meta(description='The ID of the flow to evaluate (blob ID of the flow).'),
     ]
     input: Annotated[Value, Meta(description='The input to provide to the flow.')]
+    request_id: (
+        Annotated[
+            RequestId | None,
+            Meta(
+                description="The ID of the `components/execute` request evaluating the flow, if any.\n\nWhile that request is handled, the flow is recorded as a run nested within its step's run."
+            ),
+        ]
+        | None
+    ) = None
 
 
 class GetFlowMetadataParams(Struct, kw_only=True):
@@ -131,6 +202,19 @@ class InitializeResult(Struct, kw_only=True):
             ge=0,
         ),
     ]
+    server_capabilities: Annotated[
+        Capabilities,
+        Meta(description='Optional features supported by the component server.'),
+    ] = field(default_factory=Capabilities)
+    framing: (
+        Annotated[
+            Framing | None,
+            Meta(
+                description='Framing chosen from those offered by the Stepflow runtime. Both sides use it\nfor every message after this response. Omitted to keep using JSON.'
+            ),
+        ]
+        | None
+    ) = None
 
 
 class ComponentExecuteResult(Struct, kw_only=True):
@@ -142,8 +226,27 @@ class Schema(Struct, kw_only=True):
 
 
 class GetBlobResult(Struct, kw_only=True):
-    data: Value
+    data: Annotated[
+        Value,
+        Meta(
+            description='The blob content. Binary content is a base64-encoded string of the requested range,\nor raw bytes with a binary framing.'
+        ),
+    ]
     blob_type: BlobType
+    content_type: (
+        Annotated[str | None, Meta(description='The MIME type of a binary blob.')]
+        | None
+    ) = None
+    size: (
+        Annotated[
+            int | None,
+            Meta(
+                description='Total size of a binary blob in bytes, independent of the requested range.',
+                ge=0,
+            ),
+        ]
+        | None
+    ) = None
 
 
 class PutBlobResult(Struct, kw_only=True):
@@ -214,17 +317,134 @@ class Initialized(Struct, kw_only=True):
     pass
 
 
+class LogLevel(Enum):
+    trace = 'trace'
+    debug = 'debug'
+    info = 'info'
+    warn = 'warn'
+    error = 'error'
+
+
+class LogMessage(Struct, kw_only=True):
+    level: Annotated[LogLevel, Meta(description='The severity of the message.')]
+    message: Annotated[str, Meta(description='The message.')]
+    fields: Annotated[
+        Dict[str, Any],
+        Meta(description='Structured fields attached to the message.'),
+    ] = field(default_factory=dict)
+    request_id: Annotated[
+        RequestId | None,
+        Meta(
+            description='The ID of the request being handled when the message was logged, if any.'
+        ),
+    ] = None
+
+
+class TraceContext(Struct, kw_only=True):
+    traceparent: Annotated[
+        str,
+        Meta(
+            description='The `traceparent` value, e.g. `00-<trace-id>-<parent-id>-01`.'
+        ),
+    ]
+    tracestate: (
+        Annotated[
+            str | None,
+            Meta(
+                description='The `tracestate` value carrying vendor-specific trace data.'
+            ),
+        ]
+        | None
+    ) = None
+
+
 class ComponentExecuteParams(Struct, kw_only=True):
     component: Annotated[Component, Meta(description='The component to execute.')]
     input: Annotated[Value, Meta(description='The input to the component.')]
     step_id: Annotated[str, Meta(description='The ID of the step being executed.')]
     run_id: Annotated[str, Meta(description='The ID of the workflow run.')]
     flow_id: Annotated[BlobId, Meta(description='The ID of the flow being executed.')]
+    trace_context: (
+        Annotated[
+            TraceContext | None,
+            Meta(
+                description='Trace context of the step being executed, if tracing is enabled.\n\nComponent servers may use this to record their spans as children of the step.'
+            ),
+        ]
+        | None
+    ) = None
+
+
+class ComponentBatchItem(Struct, kw_only=True):
+    request_id: Annotated[
+        RequestId,
+        Meta(
+            description='Identifies this execution in `log` and `progress` notifications, in\nplace of the ID of the batch request.'
+        ),
+    ]
+    input: Annotated[Value, Meta(description='The input to the component.')]
+    step_id: Annotated[str, Meta(description='The ID of the step being executed.')]
+    run_id: Annotated[str, Meta(description='The ID of the workflow run.')]
+    flow_id: Annotated[BlobId, Meta(description='The ID of the flow being executed.')]
+    trace_context: (
+        Annotated[
+            TraceContext | None,
+            Meta(
+                description='Trace context of the step being executed, if tracing is enabled.'
+            ),
+        ]
+        | None
+    ) = None
+
+
+class ComponentExecuteBatchParams(Struct, kw_only=True):
+    component: Annotated[Component, Meta(description='The component to execute.')]
+    items: Annotated[
+        List[ComponentBatchItem], Meta(description='The executions in the batch.')
+    ]
 
 
 class PutBlobParams(Struct, kw_only=True):
-    data: Value
+    data: Annotated[
+        Value,
+        Meta(
+            description='The blob content. Binary content is a base64-encoded string, or raw bytes with a\nbinary framing.'
+        ),
+    ]
     blob_type: BlobType
+    content_type: (
+        Annotated[
+            str | None,
+            Meta(
+                description='The MIME type of a binary blob. Defaults to `application/octet-stream`.'
+            ),
+        ]
+        | None
+    ) = None
+    chunks: (
+        Annotated[
+            List[BlobId] | None,
+            Meta(
+                description='Previously uploaded chunks to prepend to `data` (binary blobs only).'
+            ),
+        ]
+        | None
+    ) = None
+    partial: Annotated[
+        bool,
+        Meta(
+            description='Upload a chunk of a binary blob rather than storing a blob.\n\nThe returned ID may only be listed in the `chunks` of a later request.'
+        ),
+    ] = False
+    run_id: (
+        Annotated[
+            str | None,
+            Meta(
+                description="The ID of the run storing the blob, if any.\n\nWhen provided, the blob is readable through the API in the run's namespace."
+            ),
+        ]
+        | None
+    ) = None
 
 
 class ComponentInfo(Struct, kw_only=True):
@@ -267,6 +487,15 @@ FlowResult = Annotated[
 ]
 
 
+class ComponentExecuteBatchResult(Struct, kw_only=True):
+    results: Annotated[
+        List[FlowResult],
+        Meta(
+            description='The result of each execution, in the order of the items in the request.'
+        ),
+    ]
+
+
 class MethodError(Struct, kw_only=True):
     id: RequestId
     error: Annotated[
@@ -275,10 +504,33 @@ class MethodError(Struct, kw_only=True):
     jsonrpc: JsonRpc | None = '2.0'
 
 
+class ProgressMessage(Struct, kw_only=True):
+    request_id: Annotated[
+        RequestId,
+        Meta(
+            description='The ID of the `components/execute` request whose progress is reported.'
+        ),
+    ]
+    fraction: Annotated[
+        float | None,
+        Meta(description='Fraction of the work completed, from 0 to 1, if known.'),
+    ] = None
+    message: Annotated[
+        str | None, Meta(description='Description of the work being done.')
+    ] = None
+    completed: Annotated[
+        int | None,
+        Meta(description='Number of items completed, if the work is counted in items.'),
+    ] = None
+    total: Annotated[int | None, Meta(description='Total number of items, if known.')] = (
+        None
+    )
+
+
 class Notification(Struct, kw_only=True):
     method: Annotated[Method, Meta(description='The notification method being called.')]
     params: Annotated[
-        Initialized,
+        Initialized | LogMessage | ProgressMessage,
         Meta(
             description='The parameters for the notification.',
             title='NotificationParams',
@@ -293,6 +545,7 @@ class MethodRequest(Struct, kw_only=True):
     params: Annotated[
         InitializeParams
         | ComponentExecuteParams
+        | ComponentExecuteBatchParams
         | ComponentInfoParams
         | ComponentListParams
         | GetBlobParams
@@ -322,6 +575,7 @@ class MethodSuccess(Struct, kw_only=True):
     result: Annotated[
         InitializeResult
         | ComponentExecuteResult
+        | ComponentExecuteBatchResult
         | ComponentInfoResult
         | ListComponentsResult
         | GetBlobResult
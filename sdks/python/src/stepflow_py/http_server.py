@@ -266,6 +266,8 @@ class StepflowHttpServer:
                     step_id=step_id,
                     run_id=run_id,
                     flow_id=flow_id,
+                    request_id=request.id,
+                    capabilities=self.server.capabilities,
                 )
                 return StreamingResponse(
                     self._execute_with_streaming_context(
@@ -15,16 +15,19 @@
 """Message reading and decoding for the Stepflow Python SDK.
 
 This module handles the two-stage deserialization of JSON-RPC messages,
-using RawMessage as an implementation detail for efficient parsing.
+using RawMessage as an implementation detail for efficient parsing. Messages
+are JSON, or MessagePack once that framing has been negotiated.
 """
 
-from typing import Generic, TypeVar
+from typing import Any, Generic, TypeVar
 
 import msgspec
 from msgspec import Raw, Struct
 
 from .exceptions import StepflowProtocolError
 from .generated_protocol import (
+    ComponentExecuteBatchParams,
+    ComponentExecuteBatchResult,
     ComponentExecuteParams,
     ComponentExecuteResult,
     ComponentInfoParams,
@@ -32,6 +35,7 @@ from .generated_protocol import (
     ComponentListParams,
     Error,
     EvaluateFlowParams,
+    Framing,
     EvaluateFlowResult,
     GetBlobParams,
     GetBlobResult,
@@ -42,12 +46,14 @@ from .generated_protocol import (
     InitializeResult,
     JsonRpc,
     ListComponentsResult,
+    LogMessage,
     Message,
     Method,
     MethodError,
     MethodRequest,
     MethodSuccess,
     Notification,
+    ProgressMessage,
     PutBlobParams,
     PutBlobResult,
     RequestId,
@@ -82,6 +88,23 @@ class MessageDecoder(Generic[T]):
 
     def __init__(self):
         self._pending_requests: dict[RequestId, tuple[type, T]] = {}
+        self._framing = Framing.json
+
+    @property
+    def framing(self) -> Framing:
+        """The framing of the messages being decoded."""
+        return self._framing
+
+    @framing.setter
+    def framing(self, framing: Framing) -> None:
+        if framing not in (Framing.json, Framing.msgpack):
+            raise StepflowProtocolError(f"Unsupported framing: {framing.value}")
+        self._framing = framing
+
+    def _decode(self, data: bytes | Raw, type: type) -> Any:
+        if self._framing == Framing.msgpack:
+            return msgspec.msgpack.decode(data, type=type)
+        return msgspec.json.decode(data, type=type)
 
     def register_request(
         self, request_id: RequestId, response_type: type, context: T
@@ -112,7 +135,7 @@ class MessageDecoder(Generic[T]):
         """Decode JSON-RPC message bytes into a properly typed Message.
 
         Args:
-            message_bytes: Raw JSON bytes of the message
+            message_bytes: Raw bytes of the message, encoded according to the framing
 
         Returns:
             A tuple of (properly typed Message, associated context from pending request
@@ -124,7 +147,7 @@ class MessageDecoder(Generic[T]):
         """
         try:
             # First decode as raw message to determine type
-            raw_message = msgspec.json.decode(message_bytes, type=_RawMessage)
+            raw_message = self._decode(message_bytes, type=_RawMessage)
 
             # Convert to proper typed message
             return self._convert_raw_to_typed(raw_message)
@@ -143,7 +166,9 @@ class MessageDecoder(Generic[T]):
                 raise StepflowProtocolError("Notification missing 'method' field")
             if raw_message.params is msgspec.UNSET:
                 raise StepflowProtocolError("Notification missing 'params' field")
-            params = _decode_params_for_method(raw_message.method, raw_message.params)
+            params = self._decode(
+                raw_message.params, type=_get_params_type_for_method(raw_message.method)
+            )
             message = Notification(
                 jsonrpc=raw_message.jsonrpc,
                 method=raw_message.method,
@@ -154,7 +179,9 @@ class MessageDecoder(Generic[T]):
             # This has an ID and method, so it is a method request.
             if raw_message.params is msgspec.UNSET:
                 raise StepflowProtocolError("Method request missing 'params' field")
-            params = _decode_params_for_method(raw_message.method, raw_message.params)
+            params = self._decode(
+                raw_message.params, type=_get_params_type_for_method(raw_message.method)
+            )
             message = MethodRequest(
                 jsonrpc=raw_message.jsonrpc,
                 id=raw_message.id,
@@ -180,7 +207,7 @@ class MessageDecoder(Generic[T]):
             message = MethodSuccess(
                 jsonrpc=raw_message.jsonrpc,
                 id=raw_message.id,
-                result=msgspec.json.decode(raw_message.result, type=result_type),
+                result=self._decode(raw_message.result, type=result_type),
             )
             return (message, context)
 
@@ -205,26 +232,32 @@ class MessageDecoder(Generic[T]):
             )
 
 
-def _decode_params_for_method(method: Method, params_raw: Raw):
-    """Decode parameters based on the method type."""
+def _get_params_type_for_method(method: Method) -> type:
+    """Get the type of the parameters of a given method."""
     if method == Method.initialize:
-        return msgspec.json.decode(params_raw, type=InitializeParams)
+        return InitializeParams
     elif method == Method.initialized:
-        return msgspec.json.decode(params_raw, type=Initialized)
+        return Initialized
     elif method == Method.components_list:
-        return msgspec.json.decode(params_raw, type=ComponentListParams)
+        return ComponentListParams
     elif method == Method.components_info:
-        return msgspec.json.decode(params_raw, type=ComponentInfoParams)
+        return ComponentInfoParams
     elif method == Method.components_execute:
-        return msgspec.json.decode(params_raw, type=ComponentExecuteParams)
+        return ComponentExecuteParams
+    elif method == Method.components_execute_batch:
+        return ComponentExecuteBatchParams
     elif method == Method.blobs_get:
-        return msgspec.json.decode(params_raw, type=GetBlobParams)
+        return GetBlobParams
     elif method == Method.blobs_put:
-        return msgspec.json.decode(params_raw, type=PutBlobParams)
+        return PutBlobParams
     elif method == Method.flows_evaluate:
-        return msgspec.json.decode(params_raw, type=EvaluateFlowParams)
+        return EvaluateFlowParams
     elif method == Method.flows_get_metadata:
-        return msgspec.json.decode(params_raw, type=GetFlowMetadataParams)
+        return GetFlowMetadataParams
+    elif method == Method.log:
+        return LogMessage
+    elif method == Method.progress:
+        return ProgressMessage
     else:
         raise StepflowProtocolError(f"Unknown method: {method.value}")
 
@@ -239,6 +272,8 @@ def _get_result_type_for_method(method: Method) -> type:
         return ComponentInfoResult
     elif method == Method.components_execute:
         return ComponentExecuteResult
+    elif method == Method.components_execute_batch:
+        return ComponentExecuteBatchResult
     elif method == Method.blobs_get:
         return GetBlobResult
     elif method == Method.blobs_put:
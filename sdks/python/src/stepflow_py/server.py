@@ -14,17 +14,18 @@
 
 from __future__ import annotations
 
+import asyncio
 import inspect
 import sys
 import traceback
 from collections.abc import Callable
 from dataclasses import dataclass
 from functools import wraps
-from typing import Any, assert_never
+from typing import Any, assert_never, get_args
 
 import msgspec
 
-from stepflow_py.context import StepflowContext
+from stepflow_py.context import StepflowContext, contains_blob_refs
 from stepflow_py.exceptions import (
     ComponentNotFoundError,
     SkipStep,
@@ -33,6 +34,10 @@ from stepflow_py.exceptions import (
     StepflowProtocolError,
 )
 from stepflow_py.generated_protocol import (
+    Capabilities,
+    ComponentBatchItem,
+    ComponentExecuteBatchParams,
+    ComponentExecuteBatchResult,
     ComponentExecuteParams,
     ComponentExecuteResult,
     ComponentInfo,
@@ -40,7 +45,13 @@ from stepflow_py.generated_protocol import (
     ComponentInfoResult,
     ComponentListParams,
     Error,
+    FlowError,
+    FlowResult,
+    FlowResultFailed,
     FlowResultSkipped,
+    FlowResultSuccess,
+    Framing,
+    InitializeParams,
     InitializeResult,
     ListComponentsResult,
     Message,
@@ -52,6 +63,7 @@ from stepflow_py.generated_protocol import (
     Notification,
     RequestId,
 )
+from stepflow_py.tracing import component_span
 from stepflow_py.udf import udf
 
 # Check if LangChain is available
@@ -70,6 +82,8 @@ class ComponentEntry:
     input_type: type
     output_type: type
     description: str | None = None
+    # Whether the function takes a list of inputs and returns a list of outputs.
+    batch: bool = False
 
     def input_schema(self):
         return msgspec.json.schema(self.input_type)
@@ -78,6 +92,11 @@ class ComponentEntry:
         return msgspec.json.schema(self.output_type)
 
 
+def _expects_context(component: ComponentEntry) -> bool:
+    """Check whether a component function takes a context parameter."""
+    return getattr(component.function, "_expects_context", False)
+
+
 def _handle_exception(e: Exception, id: RequestId) -> MethodError:
     """Convert any exception to a proper JSON-RPC error response."""
     if not isinstance(e, StepflowError):
@@ -93,12 +112,53 @@ def _handle_exception(e: Exception, id: RequestId) -> MethodError:
     return MethodError(id=id, error=error_obj)
 
 
+def _failed_result(e: Exception) -> FlowResultFailed:
+    """Convert an exception raised by a component to a failed result."""
+    if not isinstance(e, StepflowError):
+        e = StepflowExecutionError(f"Component execution failed: {str(e)}")
+
+    error_dict = e.to_json_rpc_error()
+    return FlowResultFailed(
+        error=FlowError(
+            code=error_dict["code"],
+            message=error_dict["message"],
+            data=error_dict.get("data"),
+        )
+    )
+
+
+def _item_params(
+    params: ComponentExecuteBatchParams, item: ComponentBatchItem
+) -> ComponentExecuteParams:
+    """The parameters of executing a single item of a batch."""
+    return ComponentExecuteParams(
+        component=params.component,
+        input=item.input,
+        step_id=item.step_id,
+        run_id=item.run_id,
+        flow_id=item.flow_id,
+        trace_context=item.trace_context,
+    )
+
+
+# Version of the protocol spoken by this SDK.
+PROTOCOL_VERSION = 1
+
+# Optional protocol features supported by this SDK.
+SERVER_CAPABILITIES = Capabilities(binary_blobs=True, progress=True, logging=True)
+
+# Binary framings this SDK can switch to when offered by the runtime.
+SUPPORTED_FRAMINGS = (Framing.msgpack,)
+
+
 class StepflowServer:
     """Core Stepflow server with component registry and business logic."""
 
     def __init__(self, include_builtins: bool = True):
         self._components: dict[str, ComponentEntry] = {}
         self._initialized = False
+        self._capabilities = Capabilities()
+        self._framing = Framing.json
 
         # Add LangChain registry functionality if available
         if _HAS_LANGCHAIN:
@@ -116,12 +176,38 @@ class StepflowServer:
         """Set the initialization state."""
         self._initialized = initialized
 
+    def server_capabilities(self) -> Capabilities:
+        """Optional protocol features supported by this server.
+
+        Batching is only declared if a component processes batches, since the
+        runtime delays executions to collect them into batches.
+        """
+        batching = any(component.batch for component in self._components.values())
+        return msgspec.structs.replace(SERVER_CAPABILITIES, batching=batching)
+
+    @property
+    def capabilities(self) -> Capabilities:
+        """Optional protocol features supported by both this server and the runtime.
+
+        A feature should only be used if it is enabled here.
+        """
+        return self._capabilities
+
+    @property
+    def framing(self) -> Framing:
+        """Framing of the messages exchanged after initialization.
+
+        The runtime only offers binary framings over stdio.
+        """
+        return self._framing
+
     def component(
         self,
         func: Callable | None = None,
         *,
         name: str | None = None,
         description: str | None = None,
+        batch: bool = False,
     ):
         """Decorator to register a component function.
 
@@ -131,6 +217,9 @@ class StepflowServer:
                 name
             description: Optional description. If not provided, uses the function's
                 docstring
+            batch: Whether the function takes a list of inputs and returns a list
+                with the output for each, so concurrent executions sent by the
+                runtime in a batch are processed together
         """
 
         def decorator(f: Callable) -> Callable:
@@ -152,6 +241,10 @@ class StepflowServer:
                 input_type = params[0][1].annotation
 
             return_type = sig.return_annotation
+            if batch:
+                # Batch components take and return lists of the component types
+                (input_type,) = get_args(input_type)
+                (return_type,) = get_args(return_type)
 
             # Extract description from parameter or docstring
             component_description = description or (
@@ -164,6 +257,7 @@ class StepflowServer:
                 input_type=input_type,
                 output_type=return_type,
                 description=component_description,
+                batch=batch,
             )
 
             # Store whether function expects context
@@ -216,15 +310,20 @@ class StepflowServer:
                         # Component not found - doesn't require context (errors later)
                         return False
 
+                    # Blob references in the input are resolved through the context
+                    if contains_blob_refs(message.params.input):
+                        return True
+
                     # Check if component function expects context parameter
-                    return (
-                        hasattr(component.function, "_expects_context")
-                        and component.function._expects_context
-                    )
+                    return _expects_context(component)
                 except Exception:
                     # If we can't parse the request, assume no context needed
                     return False
 
+            # Each item of a batch is executed with a context of its own
+            if message.method == Method.components_execute_batch:
+                return True
+
             # All other methods don't require context
             return False
 
@@ -275,6 +374,8 @@ class StepflowServer:
                 return await self._handle_component_info(request)
             elif request.method == Method.components_execute:
                 return await self._handle_component_execute(request, context)
+            elif request.method == Method.components_execute_batch:
+                return await self._handle_component_execute_batch(request, context)
             else:
                 return MethodError(
                     jsonrpc="2.0",
@@ -304,8 +405,25 @@ class StepflowServer:
 
     async def _handle_initialize(self, request: MethodRequest) -> MethodResponse:
         """Handle the initialize method."""
-        # Return protocol version
-        result = InitializeResult(server_protocol_version=1)
+        assert isinstance(request.params, InitializeParams)
+        runtime = request.params.runtime_capabilities
+        server = self.server_capabilities()
+        self._capabilities = Capabilities(
+            **{
+                name: getattr(server, name) and getattr(runtime, name)
+                for name in Capabilities.__struct_fields__
+            }
+        )
+        self._framing = next(
+            (f for f in request.params.framings if f in SUPPORTED_FRAMINGS),
+            Framing.json,
+        )
+
+        result = InitializeResult(
+            server_protocol_version=PROTOCOL_VERSION,
+            server_capabilities=server,
+            framing=None if self._framing == Framing.json else self._framing,
+        )
 
         return MethodSuccess(jsonrpc="2.0", id=request.id, result=result)
 
@@ -360,23 +478,12 @@ class StepflowServer:
             raise ComponentNotFoundError(f"Component '{params.component}' not found")
 
         try:
-            # Parse input using component's input type
-            input_value: Any = msgspec.convert(params.input, type=component.input_type)
-
-            # Execute component with or without context
-            args = [input_value]
-            if context is not None:
-                args.append(context)
-
-            if inspect.iscoroutinefunction(component.function):
-                output = await component.function(*args)
-            else:
-                output = component.function(*args)
+            output = await self._execute(component, params, context)
 
             result = ComponentExecuteResult(output=output)
             print(
                 f"Executed component {params.component} "
-                f"with input {input_value} produced {output}",
+                f"with input {params.input} produced {output}",
                 file=sys.stderr,
             )
             return MethodSuccess(jsonrpc="2.0", id=request.id, result=result)
@@ -395,6 +502,135 @@ class StepflowServer:
             traceback.print_exc(file=sys.stderr)
             raise StepflowExecutionError(f"Component execution failed: {str(e)}") from e
 
+    async def _execute(
+        self,
+        component: ComponentEntry,
+        params: ComponentExecuteParams,
+        context: StepflowContext | None,
+    ) -> Any:
+        """Execute a component on the input of a request, returning its output."""
+        # Fetch the content of any blob references before decoding
+        input_value = await self._decode_input(component, params.input, context)
+
+        # Batch components are called with a batch of one
+        args = [[input_value] if component.batch else input_value]
+        if context is not None and _expects_context(component):
+            args.append(context)
+
+        with component_span(params):
+            if inspect.iscoroutinefunction(component.function):
+                output = await component.function(*args)
+            else:
+                output = component.function(*args)
+
+        if component.batch:
+            (output,) = output
+        return output
+
+    async def _decode_input(
+        self,
+        component: ComponentEntry,
+        raw_input: Any,
+        context: StepflowContext | None,
+    ) -> Any:
+        """Decode an input using the component's input type."""
+        if context is not None and contains_blob_refs(raw_input):
+            raw_input = await context.resolve_blob_refs(raw_input)
+        return msgspec.convert(raw_input, type=component.input_type)
+
+    async def _handle_component_execute_batch(
+        self, request: MethodRequest, context: StepflowContext | None = None
+    ) -> MethodResponse:
+        """Handle the components/execute_batch method.
+
+        Batch components are called once with the input of every item. Other
+        components are called concurrently for each item, with a context
+        attributing log messages and progress to the item's step.
+        """
+        assert isinstance(request.params, ComponentExecuteBatchParams)
+        params: ComponentExecuteBatchParams = request.params
+
+        component = self._components.get(params.component)
+        if component is None:
+            raise ComponentNotFoundError(f"Component '{params.component}' not found")
+
+        if component.batch:
+            results = await self._execute_batch(component, params, context)
+        else:
+            results = await asyncio.gather(
+                *(
+                    self._execute_item(
+                        component,
+                        _item_params(params, item),
+                        context.for_item(item) if context is not None else None,
+                    )
+                    for item in params.items
+                )
+            )
+
+        result = ComponentExecuteBatchResult(results=list(results))
+        return MethodSuccess(jsonrpc="2.0", id=request.id, result=result)
+
+    async def _execute_item(
+        self,
+        component: ComponentEntry,
+        params: ComponentExecuteParams,
+        context: StepflowContext | None,
+    ) -> FlowResult:
+        """Execute a single item of a batch, returning its result."""
+        try:
+            output = await self._execute(component, params, context)
+            return FlowResultSuccess(result=output)
+        except SkipStep as e:
+            return FlowResultSkipped(reason=e.message)
+        except Exception as e:
+            print("Error executing component:", file=sys.stderr)
+            traceback.print_exc(file=sys.stderr)
+            return _failed_result(e)
+
+    async def _execute_batch(
+        self,
+        component: ComponentEntry,
+        params: ComponentExecuteBatchParams,
+        context: StepflowContext | None,
+    ) -> list[FlowResult]:
+        """Execute a batch component on the input of every item at once.
+
+        If the component fails or skips the batch, every item fails or is skipped.
+        """
+        try:
+            inputs = [
+                await self._decode_input(component, item.input, context)
+                for item in params.items
+            ]
+
+            args: list[Any] = [inputs]
+            if context is not None and _expects_context(component):
+                args.append(context)
+
+            if inspect.iscoroutinefunction(component.function):
+                outputs = await component.function(*args)
+            else:
+                outputs = component.function(*args)
+
+            if len(outputs) != len(inputs):
+                raise StepflowExecutionError(
+                    f"Component returned {len(outputs)} outputs "
+                    f"for a batch of {len(inputs)} inputs"
+                )
+            print(
+                f"Executed component {params.component} on a batch of {len(inputs)}",
+                file=sys.stderr,
+            )
+            return [FlowResultSuccess(result=output) for output in outputs]
+        except SkipStep as e:
+            return [FlowResultSkipped(reason=e.message) for _ in params.items]
+        except Exception as e:
+            print("Error executing component:", file=sys.stderr)
+            traceback.print_exc(file=sys.stderr)
+            failed = _failed_result(e)
+            return [failed for _ in params.items]
+
     def langchain_component(
         self,
         func: Callable | None = None,
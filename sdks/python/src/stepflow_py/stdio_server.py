@@ -23,6 +23,7 @@ import msgspec
 from stepflow_py.context import StepflowContext
 from stepflow_py.generated_protocol import (
     ComponentExecuteParams,
+    Framing,
     Message,
     Method,
     MethodRequest,
@@ -52,9 +53,12 @@ class StepflowStdioServer:
         *,
         name: str | None = None,
         description: str | None = None,
+        batch: bool = False,
     ):
         """Delegate component registration to the underlying server."""
-        return self._server.component(func, name=name, description=description)
+        return self._server.component(
+            func, name=name, description=description, batch=batch
+        )
 
     def get_component(self, component_path: str) -> ComponentEntry | None:
         """Get a registered component by path."""
@@ -65,6 +69,7 @@ class StepflowStdioServer:
         step_id = None
         run_id = None
         flow_id = None
+        request_id = None
 
         # Extract execution parameters from component execution requests
         if (
@@ -75,6 +80,7 @@ class StepflowStdioServer:
             step_id = message.params.step_id
             run_id = message.params.run_id
             flow_id = message.params.flow_id
+            request_id = message.id
 
         return StepflowContext(
             self._outgoing_queue,
@@ -83,6 +89,8 @@ class StepflowStdioServer:
             step_id=step_id,
             run_id=run_id,
             flow_id=flow_id,
+            request_id=request_id,
+            capabilities=self._server.capabilities,
         )
 
     def get_components(self):
@@ -93,6 +101,27 @@ class StepflowStdioServer:
         """Delegate langchain_component registration to the underlying server."""
         return self._server.langchain_component(*args, **kwargs)
 
+    def _encode_frame(self, message) -> bytes:
+        """Encode a message as a frame of the negotiated framing.
+
+        MessagePack frames are prefixed with their length as a 4-byte big-endian
+        integer, and carry binary blob content as raw bytes.
+        """
+        if self._message_decoder.framing == Framing.msgpack:
+            payload = msgspec.msgpack.encode(message)
+            return len(payload).to_bytes(4, "big") + payload
+        return msgspec.json.encode(message) + b"\n"
+
+    async def _read_frame(self, reader: asyncio.StreamReader) -> bytes | None:
+        """Read the next message, or None at the end of the input."""
+        if self._message_decoder.framing == Framing.msgpack:
+            try:
+                length = int.from_bytes(await reader.readexactly(4), "big")
+                return await reader.readexactly(length)
+            except asyncio.IncompleteReadError:
+                return None
+        return await reader.readline() or None
+
     async def _handle_incoming_message(self, request_bytes: bytes):
         """Handle an incoming message in a separate task."""
         request_id = None
@@ -128,16 +157,19 @@ class StepflowStdioServer:
                 assert isinstance(message, MethodRequest)
                 # Encode and write response
                 print(f"Sending response: {response} to {message}", file=sys.stderr)
-                response_bytes = msgspec.json.encode(response) + b"\n"
+                response_bytes = self._encode_frame(response)
                 sys.stdout.buffer.write(response_bytes)
                 sys.stdout.buffer.flush()
+                if message.method == Method.initialize:
+                    # Later messages use the framing chosen in the response
+                    self._message_decoder.framing = self._server.framing
             else:
                 assert isinstance(message, Notification)
         except Exception as e:
             print(f"Error in _handle_incoming_message: {e}", file=sys.stderr)
             if request_id is not None:
                 error_response = _handle_exception(e, id=request_id)
-                sys.stdout.buffer.write(msgspec.json.encode(error_response) + b"\n")
+                sys.stdout.buffer.write(self._encode_frame(error_response))
                 sys.stdout.buffer.flush()
             else:
                 # If we can't identify the request, we can't send a proper error
@@ -185,8 +217,7 @@ class StepflowStdioServer:
     async def _send_outgoing_message(self, message_data, writer: asyncio.StreamWriter):
         """Send an outgoing message to the runtime."""
         try:
-            message_bytes = msgspec.json.encode(message_data) + b"\n"
-            writer.write(message_bytes)
+            writer.write(self._encode_frame(message_data))
             await writer.drain()
             print(f"Sent outgoing message: {message_data}", file=sys.stderr)
         except Exception as e:
@@ -228,11 +259,16 @@ class StepflowStdioServer:
         # Read messages from stdin and add to queue
         try:
             while True:
-                line = await reader.readline()
-                if not line:
-                    print("Empty line received. Exiting", file=sys.stderr)
+                frame = await self._read_frame(reader)
+                if frame is None:
+                    print("End of input received. Exiting", file=sys.stderr)
                     break
-                await self._incoming_queue.put(line)
+                if self._server.is_initialized():
+                    await self._incoming_queue.put(frame)
+                else:
+                    # Handle messages in order until initialized, so messages
+                    # after the initialize response are read with its framing.
+                    await self._handle_incoming_message(frame)
         except KeyboardInterrupt:
             pass
         finally:
@@ -0,0 +1,62 @@
+# Copyright 2025 DataStax Inc.
+#
+# Licensed under the Apache License, Version 2.0 (the "License"); you may not
+# use this file except in compliance with the License. You may obtain a copy of
+# the License at
+#
+#     http://www.apache.org/licenses/LICENSE-2.0
+#
+# Unless required by applicable law or agreed to in writing, software
+# distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+# WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+# License for the specific language governing permissions and limitations under
+# the License.
+
+"""Optional OpenTelemetry tracing for component execution.
+
+If `opentelemetry-api` is installed, each component execution is recorded as a
+span. When Stepflow sends a trace context with the request, the span is a child
+of the step that invoked the component.
+"""
+
+from __future__ import annotations
+
+from contextlib import AbstractContextManager, nullcontext
+from typing import Any
+
+from stepflow_py.generated_protocol import ComponentExecuteParams
+
+# Check if OpenTelemetry is available
+try:
+    from opentelemetry import trace
+    from opentelemetry.trace.propagation.tracecontext import (
+        TraceContextTextMapPropagator,
+    )
+
+    _HAS_OPENTELEMETRY = True
+except ImportError:
+    _HAS_OPENTELEMETRY = False
+
+
+def component_span(params: ComponentExecuteParams) -> AbstractContextManager[Any]:
+    """Return a context manager recording a span for executing a component."""
+    if not _HAS_OPENTELEMETRY:
+        return nullcontext()
+
+    carrier: dict[str, str] = {}
+    if params.trace_context is not None:
+        carrier["traceparent"] = params.trace_context.traceparent
+        if params.trace_context.tracestate:
+            carrier["tracestate"] = params.trace_context.tracestate
+    parent = TraceContextTextMapPropagator().extract(carrier)
+
+    tracer = trace.get_tracer("stepflow_py")
+    return tracer.start_as_current_span(
+        f"component {params.component}",
+        context=parent,
+        attributes={
+            "stepflow.component": params.component,
+            "stepflow.step_id": params.step_id,
+            "stepflow.run_id": params.run_id,
+        },
+    )
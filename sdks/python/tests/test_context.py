@@ -0,0 +1,121 @@
+# Copyright 2025 DataStax Inc.
+#
+# Licensed under the Apache License, Version 2.0 (the "License"); you may not
+# use this file except in compliance with the License. You may obtain a copy of
+# the License at
+#
+#     http://www.apache.org/licenses/LICENSE-2.0
+#
+# Unless required by applicable law or agreed to in writing, software
+# distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+# WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+# License for the specific language governing permissions and limitations under
+# the License.
+
+"""
+Tests for the context API available to components.
+"""
+
+import asyncio
+import json
+
+import msgspec
+
+from stepflow_py.context import StepflowContext
+from stepflow_py.generated_protocol import Capabilities, ComponentBatchItem, LogLevel
+from stepflow_py.message_decoder import MessageDecoder
+
+
+def test_log_forwards_to_runtime(capsys):
+    """Test that log messages are sent to the runtime when it supports logging."""
+    queue: asyncio.Queue = asyncio.Queue()
+    context = StepflowContext(
+        queue,
+        MessageDecoder(),
+        request_id="execute-1",
+        capabilities=Capabilities(logging=True),
+    )
+
+    context.log("retrying request", level=LogLevel.warn, attempt=2)
+
+    assert "retrying request" in capsys.readouterr().err
+    notification = json.loads(msgspec.json.encode(queue.get_nowait()))
+    assert notification == {
+        "jsonrpc": "2.0",
+        "method": "log",
+        "params": {
+            "level": "warn",
+            "message": "retrying request",
+            "fields": {"attempt": 2},
+            "request_id": "execute-1",
+        },
+    }
+
+
+def test_log_without_logging_capability(capsys):
+    """Test that log messages are only written to stderr without logging."""
+    queue: asyncio.Queue = asyncio.Queue()
+    context = StepflowContext(queue, MessageDecoder(), request_id="execute-1")
+
+    context.log("hello")
+
+    assert "hello" in capsys.readouterr().err
+    assert queue.empty()
+
+
+def test_report_progress():
+    """Test that progress is sent to the runtime when it supports progress."""
+    queue: asyncio.Queue = asyncio.Queue()
+    context = StepflowContext(
+        queue,
+        MessageDecoder(),
+        request_id="execute-1",
+        capabilities=Capabilities(progress=True),
+    )
+
+    context.report_progress(message="embedding", completed=3, total=4)
+
+    notification = json.loads(msgspec.json.encode(queue.get_nowait()))
+    assert notification["method"] == "progress"
+    assert notification["params"] == {
+        "request_id": "execute-1",
+        "fraction": 0.75,
+        "message": "embedding",
+        "completed": 3,
+        "total": 4,
+    }
+
+
+def test_report_progress_without_progress_capability():
+    """Test that progress is not sent if the runtime does not support it."""
+    queue: asyncio.Queue = asyncio.Queue()
+    context = StepflowContext(queue, MessageDecoder(), request_id="execute-1")
+
+    context.report_progress(fraction=0.5)
+
+    assert queue.empty()
+
+
+def test_for_item_attributes_to_item():
+    """Test that the context of a batch item reports with the item's request ID."""
+    queue: asyncio.Queue = asyncio.Queue()
+    context = StepflowContext(
+        queue,
+        MessageDecoder(),
+        request_id="batch-1",
+        capabilities=Capabilities(logging=True),
+    )
+    item = ComponentBatchItem(
+        request_id="item-1",
+        input={},
+        step_id="embed",
+        run_id="run-1",
+        flow_id="flow-1",
+    )
+
+    item_context = context.for_item(item)
+    item_context.log("embedding")
+
+    assert item_context.step_id == "embed"
+    notification = json.loads(msgspec.json.encode(queue.get_nowait()))
+    assert notification["params"]["request_id"] == "item-1"
@@ -21,20 +21,28 @@ This test file focuses on testing the core server functionality:
 - Component execution with/without context
 """
 
+import asyncio
 import inspect
 
 import msgspec
 import pytest
 
 from stepflow_py import StepflowContext
+from stepflow_py.context import contains_blob_refs
 from stepflow_py.generated_protocol import (
+    Capabilities,
+    ComponentBatchItem,
+    ComponentExecuteBatchParams,
     ComponentExecuteParams,
     ComponentInfoParams,
     ComponentListParams,
     InitializeParams,
+    FlowResultFailed,
+    FlowResultSuccess,
     Method,
     MethodRequest,
 )
+from stepflow_py.message_decoder import MessageDecoder
 from stepflow_py.server import ComponentEntry, StepflowServer
 from stepflow_py.stdio_server import StepflowStdioServer
 
@@ -159,6 +167,25 @@ def test_initialization_state(server):
     assert not server.is_initialized()
 
 
+@pytest.mark.asyncio
+async def test_handle_initialize_negotiates_capabilities(server):
+    """Test that only capabilities declared by both sides are enabled."""
+    request = MethodRequest(
+        jsonrpc="2.0",
+        id="init",
+        method=Method.initialize,
+        params=InitializeParams(
+            runtime_protocol_version=1,
+            runtime_capabilities=Capabilities(binary_blobs=True, streaming=True),
+        ),
+    )
+    response = await server.handle_message(request)
+    assert response.result.server_protocol_version == 1
+    assert response.result.server_capabilities.binary_blobs
+    assert not response.result.server_capabilities.streaming
+    assert server.capabilities == Capabilities(binary_blobs=True)
+
+
 @pytest.mark.asyncio
 async def test_handle_component_info(server):
     """Test component info retrieval via handle_message."""
@@ -256,6 +283,113 @@ async def test_handle_component_execute_invalid_input(server):
     assert "execution failed" in response.error.message.lower()
 
 
+def _batch_request(component: str, inputs: list) -> MethodRequest:
+    return MethodRequest(
+        jsonrpc="2.0",
+        id="batch-1",
+        method=Method.components_execute_batch,
+        params=ComponentExecuteBatchParams(
+            component=component,
+            items=[
+                ComponentBatchItem(
+                    request_id=f"item-{i}",
+                    input=input,
+                    step_id=f"step{i}",
+                    run_id="test-run-id",
+                    flow_id="test-flow-id",
+                )
+                for i, input in enumerate(inputs)
+            ],
+        ),
+    )
+
+
+def _batch_context() -> StepflowContext:
+    return StepflowContext(asyncio.Queue(), MessageDecoder(), request_id="batch-1")
+
+
+@pytest.mark.asyncio
+async def test_handle_component_execute_batch(server):
+    """Test that each item of a batch is executed and fails independently."""
+    server.set_initialized(True)
+
+    @server.component(name="test_component")
+    def test_component(input_data: ValidInput) -> ValidOutput:
+        return ValidOutput(
+            greeting=f"Hello {input_data.name}!", age_next_year=input_data.age + 1
+        )
+
+    request = _batch_request(
+        "/test_component",
+        [{"name": "Alice", "age": 25}, {"name": "Bob"}],
+    )
+    response = await server.handle_message(request, _batch_context())
+    assert response.id == request.id
+    first, second = response.result.results
+    assert isinstance(first, FlowResultSuccess)
+    assert first.result.greeting == "Hello Alice!"
+    assert isinstance(second, FlowResultFailed)
+
+
+@pytest.mark.asyncio
+async def test_handle_component_execute_batch_component(server):
+    """Test that batch components are called once with every input."""
+    server.set_initialized(True)
+    batches = []
+
+    @server.component(name="embed", batch=True)
+    def embed(inputs: list[ValidInput]) -> list[ValidOutput]:
+        batches.append(len(inputs))
+        return [
+            ValidOutput(greeting=f"Hello {i.name}!", age_next_year=i.age + 1)
+            for i in inputs
+        ]
+
+    component = server.get_component("/embed")
+    assert component.input_type == ValidInput
+    assert component.output_type == ValidOutput
+
+    request = _batch_request(
+        "/embed",
+        [{"name": "Alice", "age": 25}, {"name": "Bob", "age": 30}],
+    )
+    response = await server.handle_message(request, _batch_context())
+    assert batches == [2]
+    assert [r.result.greeting for r in response.result.results] == [
+        "Hello Alice!",
+        "Hello Bob!",
+    ]
+
+    # A single execution is passed to the component as a batch of one.
+    request = MethodRequest(
+        jsonrpc="2.0",
+        id="test-1",
+        method=Method.components_execute,
+        params=ComponentExecuteParams(
+            component="/embed",
+            input={"name": "Carol", "age": 40},
+            step_id="test_step",
+            run_id="test-run-id",
+            flow_id="test-flow-id",
+        ),
+    )
+    response = await server.handle_message(request)
+    assert batches == [2, 1]
+    assert response.result.output.greeting == "Hello Carol!"
+
+
+@pytest.mark.asyncio
+async def test_batching_capability_requires_batch_component(server):
+    """Test that batching is only declared if a component processes batches."""
+    assert not server.server_capabilities().batching
+
+    @server.component(batch=True)
+    def embed(inputs: list[ValidInput]) -> list[ValidOutput]:
+        return []
+
+    assert server.server_capabilities().batching
+
+
 @pytest.mark.asyncio
 async def test_handle_list_components(server):
     """Test component listing via handle_message."""
@@ -349,6 +483,71 @@ async def test_component_execute_with_context(server):
     assert output.age_next_year == 26
 
 
+@pytest.mark.asyncio
+async def test_component_execute_resolves_blob_refs(server):
+    """Test that blob references in the input are replaced by the blob content."""
+    server.set_initialized(True)
+
+    @server.component(name="greet")
+    def greet(input_data: ValidInput) -> ValidOutput:
+        return ValidOutput(
+            greeting=f"Hello {input_data.name}!", age_next_year=input_data.age + 1
+        )
+
+    blob_id = "a" * 64
+    request = MethodRequest(
+        jsonrpc="2.0",
+        id="test-1",
+        method=Method.components_execute,
+        params=ComponentExecuteParams(
+            component="/greet",
+            input={"$blob": blob_id},
+            step_id="test_step",
+            run_id="test-run-id",
+            flow_id="test-flow-id",
+        ),
+    )
+
+    # Components that don't take a context still need one to fetch blobs
+    assert server.requires_context(request)
+
+    class BlobContext(StepflowContext):
+        def __init__(self):
+            self.requested: list[str] = []
+
+        async def get_blob(self, blob_id: str):
+            self.requested.append(blob_id)
+            return {"name": "Alice", "age": 25}
+
+    context = BlobContext()
+    response = await server.handle_message(request, context)
+    assert context.requested == [blob_id]
+    output = response.result.output
+    assert output.greeting == "Hello Alice!"
+    assert output.age_next_year == 26
+
+
+@pytest.mark.asyncio
+async def test_resolve_blob_refs_unescapes_values():
+    """Test that escaped values that look like blob references are unescaped."""
+
+    class BlobContext(StepflowContext):
+        def __init__(self):
+            pass
+
+        async def get_blob(self, blob_id: str):
+            return {"inner": {"$$blob": blob_id}}
+
+    blob_id = "a" * 64
+    value = {"ref": {"$blob": blob_id}, "lookalike": {"$$$blob": "b"}}
+    assert contains_blob_refs({"lookalike": {"$$blob": "b"}})
+    assert not contains_blob_refs({"lookalike": {"$blob": "b", "other": 1}})
+    assert await BlobContext().resolve_blob_refs(value) == {
+        "ref": {"inner": {"$blob": blob_id}},
+        "lookalike": {"$$blob": "b"},
+    }
+
+
 @pytest.mark.asyncio
 async def test_server_responses_include_jsonrpc(server):
     """Test that server responses include jsonrpc field in JSON encoding."""
@@ -582,3 +781,54 @@ async def test_decorator_async():
     assert isinstance(result2, ValidOutput)
     assert result2.greeting == "Sync Hello Bob!"
     assert result2.age_next_year == 31
+
+
+@pytest.mark.asyncio
+async def test_component_execute_span_nests_under_step(server):
+    """Component spans are children of the step in the request's trace context."""
+    pytest.importorskip("opentelemetry.sdk")
+    from opentelemetry import trace
+    from opentelemetry.sdk.trace import TracerProvider
+    from opentelemetry.sdk.trace.export import SimpleSpanProcessor
+    from opentelemetry.sdk.trace.export.in_memory_span_exporter import (
+        InMemorySpanExporter,
+    )
+
+    from stepflow_py.generated_protocol import TraceContext
+
+    exporter = InMemorySpanExporter()
+    provider = TracerProvider()
+    provider.add_span_processor(SimpleSpanProcessor(exporter))
+    trace.set_tracer_provider(provider)
+
+    server.set_initialized(True)
+
+    @server.component(name="test_component")
+    def test_component(input_data: ValidInput) -> ValidOutput:
+        return ValidOutput(greeting="", age_next_year=0)
+
+    trace_id = "4bf92f3577b34da6a3ce929d0e0e4736"
+    step_span_id = "00f067aa0ba902b7"
+    request = MethodRequest(
+        jsonrpc="2.0",
+        id="test-1",
+        method=Method.components_execute,
+        params=ComponentExecuteParams(
+            component="/test_component",
+            input={"name": "Alice", "age": 25},
+            step_id="test_step",
+            run_id="test-run-id",
+            flow_id="test-flow-id",
+            trace_context=TraceContext(
+                traceparent=f"00-{trace_id}-{step_span_id}-01"
+            ),
+        ),
+    )
+    await server.handle_message(request)
+
+    (span,) = exporter.get_finished_spans()
+    assert span.name == "component /test_component"
+    assert span.attributes["stepflow.step_id"] == "test_step"
+    assert format(span.context.trace_id, "032x") == trace_id
+    assert span.parent is not None
+    assert format(span.parent.span_id, "016x") == step_span_id
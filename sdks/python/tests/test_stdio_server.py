@@ -26,9 +26,13 @@ import msgspec
 import pytest
 
 from stepflow_py.generated_protocol import (
+    ComponentListParams,
+    Framing,
     InitializeParams,
+    Initialized,
     Method,
     MethodRequest,
+    Notification,
 )
 from stepflow_py.stdio_server import StepflowStdioServer
 
@@ -119,6 +123,55 @@ async def test_no_response_to_initialized_notification(server, capsys):
     assert captured.out.strip() == ""
 
 
+@pytest.mark.asyncio
+async def test_msgpack_framing(server, capsysbinary):
+    """Test that messages after the initialize response use the chosen framing."""
+    server._server.set_initialized(False)
+
+    initialize = MethodRequest(
+        id="init-req",
+        method=Method.initialize,
+        params=InitializeParams(
+            runtime_protocol_version=1, framings=[Framing.msgpack]
+        ),
+    )
+    initialized = Notification(method=Method.initialized, params=Initialized())
+    list_request = MethodRequest(
+        id="list-req", method=Method.components_list, params=ComponentListParams()
+    )
+
+    def frame(message) -> bytes:
+        payload = msgspec.msgpack.encode(message)
+        return len(payload).to_bytes(4, "big") + payload
+
+    stdin_reader = asyncio.StreamReader()
+    stdin_reader.feed_data(msgspec.json.encode(initialize) + b"\n")
+    stdin_reader.feed_data(frame(initialized) + frame(list_request))
+
+    server_task = asyncio.create_task(
+        server.start(stdin=stdin_reader, stdout=MockStreamWriter())
+    )
+    await asyncio.sleep(0.1)
+    stdin_reader.feed_eof()
+
+    try:
+        await asyncio.wait_for(server_task, timeout=1.0)
+    except TimeoutError:
+        server_task.cancel()
+        await asyncio.gather(server_task, return_exceptions=True)
+
+    out = capsysbinary.readouterr().out
+    line, _, rest = out.partition(b"\n")
+    response = msgspec.json.decode(line)
+    assert response["id"] == "init-req"
+    assert response["result"]["framing"] == "msgpack"
+
+    length = int.from_bytes(rest[:4], "big")
+    response = msgspec.msgpack.decode(rest[4 : 4 + length])
+    assert response["id"] == "list-req"
+    assert "components" in response["result"]
+
+
 class MockStreamWriter:
     """Simple mock writer for testing."""
 
@@ -0,0 +1,189 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+use std::sync::{Arc, Mutex};
+
+use indexmap::IndexMap;
+use stepflow_core::{BlobId, workflow::Flow};
+
+use crate::{Result, dependency::analyze_flow_dependencies, types::AnalysisResult};
+
+/// Number of analyses an [`AnalysisCache`] holds unless configured otherwise.
+pub const DEFAULT_ANALYSIS_CACHE_SIZE: usize = 1024;
+
+/// Cache of analysis results keyed by flow ID.
+///
+/// Flows are content-addressed, so the analysis of a flow ID never changes and
+/// entries never need invalidating. Entries may be removed once a flow is deleted.
+/// The cache holds at most `capacity` analyses, evicting the least recently
+/// used one when full.
+///
+/// The cache is kept in memory rather than persisted, since the pre-built
+/// dependency graph of a [`FlowAnalysis`](crate::FlowAnalysis) is not serialized.
+#[derive(Debug)]
+pub struct AnalysisCache {
+    capacity: usize,
+    /// Entries ordered from least to most recently used.
+    entries: Mutex<IndexMap<BlobId, Arc<AnalysisResult>>>,
+}
+
+impl Default for AnalysisCache {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_ANALYSIS_CACHE_SIZE)
+    }
+}
+
+impl AnalysisCache {
+    /// Create an empty analysis cache holding the default number of analyses.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty analysis cache holding at most `capacity` analyses.
+    ///
+    /// A capacity of 0 disables caching.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(IndexMap::new()),
+        }
+    }
+
+    /// Return the cached analysis of `flow_id`, analyzing `flow` if it is not cached.
+    ///
+    /// Results with fatal diagnostics are cached too, so invalid flows are
+    /// only validated once.
+    pub fn get_or_analyze(
+        &self,
+        flow: &Arc<Flow>,
+        flow_id: &BlobId,
+    ) -> Result<Arc<AnalysisResult>> {
+        if let Some(result) = self.get(flow_id) {
+            return Ok(result);
+        }
+
+        let result = Arc::new(analyze_flow_dependencies(flow.clone(), flow_id.clone())?);
+        if self.capacity == 0 {
+            return Ok(result);
+        }
+        let mut entries = self.entries.lock().expect("analysis cache lock poisoned");
+        // Another caller may have cached the flow while it was analyzed.
+        if let Some(cached) = touch(&mut entries, flow_id) {
+            return Ok(cached);
+        }
+        if entries.len() >= self.capacity {
+            entries.shift_remove_index(0);
+        }
+        entries.insert(flow_id.clone(), result.clone());
+        Ok(result)
+    }
+
+    /// Return the cached analysis of `flow_id`, if any.
+    pub fn get(&self, flow_id: &BlobId) -> Option<Arc<AnalysisResult>> {
+        let mut entries = self.entries.lock().expect("analysis cache lock poisoned");
+        touch(&mut entries, flow_id)
+    }
+
+    /// Remove the cached analysis of `flow_id`.
+    pub fn remove(&self, flow_id: &BlobId) {
+        let mut entries = self.entries.lock().expect("analysis cache lock poisoned");
+        entries.shift_remove(flow_id);
+    }
+
+    /// The number of cached analyses.
+    pub fn len(&self) -> usize {
+        self.entries
+            .lock()
+            .expect("analysis cache lock poisoned")
+            .len()
+    }
+
+    /// Whether the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Return the entry of `flow_id`, marking it as the most recently used.
+fn touch(
+    entries: &mut IndexMap<BlobId, Arc<AnalysisResult>>,
+    flow_id: &BlobId,
+) -> Option<Arc<AnalysisResult>> {
+    let index = entries.get_index_of(flow_id)?;
+    entries.move_index(index, entries.len() - 1);
+    entries.last().map(|(_, result)| result.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stepflow_core::workflow::{FlowBuilder, JsonPath, StepBuilder, ValueTemplate};
+
+    fn test_flow(step_id: &str) -> (Arc<Flow>, BlobId) {
+        let flow = Arc::new(
+            FlowBuilder::test_flow()
+                .steps(vec![
+                    StepBuilder::mock_step(step_id)
+                        .input(ValueTemplate::workflow_input(JsonPath::default()))
+                        .build(),
+                ])
+                .output(ValueTemplate::step_ref(step_id, JsonPath::default()))
+                .build(),
+        );
+        let flow_id = BlobId::from_flow(flow.as_ref()).unwrap();
+        (flow, flow_id)
+    }
+
+    #[test]
+    fn test_analysis_is_cached_by_flow_id() {
+        let (flow, flow_id) = test_flow("step1");
+        let cache = AnalysisCache::new();
+        assert!(cache.get(&flow_id).is_none());
+
+        let first = cache.get_or_analyze(&flow, &flow_id).unwrap();
+        assert!(first.has_analysis());
+        let second = cache.get_or_analyze(&flow, &flow_id).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(cache.len(), 1);
+
+        cache.remove(&flow_id);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_least_recently_used_analysis_is_evicted() {
+        let cache = AnalysisCache::with_capacity(2);
+        let (flow_a, id_a) = test_flow("a");
+        let (flow_b, id_b) = test_flow("b");
+        let (flow_c, id_c) = test_flow("c");
+
+        cache.get_or_analyze(&flow_a, &id_a).unwrap();
+        cache.get_or_analyze(&flow_b, &id_b).unwrap();
+        // Using `a` makes `b` the least recently used analysis.
+        assert!(cache.get(&id_a).is_some());
+        cache.get_or_analyze(&flow_c, &id_c).unwrap();
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&id_a).is_some());
+        assert!(cache.get(&id_b).is_none());
+        assert!(cache.get(&id_c).is_some());
+
+        // A capacity of 0 disables caching.
+        let disabled = AnalysisCache::with_capacity(0);
+        assert!(
+            disabled
+                .get_or_analyze(&flow_a, &id_a)
+                .unwrap()
+                .has_analysis()
+        );
+        assert!(disabled.is_empty());
+    }
+}
@@ -10,6 +10,7 @@
 // or implied. See the License for the specific language governing permissions and limitations under
 // the License.
 
+mod cache;
 mod dependencies;
 mod dependency;
 mod diagnostics;
@@ -18,6 +19,7 @@ mod tracker;
 mod types;
 mod validation;
 
+pub use cache::*;
 pub use dependencies::*;
 pub use dependency::*;
 pub use diagnostics::*;
@@ -0,0 +1,237 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use error_stack::ResultExt as _;
+use futures::future::{BoxFuture, FutureExt as _};
+use serde::{Deserialize, Serialize};
+use stepflow_core::{BlobData, BlobId, BlobType};
+use stepflow_state::{BlobStore, StateError, StoredBlob};
+
+/// Configuration for [`FilesystemBlobStore`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FilesystemBlobStoreConfig {
+    /// Directory in which blobs are stored. Created if it does not exist.
+    pub path: PathBuf,
+}
+
+/// Metadata stored alongside each blob's content.
+#[derive(Debug, Serialize, Deserialize)]
+struct BlobMetadata {
+    blob_type: BlobType,
+    content_type: String,
+}
+
+/// [`BlobStore`] that keeps blobs as files in a local directory.
+///
+/// Blob content is stored at `<root>/<ab>/<cd>/<blob_id>` with a `<blob_id>.meta`
+/// JSON sidecar recording the blob type and content type. Files are written to a
+/// temporary name and renamed into place, so readers never see partial blobs.
+pub struct FilesystemBlobStore {
+    root: PathBuf,
+    temp_counter: AtomicU64,
+}
+
+impl FilesystemBlobStore {
+    /// Create a blob store rooted at `root`, creating the directory if needed.
+    pub async fn new(root: impl Into<PathBuf>) -> error_stack::Result<Self, StateError> {
+        let root = root.into();
+        tokio::fs::create_dir_all(&root)
+            .await
+            .change_context(StateError::Initialization)
+            .attach_printable_lazy(|| format!("creating blob directory {}", root.display()))?;
+        Ok(Self {
+            root,
+            temp_counter: AtomicU64::new(0),
+        })
+    }
+
+    fn content_path(&self, blob_id: &BlobId) -> PathBuf {
+        self.root.join(crate::shard_path(blob_id, 2))
+    }
+
+    fn metadata_path(&self, blob_id: &BlobId) -> PathBuf {
+        self.root
+            .join(format!("{}.meta", crate::shard_path(blob_id, 2)))
+    }
+
+    /// Atomically write `content` to `path` via a temporary file in the same directory.
+    async fn write_atomic(
+        &self,
+        path: &Path,
+        content: &[u8],
+    ) -> error_stack::Result<(), StateError> {
+        let temp_path = path.with_extension(format!(
+            "tmp-{}-{}",
+            std::process::id(),
+            self.temp_counter.fetch_add(1, Ordering::Relaxed)
+        ));
+        tokio::fs::write(&temp_path, content)
+            .await
+            .change_context(StateError::Internal)
+            .attach_printable_lazy(|| format!("writing {}", temp_path.display()))?;
+        if let Err(e) = tokio::fs::rename(&temp_path, path).await {
+            let _ = tokio::fs::remove_file(&temp_path).await;
+            return Err(error_stack::report!(e)
+                .change_context(StateError::Internal)
+                .attach_printable(format!("renaming into {}", path.display())));
+        }
+        Ok(())
+    }
+}
+
+impl BlobStore for FilesystemBlobStore {
+    fn put(&self, blob: BlobData) -> BoxFuture<'_, error_stack::Result<(), StateError>> {
+        async move {
+            let content_path = self.content_path(&blob.blob_id);
+            if tokio::fs::try_exists(&content_path)
+                .await
+                .change_context(StateError::Internal)?
+            {
+                return Ok(());
+            }
+
+            if let Some(parent) = content_path.parent() {
+                tokio::fs::create_dir_all(parent)
+                    .await
+                    .change_context(StateError::Internal)
+                    .attach_printable_lazy(|| format!("creating {}", parent.display()))?;
+            }
+
+            let stored = StoredBlob::from_blob_data(&blob);
+            let metadata = serde_json::to_vec(&BlobMetadata {
+                blob_type: stored.blob_type,
+                content_type: stored.content_type,
+            })
+            .change_context(StateError::Serialization)?;
+
+            // Write metadata first: the content file marks the blob as complete.
+            self.write_atomic(&self.metadata_path(&blob.blob_id), &metadata)
+                .await?;
+            self.write_atomic(&content_path, &stored.content).await?;
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn get(
+        &self,
+        blob_id: &BlobId,
+    ) -> BoxFuture<'_, error_stack::Result<Option<BlobData>, StateError>> {
+        let blob_id = blob_id.clone();
+        async move {
+            let content = match tokio::fs::read(self.content_path(&blob_id)).await {
+                Ok(content) => content,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+                Err(e) => {
+                    return Err(error_stack::report!(e).change_context(StateError::Internal));
+                }
+            };
+            let metadata = tokio::fs::read(self.metadata_path(&blob_id))
+                .await
+                .change_context(StateError::Internal)
+                .attach_printable_lazy(|| format!("reading metadata for blob {blob_id}"))?;
+            let metadata: BlobMetadata =
+                serde_json::from_slice(&metadata).change_context(StateError::Serialization)?;
+
+            let stored = StoredBlob {
+                blob_type: metadata.blob_type,
+                content_type: metadata.content_type,
+                content,
+            };
+            stored.into_blob_data(blob_id).map(Some)
+        }
+        .boxed()
+    }
+
+    fn delete(&self, blob_id: &BlobId) -> BoxFuture<'_, error_stack::Result<bool, StateError>> {
+        let blob_id = blob_id.clone();
+        async move {
+            let existed = match tokio::fs::remove_file(self.content_path(&blob_id)).await {
+                Ok(()) => true,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => false,
+                Err(e) => {
+                    return Err(error_stack::report!(e).change_context(StateError::Internal));
+                }
+            };
+            match tokio::fs::remove_file(self.metadata_path(&blob_id)).await {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    return Err(error_stack::report!(e).change_context(StateError::Internal));
+                }
+            }
+            Ok(existed)
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use stepflow_core::BlobValue;
+    use stepflow_core::workflow::ValueRef;
+
+    #[tokio::test]
+    async fn test_filesystem_blob_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FilesystemBlobStore::new(dir.path().join("blobs"))
+            .await
+            .unwrap();
+
+        let data = ValueRef::new(json!({"hello": "world"}));
+        let data_blob = BlobData::from_value_ref(
+            data.clone(),
+            BlobType::Data,
+            BlobId::from_content(&data).unwrap(),
+        )
+        .unwrap();
+        let content = b"\x89PNG\r\n\x1a\n\x00".to_vec();
+        let binary_blob = BlobData::new(
+            BlobValue::binary(content.clone(), "image/png"),
+            BlobId::from_bytes(&content, "image/png").unwrap(),
+        );
+
+        store.put(data_blob.clone()).await.unwrap();
+        store.put(binary_blob.clone()).await.unwrap();
+        // Storing again is a no-op.
+        store.put(binary_blob.clone()).await.unwrap();
+
+        assert_eq!(
+            store.get(&data_blob.blob_id).await.unwrap(),
+            Some(data_blob.clone())
+        );
+        assert_eq!(
+            store.get(&binary_blob.blob_id).await.unwrap(),
+            Some(binary_blob.clone())
+        );
+
+        let id = binary_blob.blob_id.as_str();
+        assert!(
+            dir.path()
+                .join("blobs")
+                .join(&id[0..2])
+                .join(&id[2..4])
+                .join(id)
+                .exists()
+        );
+
+        assert!(store.delete(&binary_blob.blob_id).await.unwrap());
+        assert!(!store.delete(&binary_blob.blob_id).await.unwrap());
+        assert_eq!(store.get(&binary_blob.blob_id).await.unwrap(), None);
+    }
+}
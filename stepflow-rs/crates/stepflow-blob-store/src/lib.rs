@@ -0,0 +1,51 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+//! BlobStore implementations for Stepflow.
+//!
+//! Provides content-addressed blob storage on the local filesystem and in
+//! S3-compatible object stores (AWS S3, MinIO, ...), keeping large payloads
+//! out of the run metadata database.
+
+mod filesystem;
+mod s3;
+
+pub use filesystem::{FilesystemBlobStore, FilesystemBlobStoreConfig};
+pub use s3::{S3BlobStore, S3BlobStoreConfig};
+
+/// Relative location of a blob, sharded by the first bytes of its hash.
+///
+/// Sharding keeps directory (and object listing) sizes manageable for large stores.
+fn shard_path(blob_id: &stepflow_core::BlobId, levels: usize) -> String {
+    let id = blob_id.as_str();
+    let mut path = String::with_capacity(id.len() + levels * 3);
+    for level in 0..levels {
+        path.push_str(&id[level * 2..level * 2 + 2]);
+        path.push('/');
+    }
+    path.push_str(id);
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stepflow_core::BlobId;
+
+    #[test]
+    fn test_shard_path() {
+        let blob_id = BlobId::new(format!("abcd{}", "0".repeat(60))).unwrap();
+        assert_eq!(shard_path(&blob_id, 0), blob_id.as_str());
+        assert_eq!(shard_path(&blob_id, 1), format!("ab/{blob_id}"));
+        assert_eq!(shard_path(&blob_id, 2), format!("ab/cd/{blob_id}"));
+    }
+}
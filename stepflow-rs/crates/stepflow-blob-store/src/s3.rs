@@ -0,0 +1,410 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+mod sigv4;
+
+use std::collections::BTreeMap;
+
+use error_stack::ResultExt as _;
+use futures::future::{BoxFuture, FutureExt as _};
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use stepflow_core::{BlobData, BlobId, BlobType, blob::DEFAULT_BINARY_CONTENT_TYPE};
+use stepflow_state::{BlobStore, StateError, StoredBlob};
+use url::Url;
+
+use self::sigv4::Credentials;
+
+/// Object metadata header recording the Stepflow blob type.
+const BLOB_TYPE_HEADER: &str = "x-amz-meta-stepflow-blob-type";
+
+fn default_region() -> String {
+    "us-east-1".to_string()
+}
+
+/// Configuration for [`S3BlobStore`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct S3BlobStoreConfig {
+    /// Endpoint URL of the S3 service, e.g. `https://s3.us-east-1.amazonaws.com`
+    /// or `http://localhost:9000` for MinIO.
+    pub endpoint: Url,
+    /// Bucket in which blobs are stored.
+    pub bucket: String,
+    /// Region used for request signing.
+    #[serde(default = "default_region")]
+    pub region: String,
+    /// Prefix prepended to all object keys (e.g. `stepflow/blobs/`).
+    #[serde(default)]
+    pub prefix: String,
+    /// Access key ID. Defaults to the `AWS_ACCESS_KEY_ID` environment variable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_key_id: Option<String>,
+    /// Secret access key. Defaults to the `AWS_SECRET_ACCESS_KEY` environment variable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret_access_key: Option<String>,
+}
+
+/// [`BlobStore`] backed by an S3-compatible object store.
+///
+/// Objects are addressed path-style (`<endpoint>/<bucket>/<key>`), which works with
+/// AWS S3 as well as MinIO and other S3-compatible services. Each blob is stored
+/// under `<prefix><ab>/<blob_id>` with its content type as the object's
+/// `Content-Type` and its blob type as user metadata.
+pub struct S3BlobStore {
+    client: reqwest::Client,
+    endpoint: Url,
+    bucket: String,
+    region: String,
+    prefix: String,
+    credentials: Credentials,
+}
+
+impl S3BlobStore {
+    /// Create an S3 blob store from its configuration.
+    ///
+    /// Credentials missing from the configuration are read from the standard
+    /// `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY` environment variables.
+    pub fn from_config(config: &S3BlobStoreConfig) -> error_stack::Result<Self, StateError> {
+        let access_key_id = config
+            .access_key_id
+            .clone()
+            .or_else(|| std::env::var("AWS_ACCESS_KEY_ID").ok())
+            .ok_or(StateError::Initialization)
+            .attach_printable("missing S3 access key ID")?;
+        let secret_access_key = config
+            .secret_access_key
+            .clone()
+            .or_else(|| std::env::var("AWS_SECRET_ACCESS_KEY").ok())
+            .ok_or(StateError::Initialization)
+            .attach_printable("missing S3 secret access key")?;
+
+        Ok(Self {
+            client: reqwest::Client::new(),
+            endpoint: config.endpoint.clone(),
+            bucket: config.bucket.clone(),
+            region: config.region.clone(),
+            prefix: config.prefix.clone(),
+            credentials: Credentials {
+                access_key_id,
+                secret_access_key,
+            },
+        })
+    }
+
+    fn object_url(&self, blob_id: &BlobId) -> error_stack::Result<Url, StateError> {
+        let key = format!("{}{}", self.prefix, crate::shard_path(blob_id, 1));
+        let mut url = self.endpoint.clone();
+        url.path_segments_mut()
+            .map_err(|_| StateError::Initialization)
+            .attach_printable("S3 endpoint cannot be used as a base URL")?
+            .pop_if_empty()
+            .push(&self.bucket)
+            .extend(key.split('/'));
+        Ok(url)
+    }
+
+    /// Send a signed request for the object holding `blob_id`.
+    async fn send(
+        &self,
+        method: Method,
+        blob_id: &BlobId,
+        extra_headers: &[(&str, &str)],
+        body: Vec<u8>,
+    ) -> error_stack::Result<reqwest::Response, StateError> {
+        let url = self.object_url(blob_id)?;
+        let now = chrono::Utc::now();
+        let content_sha256 = sigv4::payload_hash(&body);
+
+        let host = match url.port() {
+            Some(port) => format!("{}:{port}", url.host_str().unwrap_or_default()),
+            None => url.host_str().unwrap_or_default().to_string(),
+        };
+        let mut headers = BTreeMap::from([
+            ("host".to_string(), host),
+            ("x-amz-content-sha256".to_string(), content_sha256.clone()),
+            (
+                "x-amz-date".to_string(),
+                now.format(sigv4::AMZ_DATE_FORMAT).to_string(),
+            ),
+        ]);
+        for (name, value) in extra_headers {
+            headers.insert(name.to_ascii_lowercase(), value.to_string());
+        }
+        let authorization = sigv4::authorization(
+            method.as_str(),
+            &url,
+            &headers,
+            &content_sha256,
+            &self.region,
+            now,
+            &self.credentials,
+        );
+
+        let mut request = self.client.request(method, url);
+        for (name, value) in &headers {
+            // reqwest derives the Host header from the URL.
+            if name != "host" {
+                request = request.header(name, value);
+            }
+        }
+        request
+            .header(reqwest::header::AUTHORIZATION, authorization)
+            .body(body)
+            .send()
+            .await
+            .change_context(StateError::Connection)
+    }
+}
+
+/// Convert an unexpected S3 response into an error report.
+async fn unexpected_response(response: reqwest::Response) -> error_stack::Report<StateError> {
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    error_stack::report!(StateError::Internal)
+        .attach_printable(format!("unexpected S3 response {status}: {body}"))
+}
+
+impl BlobStore for S3BlobStore {
+    fn put(&self, blob: BlobData) -> BoxFuture<'_, error_stack::Result<(), StateError>> {
+        async move {
+            // Blobs are content-addressed, so an existing object already holds this content.
+            let head = self
+                .send(Method::HEAD, &blob.blob_id, &[], Vec::new())
+                .await?;
+            if head.status().is_success() {
+                return Ok(());
+            }
+            // Only a missing object may be written; other failures, such as
+            // denied access, are reported rather than masked by the PUT.
+            if head.status() != StatusCode::NOT_FOUND {
+                return Err(unexpected_response(head).await);
+            }
+
+            let stored = StoredBlob::from_blob_data(&blob);
+            let response = self
+                .send(
+                    Method::PUT,
+                    &blob.blob_id,
+                    &[
+                        ("content-type", &stored.content_type),
+                        (BLOB_TYPE_HEADER, stored.blob_type.as_str()),
+                    ],
+                    stored.content,
+                )
+                .await?;
+            if !response.status().is_success() {
+                return Err(unexpected_response(response).await);
+            }
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn get(
+        &self,
+        blob_id: &BlobId,
+    ) -> BoxFuture<'_, error_stack::Result<Option<BlobData>, StateError>> {
+        let blob_id = blob_id.clone();
+        async move {
+            let response = self.send(Method::GET, &blob_id, &[], Vec::new()).await?;
+            if response.status() == StatusCode::NOT_FOUND {
+                return Ok(None);
+            }
+            if !response.status().is_success() {
+                return Err(unexpected_response(response).await);
+            }
+
+            let header = |name| {
+                response
+                    .headers()
+                    .get(name)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string)
+            };
+            // Objects written by other tools carry no blob type; treat them as binary.
+            let blob_type = match header(BLOB_TYPE_HEADER) {
+                Some(blob_type) => blob_type
+                    .parse::<BlobType>()
+                    .change_context(StateError::Serialization)?,
+                None => BlobType::Binary,
+            };
+            let content_type = header(reqwest::header::CONTENT_TYPE.as_str())
+                .unwrap_or_else(|| DEFAULT_BINARY_CONTENT_TYPE.to_string());
+            let content = response
+                .bytes()
+                .await
+                .change_context(StateError::Connection)?;
+
+            let stored = StoredBlob {
+                blob_type,
+                content_type,
+                content: content.to_vec(),
+            };
+            stored.into_blob_data(blob_id).map(Some)
+        }
+        .boxed()
+    }
+
+    fn delete(&self, blob_id: &BlobId) -> BoxFuture<'_, error_stack::Result<bool, StateError>> {
+        let blob_id = blob_id.clone();
+        async move {
+            // S3 DELETE succeeds whether or not the object exists, so check first.
+            let head = self.send(Method::HEAD, &blob_id, &[], Vec::new()).await?;
+            if head.status() == StatusCode::NOT_FOUND {
+                return Ok(false);
+            }
+            if !head.status().is_success() {
+                return Err(unexpected_response(head).await);
+            }
+
+            let response = self.send(Method::DELETE, &blob_id, &[], Vec::new()).await?;
+            if !response.status().is_success() {
+                return Err(unexpected_response(response).await);
+            }
+            Ok(true)
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    use axum::body::Bytes;
+    use axum::extract::State;
+    use axum::http::{HeaderMap, Method as HttpMethod, Uri};
+    use axum::response::{IntoResponse as _, Response};
+    use serde_json::json;
+    use stepflow_core::BlobValue;
+    use stepflow_core::workflow::ValueRef;
+
+    type Objects = Arc<Mutex<HashMap<String, (HeaderMap, Bytes)>>>;
+
+    /// Minimal in-process stand-in for an S3 server.
+    async fn fake_s3(
+        State(objects): State<Objects>,
+        method: HttpMethod,
+        uri: Uri,
+        headers: HeaderMap,
+        body: Bytes,
+    ) -> Response {
+        let authorized = headers
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with("AWS4-HMAC-SHA256 Credential=test-key/"));
+        if !authorized {
+            return axum::http::StatusCode::FORBIDDEN.into_response();
+        }
+
+        let key = uri.path().to_string();
+        let mut objects = objects.lock().unwrap();
+        match method {
+            HttpMethod::PUT => {
+                objects.insert(key, (headers, body));
+                axum::http::StatusCode::OK.into_response()
+            }
+            HttpMethod::GET | HttpMethod::HEAD => match objects.get(&key) {
+                Some((stored_headers, body)) => {
+                    let mut response_headers = HeaderMap::new();
+                    for name in ["content-type", BLOB_TYPE_HEADER] {
+                        if let Some(value) = stored_headers.get(name) {
+                            response_headers.insert(name, value.clone());
+                        }
+                    }
+                    (response_headers, body.clone()).into_response()
+                }
+                None => axum::http::StatusCode::NOT_FOUND.into_response(),
+            },
+            HttpMethod::DELETE => {
+                objects.remove(&key);
+                axum::http::StatusCode::NO_CONTENT.into_response()
+            }
+            _ => axum::http::StatusCode::METHOD_NOT_ALLOWED.into_response(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_s3_blob_store() {
+        let objects = Objects::default();
+        let app = axum::Router::new()
+            .fallback(fake_s3)
+            .with_state(objects.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let store = S3BlobStore::from_config(&S3BlobStoreConfig {
+            endpoint: Url::parse(&format!("http://{addr}")).unwrap(),
+            bucket: "stepflow".to_string(),
+            region: default_region(),
+            prefix: "blobs/".to_string(),
+            access_key_id: Some("test-key".to_string()),
+            secret_access_key: Some("test-secret".to_string()),
+        })
+        .unwrap();
+
+        let data = ValueRef::new(json!({"hello": "world"}));
+        let data_blob = BlobData::from_value_ref(
+            data.clone(),
+            BlobType::Data,
+            BlobId::from_content(&data).unwrap(),
+        )
+        .unwrap();
+        let content = b"%PDF-1.7\n\x00\xff".to_vec();
+        let binary_blob = BlobData::new(
+            BlobValue::binary(content.clone(), "application/pdf"),
+            BlobId::from_bytes(&content, "application/pdf").unwrap(),
+        );
+
+        store.put(data_blob.clone()).await.unwrap();
+        store.put(binary_blob.clone()).await.unwrap();
+
+        let id = binary_blob.blob_id.as_str();
+        assert!(
+            objects
+                .lock()
+                .unwrap()
+                .contains_key(&format!("/stepflow/blobs/{}/{id}", &id[0..2]))
+        );
+
+        assert_eq!(
+            store.get(&data_blob.blob_id).await.unwrap(),
+            Some(data_blob.clone())
+        );
+        assert_eq!(
+            store.get(&binary_blob.blob_id).await.unwrap(),
+            Some(binary_blob.clone())
+        );
+
+        assert!(store.delete(&binary_blob.blob_id).await.unwrap());
+        assert!(!store.delete(&binary_blob.blob_id).await.unwrap());
+        assert_eq!(store.get(&binary_blob.blob_id).await.unwrap(), None);
+
+        // Failed existence checks are errors, not treated as missing objects.
+        let unauthorized = S3BlobStore::from_config(&S3BlobStoreConfig {
+            endpoint: Url::parse(&format!("http://{addr}")).unwrap(),
+            bucket: "stepflow".to_string(),
+            region: default_region(),
+            prefix: "blobs/".to_string(),
+            access_key_id: Some("other-key".to_string()),
+            secret_access_key: Some("test-secret".to_string()),
+        })
+        .unwrap();
+        assert!(unauthorized.put(binary_blob.clone()).await.is_err());
+        assert!(unauthorized.delete(&data_blob.blob_id).await.is_err());
+        assert_eq!(store.get(&binary_blob.blob_id).await.unwrap(), None);
+    }
+}
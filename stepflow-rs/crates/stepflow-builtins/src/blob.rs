@@ -12,9 +12,11 @@
 
 use error_stack::ResultExt as _;
 use serde::{Deserialize, Serialize};
+use stepflow_core::blob::{DEFAULT_BINARY_CONTENT_TYPE, decode_base64_value};
 use stepflow_core::workflow::Component;
 use stepflow_core::{
-    FlowResult, blob::BlobId, component::ComponentInfo, schema::SchemaRef, workflow::ValueRef,
+    BlobType, FlowResult, blob::BlobId, component::ComponentInfo, schema::SchemaRef,
+    workflow::ValueRef,
 };
 use stepflow_plugin::ExecutionContext;
 
@@ -38,10 +40,13 @@ impl Default for PutBlobComponent {
 /// Input for the put_blob component
 #[derive(Serialize, Deserialize, schemars::JsonSchema)]
 struct PutBlobInput {
-    /// The JSON data to store as a blob
+    /// The JSON data to store as a blob (base64-encoded string for binary blobs)
     data: serde_json::Value,
     /// The type of blob to store
     blob_type: stepflow_core::BlobType,
+    /// The MIME type of binary content (defaults to `application/octet-stream`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    content_type: Option<String>,
 }
 
 /// Output from the put_blob component
@@ -61,7 +66,8 @@ impl BuiltinComponent for PutBlobComponent {
             input_schema: Some(input_schema),
             output_schema: Some(output_schema),
             description: Some(
-                "Store JSON data as a blob and return its content-addressable ID".to_string(),
+                "Store JSON or binary data as a blob and return its content-addressable ID"
+                    .to_string(),
             ),
         })
     }
@@ -70,20 +76,43 @@ impl BuiltinComponent for PutBlobComponent {
         let input: PutBlobInput = serde_json::from_value(input.as_ref().clone())
             .change_context(BuiltinError::InvalidInput)?;
 
-        let data_ref = ValueRef::new(input.data);
+        let blob_id = if input.blob_type == BlobType::Binary {
+            let content =
+                decode_base64_value(&input.data).change_context(BuiltinError::InvalidInput)?;
+            let content_type = input
+                .content_type
+                .unwrap_or_else(|| DEFAULT_BINARY_CONTENT_TYPE.to_string());
+
+            tracing::debug!("put_blob storing {} bytes of {content_type}", content.len());
+
+            context
+                .state_store()
+                .put_binary_blob(content, content_type)
+                .await
+                .change_context(BuiltinError::Internal)?
+        } else {
+            let data_ref = ValueRef::new(input.data);
+
+            // DEBUG: Log what's being stored
+            tracing::debug!("put_blob storing data: {:?}", data_ref.as_ref());
+
+            // Create the blob through the execution context
+            context
+                .state_store()
+                .put_blob(data_ref, input.blob_type)
+                .await
+                .change_context(BuiltinError::Internal)?
+        };
 
-        // DEBUG: Log what's being stored
-        tracing::debug!("put_blob storing data: {:?}", data_ref.as_ref());
+        tracing::debug!("put_blob created blob with ID: {}", blob_id.as_str());
 
-        // Create the blob through the execution context
-        let blob_id = context
+        // Make the blob readable through the API in the namespace of the run.
+        context
             .state_store()
-            .put_blob(data_ref, input.blob_type)
+            .add_blob_to_run_namespace(context.run_id(), &blob_id)
             .await
             .change_context(BuiltinError::Internal)?;
 
-        tracing::debug!("put_blob created blob with ID: {}", blob_id.as_str());
-
         let output = PutBlobOutput {
             blob_id: blob_id.as_str().to_string(),
         };
@@ -119,8 +148,14 @@ struct GetBlobInput {
 /// Output from the get_blob component
 #[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
 struct GetBlobOutput {
-    /// The JSON data stored in the blob
+    /// The JSON data stored in the blob (base64-encoded string for binary blobs)
     data: serde_json::Value,
+    /// The MIME type of binary content
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    content_type: Option<String>,
+    /// The size of binary content in bytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    size: Option<u64>,
 }
 
 impl BuiltinComponent for GetBlobComponent {
@@ -132,7 +167,7 @@ impl BuiltinComponent for GetBlobComponent {
             component: Component::from_string("/get_blob"),
             input_schema: Some(input_schema),
             output_schema: Some(output_schema),
-            description: Some("Retrieve JSON data from a blob using its ID".to_string()),
+            description: Some("Retrieve JSON or binary data from a blob using its ID".to_string()),
         })
     }
 
@@ -153,10 +188,23 @@ impl BuiltinComponent for GetBlobComponent {
             .await
             .change_context(BuiltinError::Internal)?;
 
-        tracing::debug!("get_blob retrieved data: {:?}", data_ref.data().as_ref());
-
-        let output = GetBlobOutput {
-            data: data_ref.data().as_ref().clone(),
+        let output = match data_ref.as_binary() {
+            Some(content) => {
+                tracing::debug!("get_blob retrieved {} bytes", content.len());
+                GetBlobOutput {
+                    data: data_ref.data().as_ref().clone(),
+                    content_type: Some(data_ref.content_type().to_string()),
+                    size: Some(content.len() as u64),
+                }
+            }
+            None => {
+                tracing::debug!("get_blob retrieved data: {:?}", data_ref.data().as_ref());
+                GetBlobOutput {
+                    data: data_ref.data().as_ref().clone(),
+                    content_type: None,
+                    size: None,
+                }
+            }
         };
 
         tracing::debug!("get_blob output structure: {:?}", output);
@@ -181,6 +229,7 @@ mod tests {
         let input = PutBlobInput {
             data: test_data.clone(),
             blob_type: stepflow_core::BlobType::Data,
+            content_type: None,
         };
 
         let input_value = serde_json::to_value(input).unwrap();
@@ -260,6 +309,7 @@ mod tests {
         let create_input = PutBlobInput {
             data: test_data.clone(),
             blob_type: stepflow_core::BlobType::Data,
+            content_type: None,
         };
         let create_input_value = serde_json::to_value(create_input).unwrap();
 
@@ -297,6 +347,54 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_binary_blob_roundtrip() {
+        let mock = MockContext::new();
+        let content = [0x25u8, 0x50, 0x44, 0x46, 0x00, 0xff];
+        let encoded = stepflow_core::blob::encode_base64(&content);
+
+        let create_input = PutBlobInput {
+            data: json!(encoded),
+            blob_type: stepflow_core::BlobType::Binary,
+            content_type: Some("application/pdf".to_string()),
+        };
+        let create_result = PutBlobComponent::new()
+            .execute(
+                mock.execution_context(),
+                serde_json::to_value(create_input).unwrap().into(),
+            )
+            .await
+            .unwrap();
+        let FlowResult::Success(result) = create_result else {
+            panic!("Expected success result from create");
+        };
+        let output: PutBlobOutput = serde_json::from_value(result.as_ref().clone()).unwrap();
+        assert_eq!(
+            output.blob_id,
+            BlobId::from_bytes(&content, "application/pdf")
+                .unwrap()
+                .as_str()
+        );
+
+        let get_input = GetBlobInput {
+            blob_id: output.blob_id,
+        };
+        let get_result = GetBlobComponent::new()
+            .execute(
+                mock.execution_context(),
+                serde_json::to_value(get_input).unwrap().into(),
+            )
+            .await
+            .unwrap();
+        let FlowResult::Success(result) = get_result else {
+            panic!("Expected success result from get");
+        };
+        let output: GetBlobOutput = serde_json::from_value(result.as_ref().clone()).unwrap();
+        assert_eq!(output.data, json!(encoded));
+        assert_eq!(output.content_type.as_deref(), Some("application/pdf"));
+        assert_eq!(output.size, Some(content.len() as u64));
+    }
+
     #[tokio::test]
     async fn test_invalid_blob_id() {
         let component = GetBlobComponent::new();
@@ -20,6 +20,7 @@ use stepflow_core::{
     workflow::{Flow, ValueRef},
 };
 use stepflow_plugin::{Context as _, ExecutionContext};
+use stepflow_state::StepProgress;
 
 use crate::{BuiltinComponent, Result, error::BuiltinError};
 
@@ -117,6 +118,11 @@ impl BuiltinComponent for IterateComponent {
 
             iterations += 1;
 
+            // The number of iterations is not known in advance, so no fraction is reported
+            context
+                .report_progress(StepProgress::items(iterations.into(), None))
+                .await;
+
             match result {
                 FlowResult::Success(result) => {
                     // Parse the result to check for "result" or "next" fields
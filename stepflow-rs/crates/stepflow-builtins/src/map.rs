@@ -11,7 +11,8 @@
 // the License.
 
 use error_stack::ResultExt as _;
-use futures::future;
+use futures::StreamExt as _;
+use futures::stream::FuturesUnordered;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use stepflow_core::{
@@ -21,6 +22,7 @@ use stepflow_core::{
     workflow::{Flow, ValueRef},
 };
 use stepflow_plugin::{Context as _, ExecutionContext};
+use stepflow_state::StepProgress;
 
 use crate::{BuiltinComponent, Result, error::BuiltinError};
 
@@ -90,27 +92,41 @@ impl BuiltinComponent for MapComponent {
         let mut skipped = 0u32;
 
         // Process each item in parallel
-        let tasks = input.items.into_iter().map(|item| {
-            let context = context.clone();
-            let flow = flow.clone();
-            let flow_id = flow_id.clone();
-
-            tokio::spawn(async move {
-                context
-                    .execute_flow(flow, flow_id, item)
-                    .await
-                    .change_context(BuiltinError::Internal)
-            })
-        });
-
-        // Wait for all tasks to complete
-        let results: Vec<FlowResult> = future::join_all(tasks)
-            .await
+        let total = input.items.len();
+        let mut tasks: FuturesUnordered<_> = input
+            .items
             .into_iter()
-            .collect::<Result<Vec<_>, _>>()
-            .change_context(BuiltinError::Internal)?
-            .into_iter()
-            .collect::<Result<Vec<_>, _>>()?;
+            .enumerate()
+            .map(|(index, item)| {
+                let context = context.clone();
+                let flow = flow.clone();
+                let flow_id = flow_id.clone();
+
+                tokio::spawn(async move {
+                    let result = context
+                        .execute_flow(flow, flow_id, item)
+                        .await
+                        .change_context(BuiltinError::Internal);
+                    (index, result)
+                })
+            })
+            .collect();
+
+        // Collect results in item order, reporting progress as each item completes
+        context
+            .report_progress(StepProgress::items(0, Some(total as u64)))
+            .await;
+        let mut results: Vec<Option<FlowResult>> = (0..total).map(|_| None).collect();
+        let mut completed = 0u64;
+        while let Some(task) = tasks.next().await {
+            let (index, result) = task.change_context(BuiltinError::Internal)?;
+            results[index] = Some(result?);
+            completed += 1;
+            context
+                .report_progress(StepProgress::items(completed, Some(total as u64)))
+                .await;
+        }
+        let results: Vec<FlowResult> = results.into_iter().flatten().collect();
 
         // Update counters
         for result in &results {
@@ -164,9 +180,10 @@ mod tests {
 
         let input_value = serde_json::to_value(input).unwrap();
         let mock = MockContext::new();
+        let context = mock.execution_context();
 
         let result = component
-            .execute(mock.execution_context(), input_value.into())
+            .execute(context.clone(), input_value.into())
             .await
             .unwrap();
 
@@ -180,6 +197,17 @@ mod tests {
             }
             _ => panic!("Expected success result"),
         }
+
+        // Progress is reported as items complete
+        let progress = context
+            .state_store()
+            .get_step_progress(context.run_id())
+            .await
+            .unwrap();
+        let progress = &progress["test_step"];
+        assert_eq!(progress.completed, Some(3));
+        assert_eq!(progress.total, Some(3));
+        assert_eq!(progress.fraction, Some(1.0));
     }
 
     #[tokio::test]
@@ -36,6 +36,7 @@ impl PluginConfig for BuiltinPluginConfig {
 
     async fn create_plugin(
         self,
+        _name: &str,
         _working_directory: &std::path::Path,
     ) -> error_stack::Result<Box<DynPlugin<'static>>, Self::Error> {
         Ok(DynPlugin::boxed(Builtins::new()))
@@ -71,6 +72,12 @@ impl Plugin for Builtins {
             .change_context(PluginError::ComponentInfo)
     }
 
+    /// Builtins run in-process and read referenced blobs from the state store
+    /// themselves, so the executor does not need to resolve them.
+    fn supports_blob_refs(&self) -> bool {
+        true
+    }
+
     async fn execute(
         &self,
         component: &Component,
@@ -78,9 +85,42 @@ impl Plugin for Builtins {
         input: ValueRef,
     ) -> Result<FlowResult> {
         let component = registry::get_component(component)?;
+        let input = stepflow_state::resolve_blob_refs(input, context.state_store())
+            .await
+            .change_context(PluginError::UdfExecution)?;
         component
             .execute(context, input)
             .await
             .change_context(PluginError::UdfExecution)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_context::MockContext;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_execute_resolves_blob_refs() {
+        let mock = MockContext::new();
+        let context = mock.execution_context();
+        let data = ValueRef::new(json!({"text": "x".repeat(100)}));
+        let blob_id = context
+            .state_store()
+            .put_blob(data, stepflow_core::BlobType::Data)
+            .await
+            .unwrap();
+
+        // Storing the referenced content again yields the same blob.
+        let input = json!({"data": blob_id.to_blob_ref(), "blob_type": "data"});
+        let result = Builtins::new()
+            .execute(&Component::from_string("/put_blob"), context, input.into())
+            .await
+            .unwrap();
+        let FlowResult::Success(output) = result else {
+            panic!("Expected success, got {result:?}");
+        };
+        assert_eq!(output.as_ref()["blob_id"], blob_id.as_str());
+    }
+}
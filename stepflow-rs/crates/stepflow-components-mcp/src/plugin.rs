@@ -49,6 +49,7 @@ impl PluginConfig for McpPluginConfig {
 
     async fn create_plugin(
         self,
+        _name: &str,
         working_directory: &std::path::Path,
     ) -> error_stack::Result<Box<DynPlugin<'static>>, Self::Error> {
         Ok(DynPlugin::boxed(McpPlugin::new(
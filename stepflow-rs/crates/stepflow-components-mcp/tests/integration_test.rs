@@ -72,7 +72,7 @@ async fn test_mcp_plugin_initialization() {
     };
 
     let working_dir = std::env::current_dir().unwrap();
-    let plugin = config.create_plugin(&working_dir).await.unwrap();
+    let plugin = config.create_plugin("mcp", &working_dir).await.unwrap();
 
     let (test_context, _exec_context) = create_test_context();
     plugin.init(&test_context).await.unwrap();
@@ -99,7 +99,7 @@ async fn test_mcp_tool_execution() {
     };
 
     let working_dir = std::env::current_dir().unwrap();
-    let plugin = config.create_plugin(&working_dir).await.unwrap();
+    let plugin = config.create_plugin("mcp", &working_dir).await.unwrap();
 
     let (test_context, exec_context) = create_test_context();
     plugin.init(&test_context).await.unwrap();
@@ -161,7 +161,7 @@ async fn test_mcp_error_handling() {
     };
 
     let working_dir = std::env::current_dir().unwrap();
-    let plugin = config.create_plugin(&working_dir).await.unwrap();
+    let plugin = config.create_plugin("mcp", &working_dir).await.unwrap();
 
     let (test_context, exec_context) = create_test_context();
     plugin.init(&test_context).await.unwrap();
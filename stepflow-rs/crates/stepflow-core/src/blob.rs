@@ -13,6 +13,8 @@
 use std::borrow::Cow;
 use std::fmt;
 
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64;
 use error_stack::ResultExt as _;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest as _, Sha256};
@@ -20,6 +22,80 @@ use sha2::{Digest as _, Sha256};
 use crate::workflow::{Flow, ValueRef};
 use std::sync::Arc;
 
+/// Content type used for binary blobs stored without an explicit MIME type.
+pub const DEFAULT_BINARY_CONTENT_TYPE: &str = "application/octet-stream";
+
+/// Key identifying a blob reference object (`{"$blob": "<blob_id>"}`).
+///
+/// Large step results are stored as blobs and passed between steps as blob
+/// references, which are resolved only when the content is needed. Values that
+/// merely look like references are escaped with [`escape_blob_refs`].
+pub const BLOB_REF_KEY: &str = "$blob";
+
+/// The number of leading `$` if `object` has a single field named `$blob`,
+/// `$$blob`, `$$$blob`, ...
+fn blob_ref_key_escapes(object: &serde_json::Map<String, serde_json::Value>) -> Option<usize> {
+    if object.len() != 1 {
+        return None;
+    }
+    let key = object.keys().next()?;
+    let name = key.trim_start_matches('$');
+    let escapes = key.len() - name.len();
+    (escapes > 0 && name == &BLOB_REF_KEY[1..]).then_some(escapes)
+}
+
+/// Check whether a value holds blob references or escaped values that look like them.
+pub fn contains_blob_ref_keys(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Object(object) => {
+            blob_ref_key_escapes(object).is_some() || object.values().any(contains_blob_ref_keys)
+        }
+        serde_json::Value::Array(items) => items.iter().any(contains_blob_ref_keys),
+        _ => false,
+    }
+}
+
+/// Escape objects within a value that would be mistaken for blob references.
+///
+/// Blob references are only created by Stepflow. Any object whose single field
+/// is `$blob`, `$$blob`, ... in a value from elsewhere gets one more `$`, so it
+/// stays a plain value. [`unescape_blob_ref`] removes the extra `$` when blob
+/// references are resolved.
+pub fn escape_blob_refs(value: ValueRef) -> ValueRef {
+    fn escape(value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(object) => {
+                if blob_ref_key_escapes(object).is_some()
+                    && let Some((key, field)) = std::mem::take(object).into_iter().next()
+                {
+                    object.insert(format!("${key}"), field);
+                }
+                object.values_mut().for_each(escape);
+            }
+            serde_json::Value::Array(items) => items.iter_mut().for_each(escape),
+            _ => {}
+        }
+    }
+
+    if !contains_blob_ref_keys(value.as_ref()) {
+        return value;
+    }
+    let mut escaped = value.as_ref().clone();
+    escape(&mut escaped);
+    ValueRef::new(escaped)
+}
+
+/// Remove the escaping added by [`escape_blob_refs`] from an object, but not
+/// from the values within it.
+pub fn unescape_blob_ref(value: &mut serde_json::Value) {
+    if let serde_json::Value::Object(object) = value
+        && blob_ref_key_escapes(object).is_some_and(|escapes| escapes > 1)
+        && let Some((key, field)) = std::mem::take(object).into_iter().next()
+    {
+        object.insert(key[1..].to_owned(), field);
+    }
+}
+
 /// Typed blob value that keeps the data in a convenient form based on type.
 #[derive(Debug, Clone, PartialEq)]
 pub enum BlobValue {
@@ -27,6 +103,13 @@ pub enum BlobValue {
     Json(ValueRef),
     /// A workflow/flow definition  
     Flow(Arc<Flow>),
+    /// Raw binary content (images, documents, audio, ...)
+    Binary {
+        /// The raw bytes of the blob
+        content: Arc<[u8]>,
+        /// MIME type describing the content
+        content_type: String,
+    },
 }
 
 impl BlobValue {
@@ -35,18 +118,35 @@ impl BlobValue {
         match self {
             BlobValue::Json(_) => BlobType::Data,
             BlobValue::Flow(_) => BlobType::Flow,
+            BlobValue::Binary { .. } => BlobType::Binary,
+        }
+    }
+
+    /// Create a binary blob value with the given content type.
+    pub fn binary(content: impl Into<Arc<[u8]>>, content_type: impl Into<String>) -> Self {
+        BlobValue::Binary {
+            content: content.into(),
+            content_type: content_type.into(),
         }
     }
 
-    /// Convert to ValueRef for serialization
+    /// Convert to ValueRef for serialization.
+    ///
+    /// Binary content is represented as a base64-encoded string.
     pub fn to_value_ref(&self) -> ValueRef {
         match self {
             BlobValue::Json(value_ref) => value_ref.clone(),
             BlobValue::Flow(flow) => ValueRef::new(serde_json::to_value(flow.as_ref()).unwrap()),
+            BlobValue::Binary { content, .. } => {
+                ValueRef::new(serde_json::Value::String(BASE64.encode(content)))
+            }
         }
     }
 
     /// Try to create a BlobValue from ValueRef and type
+    ///
+    /// Binary blobs are expected to be a base64-encoded string and are given the
+    /// default content type.
     pub fn from_value_ref(data: ValueRef, blob_type: BlobType) -> Result<Self, BlobValueError> {
         match blob_type {
             BlobType::Data => Ok(BlobValue::Json(data)),
@@ -55,15 +155,54 @@ impl BlobValue {
                     .map_err(|_| BlobValueError::InvalidFlowData)?;
                 Ok(BlobValue::Flow(Arc::new(flow)))
             }
+            BlobType::Binary => {
+                let content = decode_base64_value(data.as_ref())?;
+                Ok(BlobValue::binary(content, DEFAULT_BINARY_CONTENT_TYPE))
+            }
+        }
+    }
+
+    /// The MIME type of the content when served over HTTP.
+    pub fn content_type(&self) -> &str {
+        match self {
+            BlobValue::Json(_) | BlobValue::Flow(_) => "application/json",
+            BlobValue::Binary { content_type, .. } => content_type,
+        }
+    }
+
+    /// Get the raw bytes of the blob.
+    ///
+    /// JSON and flow blobs are returned as their serialized JSON representation.
+    pub fn to_bytes(&self) -> Cow<'_, [u8]> {
+        match self {
+            BlobValue::Binary { content, .. } => Cow::Borrowed(content),
+            _ => Cow::Owned(serde_json::to_vec(self.to_value_ref().as_ref()).unwrap()),
         }
     }
 }
 
+/// Decode a JSON string containing base64-encoded binary content.
+pub fn decode_base64_value(value: &serde_json::Value) -> Result<Vec<u8>, BlobValueError> {
+    let encoded = value.as_str().ok_or(BlobValueError::InvalidBinaryData)?;
+    BASE64
+        .decode(encoded)
+        .map_err(|_| BlobValueError::InvalidBinaryData)
+}
+
+/// Encode binary content as a base64 string suitable for embedding in JSON.
+pub fn encode_base64(content: &[u8]) -> String {
+    BASE64.encode(content)
+}
+
 /// Error type for BlobValue operations
 #[derive(Debug, thiserror::Error)]
 pub enum BlobValueError {
     #[error("Invalid flow data - could not deserialize")]
     InvalidFlowData,
+    #[error("Invalid binary data - expected a base64-encoded string")]
+    InvalidBinaryData,
+    #[error("Unknown blob type '{0}'")]
+    UnknownBlobType(String),
 }
 
 /// Structured blob data containing both the content and metadata.
@@ -116,6 +255,19 @@ impl BlobData {
             _ => None,
         }
     }
+
+    /// Get a reference to the binary content
+    pub fn as_binary(&self) -> Option<&[u8]> {
+        match &self.value {
+            BlobValue::Binary { content, .. } => Some(content),
+            _ => None,
+        }
+    }
+
+    /// Get the MIME type of the blob content
+    pub fn content_type(&self) -> &str {
+        self.value.content_type()
+    }
 }
 
 /// Type of blob stored in the blob store.
@@ -126,6 +278,32 @@ pub enum BlobType {
     Flow,
     /// Generic data blob
     Data,
+    /// Raw binary content with an associated content type
+    Binary,
+}
+
+impl BlobType {
+    /// The serialized name of the blob type.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BlobType::Flow => "flow",
+            BlobType::Data => "data",
+            BlobType::Binary => "binary",
+        }
+    }
+}
+
+impl std::str::FromStr for BlobType {
+    type Err = BlobValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "flow" => Ok(BlobType::Flow),
+            "data" => Ok(BlobType::Data),
+            "binary" => Ok(BlobType::Binary),
+            _ => Err(BlobValueError::UnknownBlobType(s.to_string())),
+        }
+    }
 }
 
 impl schemars::JsonSchema for BlobType {
@@ -137,7 +315,7 @@ impl schemars::JsonSchema for BlobType {
         schemars::json_schema!({
             "description": "Type of blob stored in the blob store",
             "type": "string",
-            "enum": ["flow", "data"]
+            "enum": ["flow", "data", "binary"]
         })
     }
 }
@@ -211,11 +389,48 @@ impl BlobId {
         Self::new(hash)
     }
 
+    /// Generate a content-based blob ID for binary content using SHA-256.
+    ///
+    /// The blob type and content type are hashed along with the bytes, so binary
+    /// blobs never share an ID with JSON blobs or with the same bytes stored under
+    /// another content type.
+    pub fn from_bytes(
+        content: &[u8],
+        content_type: &str,
+    ) -> error_stack::Result<Self, BlobIdError> {
+        let mut hasher = Sha256::new();
+        hasher.update(BlobType::Binary.as_str().as_bytes());
+        hasher.update([0]);
+        hasher.update(content_type.as_bytes());
+        hasher.update([0]);
+        hasher.update(content);
+
+        let hash = hex::encode(hasher.finalize());
+        Self::new(hash)
+    }
+
     /// Get the inner hash string.
     pub fn as_str(&self) -> &str {
         &self.0
     }
 
+    /// Create a blob reference (`{"$blob": "<blob_id>"}`) pointing at this blob.
+    pub fn to_blob_ref(&self) -> serde_json::Value {
+        serde_json::json!({ BLOB_REF_KEY: self.0 })
+    }
+
+    /// Extract the blob ID from a blob reference.
+    ///
+    /// Returns None if the value is not an object whose only field is a valid `$blob` ID.
+    pub fn from_blob_ref(value: &serde_json::Value) -> Option<Self> {
+        let object = value.as_object()?;
+        if object.len() != 1 {
+            return None;
+        }
+        let blob_id = object.get(BLOB_REF_KEY)?.as_str()?;
+        Self::new(blob_id.to_string()).ok()
+    }
+
     /// Generate a content-based blob ID from a Flow using SHA-256.
     pub fn from_flow(flow: &crate::workflow::Flow) -> error_stack::Result<Self, BlobIdError> {
         let flow_data =
@@ -21,4 +21,4 @@ mod flow_result;
 pub use flow_result::*;
 
 // Re-export commonly used types
-pub use blob::{BlobData, BlobId, BlobType};
+pub use blob::{BlobData, BlobId, BlobType, BlobValue};
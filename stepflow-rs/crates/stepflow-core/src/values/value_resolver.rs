@@ -20,7 +20,7 @@ use tracing;
 
 use super::{ValueRef, ValueTemplate, ValueTemplateRepr};
 use crate::{
-    FlowResult,
+    BlobId, FlowResult,
     workflow::{BaseRef, Expr, Flow, SkipAction, StepId},
 };
 
@@ -30,18 +30,21 @@ use crate::{
 /// inputs without being tightly coupled to specific storage implementations.
 #[async_trait]
 pub trait ValueLoader: Send + Sync {
-    type Error: std::error::Error + Send + Sync + 'static;
+    type Error: error_stack::Context;
 
     /// Load the result of a completed step by its index.
     async fn load_step_result(
         &self,
         run_id: Uuid,
         step_index: usize,
-    ) -> std::result::Result<FlowResult, Self::Error>;
+    ) -> error_stack::Result<FlowResult, Self::Error>;
 
     /// Load the input value for the workflow.
     async fn load_workflow_input(&self, run_id: Uuid)
-    -> std::result::Result<ValueRef, Self::Error>;
+    -> error_stack::Result<ValueRef, Self::Error>;
+
+    /// Load the content of a blob referenced by a step result.
+    async fn load_blob(&self, blob_id: &BlobId) -> error_stack::Result<ValueRef, Self::Error>;
 }
 
 /// Errors that can occur during value resolution
@@ -156,6 +159,15 @@ impl<L: ValueLoader> ValueResolver<L> {
         let path_result = if let Some(path) = expr.path() {
             match base_result {
                 FlowResult::Success(result) => {
+                    // Paths into offloaded step results need the blob content.
+                    let result = match BlobId::from_blob_ref(result.as_ref()) {
+                        Some(blob_id) => self
+                            .loader
+                            .load_blob(&blob_id)
+                            .await
+                            .change_context(ValueResolverError::StateError)?,
+                        None => result,
+                    };
                     tracing::debug!("Resolving path '{}' on value: {:?}", path, result.as_ref());
                     if let Some(sub_value) = result.resolve_json_path(path) {
                         tracing::debug!("Path '{}' resolved to: {:?}", path, sub_value.as_ref());
@@ -277,6 +289,7 @@ mod tests {
     struct MockValueLoader {
         workflow_input: ValueRef,
         step_results: HashMap<usize, FlowResult>,
+        blobs: HashMap<BlobId, ValueRef>,
     }
 
     impl MockValueLoader {
@@ -284,6 +297,7 @@ mod tests {
             Self {
                 workflow_input,
                 step_results: HashMap::new(),
+                blobs: HashMap::new(),
             }
         }
     }
@@ -302,18 +316,25 @@ mod tests {
             &self,
             _run_id: Uuid,
             step_index: usize,
-        ) -> std::result::Result<FlowResult, Self::Error> {
+        ) -> error_stack::Result<FlowResult, Self::Error> {
             self.step_results.get(&step_index).cloned().ok_or_else(|| {
-                MockError::Generic(format!("Step result not found for index {step_index}"))
+                MockError::Generic(format!("Step result not found for index {step_index}")).into()
             })
         }
 
         async fn load_workflow_input(
             &self,
             _run_id: Uuid,
-        ) -> std::result::Result<ValueRef, Self::Error> {
+        ) -> error_stack::Result<ValueRef, Self::Error> {
             Ok(self.workflow_input.clone())
         }
+
+        async fn load_blob(&self, blob_id: &BlobId) -> error_stack::Result<ValueRef, Self::Error> {
+            self.blobs
+                .get(blob_id)
+                .cloned()
+                .ok_or_else(|| MockError::Generic(format!("Blob not found: {blob_id}")).into())
+        }
     }
 
     fn create_test_flow() -> Arc<Flow> {
@@ -350,4 +371,31 @@ mod tests {
             _ => panic!("Expected successful result, got: {resolved:?}"),
         }
     }
+
+    #[tokio::test]
+    async fn test_resolve_path_through_blob_ref() {
+        let workflow_input = ValueRef::new(json!({}));
+        let mut loader = MockValueLoader::new(workflow_input.clone());
+        let content = ValueRef::new(json!({"document": {"title": "Large"}}));
+        let blob_id = BlobId::from_content(&content).unwrap();
+        loader.blobs.insert(blob_id.clone(), content);
+        loader
+            .step_results
+            .insert(0, FlowResult::Success(ValueRef::new(blob_id.to_blob_ref())));
+        let resolver =
+            ValueResolver::new(Uuid::new_v4(), workflow_input, loader, create_test_flow());
+
+        // Whole-step references pass the blob reference through unchanged.
+        let template = ValueTemplate::step_ref("step1", JsonPath::default());
+        let resolved = resolver.resolve_template(&template).await.unwrap();
+        assert_eq!(
+            resolved,
+            FlowResult::Success(ValueRef::new(blob_id.to_blob_ref()))
+        );
+
+        // Paths are resolved against the blob content.
+        let template = ValueTemplate::step_ref("step1", JsonPath::from("$.document.title"));
+        let resolved = resolver.resolve_template(&template).await.unwrap();
+        assert_eq!(resolved, FlowResult::Success(ValueRef::new(json!("Large"))));
+    }
 }
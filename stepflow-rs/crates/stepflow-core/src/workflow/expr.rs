@@ -173,10 +173,13 @@ impl Expr {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, utoipa::ToSchema)]
+#[derive(
+    Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema, utoipa::ToSchema,
+)]
 #[serde(rename_all = "camelCase", tag = "action")]
 pub enum SkipAction {
     /// # OnSkipSkip
+    #[default]
     Skip,
     #[serde(rename_all = "camelCase")]
     /// # OnSkipDefault
@@ -186,12 +189,6 @@ pub enum SkipAction {
     },
 }
 
-impl Default for SkipAction {
-    fn default() -> Self {
-        Self::Skip
-    }
-}
-
 impl SkipAction {
     pub fn is_default(&self) -> bool {
         matches!(self, Self::Skip)
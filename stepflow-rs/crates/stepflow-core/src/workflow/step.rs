@@ -49,12 +49,20 @@ pub struct Step {
 }
 
 #[derive(
-    Clone, serde::Serialize, serde::Deserialize, Debug, PartialEq, JsonSchema, utoipa::ToSchema,
+    Clone,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    PartialEq,
+    JsonSchema,
+    utoipa::ToSchema,
 )]
 #[serde(rename_all = "camelCase", tag = "action")]
 pub enum ErrorAction {
     /// # OnErrorFail
     /// If the step fails, the flow will fail.
+    #[default]
     Fail,
     /// # OnErrorSkip
     /// If the step fails, mark it as skipped. This allows down-stream steps to handle the skipped step.
@@ -77,12 +85,6 @@ impl ErrorAction {
     }
 }
 
-impl Default for ErrorAction {
-    fn default() -> Self {
-        Self::Fail
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,182 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+//! Offloading of large step results into blobs.
+//!
+//! Step results larger than the configured threshold are stored as blobs and
+//! replaced by blob references (`{"$blob": "<blob_id>"}`). References are passed
+//! between steps as-is and only resolved when the content is actually needed.
+//!
+//! Plugins that can fetch blobs resolve references themselves: component
+//! servers with `blobRefs` enabled fetch them via `blobs/get` when a component
+//! reads its input, and builtins read them from the state store. The executor
+//! only resolves the inputs of plugins that cannot. References are not
+//! resolved within the protocol layer (its `LazyValue` only defers parsing of
+//! messages), since the component server is the one that knows whether the
+//! content is needed.
+//!
+//! Step results and workflow inputs that merely look like blob references are
+//! escaped when they enter the run (see [`escape_blob_refs`]) and unescaped
+//! when references are resolved.
+
+use std::sync::Arc;
+
+use error_stack::ResultExt as _;
+use stepflow_core::blob::escape_blob_refs;
+use stepflow_core::{BlobType, FlowResult, workflow::ValueRef};
+use stepflow_state::StateStore;
+use uuid::Uuid;
+
+use crate::{ExecutionError, Result};
+
+/// Store a successful result as a blob if it is larger than `threshold` bytes.
+///
+/// Values in the result that look like blob references are escaped first.
+/// The result is otherwise unchanged if offloading is disabled, the result is
+/// not a success, or the serialized value fits within the threshold. The blob
+/// is readable in the namespace of the run that produced it.
+pub(crate) async fn offload_large_result(
+    result: FlowResult,
+    threshold: Option<usize>,
+    run_id: Uuid,
+    state_store: &Arc<dyn StateStore>,
+) -> Result<FlowResult> {
+    let FlowResult::Success(value) = result else {
+        return Ok(result);
+    };
+    let value = escape_blob_refs(value);
+    let Some(threshold) = threshold else {
+        return Ok(FlowResult::Success(value));
+    };
+
+    let size = serde_json::to_vec(value.as_ref())
+        .change_context(ExecutionError::internal("failed to serialize step result"))?
+        .len();
+    if size <= threshold {
+        return Ok(FlowResult::Success(value));
+    }
+
+    let blob_id = state_store
+        .put_blob(value.clone(), BlobType::Data)
+        .await
+        .change_context(ExecutionError::StateError)?;
+    state_store
+        .add_blob_to_run_namespace(run_id, &blob_id)
+        .await
+        .change_context(ExecutionError::StateError)?;
+    tracing::debug!("Offloaded {size} byte step result to blob {blob_id}");
+    Ok(FlowResult::Success(ValueRef::new(blob_id.to_blob_ref())))
+}
+
+/// Replace all blob references within `value` by the blob content, and
+/// unescape values that look like blob references.
+///
+/// See [`stepflow_state::resolve_blob_refs`].
+pub async fn resolve_blob_refs(
+    value: ValueRef,
+    state_store: &Arc<dyn StateStore>,
+) -> Result<ValueRef> {
+    stepflow_state::resolve_blob_refs(value, state_store)
+        .await
+        .change_context(ExecutionError::StateError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use stepflow_core::BlobId;
+    use stepflow_state::InMemoryStateStore;
+
+    #[tokio::test]
+    async fn test_offload_and_resolve() {
+        let state_store: Arc<dyn StateStore> = Arc::new(InMemoryStateStore::new());
+        let large = ValueRef::new(json!({"text": "x".repeat(100)}));
+
+        // Small results and disabled offloading keep the value inline.
+        let result = FlowResult::Success(large.clone());
+        assert_eq!(
+            offload_large_result(result.clone(), None, Uuid::nil(), &state_store)
+                .await
+                .unwrap(),
+            result
+        );
+        assert_eq!(
+            offload_large_result(result.clone(), Some(1000), Uuid::nil(), &state_store)
+                .await
+                .unwrap(),
+            result
+        );
+
+        // Large results are replaced by a blob reference.
+        let offloaded = offload_large_result(result, Some(50), Uuid::nil(), &state_store)
+            .await
+            .unwrap();
+        let FlowResult::Success(blob_ref) = offloaded else {
+            panic!("Expected success, got {offloaded:?}");
+        };
+        let blob_id = BlobId::from_blob_ref(blob_ref.as_ref()).unwrap();
+
+        // Nested references are resolved to the blob content.
+        let input = ValueRef::new(json!({"documents": [blob_ref.as_ref(), "inline"]}));
+        let resolved = resolve_blob_refs(input, &state_store).await.unwrap();
+        assert_eq!(
+            resolved.as_ref(),
+            &json!({"documents": [large.as_ref(), "inline"]})
+        );
+        assert_eq!(state_store.get_blob(&blob_id).await.unwrap().data(), large);
+    }
+
+    #[tokio::test]
+    async fn test_values_like_blob_refs_are_escaped() {
+        let state_store: Arc<dyn StateStore> = Arc::new(InMemoryStateStore::new());
+        let fake_ref = json!({"$blob": "a".repeat(64)});
+        let value = json!({"fake": fake_ref, "escaped": {"$$blob": 1}, "text": "x".repeat(100)});
+
+        // Values that look like references are escaped, whether offloaded or not.
+        let inline = offload_large_result(
+            FlowResult::Success(ValueRef::new(value.clone())),
+            None,
+            Uuid::nil(),
+            &state_store,
+        )
+        .await
+        .unwrap();
+        let FlowResult::Success(inline) = inline else {
+            panic!("Expected success, got {inline:?}");
+        };
+        assert_eq!(
+            inline.as_ref(),
+            &json!({
+                "fake": {"$$blob": "a".repeat(64)},
+                "escaped": {"$$$blob": 1},
+                "text": "x".repeat(100),
+            })
+        );
+        let offloaded = offload_large_result(
+            FlowResult::Success(ValueRef::new(value.clone())),
+            Some(50),
+            Uuid::nil(),
+            &state_store,
+        )
+        .await
+        .unwrap();
+        let FlowResult::Success(blob_ref) = offloaded else {
+            panic!("Expected success, got {offloaded:?}");
+        };
+
+        // Resolving restores the original values without following the fake reference.
+        let input = ValueRef::new(json!([inline.as_ref(), blob_ref.as_ref()]));
+        let resolved = resolve_blob_refs(input, &state_store).await.unwrap();
+        assert_eq!(resolved.as_ref(), &json!([value, value]));
+    }
+}
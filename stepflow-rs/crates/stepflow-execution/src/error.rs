@@ -61,6 +61,11 @@ pub enum ExecutionError {
     WorkflowNotFound(BlobId),
     #[error("failed to resolve value")]
     ValueResolverFailure,
+    #[error("namespace '{namespace}' already has {max_active_runs} active runs")]
+    QuotaExceeded {
+        namespace: String,
+        max_active_runs: usize,
+    },
 }
 
 impl ExecutionError {
@@ -10,35 +10,91 @@
 // or implied. See the License for the specific language governing permissions and limitations under
 // the License.
 
-use std::{collections::HashMap, path::PathBuf, sync::Arc};
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
 
-use crate::workflow_executor::{WorkflowExecutor, execute_workflow};
+use crate::workflow_executor::{WorkflowExecutor, execute_recorded_run, record_run};
 use crate::{ExecutionError, Result};
 use error_stack::ResultExt as _;
 use futures::future::{BoxFuture, FutureExt as _};
+use stepflow_analysis::{AnalysisCache, DEFAULT_ANALYSIS_CACHE_SIZE};
 use stepflow_core::BlobId;
 use stepflow_core::{
     FlowError, FlowResult,
     workflow::{Component, Flow, ValueRef},
 };
-use stepflow_plugin::{Context, DynPlugin, ExecutionContext, Plugin as _, routing::PluginRouter};
-use stepflow_state::{InMemoryStateStore, StateStore};
+use stepflow_plugin::{
+    Context, DynPlugin, ExecutionContext, Plugin as _, PluginError, routing::PluginRouter,
+};
+use stepflow_state::{CreateRunParams, DEFAULT_NAMESPACE, InMemoryStateStore, StateStore};
 use tokio::sync::{RwLock, oneshot};
+use tracing::Instrument as _;
 use uuid::Uuid;
 
 type FutureFlowResult = futures::future::Shared<oneshot::Receiver<FlowResult>>;
 
+/// Options controlling how the executor runs flows.
+#[derive(Debug, Clone, Default)]
+pub struct ExecutorOptions {
+    /// Size in bytes above which step results are stored as blobs and passed
+    /// between steps as blob references. If not set, results are always inline.
+    pub blob_threshold: Option<usize>,
+    /// Maximum number of flow analyses kept in memory. If not set,
+    /// [`DEFAULT_ANALYSIS_CACHE_SIZE`] analyses are kept.
+    pub analysis_cache_size: Option<usize>,
+}
+
+/// Settings overriding the executor's defaults for runs in one namespace.
+#[derive(Default)]
+pub struct NamespaceSettings {
+    /// Plugin router used for runs in the namespace instead of the default one.
+    pub plugin_router: Option<PluginRouter>,
+    /// Maximum number of top-level runs executing in the namespace at once.
+    ///
+    /// Nested runs are part of their parent and do not count towards the limit.
+    pub max_active_runs: Option<usize>,
+}
+
+/// A top-level run counted against the active runs of its namespace until dropped.
+struct ActiveRun {
+    executor: Arc<StepflowExecutor>,
+    namespace: String,
+}
+
+impl Drop for ActiveRun {
+    fn drop(&mut self) {
+        let mut active_runs = self
+            .executor
+            .active_runs
+            .lock()
+            .expect("active runs poisoned");
+        if let Some(count) = active_runs.get_mut(&self.namespace) {
+            *count = count.saturating_sub(1);
+        }
+    }
+}
+
 /// Main executor of Stepflow flows.
 pub struct StepflowExecutor {
     state_store: Arc<dyn StateStore>,
     working_directory: PathBuf,
     plugin_router: PluginRouter,
+    options: ExecutorOptions,
+    /// Settings of namespaces that override the defaults
+    namespaces: HashMap<String, NamespaceSettings>,
+    /// Number of top-level runs executing in each namespace
+    active_runs: Mutex<HashMap<String, usize>>,
     /// Pending flows and their result futures.
     // TODO: Should treat this as a cache and evict old executions.
     // TODO: Should write execution state to the state store for persistence.
     pending: Arc<RwLock<HashMap<Uuid, FutureFlowResult>>>,
     /// Active debug sessions for step-by-step execution control
     debug_sessions: Arc<RwLock<HashMap<Uuid, WorkflowExecutor>>>,
+    /// Analysis results of flows, computed once per flow ID
+    analysis_cache: AnalysisCache,
     // Keep a weak reference to self for spawning tasks without circular references
     self_weak: std::sync::Weak<Self>,
 }
@@ -50,22 +106,78 @@ impl StepflowExecutor {
         working_directory: PathBuf,
         plugin_router: PluginRouter,
     ) -> Arc<Self> {
+        Self::with_options(
+            state_store,
+            working_directory,
+            plugin_router,
+            ExecutorOptions::default(),
+        )
+    }
+
+    /// Create a new stepflow executor with the given options.
+    pub fn with_options(
+        state_store: Arc<dyn StateStore>,
+        working_directory: PathBuf,
+        plugin_router: PluginRouter,
+        options: ExecutorOptions,
+    ) -> Arc<Self> {
+        Self::with_namespaces(
+            state_store,
+            working_directory,
+            plugin_router,
+            options,
+            HashMap::new(),
+        )
+    }
+
+    /// Create a new stepflow executor with the given options and namespace settings.
+    ///
+    /// Namespaces without settings use the default plugin router and have no quota.
+    pub fn with_namespaces(
+        state_store: Arc<dyn StateStore>,
+        working_directory: PathBuf,
+        plugin_router: PluginRouter,
+        options: ExecutorOptions,
+        namespaces: HashMap<String, NamespaceSettings>,
+    ) -> Arc<Self> {
+        let analysis_cache = AnalysisCache::with_capacity(
+            options
+                .analysis_cache_size
+                .unwrap_or(DEFAULT_ANALYSIS_CACHE_SIZE),
+        );
         Arc::new_cyclic(|weak| Self {
             state_store,
             working_directory,
             plugin_router,
+            options,
+            namespaces,
+            active_runs: Mutex::new(HashMap::new()),
             pending: Arc::new(RwLock::new(HashMap::new())),
             debug_sessions: Arc::new(RwLock::new(HashMap::new())),
+            analysis_cache,
             self_weak: weak.clone(),
         })
     }
 
-    /// Initialize all plugins in the plugin router
+    /// Initialize all plugins in the plugin routers
     pub async fn initialize_plugins(&self) -> Result<()> {
         let context: Arc<dyn Context> = self.executor();
 
+        // Namespace routers may share plugins with the default router.
+        let mut plugins: Vec<&Arc<DynPlugin<'static>>> = Vec::new();
+        let routers = std::iter::once(&self.plugin_router).chain(
+            self.namespaces
+                .values()
+                .filter_map(|settings| settings.plugin_router.as_ref()),
+        );
+        for plugin in routers.flat_map(PluginRouter::plugins) {
+            if !plugins.iter().any(|known| Arc::ptr_eq(known, plugin)) {
+                plugins.push(plugin);
+            }
+        }
+
         // Initialize each unique plugin once
-        for plugin in self.plugin_router.plugins() {
+        for plugin in plugins {
             plugin
                 .init(&context)
                 .await
@@ -104,13 +216,19 @@ impl StepflowExecutor {
         self.state_store.clone()
     }
 
+    /// Get the options this executor was created with.
+    pub fn options(&self) -> &ExecutorOptions {
+        &self.options
+    }
+
     pub async fn get_plugin_and_component(
         &self,
+        namespace: &str,
         component: &Component,
         input: ValueRef,
-    ) -> Result<(&Arc<DynPlugin<'static>>, String)> {
+    ) -> Result<(&str, &Arc<DynPlugin<'static>>, String)> {
         // Use the integrated plugin router to get the plugin and resolved component name
-        self.plugin_router
+        self.plugin_router_for(namespace)
             .get_plugin_and_component(component.path(), input)
             .change_context(ExecutionError::RouterError)
     }
@@ -125,6 +243,138 @@ impl StepflowExecutor {
         &self.plugin_router
     }
 
+    /// Get the plugin router used for runs in `namespace`
+    pub fn plugin_router_for(&self, namespace: &str) -> &PluginRouter {
+        self.namespaces
+            .get(namespace)
+            .and_then(|settings| settings.plugin_router.as_ref())
+            .unwrap_or(&self.plugin_router)
+    }
+
+    /// Get the number of top-level runs executing in `namespace`
+    pub fn active_runs(&self, namespace: &str) -> usize {
+        let active_runs = self.active_runs.lock().expect("active runs poisoned");
+        active_runs.get(namespace).copied().unwrap_or(0)
+    }
+
+    /// Count a new top-level run in `namespace`, failing if its quota is used up.
+    fn start_active_run(&self, namespace: &str) -> Result<ActiveRun> {
+        let max_active_runs = self
+            .namespaces
+            .get(namespace)
+            .and_then(|settings| settings.max_active_runs);
+
+        let mut active_runs = self.active_runs.lock().expect("active runs poisoned");
+        let count = active_runs.entry(namespace.to_string()).or_default();
+        if let Some(max_active_runs) = max_active_runs
+            && *count >= max_active_runs
+        {
+            return Err(error_stack::report!(ExecutionError::QuotaExceeded {
+                namespace: namespace.to_string(),
+                max_active_runs,
+            }));
+        }
+        *count += 1;
+
+        Ok(ActiveRun {
+            executor: self.executor(),
+            namespace: namespace.to_string(),
+        })
+    }
+
+    /// Start executing a workflow in `namespace` in the background and return its run ID.
+    ///
+    /// Fails with [`ExecutionError::QuotaExceeded`] if the namespace already has
+    /// as many active runs as its settings allow.
+    pub async fn submit_namespaced_flow(
+        &self,
+        namespace: &str,
+        flow: Arc<Flow>,
+        flow_id: BlobId,
+        input: ValueRef,
+    ) -> Result<Uuid> {
+        let params = CreateRunParams {
+            namespace: namespace.to_string(),
+            ..CreateRunParams::new(Uuid::new_v4(), flow_id, input)
+        };
+        self.submit_run(flow, params).await
+    }
+
+    /// Start executing a top-level run described by `params` in the background.
+    ///
+    /// The run is recorded under `params.run_id` before this returns, so callers
+    /// can refer to it right away. Fails with [`ExecutionError::QuotaExceeded`]
+    /// if the run's namespace already has as many active runs as its settings allow.
+    pub async fn submit_run(&self, flow: Arc<Flow>, params: CreateRunParams) -> Result<Uuid> {
+        let active_run = self.start_active_run(&params.namespace)?;
+        self.submit(flow, params, Some(active_run)).await
+    }
+
+    /// Get the cache of flow analysis results
+    pub fn analysis_cache(&self) -> &AnalysisCache {
+        &self.analysis_cache
+    }
+
+    /// Record a run and start executing it in the background, returning its run ID.
+    async fn submit(
+        &self,
+        flow: Arc<Flow>,
+        params: CreateRunParams,
+        active_run: Option<ActiveRun>,
+    ) -> Result<Uuid> {
+        let executor = self.executor();
+
+        let run_id = params.run_id;
+        record_run(&flow, params.clone(), &self.state_store).await?;
+        let (tx, rx) = oneshot::channel();
+
+        // Store the receiver for later retrieval
+        {
+            let mut pending = self.pending.write().await;
+            pending.insert(run_id, rx.shared());
+        }
+
+        // Spawn the execution
+        tokio::spawn(
+            async move {
+                tracing::info!("Executing workflow using tracker-based execution");
+                let state_store = executor.state_store.clone();
+
+                let result = execute_recorded_run(executor, flow, params, state_store).await;
+                drop(active_run);
+
+                let flow_result = match result {
+                    Ok(flow_result) => flow_result,
+                    Err(e) => {
+                        if let Some(error) = e.downcast_ref::<FlowError>().cloned() {
+                            FlowResult::Failed(error)
+                        } else {
+                            tracing::error!(?e, "Flow execution failed");
+                            FlowResult::Failed(stepflow_core::FlowError::new(
+                                500,
+                                format!("Flow execution failed: {e}"),
+                            ))
+                        }
+                    }
+                };
+
+                // Send the result back
+                let _ = tx.send(flow_result);
+            }
+            .in_current_span(),
+        );
+
+        Ok(run_id)
+    }
+
+    /// Forget in-process state kept for a run, such as its pending result.
+    ///
+    /// Used when the run is deleted from the state store.
+    pub async fn forget_run(&self, run_id: Uuid) {
+        self.pending.write().await.remove(&run_id);
+        self.debug_sessions.write().await.remove(&run_id);
+    }
+
     /// Get or create a debug session for step-by-step execution control
     pub async fn debug_session(&self, run_id: Uuid) -> Result<WorkflowExecutor> {
         // Check if session already exists
@@ -164,7 +414,8 @@ impl StepflowExecutor {
             run_id,
             execution.input,
             self.state_store.clone(),
-        )?;
+        )?
+        .with_namespace(execution.summary.namespace);
 
         // Recover state from the state store to ensure consistency
         let corrections_made = workflow_executor.recover_from_state_store().await?;
@@ -199,46 +450,46 @@ impl Context for StepflowExecutor {
         flow_id: BlobId,
         input: ValueRef,
     ) -> BoxFuture<'_, stepflow_plugin::Result<Uuid>> {
-        let executor = self.executor();
-
         async move {
-            let run_id = Uuid::new_v4();
-            let (tx, rx) = oneshot::channel();
-
-            // Store the receiver for later retrieval
-            {
-                let mut pending = self.pending.write().await;
-                pending.insert(run_id, rx.shared());
-            }
-
-            // Spawn the execution
-            tokio::spawn(async move {
-                tracing::info!("Executing workflow using tracker-based execution");
-                let state_store = executor.state_store.clone();
-
-                let result =
-                    execute_workflow(executor, flow, flow_id, run_id, input, state_store).await;
-
-                let flow_result = match result {
-                    Ok(flow_result) => flow_result,
-                    Err(e) => {
-                        if let Some(error) = e.downcast_ref::<FlowError>().cloned() {
-                            FlowResult::Failed(error)
-                        } else {
-                            tracing::error!(?e, "Flow execution failed");
-                            FlowResult::Failed(stepflow_core::FlowError::new(
-                                500,
-                                format!("Flow execution failed: {e}"),
-                            ))
-                        }
-                    }
-                };
-
-                // Send the result back
-                let _ = tx.send(flow_result);
-            });
+            self.submit_namespaced_flow(DEFAULT_NAMESPACE, flow, flow_id, input)
+                .await
+                .change_context(PluginError::Execution)
+        }
+        .boxed()
+    }
 
-            Ok(run_id)
+    /// Submits a workflow nested within the run `parent_run_id`.
+    ///
+    /// The parent run and step are recorded with the run so it can be found from
+    /// the parent, and the nested run belongs to the parent's namespace and run tree.
+    fn submit_nested_flow(
+        &self,
+        parent_run_id: Uuid,
+        parent_step_id: Option<String>,
+        flow: Arc<Flow>,
+        flow_id: BlobId,
+        input: ValueRef,
+    ) -> BoxFuture<'_, stepflow_plugin::Result<Uuid>> {
+        async move {
+            let parent = self
+                .state_store
+                .get_run(parent_run_id)
+                .await
+                .change_context(PluginError::Execution)?
+                .ok_or_else(|| {
+                    error_stack::report!(PluginError::Execution)
+                        .attach_printable(format!("parent run '{parent_run_id}' not found"))
+                })?;
+            let params = CreateRunParams {
+                namespace: parent.summary.namespace,
+                parent_run_id: Some(parent_run_id),
+                parent_step_id,
+                root_run_id: parent.summary.root_run_id,
+                ..CreateRunParams::new(Uuid::new_v4(), flow_id, input)
+            };
+            self.submit(flow, params, None)
+                .await
+                .change_context(PluginError::Execution)
         }
         .boxed()
     }
@@ -350,4 +601,122 @@ mod tests {
         let retrieved_executor = executor.state_store().get_blob(&blob_id).await.unwrap();
         assert_eq!(retrieved_executor.data().as_ref(), &test_data);
     }
+
+    #[tokio::test]
+    async fn test_namespace_quota() {
+        use stepflow_core::workflow::FlowBuilder;
+
+        let mut namespaces = HashMap::new();
+        namespaces.insert(
+            "frozen".to_string(),
+            NamespaceSettings {
+                max_active_runs: Some(0),
+                ..NamespaceSettings::default()
+            },
+        );
+        let executor = StepflowExecutor::with_namespaces(
+            Arc::new(InMemoryStateStore::new()),
+            PathBuf::from("."),
+            PluginRouter::builder().build().unwrap(),
+            ExecutorOptions::default(),
+            namespaces,
+        );
+        let flow = Arc::new(FlowBuilder::test_flow().build());
+        let flow_id = BlobId::from_flow(flow.as_ref()).unwrap();
+
+        let err = executor
+            .submit_namespaced_flow(
+                "frozen",
+                flow.clone(),
+                flow_id.clone(),
+                ValueRef::new(json!({})),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.current_context(),
+            ExecutionError::QuotaExceeded {
+                max_active_runs: 0,
+                ..
+            }
+        ));
+
+        // Namespaces without a quota are unaffected, and runs are recorded in their namespace.
+        let run_id = executor
+            .submit_namespaced_flow("team-a", flow, flow_id, ValueRef::new(json!({})))
+            .await
+            .unwrap();
+        executor.flow_result(run_id).await.unwrap();
+        let run = executor
+            .state_store()
+            .get_run(run_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(run.summary.namespace, "team-a");
+        assert_eq!(executor.active_runs("team-a"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_nested_run_lineage() {
+        use stepflow_core::workflow::FlowBuilder;
+
+        let executor = StepflowExecutor::new_in_memory();
+        let flow = Arc::new(FlowBuilder::test_flow().build());
+        let flow_id = BlobId::from_flow(flow.as_ref()).unwrap();
+
+        // Top-level runs are recorded under the requested ID before they execute.
+        let root = Uuid::new_v4();
+        let params = CreateRunParams {
+            namespace: "team-a".to_string(),
+            ..CreateRunParams::new(root, flow_id.clone(), ValueRef::new(json!({})))
+        };
+        assert_eq!(
+            executor.submit_run(flow.clone(), params).await.unwrap(),
+            root
+        );
+        assert!(
+            executor
+                .state_store()
+                .get_run(root)
+                .await
+                .unwrap()
+                .is_some()
+        );
+
+        // Runs submitted by a step record the step and belong to the parent's tree.
+        let context = ExecutionContext::for_step(executor.clone(), root, "map".to_string());
+        let child = context
+            .submit_flow(flow.clone(), flow_id.clone(), ValueRef::new(json!({})))
+            .await
+            .unwrap();
+        let grandchild = executor
+            .submit_nested_flow(child, None, flow, flow_id, ValueRef::new(json!({})))
+            .await
+            .unwrap();
+        for run_id in [root, child, grandchild] {
+            executor.flow_result(run_id).await.unwrap();
+        }
+
+        let child = executor
+            .state_store()
+            .get_run(child)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(child.summary.parent_run_id, Some(root));
+        assert_eq!(child.summary.parent_step_id.as_deref(), Some("map"));
+        assert_eq!(child.summary.root_run_id, root);
+        assert_eq!(child.summary.namespace, "team-a");
+
+        let grandchild = executor
+            .state_store()
+            .get_run(grandchild)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(grandchild.summary.parent_run_id, Some(child.summary.run_id));
+        assert_eq!(grandchild.summary.parent_step_id, None);
+        assert_eq!(grandchild.summary.root_run_id, root);
+    }
 }
@@ -10,13 +10,17 @@
 // or implied. See the License for the specific language governing permissions and limitations under
 // the License.
 
+mod blob_refs;
 mod error;
 mod executor;
+mod metrics;
 mod state_value_loader;
 mod workflow_executor;
 mod write_cache;
 
+pub use blob_refs::resolve_blob_refs;
 pub use error::{ExecutionError, Result};
-pub use executor::StepflowExecutor;
+pub use executor::{ExecutorOptions, NamespaceSettings, StepflowExecutor};
+pub use metrics::describe_metrics;
 pub use state_value_loader::StateValueLoader;
 pub use workflow_executor::{StepExecutionResult, StepInspection, StepMetadata, WorkflowExecutor};
@@ -0,0 +1,98 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+//! Metrics recorded while executing runs.
+//!
+//! Metrics are recorded through the [`metrics`] facade and are discarded
+//! unless a recorder has been installed.
+
+use std::time::Instant;
+
+use metrics::{Unit, describe_counter, describe_gauge, describe_histogram};
+use stepflow_core::FlowResult;
+
+pub const RUNS_STARTED: &str = "stepflow_runs_started_total";
+pub const RUNS_COMPLETED: &str = "stepflow_runs_completed_total";
+pub const RUNS_FAILED: &str = "stepflow_runs_failed_total";
+pub const STEP_DURATION: &str = "stepflow_step_duration_seconds";
+pub const STEPS_IN_FLIGHT: &str = "stepflow_steps_in_flight";
+
+/// Register descriptions of the execution metrics with the installed recorder.
+pub fn describe_metrics() {
+    describe_counter!(RUNS_STARTED, "Number of runs started, by flow name.");
+    describe_counter!(
+        RUNS_COMPLETED,
+        "Number of runs that completed successfully, by flow name."
+    );
+    describe_counter!(
+        RUNS_FAILED,
+        "Number of runs that failed or errored, by flow name."
+    );
+    describe_histogram!(
+        STEP_DURATION,
+        Unit::Seconds,
+        "Duration of step executions, by component path and plugin."
+    );
+    describe_gauge!(STEPS_IN_FLIGHT, "Number of steps currently executing.");
+}
+
+/// Label value for runs of flows without a name.
+fn flow_name_label(flow_name: Option<&str>) -> String {
+    flow_name.unwrap_or("").to_string()
+}
+
+/// Record that a run of the given flow has started.
+pub(crate) fn record_run_started(flow_name: Option<&str>) {
+    metrics::counter!(RUNS_STARTED, "flow_name" => flow_name_label(flow_name)).increment(1);
+}
+
+/// Record the outcome of a run of the given flow.
+pub(crate) fn record_run_finished<E>(flow_name: Option<&str>, result: &Result<FlowResult, E>) {
+    let name = match result {
+        Ok(FlowResult::Success(_)) | Ok(FlowResult::Skipped { .. }) => RUNS_COMPLETED,
+        Ok(FlowResult::Failed(_)) | Err(_) => RUNS_FAILED,
+    };
+    metrics::counter!(name, "flow_name" => flow_name_label(flow_name)).increment(1);
+}
+
+/// Tracks a step while it executes.
+///
+/// The step counts as in flight until the guard is dropped, at which point
+/// its duration is recorded.
+pub(crate) struct StepGuard {
+    component: String,
+    plugin: String,
+    start: Instant,
+}
+
+impl StepGuard {
+    pub(crate) fn start(component: &str, plugin: &str) -> Self {
+        metrics::gauge!(STEPS_IN_FLIGHT).increment(1);
+        Self {
+            component: component.to_string(),
+            plugin: plugin.to_string(),
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for StepGuard {
+    fn drop(&mut self) {
+        metrics::gauge!(STEPS_IN_FLIGHT).decrement(1);
+        metrics::histogram!(
+            STEP_DURATION,
+            "component" => std::mem::take(&mut self.component),
+            "plugin" => std::mem::take(&mut self.plugin),
+        )
+        .record(self.start.elapsed());
+    }
+}
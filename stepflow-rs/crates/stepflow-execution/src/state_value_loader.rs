@@ -13,8 +13,9 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use error_stack::ResultExt as _;
 use stepflow_core::{
-    FlowResult,
+    BlobId, FlowResult,
     values::{ValueLoader, ValueRef},
     workflow::{Flow, StepId},
 };
@@ -60,26 +61,31 @@ impl ValueLoader for StateValueLoader {
         &self,
         run_id: Uuid,
         step_index: usize,
-    ) -> std::result::Result<FlowResult, Self::Error> {
+    ) -> error_stack::Result<FlowResult, Self::Error> {
         let step_id = StepId {
             index: step_index,
             flow: self.flow.clone(),
         };
 
-        match self
-            .write_cache
+        self.write_cache
             .get_step_result_with_fallback(&step_id, run_id, &self.state_store)
             .await
-        {
-            Ok(result) => Ok(result),
-            Err(_e) => Err(ExecutionError::StateError),
-        }
+            .change_context(ExecutionError::StateError)
     }
 
     async fn load_workflow_input(
         &self,
         _run_id: Uuid,
-    ) -> std::result::Result<ValueRef, Self::Error> {
+    ) -> error_stack::Result<ValueRef, Self::Error> {
         Ok(self.input.clone())
     }
+
+    async fn load_blob(&self, blob_id: &BlobId) -> error_stack::Result<ValueRef, Self::Error> {
+        let blob = self
+            .state_store
+            .get_blob(blob_id)
+            .await
+            .change_context(ExecutionError::StateError)?;
+        Ok(blob.data())
+    }
 }
@@ -16,59 +16,115 @@ use bit_set::BitSet;
 use error_stack::ResultExt as _;
 use futures::{StreamExt as _, future::BoxFuture, stream::FuturesUnordered};
 use stepflow_core::BlobId;
-use stepflow_core::status::{StepExecution, StepStatus};
+use stepflow_core::status::{ExecutionStatus, StepExecution, StepStatus};
 use stepflow_core::{
     FlowResult,
     values::{ValueRef, ValueResolver, ValueTemplate},
     workflow::{Expr, Flow},
 };
 use stepflow_plugin::{DynPlugin, ExecutionContext, Plugin as _};
-use stepflow_state::{StateStore, StepResult};
+use stepflow_state::{CreateRunParams, DEFAULT_NAMESPACE, StateStore, StepResult};
+use tracing::Instrument as _;
 use uuid::Uuid;
 
-use crate::{ExecutionError, Result, StateValueLoader, StepflowExecutor, write_cache::WriteCache};
+use crate::{
+    ExecutionError, Result, StateValueLoader, StepflowExecutor, blob_refs, metrics,
+    write_cache::WriteCache,
+};
 
 /// Execute a workflow and return the result.
+///
+/// The run is recorded using `params`, with the flow name taken from `flow`.
+#[cfg(test)]
 pub(crate) async fn execute_workflow(
     executor: Arc<StepflowExecutor>,
     flow: Arc<Flow>,
-    flow_id: BlobId,
-    run_id: Uuid,
-    input: ValueRef,
+    params: CreateRunParams,
     state_store: Arc<dyn StateStore>,
 ) -> Result<FlowResult> {
+    record_run(&flow, params.clone(), &state_store).await?;
+    execute_recorded_run(executor, flow, params, state_store).await
+}
+
+/// Store the flow and create the record of a run of it.
+///
+/// The flow name is taken from `flow`.
+pub(crate) async fn record_run(
+    flow: &Arc<Flow>,
+    params: CreateRunParams,
+    state_store: &Arc<dyn StateStore>,
+) -> Result<()> {
     // Store workflow first (this is idempotent if workflow already exists)
     let computed_hash = state_store
-        .store_flow(flow.clone())
+        .store_flow(&params.namespace, flow.clone())
         .await
         .change_context(ExecutionError::StateError)?;
 
     // Verify the hash matches (should be the same if workflow is deterministic)
-    if computed_hash != flow_id {
+    if computed_hash != params.flow_id {
         tracing::warn!(
             "Flow hash mismatch: expected {}, computed {}",
-            flow_id,
+            params.flow_id,
             computed_hash
         );
     }
 
     // Create run record in state store before starting workflow
     state_store
-        .create_run(
-            run_id,
-            flow_id.clone(),
-            flow.name(),
-            None,  // No label for direct execution
-            false, // Not debug mode
-            input.clone(),
-        )
+        .create_run(CreateRunParams {
+            flow_name: flow.name().map(str::to_owned),
+            ..params
+        })
         .await
         .change_context(ExecutionError::StateError)?;
+    Ok(())
+}
 
-    let mut workflow_executor =
-        WorkflowExecutor::new(executor, flow, flow_id, run_id, input, state_store)?;
+/// Execute a run recorded by [`record_run`] and record its outcome.
+pub(crate) async fn execute_recorded_run(
+    executor: Arc<StepflowExecutor>,
+    flow: Arc<Flow>,
+    params: CreateRunParams,
+    state_store: Arc<dyn StateStore>,
+) -> Result<FlowResult> {
+    let CreateRunParams {
+        run_id,
+        flow_id,
+        namespace,
+        input,
+        ..
+    } = params;
+
+    let span = tracing::info_span!("run", %run_id, %flow_id, %namespace, flow_name = flow.name());
+    let flow_name = flow.name().map(str::to_owned);
+    metrics::record_run_started(flow_name.as_deref());
+
+    let result = async {
+        let mut workflow_executor =
+            WorkflowExecutor::new(executor, flow, flow_id, run_id, input, state_store.clone())?
+                .with_namespace(namespace);
+        workflow_executor.execute_to_completion().await
+    }
+    .instrument(span)
+    .await;
+    metrics::record_run_finished(flow_name.as_deref(), &result);
+
+    // Record the outcome so the run no longer counts as running.
+    let (status, output) = match &result {
+        Ok(FlowResult::Success(output)) => (ExecutionStatus::Completed, Some(output.clone())),
+        Ok(_) | Err(_) => (ExecutionStatus::Failed, None),
+    };
+    if let Err(e) = state_store.update_run_status(run_id, status, output).await {
+        tracing::warn!(%run_id, "Failed to record run status: {e:?}");
+    } else if let Err(e) = state_store
+        .record_run_callback(run_id, result.as_ref().ok().cloned())
+        .await
+    {
+        // The delivery itself is sent by the server, which polls for pending deliveries.
+        tracing::warn!(%run_id, "Failed to record run callback delivery: {e:?}");
+    }
 
-    workflow_executor.execute_to_completion().await
+    result
 }
 
 /// Workflow executor that manages the execution of a single workflow.
@@ -90,6 +146,8 @@ pub struct WorkflowExecutor {
     context: ExecutionContext,
     /// Write-through cache for avoiding unnecessary flushes
     write_cache: WriteCache,
+    /// Namespace of the run, selecting the plugin router for its steps
+    namespace: String,
 }
 
 impl WorkflowExecutor {
@@ -103,11 +161,12 @@ impl WorkflowExecutor {
         state_store: Arc<dyn StateStore>,
     ) -> Result<Self> {
         // Build dependencies for the workflow using the analysis crate
-        let analysis_result =
-            stepflow_analysis::analyze_flow_dependencies(flow.clone(), flow_id.clone())
-                .change_context(ExecutionError::AnalysisError)?;
+        let analysis_result = executor
+            .analysis_cache()
+            .get_or_analyze(&flow, &flow_id)
+            .change_context(ExecutionError::AnalysisError)?;
 
-        let analysis = match analysis_result.analysis {
+        let analysis = match &analysis_result.analysis {
             Some(analysis) => analysis,
             None => {
                 // Convert validation failure to execution error
@@ -126,7 +185,9 @@ impl WorkflowExecutor {
         // Create write cache and step ID mapping
         let write_cache = WriteCache::new(flow.steps().len());
 
-        // Create state value loader and value resolver
+        // Create state value loader and value resolver. Input values that look
+        // like blob references must not be resolved as references.
+        let input = stepflow_core::blob::escape_blob_refs(input);
         let state_loader = StateValueLoader::new(
             input.clone(),
             state_store.clone(),
@@ -151,9 +212,16 @@ impl WorkflowExecutor {
             write_cache,
             flow,
             context,
+            namespace: DEFAULT_NAMESPACE.to_string(),
         })
     }
 
+    /// Execute the run within `namespace`, routing its steps with the namespace's plugin router.
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = namespace.into();
+        self
+    }
+
     /// Get the execution ID for this executor.
     pub fn run_id(&self) -> Uuid {
         self.context.run_id()
@@ -647,13 +715,14 @@ impl WorkflowExecutor {
         };
 
         // Get plugin and resolved component name
-        let (plugin, resolved_component) = self
+        let (plugin_name, plugin, resolved_component) = self
             .executor
-            .get_plugin_and_component(&step.component, step_input.clone())
+            .get_plugin_and_component(&self.namespace, &step.component, step_input.clone())
             .await?;
         // Create step-specific execution context reusing the workflow context
         let step_context = self.context.with_step(step_id.clone());
 
+        let step_guard = metrics::StepGuard::start(step.component.path(), plugin_name);
         let result = execute_step_async(
             plugin,
             step,
@@ -661,8 +730,11 @@ impl WorkflowExecutor {
             step_input,
             step_context,
             &self.resolver,
+            self.executor.options().blob_threshold,
         )
-        .await?;
+        .await;
+        drop(step_guard);
+        let result = result?;
 
         // Record the result
         self.record_step_completion(step_index, &result).await?;
@@ -702,11 +774,20 @@ impl WorkflowExecutor {
     }
 
     /// Resolve the workflow output.
+    ///
+    /// Blob references to offloaded step results are replaced by their content.
     pub async fn resolve_workflow_output(&self) -> Result<FlowResult> {
-        self.resolver
+        let output = self
+            .resolver
             .resolve_template(self.flow.output())
             .await
-            .change_context(ExecutionError::ValueResolverFailure)
+            .change_context(ExecutionError::ValueResolverFailure)?;
+        match output {
+            FlowResult::Success(value) => Ok(FlowResult::Success(
+                blob_refs::resolve_blob_refs(value, &self.state_store).await?,
+            )),
+            other => Ok(other),
+        }
     }
 
     /// Get access to the state store for querying step results.
@@ -863,15 +944,17 @@ impl WorkflowExecutor {
         tracing::debug!("Starting execution of step {}", step.id);
 
         // Get plugin and resolved component name for this step
-        let (plugin, resolved_component) = self
+        let (plugin_name, plugin, resolved_component) = self
             .executor
-            .get_plugin_and_component(&step.component, step_input.clone())
+            .get_plugin_and_component(&self.namespace, &step.component, step_input.clone())
             .await?;
+        let step_guard = metrics::StepGuard::start(step.component.path(), plugin_name);
 
         // Clone necessary data for the async task
         let flow = self.flow.clone();
         let base_context = self.context.clone();
         let resolver = self.resolver.clone();
+        let blob_threshold = self.executor.options().blob_threshold;
 
         // Create the async task
         let plugin_clone = plugin.clone();
@@ -887,8 +970,10 @@ impl WorkflowExecutor {
                 step_input,
                 step_context,
                 &resolver,
+                blob_threshold,
             )
             .await;
+            drop(step_guard);
             (step_index, result)
         });
 
@@ -906,10 +991,47 @@ pub(crate) async fn execute_step_async(
     input: ValueRef,
     context: ExecutionContext,
     resolver: &ValueResolver<StateValueLoader>,
+    blob_threshold: Option<usize>,
+) -> Result<FlowResult> {
+    let span = tracing::info_span!(
+        "step",
+        step_id = %step.id,
+        component = resolved_component,
+        run_id = %context.run_id()
+    );
+    execute_step_in_span(
+        plugin,
+        step,
+        resolved_component,
+        input,
+        context,
+        resolver,
+        blob_threshold,
+    )
+    .instrument(span)
+    .await
+}
+
+async fn execute_step_in_span(
+    plugin: &Arc<DynPlugin<'static>>,
+    step: &stepflow_core::workflow::Step,
+    resolved_component: &str,
+    input: ValueRef,
+    context: ExecutionContext,
+    resolver: &ValueResolver<StateValueLoader>,
+    blob_threshold: Option<usize>,
 ) -> Result<FlowResult> {
     // Create a component from the resolved component name
     let component = stepflow_core::workflow::Component::from_string(resolved_component);
 
+    let state_store = context.state_store().clone();
+    let run_id = context.run_id();
+    let input = if plugin.supports_blob_refs() {
+        input
+    } else {
+        blob_refs::resolve_blob_refs(input, &state_store).await?
+    };
+
     // Execute the component
     let result = plugin
         .execute(&component, context, input)
@@ -918,7 +1040,7 @@ pub(crate) async fn execute_step_async(
             step: step.id.to_owned(),
         })?;
 
-    match &result {
+    let result: Result<FlowResult> = match &result {
         FlowResult::Failed(error) => {
             match &step.on_error {
                 stepflow_core::workflow::ErrorAction::Skip => {
@@ -970,7 +1092,9 @@ pub(crate) async fn execute_step_async(
             }
         }
         _ => Ok(result),
-    }
+    };
+
+    blob_refs::offload_large_result(result?, blob_threshold, run_id, &state_store).await
 }
 
 /// Basic metadata about a step.
@@ -1101,7 +1225,13 @@ mod tests {
         let state_store: Arc<dyn StateStore> = Arc::new(InMemoryStateStore::new());
         let input_ref = ValueRef::new(input);
 
-        execute_workflow(executor, flow, flow_id, run_id, input_ref, state_store).await
+        execute_workflow(
+            executor,
+            flow,
+            CreateRunParams::new(run_id, flow_id, input_ref),
+            state_store,
+        )
+        .await
     }
 
     /// Create a WorkflowExecutor from YAML string for step-by-step testing
@@ -1159,6 +1289,117 @@ mod tests {
         // This confirms the tracker integration is working
     }
 
+    #[tokio::test]
+    async fn test_large_results_offloaded_to_blobs() {
+        let workflow_yaml = r#"
+schema: https://stepflow.org/schemas/v1/flow.json
+steps:
+  - id: produce
+    component: /mock/produce
+    input: {}
+  - id: consume
+    component: /mock/consume
+    input:
+      $from:
+        step: produce
+output:
+  produced:
+    $from:
+      step: produce
+  title:
+    $from:
+      step: produce
+    path: title
+  consumed:
+    $from:
+      step: consume
+"#;
+        let flow: Arc<Flow> = Arc::new(serde_yaml_ng::from_str(workflow_yaml).unwrap());
+        let flow_id = BlobId::from_flow(flow.as_ref()).unwrap();
+        let large = json!({"title": "doc", "body": "x".repeat(200)});
+
+        // The mock plugin does not accept blob references, so `consume` must
+        // receive the blob content as its input.
+        let mut mock_plugin = MockPlugin::new();
+        mock_plugin.mock_component("/mock/produce").behavior(
+            ValueRef::new(json!({})),
+            MockComponentBehavior::result(FlowResult::Success(ValueRef::new(large.clone()))),
+        );
+        mock_plugin.mock_component("/mock/consume").behavior(
+            ValueRef::new(large.clone()),
+            MockComponentBehavior::result(FlowResult::Success(ValueRef::new(json!("ok")))),
+        );
+        let plugin_router = stepflow_plugin::routing::PluginRouter::builder()
+            .with_routing_path(
+                "/{*component}".to_string(),
+                vec![stepflow_plugin::routing::RouteRule {
+                    conditions: vec![],
+                    component_allow: None,
+                    component_deny: None,
+                    plugin: "mock".into(),
+                    component: None,
+                }],
+            )
+            .register_plugin(
+                "mock".to_string(),
+                stepflow_plugin::DynPlugin::boxed(mock_plugin),
+            )
+            .build()
+            .unwrap();
+
+        let state_store: Arc<dyn StateStore> = Arc::new(InMemoryStateStore::new());
+        let executor = crate::executor::StepflowExecutor::with_options(
+            state_store.clone(),
+            std::path::PathBuf::from("."),
+            plugin_router,
+            crate::ExecutorOptions {
+                blob_threshold: Some(100),
+                ..Default::default()
+            },
+        );
+
+        let run_id = Uuid::new_v4();
+        let result = execute_workflow(
+            executor,
+            flow,
+            CreateRunParams::new(run_id, flow_id, ValueRef::new(json!({}))),
+            state_store.clone(),
+        )
+        .await
+        .unwrap();
+
+        // The flow output contains the blob content, not references.
+        assert_eq!(
+            result,
+            FlowResult::Success(ValueRef::new(json!({
+                "produced": large,
+                "title": "doc",
+                "consumed": "ok",
+            })))
+        );
+
+        // The recorded step result is a reference to the stored blob.
+        state_store.flush_pending_writes(run_id).await.unwrap();
+        let FlowResult::Success(recorded) = state_store.get_step_result(run_id, 0).await.unwrap()
+        else {
+            panic!("Expected successful step result");
+        };
+        let blob_id = BlobId::from_blob_ref(recorded.as_ref()).expect("blob reference");
+        assert_eq!(
+            state_store
+                .get_blob(&blob_id)
+                .await
+                .unwrap()
+                .data()
+                .as_ref(),
+            &large
+        );
+
+        // Small results stay inline.
+        let recorded = state_store.get_step_result(run_id, 1).await.unwrap();
+        assert_eq!(recorded, FlowResult::Success(ValueRef::new(json!("ok"))));
+    }
+
     #[tokio::test]
     async fn test_simple_workflow_execution() {
         let workflow_yaml = r#"
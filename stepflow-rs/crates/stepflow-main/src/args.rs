@@ -20,6 +20,6 @@ pub mod workflow;
 pub use config::ConfigArgs;
 pub use file_loader::{Format, load};
 pub use input::{InputArgs, InputFormat};
-pub use logging::{LogLevel, init_tracing};
+pub use logging::{LogLevel, TracingConfig, init_trace_export, init_tracing, shutdown_tracing};
 pub use output::OutputArgs;
 pub use workflow::WorkflowLoader;
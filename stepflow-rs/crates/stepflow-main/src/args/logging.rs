@@ -11,11 +11,29 @@
 // the License.
 
 use error_stack::ResultExt as _;
-use std::{fs::File, path::Path};
-use tracing_subscriber::{EnvFilter, layer::SubscriberExt as _, util::SubscriberInitExt as _};
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::WithExportConfig as _;
+use opentelemetry_sdk::{
+    Resource,
+    trace::{SdkTracer, SdkTracerProvider},
+};
+use serde::{Deserialize, Serialize};
+use std::{fs::File, path::Path, sync::OnceLock};
+use tracing_subscriber::{
+    EnvFilter, Registry, layer::SubscriberExt as _, reload, util::SubscriberInitExt as _,
+};
+use url::Url;
 
 use crate::{MainError, Result};
 
+type OtelLayer = tracing_opentelemetry::OpenTelemetryLayer<Registry, SdkTracer>;
+
+/// Handle for installing the OpenTelemetry layer once the config is loaded.
+static OTEL_LAYER: OnceLock<reload::Handle<Option<OtelLayer>, Registry>> = OnceLock::new();
+
+/// Tracer provider exporting spans, kept so pending spans can be flushed on exit.
+static TRACER_PROVIDER: OnceLock<SdkTracerProvider> = OnceLock::new();
+
 /// Log level for tracing configuration
 #[derive(clap::ValueEnum, Clone, Debug)]
 pub enum LogLevel {
@@ -39,6 +57,78 @@ impl std::fmt::Display for LogLevel {
     }
 }
 
+/// Configuration for exporting traces via OTLP.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TracingConfig {
+    /// Base URL of the OTLP/HTTP collector, e.g. `http://localhost:4318`.
+    ///
+    /// Spans are sent to `<otlpEndpoint>/v1/traces`.
+    pub otlp_endpoint: Url,
+    /// Service name reported for exported spans.
+    #[serde(default = "default_service_name")]
+    pub service_name: String,
+}
+
+fn default_service_name() -> String {
+    "stepflow".to_string()
+}
+
+impl TracingConfig {
+    /// Create a tracer provider exporting spans to the configured collector.
+    fn create_tracer_provider(&self) -> Result<SdkTracerProvider> {
+        let endpoint = format!(
+            "{}/v1/traces",
+            self.otlp_endpoint.as_str().trim_end_matches('/')
+        );
+        let exporter = opentelemetry_otlp::SpanExporter::builder()
+            .with_http()
+            .with_endpoint(endpoint)
+            .build()
+            .change_context(MainError::TracingInit)?;
+        let resource = Resource::builder()
+            .with_service_name(self.service_name.clone())
+            .build();
+        Ok(SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(resource)
+            .build())
+    }
+}
+
+/// Start exporting spans as configured.
+///
+/// Tracing is initialized before the config is loaded, so [`init_tracing`]
+/// installs an empty slot that this fills in. Only the first call has any
+/// effect.
+pub fn init_trace_export(config: &TracingConfig) -> Result<()> {
+    let Some(handle) = OTEL_LAYER.get() else {
+        return Ok(());
+    };
+    if TRACER_PROVIDER.get().is_some() {
+        return Ok(());
+    }
+
+    let provider = config.create_tracer_provider()?;
+    let layer = tracing_opentelemetry::layer().with_tracer(provider.tracer("stepflow"));
+    handle
+        .reload(Some(layer))
+        .change_context(MainError::TracingInit)?;
+    let _ = TRACER_PROVIDER.set(provider);
+
+    tracing::info!("Exporting traces to {}", config.otlp_endpoint);
+    Ok(())
+}
+
+/// Flush and stop trace export, if enabled.
+pub fn shutdown_tracing() {
+    if let Some(provider) = TRACER_PROVIDER.get()
+        && let Err(e) = provider.shutdown()
+    {
+        tracing::warn!("Failed to flush traces: {e}");
+    }
+}
+
 /// Initialize tracing with the specified configuration.
 pub fn init_tracing(
     log_level: &LogLevel,
@@ -51,7 +141,9 @@ pub fn init_tracing(
     let fmt_layer = tracing_subscriber::fmt::layer()
         .with_timer(tracing_subscriber::fmt::time::LocalTime::rfc_3339());
 
+    let (otel_layer, otel_handle) = reload::Layer::new(None);
     let registry = tracing_subscriber::registry()
+        .with(otel_layer)
         .with(filter)
         .with(tracing_error::ErrorLayer::default());
 
@@ -73,6 +165,7 @@ pub fn init_tracing(
         }
     }
 
+    let _ = OTEL_LAYER.set(otel_handle);
     Ok(())
 }
 
@@ -88,4 +181,63 @@ mod tests {
         assert_eq!(LogLevel::Warn.to_string(), "warn");
         assert_eq!(LogLevel::Error.to_string(), "error");
     }
+
+    /// Export spans to a stand-in collector and check the run/step nesting.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_otlp_trace_export() {
+        use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
+        use opentelemetry_proto::tonic::common::v1::any_value;
+        use prost::Message as _;
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let collector = axum::Router::new().route(
+            "/v1/traces",
+            axum::routing::post(move |body: axum::body::Bytes| async move {
+                let _ = tx.send(ExportTraceServiceRequest::decode(body).unwrap());
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, collector).await });
+
+        let config: TracingConfig = serde_json::from_value(serde_json::json!({
+            "otlpEndpoint": format!("http://{addr}"),
+            "serviceName": "stepflow-test",
+        }))
+        .unwrap();
+        let provider = config.create_tracer_provider().unwrap();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("stepflow")));
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info_span!("run").in_scope(|| {
+                tracing::info_span!("step").in_scope(|| {});
+            });
+        });
+        // Exporting blocks on the collector, which runs on this runtime.
+        tokio::task::spawn_blocking(move || provider.shutdown())
+            .await
+            .unwrap()
+            .unwrap();
+
+        let request = rx.recv().await.unwrap();
+        let resource_spans = &request.resource_spans[0];
+        let service_name = resource_spans
+            .resource
+            .as_ref()
+            .unwrap()
+            .attributes
+            .iter()
+            .find(|attr| attr.key == "service.name")
+            .and_then(|attr| attr.value.as_ref()?.value.as_ref());
+        assert_eq!(
+            service_name,
+            Some(&any_value::Value::StringValue("stepflow-test".to_string()))
+        );
+
+        let spans = &resource_spans.scope_spans[0].spans;
+        let run = spans.iter().find(|span| span.name == "run").unwrap();
+        let step = spans.iter().find(|span| span.name == "step").unwrap();
+        assert_eq!(step.trace_id, run.trace_id);
+        assert_eq!(step.parent_span_id, run.span_id);
+    }
 }
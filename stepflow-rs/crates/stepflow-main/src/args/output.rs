@@ -11,7 +11,11 @@
 // the License.
 
 use error_stack::ResultExt as _;
-use std::{fs::File, path::PathBuf};
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::PathBuf,
+};
 
 use crate::{MainError, Result, args::file_loader::Format};
 
@@ -54,6 +58,18 @@ impl OutputArgs {
         write_output_impl(self.output_path.clone(), output)
     }
 
+    /// Open the configured path or stdout for writing output line by line
+    pub fn create_writer(&self) -> Result<Box<dyn Write>> {
+        match &self.output_path {
+            Some(path) => {
+                let file = File::create(path)
+                    .change_context_lazy(|| MainError::CreateOutput(path.clone()))?;
+                Ok(Box::new(BufWriter::new(file)))
+            }
+            None => Ok(Box::new(std::io::stdout().lock())),
+        }
+    }
+
     /// Create OutputArgs with a specific path
     pub fn with_path(output_path: Option<PathBuf>) -> Self {
         Self { output_path }
@@ -11,47 +11,101 @@
 // the License.
 
 use error_stack::ResultExt as _;
-use std::{path::Path, sync::Arc};
+use std::{collections::HashMap, path::Path, sync::Arc};
 use stepflow_core::workflow::Flow;
-use stepflow_execution::StepflowExecutor;
-use stepflow_plugin::routing::PluginRouter;
+use stepflow_execution::{ExecutorOptions, NamespaceSettings, StepflowExecutor};
+use stepflow_plugin::DynPlugin;
+use stepflow_plugin::routing::{PluginRouter, RoutingConfig};
 
 use crate::{
     MainError, Result,
-    args::{config::ConfigArgs, file_loader::load},
+    args::{config::ConfigArgs, file_loader::load, logging::init_trace_export},
     stepflow_config::StepflowConfig,
 };
 
 /// Create executor from StepflowConfig
 async fn create_executor_impl(config: StepflowConfig) -> Result<Arc<StepflowExecutor>> {
-    // Create state store from configuration
-    let state_store = config.state_store.create_state_store().await?;
-
     let working_directory = config
         .working_directory
         .as_ref()
         .expect("working_directory");
 
-    // Build the plugin router
-    tracing::info!("Routing Config: {:?}", config.routing);
-    let mut plugin_router_builder = PluginRouter::builder().with_routing_config(config.routing);
+    if let Some(tracing_config) = &config.tracing {
+        init_trace_export(tracing_config)?;
+    }
 
-    // Register plugins from IndexMap
+    // Create blob and state stores from configuration
+    let blob_store = config
+        .blob_store
+        .create_blob_store(working_directory)
+        .await?;
+    let state_store = config.state_store.create_state_store(blob_store).await?;
+
+    // Instantiate the global plugins. They are shared with the namespaces.
+    let mut plugins: Vec<(String, Arc<DynPlugin<'static>>)> = Vec::new();
     for (plugin_name, plugin_config) in config.plugins {
         let plugin = plugin_config
-            .instantiate(working_directory)
+            .instantiate(&plugin_name, working_directory)
             .await
             .attach_printable_lazy(|| {
                 format!("Failed to instantiate plugin for '{plugin_name}'")
             })?;
-        plugin_router_builder = plugin_router_builder.register_plugin(plugin_name, plugin);
+        plugins.push((plugin_name, Arc::from(plugin)));
     }
 
-    let plugin_router = plugin_router_builder
-        .build()
-        .change_context(MainError::Configuration)?;
+    // Build the plugin router
+    tracing::info!("Routing Config: {:?}", config.routing);
+    let plugin_router = build_plugin_router(&plugins, config.routing.clone())?;
+
+    // Build the routers of namespaces overriding plugins or routes
+    let mut namespaces = HashMap::new();
+    for (namespace, namespace_config) in config.namespaces {
+        let plugin_router = if namespace_config.plugins.is_empty()
+            && namespace_config.routes.is_none()
+        {
+            None
+        } else {
+            let mut namespace_plugins = plugins.clone();
+            for (plugin_name, plugin_config) in namespace_config.plugins {
+                let plugin = plugin_config
+                    .instantiate(&plugin_name, working_directory)
+                    .await
+                    .attach_printable_lazy(|| {
+                        format!(
+                            "Failed to instantiate plugin for '{plugin_name}' in namespace '{namespace}'"
+                        )
+                    })?;
+                namespace_plugins.push((plugin_name, Arc::from(plugin)));
+            }
+            let routing = match namespace_config.routes {
+                Some(routes) => RoutingConfig { routes },
+                None => config.routing.clone(),
+            };
+            Some(
+                build_plugin_router(&namespace_plugins, routing).attach_printable_lazy(|| {
+                    format!("Invalid routes for namespace '{namespace}'")
+                })?,
+            )
+        };
+        namespaces.insert(
+            namespace,
+            NamespaceSettings {
+                plugin_router,
+                max_active_runs: namespace_config.max_active_runs,
+            },
+        );
+    }
 
-    let executor = StepflowExecutor::new(state_store, working_directory.clone(), plugin_router);
+    let executor = StepflowExecutor::with_namespaces(
+        state_store,
+        working_directory.clone(),
+        plugin_router,
+        ExecutorOptions {
+            blob_threshold: config.blob_threshold,
+            analysis_cache_size: config.analysis_cache_size,
+        },
+        namespaces,
+    );
 
     // Initialize all plugins
     executor
@@ -62,6 +116,24 @@ async fn create_executor_impl(config: StepflowConfig) -> Result<Arc<StepflowExec
     Ok(executor)
 }
 
+/// Build a plugin router from plugin instances, later plugins replacing earlier
+/// ones of the same name.
+fn build_plugin_router(
+    plugins: &[(String, Arc<DynPlugin<'static>>)],
+    routing: RoutingConfig,
+) -> Result<PluginRouter> {
+    plugins
+        .iter()
+        .fold(
+            PluginRouter::builder().with_routing_config(routing),
+            |builder, (plugin_name, plugin)| {
+                builder.register_shared_plugin(plugin_name.clone(), plugin.clone())
+            },
+        )
+        .build()
+        .change_context(MainError::Configuration)
+}
+
 /// Shared workflow operations
 pub struct WorkflowLoader;
 
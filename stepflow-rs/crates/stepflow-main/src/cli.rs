@@ -13,6 +13,8 @@
 use error_stack::ResultExt as _;
 use std::{path::PathBuf, sync::Arc};
 use stepflow_core::{BlobId, workflow::Flow};
+use stepflow_state::DEFAULT_NAMESPACE;
+use tokio::io::AsyncRead;
 use url::Url;
 
 use crate::{
@@ -20,8 +22,8 @@ use crate::{
     error::Result,
     list_components::OutputFormat,
     repl::run_repl,
-    run::run,
-    serve::serve,
+    run::{run, run_batch},
+    serve::{init_metrics, serve},
     submit::submit,
     test::TestOptions,
     validate, visualize,
@@ -93,18 +95,44 @@ pub enum Command {
     ///
     /// stepflow run --flow=workflow.yaml --input=input.json --config=my-config.yml --output=result.json
     ///
+    /// # Run once per line of a JSONL file, writing JSONL results in the same order
+    ///
+    /// stepflow run --flow=workflow.yaml --inputs-jsonl=inputs.jsonl --max-concurrency=16 --output=results.jsonl
+    ///
     /// ```
     Run {
         /// Path to the workflow file to execute.
         #[arg(long="flow", value_name = "FILE", value_hint = clap::ValueHint::FilePath)]
         flow_path: PathBuf,
 
+        /// Namespace to run the workflow in, selecting its plugins, routes and quota.
+        #[arg(long, value_name = "NAMESPACE", default_value = DEFAULT_NAMESPACE)]
+        namespace: String,
+
         #[command(flatten)]
         config_args: ConfigArgs,
 
         #[command(flatten)]
         input_args: InputArgs,
 
+        /// Path to a JSONL file with one input per line, or `-` for stdin.
+        ///
+        /// The workflow is run once for each input, and the results are written
+        /// as JSONL in the order of the inputs. Lines that are not valid JSON, or
+        /// that could not be run, are written as failed results.
+        #[arg(long = "inputs-jsonl", value_name = "FILE", value_hint = clap::ValueHint::FilePath,
+              conflicts_with_all = ["input", "input_json", "input_yaml"])]
+        inputs_jsonl: Option<PathBuf>,
+
+        /// Maximum number of inputs from `--inputs-jsonl` to run at once.
+        #[arg(
+            long = "max-concurrency",
+            value_name = "N",
+            default_value = "8",
+            requires = "inputs_jsonl"
+        )]
+        max_concurrency: usize,
+
         #[command(flatten)]
         output_args: OutputArgs,
     },
@@ -153,6 +181,10 @@ pub enum Command {
     ///
     /// stepflow submit --flow=workflow.yaml --input-yaml='param: value'
     ///
+    /// # Submit to a team's namespace
+    ///
+    /// stepflow submit --namespace=team-a --flow=workflow.yaml --input=input.json
+    ///
     /// ```
     Submit {
         /// The URL of the Stepflow service to submit the workflow to.
@@ -163,6 +195,10 @@ pub enum Command {
         #[arg(long="flow", value_name = "FILE", value_hint = clap::ValueHint::FilePath)]
         flow_path: PathBuf,
 
+        /// Namespace to store the workflow and create the run in.
+        #[arg(long, value_name = "NAMESPACE", default_value = DEFAULT_NAMESPACE)]
+        namespace: String,
+
         #[command(flatten)]
         input_args: InputArgs,
 
@@ -390,8 +426,11 @@ impl Cli {
         match self.command {
             Command::Run {
                 flow_path,
+                namespace,
                 config_args,
                 input_args,
+                inputs_jsonl,
+                max_concurrency,
                 output_args,
             } => {
                 let flow: Arc<Flow> = load(&flow_path)?;
@@ -399,29 +438,63 @@ impl Cli {
                 let config = config_args.load_config(flow_dir)?;
                 let executor = WorkflowLoader::create_executor_from_config(config).await?;
 
-                let input = input_args.parse_input(true)?;
-
                 let flow_id =
                     BlobId::from_flow(&flow).change_context(crate::MainError::Configuration)?;
-                let output = run(executor, flow, flow_id, input).await?;
-                output_args.write_output(output)?;
+
+                if let Some(inputs_path) = inputs_jsonl {
+                    let inputs: Box<dyn AsyncRead + Unpin> = if inputs_path.as_os_str() == "-" {
+                        Box::new(tokio::io::stdin())
+                    } else {
+                        let file = tokio::fs::File::open(&inputs_path)
+                            .await
+                            .change_context_lazy(|| {
+                                crate::MainError::MissingFile(inputs_path.clone())
+                            })?;
+                        Box::new(file)
+                    };
+                    let inputs = tokio::io::BufReader::new(inputs);
+                    let output = output_args.create_writer()?;
+                    let summary = run_batch(
+                        executor,
+                        &namespace,
+                        flow,
+                        flow_id,
+                        inputs,
+                        output,
+                        max_concurrency,
+                    )
+                    .await?;
+                    tracing::info!(
+                        total = summary.total,
+                        failed = summary.failed,
+                        "Finished running inputs"
+                    );
+                } else {
+                    let input = input_args.parse_input(true)?;
+                    let output = run(executor, &namespace, flow, flow_id, input).await?;
+                    output_args.write_output(output)?;
+                }
             }
             Command::Serve { port, config_args } => {
+                init_metrics();
                 let config = config_args.load_config(None)?;
+                let auth = config.auth.clone();
+                let webhooks = config.webhooks.clone();
                 let executor = WorkflowLoader::create_executor_from_config(config).await?;
 
-                serve(executor, port).await?;
+                serve(executor, port, auth, webhooks).await?;
             }
             Command::Submit {
                 url,
                 flow_path,
+                namespace,
                 input_args,
                 output_args,
             } => {
                 let flow: Flow = load(&flow_path)?;
                 let input = input_args.parse_input(true)?;
 
-                let output = submit(url, flow, input).await?;
+                let output = submit(url, &namespace, flow, input).await?;
                 output_args.write_output(output)?;
             }
             Command::Test {
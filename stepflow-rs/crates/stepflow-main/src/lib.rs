@@ -28,4 +28,4 @@ mod visualize;
 
 pub use cli::Cli;
 pub use error::*;
-pub use run::run;
+pub use run::{BatchSummary, run, run_batch};
@@ -11,7 +11,10 @@
 // the License.
 
 use clap::Parser as _;
-use stepflow_main::{Cli, Result, args::init_tracing};
+use stepflow_main::{
+    Cli, Result,
+    args::{init_tracing, shutdown_tracing},
+};
 
 async fn run(cli: Cli) -> Result<()> {
     // Initialize tracing with the specified configuration
@@ -21,7 +24,9 @@ async fn run(cli: Cli) -> Result<()> {
         cli.log_file.as_deref(),
     )?;
 
-    cli.execute().await
+    let result = cli.execute().await;
+    shutdown_tracing();
+    result
 }
 
 #[tokio::main]
@@ -636,6 +636,28 @@ async fn handle_inspect_command(step_id: String, state: &ReplState) -> Result<()
                     }
 
                     println!("  Error handling: {:?}", inspection.on_error);
+
+                    let logs = state
+                        .executor
+                        .state_store()
+                        .get_step_logs(debug_session.run_id(), &step_id)
+                        .await
+                        .change_context(MainError::FlowExecution)?;
+                    if !logs.is_empty() {
+                        println!("  Logs:");
+                        for log in logs {
+                            let timestamp = log.timestamp.format("%H:%M:%S%.3f");
+                            if log.fields.is_empty() {
+                                println!("    {timestamp} {:5} {}", log.level, log.message);
+                            } else {
+                                let fields = serde_json::Value::Object(log.fields);
+                                println!(
+                                    "    {timestamp} {:5} {} {fields}",
+                                    log.level, log.message
+                                );
+                            }
+                        }
+                    }
                 }
                 Err(e) => {
                     println!("Failed to inspect step '{step_id}': {e}");
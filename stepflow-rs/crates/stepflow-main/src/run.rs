@@ -10,22 +10,30 @@
 // or implied. See the License for the specific language governing permissions and limitations under
 // the License.
 
+use std::io::Write;
 use std::sync::Arc;
 
 use crate::{MainError, Result};
 use error_stack::ResultExt as _;
-use stepflow_core::{BlobId, FlowResult, workflow::Flow};
+use futures::StreamExt as _;
+use stepflow_core::{
+    BlobId, FlowError, FlowResult,
+    workflow::{Flow, ValueRef},
+};
 use stepflow_execution::StepflowExecutor;
 use stepflow_plugin::Context as _;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt as _};
 
+/// Run a flow in the given namespace and wait for its result.
 pub async fn run(
     executor: Arc<StepflowExecutor>,
+    namespace: &str,
     flow: Arc<Flow>,
     flow_id: BlobId,
     input: stepflow_core::workflow::ValueRef,
 ) -> Result<FlowResult> {
     let run_id = executor
-        .submit_flow(flow, flow_id, input)
+        .submit_namespaced_flow(namespace, flow, flow_id, input)
         .await
         .change_context(MainError::FlowExecution)?;
     let output = executor
@@ -34,3 +42,85 @@ pub async fn run(
         .change_context(MainError::FlowExecution)?;
     Ok(output)
 }
+
+/// Counts of the runs made by [`run_batch`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchSummary {
+    pub total: usize,
+    pub failed: usize,
+}
+
+/// Run a flow in the given namespace once for each JSONL line of `inputs`.
+///
+/// Inputs are read as they are needed, and up to `max_concurrency` of them run
+/// at once. The results are written to `output` as JSONL, in the order of the
+/// inputs. Blank lines are skipped. A line that is not valid JSON, or whose run
+/// could not be executed, is written as a failed result and does not stop the
+/// remaining inputs.
+pub async fn run_batch(
+    executor: Arc<StepflowExecutor>,
+    namespace: &str,
+    flow: Arc<Flow>,
+    flow_id: BlobId,
+    inputs: impl AsyncBufRead + Unpin,
+    mut output: impl Write,
+    max_concurrency: usize,
+) -> Result<BatchSummary> {
+    let lines = futures::stream::unfold(inputs.split(b'\n'), |mut lines| async move {
+        lines
+            .next_segment()
+            .await
+            .transpose()
+            .map(|line| (line, lines))
+    });
+    let results = lines
+        .enumerate()
+        .filter(|(_, line)| {
+            std::future::ready(!matches!(line, Ok(line) if line.trim_ascii().is_empty()))
+        })
+        .map(|(index, line)| {
+            let executor = executor.clone();
+            let flow = flow.clone();
+            let flow_id = flow_id.clone();
+            async move {
+                let line_number = index + 1;
+                let line = line.change_context_lazy(|| {
+                    MainError::InvalidArgument(format!("failed to read input line {line_number}"))
+                })?;
+                let result = match serde_json::from_slice::<ValueRef>(&line) {
+                    Ok(input) => run(executor, namespace, flow, flow_id, input)
+                        .await
+                        .unwrap_or_else(|report| {
+                            let message =
+                                format!("failed to run input on line {line_number}: {report}");
+                            FlowResult::Failed(FlowError::new(500, message))
+                        }),
+                    Err(error) => {
+                        let message = format!("invalid JSON input on line {line_number}: {error}");
+                        FlowResult::Failed(FlowError::new(400, message))
+                    }
+                };
+                Result::<_>::Ok(result)
+            }
+        })
+        .buffered(max_concurrency.max(1));
+    let mut results = std::pin::pin!(results);
+
+    let mut summary = BatchSummary {
+        total: 0,
+        failed: 0,
+    };
+    let write_error = || MainError::WriteOutput("output".into());
+    while let Some(result) = results.next().await {
+        // Only failing to read the inputs stops the batch.
+        let result = result?;
+        summary.total += 1;
+        if !matches!(result, FlowResult::Success(_)) {
+            summary.failed += 1;
+        }
+        serde_json::to_writer(&mut output, &result).change_context_lazy(write_error)?;
+        writeln!(output).change_context_lazy(write_error)?;
+    }
+    output.flush().change_context_lazy(write_error)?;
+    Ok(summary)
+}
@@ -15,6 +15,7 @@ use std::sync::Arc;
 use crate::{MainError, Result};
 use error_stack::ResultExt as _;
 use stepflow_execution::StepflowExecutor;
+use stepflow_server::{AppConfig, AuthConfig, WebhookConfig, WebhookOptions};
 
 /// Start the Stepflow HTTP server
 ///
@@ -35,6 +36,11 @@ use stepflow_execution::StepflowExecutor;
 /// GET /health                        # Check service health and status
 /// ```
 ///
+/// Prometheus metrics are served outside the API base URL:
+/// ```
+/// GET /metrics                       # Metrics in Prometheus text format
+/// ```
+///
 /// ### 2. Component Management
 /// ```
 /// GET /components                    # List all available components
@@ -49,16 +55,33 @@ use stepflow_execution::StepflowExecutor;
 /// DELETE /flows/{flow_id}            # Delete workflow (placeholder)
 /// ```
 ///
+/// Named flows can be promoted by pointing labels (like `production`) at a version:
+/// ```
+/// GET  /flows                        # List flow names
+/// GET  /flows/{name}/versions        # List stored versions of a named flow
+/// GET  /flows/{name}/labels          # List labels of a named flow
+/// GET  /flows/{name}/labels/{label}  # Get the version a label points at
+/// PUT  /flows/{name}/labels/{label}  # Set or move a label
+/// DELETE /flows/{name}/labels/{label} # Delete a label
+/// GET  /flows/{name}/labels/{label}/history   # List changes to a label
+/// POST /flows/{name}/labels/{label}/rollback  # Move a label back to an earlier version
+/// ```
+///
 /// ### 4. Execution Management
 /// Create and manage workflow executions:
 /// ```
 /// POST /runs                         # Create and execute workflow by hash
+/// POST /flows/{name}/labels/{label}/runs # Create and execute the labeled workflow
 /// GET  /runs                         # List all executions
+/// DELETE /runs                       # Delete finished executions matching filters
+///     ?status=&flowId=&flowName=&flowLabel=&cascade=true
 /// GET  /runs/{run_id}                # Get execution details
 /// GET  /runs/{run_id}/flow           # Get workflow definition for execution
 /// GET  /runs/{run_id}/steps          # Get step-level execution details
+/// GET  /runs/{run_id}/steps/{step_id}/logs # Get log messages recorded for a step
 /// POST /runs/{run_id}/cancel         # Cancel running execution (placeholder)
-/// DELETE /runs/{run_id}              # Delete completed execution (placeholder)
+/// DELETE /runs/{run_id}              # Delete finished execution and its step results
+///     ?cascade=true                  # Also delete runs nested within it (default: false)
 /// ```
 ///
 /// ### 5. Debug Mode
@@ -69,6 +92,19 @@ use stepflow_execution::StepflowExecutor;
 /// GET  /runs/{run_id}/debug/runnable # Get list of runnable steps
 /// ```
 ///
+/// ## Authentication
+///
+/// When `auth` is configured, every endpoint except `/health` requires an API key
+/// (`X-API-Key: <key>` or `Authorization: Bearer <key>`) or an HS256 JWT
+/// (`Authorization: Bearer <token>`) granting the endpoint's scope:
+/// - `flows:read` / `flows:write`: read or change flows, labels and components
+/// - `runs:read` / `runs:create` / `runs:write`: inspect, create, or cancel and delete runs
+/// - `debug`: control debug runs
+/// - `metrics:read`: read `/metrics`, for credentials not restricted to namespaces
+/// - `*`: every scope
+///
+/// The caller's identity is recorded on the runs they create and the label changes they make.
+///
 /// ## Key Concepts
 ///
 /// ### Content-based Flow Storage
@@ -162,14 +198,38 @@ use stepflow_execution::StepflowExecutor;
 /// ## Production Considerations
 ///
 /// - **State Storage**: Configure SQLite or other backends for persistence
-/// - **Security**: Add authentication/authorization as needed
-/// - **Monitoring**: Built-in health checks and structured logging
+/// - **Security**: Configure `auth` before exposing the server beyond localhost
+/// - **Monitoring**: Built-in health checks, Prometheus metrics and structured logging
 /// - **Scaling**: Stateless design supports horizontal scaling
 /// - **Performance**: Content-based caching and efficient execution engine
 ///
-pub async fn serve(executor: Arc<StepflowExecutor>, port: u16) -> Result<()> {
-    // Use the experimental utoipa version for testing
-    stepflow_server::start_server(port, executor)
+/// Install the metrics recorder and describe the metrics Stepflow records.
+///
+/// This should be called before the executor is created so that metrics
+/// recorded while starting plugins are captured.
+pub fn init_metrics() {
+    stepflow_server::install_metrics_recorder();
+    stepflow_execution::describe_metrics();
+    stepflow_protocol::describe_metrics();
+    stepflow_state_sql::describe_metrics();
+}
+
+pub async fn serve(
+    executor: Arc<StepflowExecutor>,
+    port: u16,
+    auth: Option<AuthConfig>,
+    webhooks: WebhookConfig,
+) -> Result<()> {
+    let app_config = AppConfig {
+        auth,
+        webhooks: WebhookOptions {
+            secret_key: webhooks.secret_key,
+            allowed_hosts: webhooks.allowed_hosts,
+            ..WebhookOptions::default()
+        },
+        ..AppConfig::default()
+    };
+    stepflow_server::start_server(port, executor, app_config)
         .await
         .map_err(Arc::<dyn std::error::Error + Send + Sync>::from)
         .change_context(MainError::ServerError)
@@ -10,20 +10,26 @@
 // or implied. See the License for the specific language governing permissions and limitations under
 // the License.
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use crate::args::TracingConfig;
 use crate::{MainError, Result};
 use error_stack::ResultExt as _;
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
+use stepflow_blob_store::{
+    FilesystemBlobStore, FilesystemBlobStoreConfig, S3BlobStore, S3BlobStoreConfig,
+};
 use stepflow_builtins::BuiltinPluginConfig;
 use stepflow_components_mcp::McpPluginConfig;
 use stepflow_mock::MockPlugin;
-use stepflow_plugin::routing::RoutingConfig;
+use stepflow_plugin::routing::{RouteRule, RoutingConfig};
 use stepflow_plugin::{DynPlugin, PluginConfig};
 use stepflow_protocol::StepflowPluginConfig;
-use stepflow_state::{InMemoryStateStore, StateStore};
+use stepflow_server::{AuthConfig, WebhookConfig};
+use stepflow_state::{BlobStore, InMemoryStateStore, StateStore};
 use stepflow_state_sql::{SqliteStateStore, SqliteStateStoreConfig};
 
 #[derive(Serialize, Deserialize)]
@@ -40,6 +46,45 @@ pub struct StepflowConfig {
     /// State store configuration. If not specified, uses in-memory storage.
     #[serde(default)]
     pub state_store: StateStoreConfig,
+    /// Blob store configuration. If not specified, blobs are kept in the state store.
+    #[serde(default)]
+    pub blob_store: BlobStoreConfig,
+    /// Size in bytes above which step results are stored as blobs and passed
+    /// between steps by reference. If not specified, results are always inline.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_threshold: Option<usize>,
+    /// Maximum number of flow analyses kept in memory, evicting the least
+    /// recently used. If not specified, 1024 analyses are kept.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub analysis_cache_size: Option<usize>,
+    /// Trace export configuration. If not specified, traces are not exported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tracing: Option<TracingConfig>,
+    /// Authentication for `stepflow serve`. If not specified, the API is open.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<AuthConfig>,
+    /// Run callback settings for `stepflow serve`.
+    #[serde(default)]
+    pub webhooks: WebhookConfig,
+    /// Settings for individual namespaces. Namespaces not listed here use the
+    /// global plugins and routes without a quota.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub namespaces: IndexMap<String, NamespaceConfig>,
+}
+
+/// Overrides applied to runs in a namespace.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NamespaceConfig {
+    /// Plugins added to the global plugins, replacing global plugins of the same name.
+    #[serde(default)]
+    pub plugins: IndexMap<String, SupportedPluginConfig>,
+    /// Routes replacing the global routes. If not specified, the global routes are used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub routes: Option<HashMap<String, Vec<RouteRule>>>,
+    /// Maximum number of runs executing in the namespace at once.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_active_runs: Option<usize>,
 }
 
 impl Default for StepflowConfig {
@@ -57,40 +102,91 @@ impl Default for StepflowConfig {
             plugins,
             routing: RoutingConfig::default(),
             state_store: StateStoreConfig::default(),
+            blob_store: BlobStoreConfig::default(),
+            blob_threshold: None,
+            analysis_cache_size: None,
+            tracing: None,
+            auth: None,
+            webhooks: WebhookConfig::default(),
+            namespaces: IndexMap::new(),
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum StateStoreConfig {
     /// In-memory state store (default, for testing and demos)
+    #[default]
     InMemory,
     /// SQLite-based persistent state store
     Sqlite(SqliteStateStoreConfig),
 }
 
-impl Default for StateStoreConfig {
-    fn default() -> Self {
-        Self::InMemory
-    }
-}
-
 impl StateStoreConfig {
     /// Create a StateStore instance from this configuration
-    pub async fn create_state_store(&self) -> Result<Arc<dyn StateStore>> {
+    ///
+    /// If `blob_store` is set, blob content is stored there instead of in the state store.
+    pub async fn create_state_store(
+        &self,
+        blob_store: Option<Arc<dyn BlobStore>>,
+    ) -> Result<Arc<dyn StateStore>> {
         match self {
-            StateStoreConfig::InMemory => Ok(Arc::new(InMemoryStateStore::new())),
+            StateStoreConfig::InMemory => match blob_store {
+                Some(blob_store) => Ok(Arc::new(InMemoryStateStore::with_blob_store(blob_store))),
+                None => Ok(Arc::new(InMemoryStateStore::new())),
+            },
             StateStoreConfig::Sqlite(config) => {
-                let store = SqliteStateStore::new(config.clone())
+                let mut store = SqliteStateStore::new(config.clone())
                     .await
                     .change_context(MainError::Configuration)?;
+                if let Some(blob_store) = blob_store {
+                    store = store.with_blob_store(blob_store);
+                }
                 Ok(Arc::new(store))
             }
         }
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum BlobStoreConfig {
+    /// Store blobs in the state store (default)
+    #[default]
+    StateStore,
+    /// Store blobs as files in a local directory
+    Filesystem(FilesystemBlobStoreConfig),
+    /// Store blobs in an S3-compatible object store
+    S3(S3BlobStoreConfig),
+}
+
+impl BlobStoreConfig {
+    /// Create a BlobStore instance from this configuration.
+    ///
+    /// Returns `None` if blobs should be kept in the state store. Relative
+    /// filesystem paths are resolved against `working_directory`.
+    pub async fn create_blob_store(
+        &self,
+        working_directory: &Path,
+    ) -> Result<Option<Arc<dyn BlobStore>>> {
+        match self {
+            BlobStoreConfig::StateStore => Ok(None),
+            BlobStoreConfig::Filesystem(config) => {
+                let store = FilesystemBlobStore::new(working_directory.join(&config.path))
+                    .await
+                    .change_context(MainError::Configuration)?;
+                Ok(Some(Arc::new(store)))
+            }
+            BlobStoreConfig::S3(config) => {
+                let store =
+                    S3BlobStore::from_config(config).change_context(MainError::Configuration)?;
+                Ok(Some(Arc::new(store)))
+            }
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = "type", rename_all = "camelCase")]
 enum SupportedPlugin {
@@ -108,21 +204,30 @@ pub struct SupportedPluginConfig {
 
 async fn create_plugin<P: PluginConfig>(
     plugin: P,
+    name: &str,
     working_directory: &Path,
 ) -> Result<Box<DynPlugin<'static>>> {
     plugin
-        .create_plugin(working_directory)
+        .create_plugin(name, working_directory)
         .await
         .change_context(MainError::RegisterPlugin)
 }
 
 impl SupportedPluginConfig {
-    pub async fn instantiate(self, working_directory: &Path) -> Result<Box<DynPlugin<'static>>> {
+    pub async fn instantiate(
+        self,
+        name: &str,
+        working_directory: &Path,
+    ) -> Result<Box<DynPlugin<'static>>> {
         let plugin = match self.plugin {
-            SupportedPlugin::Stepflow(plugin) => create_plugin(plugin, working_directory).await?,
-            SupportedPlugin::Builtin(plugin) => create_plugin(plugin, working_directory).await?,
-            SupportedPlugin::Mock(plugin) => create_plugin(plugin, working_directory).await?,
-            SupportedPlugin::Mcp(plugin) => create_plugin(plugin, working_directory).await?,
+            SupportedPlugin::Stepflow(plugin) => {
+                create_plugin(plugin, name, working_directory).await?
+            }
+            SupportedPlugin::Builtin(plugin) => {
+                create_plugin(plugin, name, working_directory).await?
+            }
+            SupportedPlugin::Mock(plugin) => create_plugin(plugin, name, working_directory).await?,
+            SupportedPlugin::Mcp(plugin) => create_plugin(plugin, name, working_directory).await?,
         };
         Ok(plugin)
     }
@@ -15,12 +15,20 @@ use error_stack::ResultExt as _;
 use std::sync::Arc;
 use stepflow_core::FlowResult;
 use stepflow_core::workflow::{Flow, ValueRef};
-use stepflow_server::{CreateRunRequest, CreateRunResponse, StoreFlowRequest, StoreFlowResponse};
+use stepflow_server::{
+    CreateRunRequest, CreateRunResponse, RunMode, StoreFlowRequest, StoreFlowResponse,
+};
 use url::Url;
 
-/// Submit a workflow to a Stepflow service for execution
-pub async fn submit(service_url: Url, flow: Flow, input: ValueRef) -> Result<FlowResult> {
+/// Submit a workflow to a Stepflow service for execution in a namespace
+pub async fn submit(
+    service_url: Url,
+    namespace: &str,
+    flow: Flow,
+    input: ValueRef,
+) -> Result<FlowResult> {
     let client = reqwest::Client::new();
+    let api_base = format!("/api/v1/namespaces/{namespace}");
 
     // Step 1: Store the flow to get its hash
     let store_request = StoreFlowRequest {
@@ -28,7 +36,7 @@ pub async fn submit(service_url: Url, flow: Flow, input: ValueRef) -> Result<Flo
     };
 
     let store_url = service_url
-        .join("/api/v1/flows")
+        .join(&format!("{api_base}/flows"))
         .map_err(|_| MainError::Configuration)?;
 
     let store_response = client
@@ -71,10 +79,13 @@ pub async fn submit(service_url: Url, flow: Flow, input: ValueRef) -> Result<Flo
         flow_id,
         input,
         debug: false, // TODO: Add debug option to CLI
+        callback: None,
+        mode: RunMode::Sync,
+        timeout_ms: None,
     };
 
     let execute_url = service_url
-        .join("/api/v1/runs")
+        .join(&format!("{api_base}/runs"))
         .map_err(|_| MainError::Configuration)?;
 
     let execute_response = client
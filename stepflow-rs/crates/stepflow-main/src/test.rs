@@ -24,6 +24,7 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use stepflow_core::workflow::Flow;
 use stepflow_core::{BlobId, FlowResult};
+use stepflow_state::DEFAULT_NAMESPACE;
 use walkdir::WalkDir;
 
 /// Normalize run_id fields in FlowResult for consistent testing
@@ -390,6 +391,7 @@ async fn run_single_flow_test(
         println!("----------\nRunning Test Case {}", test_case.name);
         let result = crate::run::run(
             executor.clone(),
+            DEFAULT_NAMESPACE,
             flow.clone(),
             flow_id.clone(),
             test_case.input.clone(),
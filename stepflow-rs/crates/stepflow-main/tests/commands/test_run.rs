@@ -59,6 +59,27 @@ fn test_run_with_custom_config() {
     );
 }
 
+#[test]
+fn test_run_basic_workflow_with_jsonl_inputs() {
+    assert_cmd_snapshot!(
+        stepflow()
+            .arg("run")
+            .arg("--flow=tests/mock/basic.yaml")
+            .arg("--inputs-jsonl=tests/mock/inputs.jsonl")
+            .arg("--max-concurrency=2")
+    );
+}
+
+#[test]
+fn test_run_jsonl_inputs_with_invalid_line() {
+    assert_cmd_snapshot!(
+        stepflow()
+            .arg("run")
+            .arg("--flow=tests/mock/basic.yaml")
+            .arg("--inputs-jsonl=tests/mock/inputs_invalid.jsonl")
+    );
+}
+
 #[test]
 fn test_run_nonexistent_workflow() {
     assert_cmd_snapshot!(
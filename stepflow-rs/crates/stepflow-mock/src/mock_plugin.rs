@@ -29,6 +29,7 @@ impl PluginConfig for MockPlugin {
 
     async fn create_plugin(
         self,
+        _name: &str,
         _working_directory: &std::path::Path,
     ) -> error_stack::Result<Box<DynPlugin<'static>>, Self::Error> {
         Ok(DynPlugin::boxed(self))
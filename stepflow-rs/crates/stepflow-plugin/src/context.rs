@@ -17,7 +17,7 @@ use stepflow_core::{
     BlobId, FlowResult,
     workflow::{Flow, ValueRef},
 };
-use stepflow_state::StateStore;
+use stepflow_state::{StateStore, StepProgress};
 use uuid::Uuid;
 
 /// Trait for interacting with the workflow runtime.
@@ -33,6 +33,23 @@ pub trait Context: Send + Sync {
         input: ValueRef,
     ) -> BoxFuture<'_, crate::Result<Uuid>>;
 
+    /// Submits a workflow nested within the run `parent_run_id` and returns its execution ID.
+    ///
+    /// `parent_step_id` is the step of the parent run that submitted the workflow, if known.
+    /// Implementations that record runs should remember the parent so nested runs
+    /// can be found from it. The default ignores the parent.
+    fn submit_nested_flow(
+        &self,
+        parent_run_id: Uuid,
+        parent_step_id: Option<String>,
+        flow: Arc<Flow>,
+        flow_id: BlobId,
+        input: ValueRef,
+    ) -> BoxFuture<'_, crate::Result<Uuid>> {
+        let _ = (parent_run_id, parent_step_id);
+        self.submit_flow(flow, flow_id, input)
+    }
+
     /// Retrieves the result of a previously submitted workflow.
     fn flow_result(&self, run_id: Uuid) -> BoxFuture<'_, crate::Result<FlowResult>>;
 
@@ -227,6 +244,23 @@ impl ExecutionContext {
     pub fn context(&self) -> &Arc<dyn Context> {
         &self.context
     }
+
+    /// Record the progress of the step being executed.
+    ///
+    /// Progress is only recorded for step contexts. Failing to record progress
+    /// does not fail the step, so errors are logged rather than returned.
+    pub async fn report_progress(&self, progress: StepProgress) {
+        let Some(step_id) = &self.step_id else {
+            return;
+        };
+        if let Err(e) = self
+            .state_store()
+            .update_step_progress(self.run_id, step_id.clone(), progress)
+            .await
+        {
+            tracing::warn!(run_id = %self.run_id, step_id, "Failed to record step progress: {e:?}");
+        }
+    }
 }
 
 impl Context for ExecutionContext {
@@ -234,14 +268,15 @@ impl Context for ExecutionContext {
         self.context.state_store()
     }
 
-    /// Submit a nested workflow for execution.
+    /// Submit a workflow nested within this run.
     fn submit_flow(
         &self,
         flow: Arc<Flow>,
         flow_id: BlobId,
         input: ValueRef,
     ) -> BoxFuture<'_, crate::Result<Uuid>> {
-        self.context.submit_flow(flow, flow_id, input)
+        self.context
+            .submit_nested_flow(self.run_id, self.step_id.clone(), flow, flow_id, input)
     }
 
     /// Get the result of a workflow execution.
@@ -249,16 +284,6 @@ impl Context for ExecutionContext {
         self.context.flow_result(run_id)
     }
 
-    /// Execute a nested workflow and wait for completion.
-    fn execute_flow(
-        &self,
-        flow: Arc<Flow>,
-        flow_id: BlobId,
-        input: ValueRef,
-    ) -> BoxFuture<'_, crate::Result<FlowResult>> {
-        self.context.execute_flow(flow, flow_id, input)
-    }
-
     fn working_directory(&self) -> &Path {
         self.context.working_directory()
     }
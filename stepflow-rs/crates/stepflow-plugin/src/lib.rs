@@ -17,4 +17,4 @@ pub mod routing;
 
 pub use context::{Context, ExecutionContext};
 pub use error::{PluginError, Result};
-pub use plugin::{DynPlugin, Plugin, PluginConfig};
+pub use plugin::{DynPlugin, Plugin, PluginConfig, PluginHealth, PluginStatus};
@@ -13,7 +13,7 @@
 use std::{path::Path, sync::Arc};
 
 use crate::{Context, ExecutionContext, Result};
-use serde::{Serialize, de::DeserializeOwned};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use stepflow_core::{
     FlowResult,
     component::ComponentInfo,
@@ -31,6 +31,21 @@ pub trait Plugin: Send + Sync {
     /// Return the outputs for the given component.
     async fn component_info(&self, component: &Component) -> Result<ComponentInfo>;
 
+    /// Whether this plugin accepts blob references (`{"$blob": "<blob_id>"}`) in step inputs.
+    ///
+    /// Plugins that return false receive inputs with blob references replaced by
+    /// the blob content.
+    fn supports_blob_refs(&self) -> bool {
+        false
+    }
+
+    /// Report the health of the plugin.
+    ///
+    /// Plugins that cannot fail after initialization are always healthy.
+    fn health(&self) -> PluginHealth {
+        PluginHealth::default()
+    }
+
     /// Execute the step and return the resulting arguments.
     ///
     /// The arguments should be fully resolved.
@@ -42,12 +57,41 @@ pub trait Plugin: Send + Sync {
     ) -> Result<FlowResult>;
 }
 
+/// Whether a plugin is able to execute components.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum PluginStatus {
+    /// The plugin is running.
+    #[default]
+    Healthy,
+    /// The plugin failed and is being restarted.
+    Restarting,
+    /// The plugin failed and will not be restarted.
+    Failed,
+}
+
+/// Health of a plugin, as reported by [`Plugin::health`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginHealth {
+    pub status: PluginStatus,
+    /// Number of times the plugin was restarted after failing.
+    pub restarts: u32,
+    /// Why the plugin last failed, if it has.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
 /// Trait implemented by a deserializable plugin configuration.
 pub trait PluginConfig: Serialize + DeserializeOwned {
     type Error: error_stack::Context;
 
+    /// Create the plugin registered under `name`.
+    ///
+    /// The name identifies the plugin in logs and metrics.
     fn create_plugin(
         self,
+        name: &str,
         working_directory: &Path,
     ) -> impl Future<Output = error_stack::Result<Box<DynPlugin<'static>>, Self::Error>>;
 }
@@ -39,11 +39,13 @@ impl PluginRouter {
     }
 
     /// Get a plugin and resolved component name for the given component path and input data
+    ///
+    /// Returns the name the plugin is registered under, the plugin, and the resolved component name.
     pub fn get_plugin_and_component(
         &self,
         component_path: &str,
         input: ValueRef,
-    ) -> Result<(&Arc<DynPlugin<'static>>, String)> {
+    ) -> Result<(&str, &Arc<DynPlugin<'static>>, String)> {
         // Route the component path to get a component router
         let component_router = self
             .router
@@ -61,13 +63,12 @@ impl PluginRouter {
         );
 
         // Get the plugin by index.
-        let plugin = self
+        let (plugin_name, plugin) = self
             .plugins
             .get_index(route_rule.plugin_index)
-            .expect("Plugin index should be valid")
-            .1;
+            .expect("Plugin index should be valid");
 
-        Ok((plugin, resolved_component))
+        Ok((plugin_name, plugin, resolved_component))
     }
 
     /// Get all registered plugins
@@ -126,6 +127,14 @@ impl PluginRouterBuilder {
         self
     }
 
+    /// Register a plugin instance that may also be registered with other routers
+    ///
+    /// Registering a name a second time replaces the earlier plugin.
+    pub fn register_shared_plugin(mut self, name: String, plugin: Arc<DynPlugin<'static>>) -> Self {
+        self.plugins.insert(name, plugin);
+        self
+    }
+
     /// Build the PluginRouter
     pub fn build(self) -> Result<PluginRouter> {
         // Validate that all plugins referenced in rules exist
@@ -225,9 +234,10 @@ mod tests {
             .unwrap();
 
         let input = ValueRef::new(json!({}));
-        let (_plugin, path) = router
+        let (plugin_name, _plugin, path) = router
             .get_plugin_and_component("/test/example", input)
             .unwrap();
+        assert_eq!(plugin_name, "test");
         assert_eq!(path, "/example");
     }
 
@@ -266,10 +276,10 @@ mod tests {
             .unwrap();
 
         let input = ValueRef::new(json!({}));
-        let (plugin1, path1) = router
+        let (_, plugin1, path1) = router
             .get_plugin_and_component("/test/example", input.clone())
             .unwrap();
-        let (plugin2, path2) = router
+        let (_, plugin2, path2) = router
             .get_plugin_and_component("/other/example", input)
             .unwrap();
 
@@ -0,0 +1,125 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+//! Throughput of the stdio framings.
+//!
+//! `runtime` measures the Stepflow side: writing a message, given as JSON, as a
+//! frame and decoding the frame back to JSON. `server` approximates the component
+//! server side by parsing the payload of a frame without building a value.
+//!
+//! Run with `cargo bench -p stepflow-protocol --bench framing`.
+
+#![allow(clippy::print_stdout)]
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use serde::de::IgnoredAny;
+use serde_json::json;
+use stepflow_core::blob::encode_base64;
+use stepflow_protocol::Framing;
+
+const FRAMINGS: [Framing; 3] = [Framing::Json, Framing::MessagePack, Framing::Cbor];
+
+/// A `components/execute` request embedding a batch of texts.
+fn embedding_request() -> String {
+    let embeddings: Vec<Vec<f64>> = (0..64)
+        .map(|i| (0..768).map(|j| ((i * 768 + j) as f64).sin()).collect())
+        .collect();
+    let texts: Vec<String> = (0..64)
+        .map(|i| format!("Document {i}: the quick brown fox jumps over the lazy dog."))
+        .collect();
+    json!({
+        "jsonrpc": "2.0",
+        "id": "4a1f6f3e-2b0c-4a53-9d43-7c1f0b9e8f21",
+        "method": "components/execute",
+        "params": {
+            "component": "/python/embed",
+            "input": { "texts": texts, "embeddings": embeddings },
+            "step_id": "embed",
+            "run_id": "0d3c2a56-5f59-4f8b-8c3e-7a8f9b0c1d2e",
+            "flow_id": "flow",
+        },
+    })
+    .to_string()
+}
+
+/// A `blobs/put` request storing 1 MiB of binary content.
+fn binary_blob_request() -> String {
+    let content: Vec<u8> = (0..1024 * 1024).map(|i| (i * 31 % 251) as u8).collect();
+    json!({
+        "jsonrpc": "2.0",
+        "id": "put",
+        "method": "blobs/put",
+        "params": { "data": encode_base64(&content), "blob_type": "binary" },
+    })
+    .to_string()
+}
+
+fn payload(framing: Framing, frame: &[u8]) -> &[u8] {
+    match framing {
+        Framing::Json => &frame[..frame.len() - 1],
+        Framing::MessagePack | Framing::Cbor => &frame[4..],
+    }
+}
+
+fn bench_message(c: &mut Criterion, name: &str, json: &str) {
+    let mut group = c.benchmark_group(format!("runtime/{name}"));
+    group.throughput(Throughput::Bytes(json.len() as u64));
+    for framing in FRAMINGS {
+        let mut frame = Vec::new();
+        framing
+            .write_frame(json, &mut Default::default(), &mut frame)
+            .unwrap();
+        println!("{name}: {framing} frame is {} bytes", frame.len());
+
+        group.bench_function(BenchmarkId::new("write_frame", framing), |b| {
+            b.iter(|| {
+                let mut out = Vec::with_capacity(frame.len());
+                framing
+                    .write_frame(json, &mut Default::default(), &mut out)
+                    .unwrap();
+                out
+            })
+        });
+        group.bench_function(BenchmarkId::new("decode", framing), |b| {
+            b.iter(|| framing.decode(payload(framing, &frame).to_vec()).unwrap())
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group(format!("server/{name}"));
+    group.throughput(Throughput::Bytes(json.len() as u64));
+    for framing in FRAMINGS {
+        let mut frame = Vec::new();
+        framing
+            .write_frame(json, &mut Default::default(), &mut frame)
+            .unwrap();
+        let payload = payload(framing, &frame);
+        group.bench_function(BenchmarkId::new("parse", framing), |b| {
+            b.iter(|| -> IgnoredAny {
+                match framing {
+                    Framing::Json => serde_json::from_slice(payload).unwrap(),
+                    Framing::MessagePack => rmp_serde::from_slice(payload).unwrap(),
+                    Framing::Cbor => ciborium::from_reader(payload).unwrap(),
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn framing(c: &mut Criterion) {
+    bench_message(c, "embedding_request", &embedding_request());
+    bench_message(c, "binary_blob", &binary_blob_request());
+}
+
+criterion_group!(benches, framing);
+criterion_main!(benches);
@@ -0,0 +1,613 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+//! Persistent connections to component servers Stepflow does not launch.
+//!
+//! A single connection carries every message in both directions, so requests
+//! from the component server (such as `blobs/put`) are answered on the same
+//! connection without a request of their own. If the connection is lost, it
+//! is re-established and initialized again. Transports differ only in how
+//! they connect and frame messages, described by [`Socket`].
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use error_stack::ResultExt as _;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use stepflow_plugin::{Context, PluginHealth, PluginStatus};
+use tokio::sync::{mpsc, oneshot};
+use tracing::Instrument as _;
+
+use crate::OwnedJson;
+use crate::error::{Result, TransportError};
+use crate::lazy_value::LazyValue;
+use crate::protocol::{
+    Capabilities, InitializeParams, InitializeResult, Initialized, Method, MethodResponse,
+    ProtocolMethod, ProtocolNotification,
+};
+use crate::{Message, MessageHandlerRegistry, MethodRequest, Notification, RequestId};
+
+/// How long a component server has to respond to `initialize` on a new connection.
+const INITIALIZE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A request awaiting its response, and the channel to send the response to.
+pub type PendingRequest = (RequestId, oneshot::Sender<Result<OwnedJson>>);
+
+/// What was received from a [`Socket`].
+pub enum SocketEvent {
+    /// A JSON-RPC message.
+    Message(String),
+    /// A response to [`Socket::ping`].
+    Pong,
+    /// The connection was closed or failed, and why.
+    Closed(String),
+}
+
+/// A connection to a component server carrying JSON-RPC messages.
+pub trait Socket: Sized + Send + 'static {
+    /// Connect to the component server at `address`.
+    fn connect(address: &str) -> impl Future<Output = Result<Self>> + Send;
+
+    fn send(&mut self, json: String) -> impl Future<Output = Result<()>> + Send;
+
+    fn recv(&mut self) -> impl Future<Output = SocketEvent> + Send;
+
+    /// Check that the connection is alive. Only called if heartbeats are
+    /// configured for the connection.
+    fn ping(&mut self) -> impl Future<Output = Result<()>> + Send;
+
+    fn close(&mut self) -> impl Future<Output = ()> + Send;
+}
+
+/// How a lost connection to a component server is re-established.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ReconnectPolicy {
+    /// Delay before the first attempt to reconnect. Each further attempt
+    /// doubles the delay.
+    pub initial_backoff_ms: u64,
+    /// Upper bound on the delay between attempts.
+    pub max_backoff_ms: u64,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            initial_backoff_ms: 500,
+            max_backoff_ms: 30_000,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// The delay before the given attempt to reconnect, counting from zero.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        Duration::from_millis(self.initial_backoff_ms)
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(Duration::from_millis(self.max_backoff_ms))
+    }
+}
+
+/// Where and how to connect to a component server.
+#[derive(Debug, Clone)]
+pub struct ConnectionConfig {
+    pub address: String,
+    /// How often to check that the connection is alive, if at all.
+    pub heartbeat_interval: Option<Duration>,
+    pub reconnect: ReconnectPolicy,
+}
+
+/// Why a connection stopped serving requests.
+enum Disconnect {
+    /// Every client handle was dropped.
+    Shutdown,
+    /// The connection failed or was closed by the component server.
+    Lost(String),
+}
+
+/// An initialized connection to a component server and the requests awaiting
+/// responses on it.
+struct Connection<S> {
+    socket: S,
+    pending_requests: HashMap<RequestId, oneshot::Sender<Result<OwnedJson>>>,
+    outgoing_tx: mpsc::Sender<String>,
+    /// Handlers for requests and notifications from the component server.
+    handlers: Arc<MessageHandlerRegistry>,
+    /// Capabilities negotiated during the initialization handshake.
+    capabilities: Capabilities,
+}
+
+impl<S: Socket> Connection<S> {
+    /// Connect to the component server and perform the initialization handshake.
+    async fn connect(address: &str, outgoing_tx: mpsc::Sender<String>) -> Result<Self> {
+        let socket = S::connect(address).await?;
+        let mut connection = Self {
+            socket,
+            pending_requests: HashMap::new(),
+            outgoing_tx,
+            handlers: Arc::new(MessageHandlerRegistry::default()),
+            capabilities: Capabilities::default(),
+        };
+        connection.capabilities = tokio::time::timeout(INITIALIZE_TIMEOUT, connection.initialize())
+            .await
+            .change_context(TransportError::Recv)
+            .attach_printable("timed out waiting for initialize response")??;
+        Ok(connection)
+    }
+
+    async fn send(&mut self, json: String) -> Result<()> {
+        tracing::debug!("Sending message to component server: {json}");
+        self.socket.send(json).await
+    }
+
+    /// Perform the initialization handshake, returning the capabilities supported by both sides.
+    async fn initialize(&mut self) -> Result<Capabilities> {
+        let id = RequestId::new_uuid();
+        let params = InitializeParams::default();
+        let request = MethodRequest::new(
+            id.clone(),
+            InitializeParams::METHOD_NAME,
+            Some(LazyValue::write_ref(&params)),
+        );
+        let request = serde_json::to_string(&request)
+            .change_context(TransportError::SerializeRequest(Method::Initialize))?;
+        self.send(request).await?;
+
+        let capabilities = self
+            .wait_for_response(&id)
+            .await?
+            .into_success_value()?
+            .value()
+            .deserialize_to::<InitializeResult>()
+            .change_context(TransportError::InvalidResponse(Method::Initialize))?
+            .negotiate()?;
+
+        let notification = Notification::new(
+            Initialized::METHOD_NAME,
+            Some(LazyValue::write_ref(&Initialized {})),
+        );
+        let notification = serde_json::to_string(&notification)
+            .change_context(TransportError::SerializeRequest(Method::Initialized))?;
+        self.send(notification).await?;
+        Ok(capabilities)
+    }
+
+    /// Read messages from the connection until the response to `id` arrives.
+    ///
+    /// Only used before the connection serves requests, so other messages are ignored.
+    async fn wait_for_response(
+        &mut self,
+        id: &RequestId,
+    ) -> Result<OwnedJson<MethodResponse<'static>>> {
+        loop {
+            match self.socket.recv().await {
+                SocketEvent::Message(text) => {
+                    let msg = OwnedJson::try_new(text).change_context(TransportError::Recv)?;
+                    if matches!(msg.message(), Message::Response(response) if response.id() == id) {
+                        return msg.owned_response();
+                    }
+                    tracing::warn!(
+                        "Ignoring message received during initialization: {}",
+                        msg.json()
+                    );
+                }
+                SocketEvent::Pong => {}
+                SocketEvent::Closed(reason) => {
+                    return Err(error_stack::report!(TransportError::ConnectionLost(reason)))
+                        .attach_printable("closed during initialization");
+                }
+            }
+        }
+    }
+
+    /// Exchange messages until the connection is lost or every client handle
+    /// is dropped.
+    async fn serve(
+        &mut self,
+        outgoing_rx: &mut mpsc::Receiver<String>,
+        pending_rx: &mut mpsc::Receiver<PendingRequest>,
+        heartbeat_interval: Option<Duration>,
+        context: &Arc<dyn Context>,
+    ) -> Disconnect {
+        let mut heartbeat = heartbeat_interval.map(|interval| {
+            tokio::time::interval_at(tokio::time::Instant::now() + interval, interval)
+        });
+        let mut awaiting_pong = false;
+
+        loop {
+            tokio::select! {
+                pending = pending_rx.recv() => {
+                    let Some((id, pending)) = pending else {
+                        self.socket.close().await;
+                        return Disconnect::Shutdown;
+                    };
+                    self.pending_requests.insert(id, pending);
+                }
+                Some(outgoing) = outgoing_rx.recv() => {
+                    if let Err(e) = self.send(outgoing).await {
+                        return Disconnect::Lost(e.to_string());
+                    }
+                }
+                event = self.socket.recv() => match event {
+                    SocketEvent::Message(text) => {
+                        if let Err(e) = self.receive(text, pending_rx, context).await {
+                            return Disconnect::Lost(e.to_string());
+                        }
+                    }
+                    SocketEvent::Pong => awaiting_pong = false,
+                    SocketEvent::Closed(reason) => return Disconnect::Lost(reason),
+                },
+                _ = next_heartbeat(&mut heartbeat) => {
+                    if awaiting_pong {
+                        return Disconnect::Lost("no response to heartbeat".to_string());
+                    }
+                    if let Err(e) = self.socket.ping().await {
+                        return Disconnect::Lost(e.to_string());
+                    }
+                    awaiting_pong = true;
+                }
+            }
+        }
+    }
+
+    /// Handle a message received from the component server.
+    async fn receive(
+        &mut self,
+        text: String,
+        pending_rx: &mut mpsc::Receiver<PendingRequest>,
+        context: &Arc<dyn Context>,
+    ) -> Result<()> {
+        tracing::debug!("Received message from component server: {text}");
+        let msg = OwnedJson::try_new(text).change_context(TransportError::Recv)?;
+        match msg.message() {
+            Message::Request(request) => {
+                let Some(handler) = self.handlers.get_method_handler(request.method) else {
+                    tracing::warn!("No handler found for method '{}'", request.method);
+                    let response = Message::Response(MethodResponse::error(
+                        request.id.clone(),
+                        crate::Error::method_not_found(request.method),
+                    ));
+                    let response =
+                        serde_json::to_string(&response).change_context(TransportError::Send)?;
+                    return self.send(response).await;
+                };
+
+                let outgoing_tx = self.outgoing_tx.clone();
+                let context = context.clone();
+                tokio::spawn(async move {
+                    let Message::Request(request) = msg.message() else {
+                        panic!("Expected a request message");
+                    };
+                    if let Err(err) = handler
+                        .handle_message(request, outgoing_tx, context.clone())
+                        .await
+                    {
+                        tracing::error!(
+                            "Error handling request for method '{}': {:?}",
+                            request.method,
+                            err
+                        );
+                    }
+                });
+            }
+            Message::Notification(notification) => {
+                self.handlers
+                    .handle_notification(notification, context.clone())
+                    .await;
+            }
+            Message::Response(response) => {
+                let Some(pending) = self.take_pending(pending_rx, response.id()) else {
+                    tracing::warn!("Unexpected response {:?}", response.id());
+                    return Ok(());
+                };
+                if pending.send(Ok(msg)).is_err() {
+                    tracing::debug!("Request was abandoned before its response arrived");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the channel awaiting the response to `id`.
+    ///
+    /// Requests are queued before they are sent, so a request whose response
+    /// arrived is either registered or still queued.
+    fn take_pending(
+        &mut self,
+        pending_rx: &mut mpsc::Receiver<PendingRequest>,
+        id: &RequestId,
+    ) -> Option<oneshot::Sender<Result<OwnedJson>>> {
+        while let Ok((pending_id, pending)) = pending_rx.try_recv() {
+            self.pending_requests.insert(pending_id, pending);
+        }
+        self.pending_requests.remove(id)
+    }
+
+    /// Fail the requests awaiting responses on this connection, and those
+    /// queued for it.
+    fn fail_requests(
+        self,
+        outgoing_rx: &mut mpsc::Receiver<String>,
+        pending_rx: &mut mpsc::Receiver<PendingRequest>,
+        reason: &str,
+    ) {
+        let queued = std::iter::from_fn(|| pending_rx.try_recv().ok());
+        for (_, pending) in self.pending_requests.into_iter().chain(queued) {
+            let error = TransportError::ConnectionLost(reason.to_string());
+            let _ = pending.send(Err(error_stack::report!(error)));
+        }
+        while outgoing_rx.try_recv().is_ok() {}
+    }
+}
+
+/// Wait until the next heartbeat is due, or forever without heartbeats.
+async fn next_heartbeat(heartbeat: &mut Option<tokio::time::Interval>) {
+    match heartbeat {
+        Some(heartbeat) => {
+            heartbeat.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+/// Fail requests made while disconnected until `wait` elapses.
+///
+/// Returns `false` if every client handle was dropped.
+async fn reject_requests(
+    outgoing_rx: &mut mpsc::Receiver<String>,
+    pending_rx: &mut mpsc::Receiver<PendingRequest>,
+    reason: &str,
+    wait: Duration,
+) -> bool {
+    let deadline = tokio::time::sleep(wait);
+    tokio::pin!(deadline);
+    loop {
+        tokio::select! {
+            _ = &mut deadline => return true,
+            pending = pending_rx.recv() => {
+                let Some((_, pending)) = pending else {
+                    return false;
+                };
+                let error = TransportError::ConnectionLost(reason.to_string());
+                let _ = pending.send(Err(error_stack::report!(error)));
+            }
+            Some(_) = outgoing_rx.recv() => {}
+        }
+    }
+}
+
+fn update_health(health: &Mutex<PluginHealth>, update: impl FnOnce(&mut PluginHealth)) {
+    update(&mut health.lock().expect("plugin health poisoned"));
+}
+
+/// Exchange messages with the component server, reconnecting when the
+/// connection is lost.
+///
+/// Runs until every client handle is dropped. Requests made while
+/// disconnected fail immediately.
+async fn connection_loop<S: Socket>(
+    mut connection: Connection<S>,
+    config: ConnectionConfig,
+    health: Arc<Mutex<PluginHealth>>,
+    outgoing_tx: mpsc::Sender<String>,
+    mut outgoing_rx: mpsc::Receiver<String>,
+    mut pending_rx: mpsc::Receiver<PendingRequest>,
+    context: Arc<dyn Context>,
+) {
+    loop {
+        let mut reason = match connection
+            .serve(
+                &mut outgoing_rx,
+                &mut pending_rx,
+                config.heartbeat_interval,
+                &context,
+            )
+            .await
+        {
+            Disconnect::Shutdown => return,
+            Disconnect::Lost(reason) => reason,
+        };
+        tracing::error!("Connection to component server lost: {reason}");
+        connection.fail_requests(&mut outgoing_rx, &mut pending_rx, &reason);
+
+        let mut attempt = 0;
+        connection = loop {
+            update_health(&health, |health| {
+                health.status = PluginStatus::Restarting;
+                health.last_error = Some(reason.clone());
+            });
+            let backoff = config.reconnect.backoff(attempt);
+            if !reject_requests(&mut outgoing_rx, &mut pending_rx, &reason, backoff).await {
+                return;
+            }
+            match Connection::connect(&config.address, outgoing_tx.clone()).await {
+                Ok(connection) => {
+                    tracing::info!("Reconnected to component server");
+                    update_health(&health, |health| {
+                        health.status = PluginStatus::Healthy;
+                        health.restarts += 1;
+                    });
+                    break connection;
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to reconnect to component server: {e:?}");
+                    reason = e.to_string();
+                    attempt += 1;
+                }
+            }
+        };
+    }
+}
+
+/// Client holding a persistent connection to a component server.
+pub struct ConnectionClient<S> {
+    handle: ConnectionClientHandle,
+    socket: PhantomData<S>,
+}
+
+impl<S: Socket> ConnectionClient<S> {
+    /// Connect to the component server and perform the initialization handshake.
+    ///
+    /// Further connections, made if this one is lost, are initialized the same way.
+    pub async fn try_new(config: ConnectionConfig, context: Arc<dyn Context>) -> Result<Self> {
+        let (outgoing_tx, outgoing_rx) = mpsc::channel(100);
+        let (pending_tx, pending_rx) = mpsc::channel(100);
+
+        // Make the first connection here, so failures to connect are reported to the caller.
+        let connection = Connection::<S>::connect(&config.address, outgoing_tx.clone()).await?;
+        let capabilities = connection.capabilities;
+        let health = Arc::new(Mutex::new(PluginHealth::default()));
+
+        let span = tracing::info_span!("connection_loop", address = %config.address);
+        tokio::spawn(
+            connection_loop(
+                connection,
+                config,
+                health.clone(),
+                outgoing_tx.clone(),
+                outgoing_rx,
+                pending_rx,
+                context,
+            )
+            .instrument(span),
+        );
+
+        Ok(Self {
+            handle: ConnectionClientHandle {
+                outgoing_tx,
+                pending_tx,
+                health,
+                capabilities,
+            },
+            socket: PhantomData,
+        })
+    }
+
+    pub fn handle(&self) -> ConnectionClientHandle {
+        self.handle.clone()
+    }
+}
+
+#[derive(Clone)]
+pub struct ConnectionClientHandle {
+    outgoing_tx: mpsc::Sender<String>,
+    /// Channel to send new pending requests to.
+    pending_tx: mpsc::Sender<PendingRequest>,
+    health: Arc<Mutex<PluginHealth>>,
+    /// Capabilities negotiated on the first connection.
+    capabilities: Capabilities,
+}
+
+impl ConnectionClientHandle {
+    pub fn health(&self) -> PluginHealth {
+        self.health.lock().expect("plugin health poisoned").clone()
+    }
+
+    pub fn capabilities(&self) -> Capabilities {
+        self.capabilities
+    }
+
+    pub async fn method<I>(&self, params: &I) -> Result<I::Response>
+    where
+        I: ProtocolMethod + serde::Serialize + Send + Sync + std::fmt::Debug,
+        I::Response: DeserializeOwned + Send + Sync + 'static,
+    {
+        self.method_with_id(RequestId::new_uuid(), params).await
+    }
+
+    /// Send a request with the given ID and wait for its response.
+    pub async fn method_with_id<I>(&self, id: RequestId, params: &I) -> Result<I::Response>
+    where
+        I: ProtocolMethod + serde::Serialize + Send + Sync + std::fmt::Debug,
+        I::Response: DeserializeOwned + Send + Sync + 'static,
+    {
+        let response = self
+            .method_dyn(id, I::METHOD_NAME, LazyValue::write_ref(params))
+            .await?;
+        response
+            .value()
+            .deserialize_to::<I::Response>()
+            .change_context(TransportError::InvalidResponse(I::METHOD_NAME))
+    }
+
+    pub async fn notify<I>(&self, params: &I) -> Result<()>
+    where
+        I: ProtocolNotification + serde::Serialize + Send + Sync + std::fmt::Debug,
+    {
+        self.send(&Notification::new(
+            I::METHOD_NAME,
+            Some(LazyValue::write_ref(params)),
+        ))
+        .await
+    }
+
+    async fn send(&self, msg: &(dyn erased_serde::Serialize + Send + Sync)) -> Result<()> {
+        let msg = serde_json::to_string(&msg).change_context(TransportError::Send)?;
+        self.outgoing_tx
+            .send(msg)
+            .await
+            .change_context(TransportError::Send)
+    }
+
+    async fn method_dyn(
+        &self,
+        id: RequestId,
+        method: Method,
+        params: LazyValue<'_>,
+    ) -> Result<OwnedJson<LazyValue<'static>>> {
+        let span = tracing::info_span!("protocol_request", %method, request_id = %id);
+        async move {
+            let (response_tx, response_rx) = tokio::sync::oneshot::channel();
+
+            self.pending_tx
+                .send((id.clone(), response_tx))
+                .await
+                .map_err(|_| TransportError::Send)?;
+
+            let request = MethodRequest::new(id.clone(), method, Some(params));
+            self.send(&request).await?;
+
+            // The connection loop responds with an error if the connection is lost first.
+            let response = response_rx
+                .await
+                .change_context(TransportError::Recv)??
+                .owned_response()?;
+
+            debug_assert_eq!(response.response().id(), &id);
+            response.into_success_value()
+        }
+        .instrument(span)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reconnect_backoff() {
+        let policy = ReconnectPolicy {
+            initial_backoff_ms: 100,
+            max_backoff_ms: 1_000,
+        };
+        let backoffs: Vec<_> = [0, 1, 3, 4, 40]
+            .into_iter()
+            .map(|attempt| policy.backoff(attempt).as_millis())
+            .collect();
+        assert_eq!(backoffs, vec![100, 200, 800, 1_000, 1_000]);
+    }
+}
@@ -15,7 +15,7 @@ use std::path::PathBuf;
 
 use thiserror::Error;
 
-use crate::protocol::Method;
+use crate::protocol::{Framing, Method};
 
 #[derive(Error, Debug)]
 pub enum TransportError {
@@ -41,6 +41,23 @@ pub enum TransportError {
     },
     #[error("component server failed with exit code {exit_code:?}")]
     ServerFailure { exit_code: Option<i32> },
+    #[error("component server exited with {status}{}", format_stderr_tail(.stderr_tail))]
+    ServerExited {
+        status: String,
+        stderr_tail: Vec<String>,
+    },
+    #[error("component server failed and will not be restarted: {0}")]
+    ServerUnavailable(String),
+    #[error(
+        "component server uses protocol version {server}, but this Stepflow runtime supports versions {min} to {max}"
+    )]
+    IncompatibleProtocolVersion { server: u32, min: u32, max: u32 },
+    #[error("component server chose framing '{0}', which was not offered")]
+    UnofferedFraming(Framing),
+    #[error("error connecting to component server at {0}")]
+    Connect(String),
+    #[error("connection to component server lost: {0}")]
+    ConnectionLost(String),
     #[error("error closing stepflow component process")]
     Close,
     #[error("invalid command: {}", .0.display())]
@@ -62,4 +79,13 @@ pub enum TransportError {
     },
 }
 
+/// Format the last lines a component server wrote to stderr for an error message.
+fn format_stderr_tail(lines: &[String]) -> String {
+    if lines.is_empty() {
+        String::new()
+    } else {
+        format!("; stderr:\n{}", lines.join("\n"))
+    }
+}
+
 pub type Result<T, E = error_stack::Report<TransportError>> = std::result::Result<T, E>;
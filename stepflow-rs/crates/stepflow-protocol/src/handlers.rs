@@ -11,7 +11,11 @@
 // the License.
 
 mod blob_handlers;
+mod executing_steps;
 mod flow_handlers;
+mod log_handler;
 mod message_handler;
+mod progress_handler;
 
+pub(crate) use executing_steps::{ExecutingStep, ExecutingStepScope};
 pub(crate) use message_handler::*;
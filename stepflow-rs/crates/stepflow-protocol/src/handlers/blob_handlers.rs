@@ -13,9 +13,15 @@
 use error_stack::ResultExt as _;
 use futures::future::{BoxFuture, FutureExt as _};
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use stepflow_core::blob::{DEFAULT_BINARY_CONTENT_TYPE, decode_base64_value, encode_base64};
+use stepflow_core::workflow::ValueRef;
+use stepflow_core::{BlobId, BlobType};
 use stepflow_plugin::Context;
 use tokio::sync::mpsc;
+use uuid::Uuid;
 
 use crate::error::{Result, TransportError};
 use crate::lazy_value::LazyValue;
@@ -85,8 +91,120 @@ where
     Ok(())
 }
 
+/// How long an uploaded chunk waits for the request completing its blob.
+const CHUNK_TTL: Duration = Duration::from_secs(600);
+
+/// Maximum total size of the chunks held for a connection.
+const MAX_PENDING_CHUNK_BYTES: usize = 256 * 1024 * 1024;
+
+/// Maximum number of distinct chunks held for a connection.
+const MAX_PENDING_CHUNKS: usize = 1024;
+
+/// A chunk of a binary blob awaiting the request that completes the upload.
+struct PendingChunk {
+    content: Arc<[u8]>,
+    /// Number of uploads of this content not yet consumed.
+    uploads: usize,
+    expires_at: Instant,
+}
+
+/// Chunks uploaded with `partial` on one connection, held in memory until the
+/// final request of their upload. They are never stored as blobs.
+///
+/// Uploads that would hold more than `max_bytes` or `max_chunks` are rejected.
+struct PendingChunks {
+    chunks: Mutex<HashMap<BlobId, PendingChunk>>,
+    max_bytes: usize,
+    max_chunks: usize,
+}
+
+impl Default for PendingChunks {
+    fn default() -> Self {
+        Self::with_limits(MAX_PENDING_CHUNK_BYTES, MAX_PENDING_CHUNKS)
+    }
+}
+
+impl PendingChunks {
+    fn with_limits(max_bytes: usize, max_chunks: usize) -> Self {
+        Self {
+            chunks: Mutex::default(),
+            max_bytes,
+            max_chunks,
+        }
+    }
+
+    /// Hold a chunk, returning the ID under which it may be listed in `chunks`.
+    fn put(&self, content: Vec<u8>) -> Result<BlobId, Error<'static>> {
+        let chunk_id = BlobId::from_bytes(&content, DEFAULT_BINARY_CONTENT_TYPE)
+            .expect("SHA-256 hashes are valid blob IDs");
+        let now = Instant::now();
+        let mut chunks = self.chunks.lock().expect("pending chunks lock poisoned");
+        chunks.retain(|_, chunk| chunk.expires_at > now);
+
+        // Repeated content is held once, so only new content counts against the limits.
+        if !chunks.contains_key(&chunk_id) {
+            let held_bytes: usize = chunks.values().map(|chunk| chunk.content.len()).sum();
+            if chunks.len() >= self.max_chunks {
+                return Err(Error::resource_unavailable(format!(
+                    "Too many pending chunks: at most {} may be held per connection",
+                    self.max_chunks
+                )));
+            }
+            if held_bytes + content.len() > self.max_bytes {
+                return Err(Error::resource_unavailable(format!(
+                    "Pending chunks too large: at most {} bytes may be held per connection",
+                    self.max_bytes
+                )));
+            }
+        }
+
+        let chunk = chunks
+            .entry(chunk_id.clone())
+            .or_insert_with(|| PendingChunk {
+                content: content.into(),
+                uploads: 0,
+                expires_at: now,
+            });
+        chunk.uploads += 1;
+        chunk.expires_at = now + CHUNK_TTL;
+        Ok(chunk_id)
+    }
+
+    /// Concatenate the given chunks, releasing them.
+    ///
+    /// Nothing is released if any chunk is missing.
+    fn take(&self, chunk_ids: &[BlobId]) -> Result<Vec<u8>, Error<'static>> {
+        let now = Instant::now();
+        let mut chunks = self.chunks.lock().expect("pending chunks lock poisoned");
+        let mut content = Vec::new();
+        for chunk_id in chunk_ids {
+            let chunk = chunks
+                .get(chunk_id)
+                .filter(|chunk| chunk.expires_at > now)
+                .ok_or_else(|| Error::not_found("chunk", chunk_id.as_str()))?;
+            content.extend_from_slice(&chunk.content);
+        }
+        for chunk_id in chunk_ids {
+            if let Some(chunk) = chunks.get_mut(chunk_id) {
+                chunk.uploads -= 1;
+                if chunk.uploads == 0 {
+                    chunks.remove(chunk_id);
+                }
+            }
+        }
+        Ok(content)
+    }
+}
+
 /// Handler for put_blob method calls from component servers.
-pub struct PutBlobHandler;
+///
+/// Chunks of uploads in progress are held by the handler, and so are scoped
+/// to the connection whose [`MessageHandlerRegistry`](super::MessageHandlerRegistry)
+/// it belongs to.
+#[derive(Default)]
+pub struct PutBlobHandler {
+    pending_chunks: Arc<PendingChunks>,
+}
 
 impl MethodHandler for PutBlobHandler {
     fn handle_message<'a>(
@@ -95,18 +213,53 @@ impl MethodHandler for PutBlobHandler {
         response_tx: mpsc::Sender<String>,
         context: Arc<dyn Context>,
     ) -> BoxFuture<'a, error_stack::Result<(), TransportError>> {
+        let pending_chunks = self.pending_chunks.clone();
         handle_method_call(
             request,
             response_tx,
             |request: crate::protocol::PutBlobParams| async move {
-                let blob_id = context
-                    .state_store()
-                    .put_blob(request.data, request.blob_type)
-                    .await
-                    .map_err(|e| {
-                        tracing::error!("Failed to put blob: {e}");
-                        Error::internal("Failed to put blob")
-                    })?;
+                if request.partial {
+                    if request.blob_type != BlobType::Binary {
+                        return Err(Error::invalid_value("blob_type", "binary"));
+                    }
+                    if request.chunks.is_some() {
+                        return Err(Error::invalid_value("chunks", "none for a partial upload"));
+                    }
+                    let content = decode_base64_value(request.data.as_ref())
+                        .map_err(|_| Error::invalid_value("data", "base64-encoded string"))?;
+                    let blob_id = pending_chunks.put(content)?;
+                    return Ok(crate::protocol::PutBlobResult { blob_id });
+                }
+
+                let state_store = context.state_store();
+                let blob_id = if request.blob_type == BlobType::Binary {
+                    let content = assemble_binary_content(&pending_chunks, &request)?;
+                    let content_type = request
+                        .content_type
+                        .unwrap_or_else(|| DEFAULT_BINARY_CONTENT_TYPE.to_string());
+                    state_store.put_binary_blob(content, content_type).await
+                } else {
+                    if request.chunks.is_some() || request.content_type.is_some() {
+                        return Err(Error::invalid_value("blob_type", "binary"));
+                    }
+                    state_store.put_blob(request.data, request.blob_type).await
+                }
+                .map_err(|e| {
+                    tracing::error!("Failed to put blob: {e}");
+                    Error::internal("Failed to put blob")
+                })?;
+
+                if let Some(run_id) = request.run_id {
+                    let run_id = Uuid::parse_str(&run_id)
+                        .map_err(|_| Error::invalid_value("run_id", "a UUID"))?;
+                    state_store
+                        .add_blob_to_run_namespace(run_id, &blob_id)
+                        .await
+                        .map_err(|e| {
+                            tracing::error!("Failed to add blob to run namespace: {e}");
+                            Error::internal("Failed to put blob")
+                        })?;
+                }
                 Ok(crate::protocol::PutBlobResult { blob_id })
             },
         )
@@ -114,6 +267,23 @@ impl MethodHandler for PutBlobHandler {
     }
 }
 
+/// Concatenate the previously uploaded chunks and the final `data` of a binary put.
+fn assemble_binary_content(
+    pending_chunks: &PendingChunks,
+    request: &crate::protocol::PutBlobParams,
+) -> Result<Vec<u8>, Error<'static>> {
+    let tail = decode_base64_value(request.data.as_ref())
+        .map_err(|_| Error::invalid_value("data", "base64-encoded string"))?;
+
+    let Some(chunks) = &request.chunks else {
+        return Ok(tail);
+    };
+
+    let mut content = pending_chunks.take(chunks)?;
+    content.extend_from_slice(&tail);
+    Ok(content)
+}
+
 /// Handler for get_blob method calls from component servers.
 pub struct GetBlobHandler;
 
@@ -136,12 +306,76 @@ impl MethodHandler for GetBlobHandler {
                         tracing::error!("Failed to get blob: {e}");
                         Error::internal("Failed to get blob")
                     })?;
+
+                let Some(content) = blob_data.as_binary() else {
+                    if request.offset.is_some() || request.length.is_some() {
+                        return Err(Error::invalid_value("blob_id", "a binary blob"));
+                    }
+                    return Ok(crate::protocol::GetBlobResult {
+                        data: blob_data.data(),
+                        blob_type: blob_data.blob_type(),
+                        content_type: None,
+                        size: None,
+                    });
+                };
+
+                let size = content.len();
+                let start = request.offset.map_or(0, |offset| offset as usize).min(size);
+                let end = request
+                    .length
+                    .map_or(size, |length| start.saturating_add(length as usize))
+                    .min(size);
                 Ok(crate::protocol::GetBlobResult {
-                    data: blob_data.data(),
-                    blob_type: blob_data.blob_type(),
+                    data: ValueRef::new(encode_base64(&content[start..end]).into()),
+                    blob_type: BlobType::Binary,
+                    content_type: Some(blob_data.content_type().to_string()),
+                    size: Some(size as u64),
                 })
             },
         )
         .boxed()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pending_chunks() {
+        let pending = PendingChunks::default();
+        let first = pending.put(b"abc".to_vec()).unwrap();
+        let second = pending.put(b"def".to_vec()).unwrap();
+        let repeated = pending.put(b"abc".to_vec()).unwrap();
+        assert_eq!(first, repeated);
+
+        // A missing chunk fails without releasing the others.
+        let missing = BlobId::new("0".repeat(64)).unwrap();
+        assert!(pending.take(&[first.clone(), missing]).is_err());
+
+        let chunks = [first.clone(), second.clone(), first.clone()];
+        assert_eq!(pending.take(&chunks).unwrap(), b"abcdefabc");
+
+        // Chunks are released once consumed by as many uploads as sent them.
+        assert!(pending.take(&[first]).is_err());
+        assert!(pending.take(&[second]).is_err());
+        assert!(pending.chunks.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_pending_chunks_limits() {
+        let pending = PendingChunks::with_limits(6, 2);
+        let first = pending.put(b"abc".to_vec()).unwrap();
+
+        // Repeated content is not held again, so does not count against the limits.
+        pending.put(b"abc".to_vec()).unwrap();
+        assert!(pending.put(b"defg".to_vec()).is_err());
+        let second = pending.put(b"de".to_vec()).unwrap();
+        assert!(pending.put(b"f".to_vec()).is_err());
+
+        // Consumed chunks no longer count against the limits.
+        let chunks = [first.clone(), first, second];
+        assert_eq!(pending.take(&chunks).unwrap(), b"abcabcde");
+        pending.put(b"defghi".to_vec()).unwrap();
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+use uuid::Uuid;
+
+use crate::protocol::RequestId;
+
+/// A step being executed by a component server.
+#[derive(Debug, Clone)]
+pub(crate) struct ExecutingStep {
+    pub run_id: Uuid,
+    pub step_id: String,
+    /// Whether log messages are recorded in the state store.
+    pub persist_logs: bool,
+}
+
+/// Steps being executed, by the ID of their `components/execute` request.
+static EXECUTING_STEPS: LazyLock<Mutex<HashMap<RequestId, ExecutingStep>>> =
+    LazyLock::new(Default::default);
+
+fn executing_steps() -> std::sync::MutexGuard<'static, HashMap<RequestId, ExecutingStep>> {
+    EXECUTING_STEPS.lock().expect("executing steps poisoned")
+}
+
+/// Look up the step executed by a request, if it is still in flight.
+pub(crate) fn executing_step(request_id: &RequestId) -> Option<ExecutingStep> {
+    executing_steps().get(request_id).cloned()
+}
+
+/// Attributes notifications sent with the ID of a request, such as log
+/// messages and progress, to the step it executes, until dropped.
+pub(crate) struct ExecutingStepScope {
+    request_id: RequestId,
+}
+
+impl ExecutingStepScope {
+    pub fn new(request_id: RequestId, step: ExecutingStep) -> Self {
+        executing_steps().insert(request_id.clone(), step);
+        Self { request_id }
+    }
+}
+
+impl Drop for ExecutingStepScope {
+    fn drop(&mut self) {
+        executing_steps().remove(&self.request_id);
+    }
+}
@@ -12,13 +12,14 @@
 
 use futures::future::{BoxFuture, FutureExt as _};
 use std::sync::Arc;
-use stepflow_plugin::Context;
+use stepflow_plugin::{Context, ExecutionContext};
 use tokio::sync::mpsc;
 
 use crate::error::TransportError;
 use crate::{Error, MethodHandler, MethodRequest};
 
 use super::blob_handlers::handle_method_call;
+use super::executing_steps::executing_step;
 
 /// Handler for flow evaluation method calls from component servers.
 pub struct EvaluateFlowHandler;
@@ -37,6 +38,23 @@ impl MethodHandler for EvaluateFlowHandler {
             request,
             response_tx,
             |request: crate::protocol::EvaluateFlowParams| async move {
+                // Evaluate the flow nested within the run of the step executed by
+                // the calling request. The lineage is taken from the request
+                // being handled, not from the component.
+                let context = match &request.request_id {
+                    Some(request_id) => {
+                        let step = executing_step(request_id).ok_or_else(|| {
+                            Error::not_found("executing request", &request_id.to_string())
+                        })?;
+                        Arc::new(ExecutionContext::new(
+                            context,
+                            step.run_id,
+                            Some(step.step_id),
+                        ))
+                    }
+                    None => context,
+                };
+
                 // Execute the flow using the shared utility
                 let result = context
                     .execute_flow_by_id(&request.flow_id, request.input)
@@ -0,0 +1,100 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+use std::sync::Arc;
+
+use error_stack::ResultExt as _;
+use futures::future::{BoxFuture, FutureExt as _};
+use stepflow_plugin::Context;
+use stepflow_state::StepLog;
+use tracing::Level;
+
+use crate::error::TransportError;
+use crate::protocol::{LogLevel, LogMessage, Notification};
+
+use super::NotificationHandler;
+use super::executing_steps::{ExecutingStep, executing_step};
+
+/// Handler for log messages sent by component servers.
+///
+/// Messages are emitted as `tracing` events with the run and step they are
+/// attributed to, and recorded in the state store if the plugin persists logs.
+pub struct LogHandler;
+
+impl NotificationHandler for LogHandler {
+    fn handle_notification<'a>(
+        &self,
+        notification: &'a Notification<'a>,
+        context: Arc<dyn Context>,
+    ) -> BoxFuture<'a, error_stack::Result<(), TransportError>> {
+        async move {
+            let params = notification
+                .params
+                .as_ref()
+                .ok_or_else(|| TransportError::InvalidMessage("missing log params".to_string()))?;
+            let log: LogMessage =
+                params
+                    .deserialize_to()
+                    .change_context(TransportError::InvalidMessage(
+                        "invalid log params".to_string(),
+                    ))?;
+            let step = log.request_id.as_ref().and_then(executing_step);
+
+            emit(&log, step.as_ref());
+
+            if let Some(step) = step.filter(|step| step.persist_logs) {
+                let entry = StepLog {
+                    level: log.level.into(),
+                    message: log.message,
+                    fields: log.fields,
+                    timestamp: chrono::Utc::now(),
+                };
+                context
+                    .state_store()
+                    .record_step_log(step.run_id, step.step_id, entry)
+                    .await
+                    .change_context(TransportError::RecvLoop)
+                    .attach_printable("failed to record step log")?;
+            }
+            Ok(())
+        }
+        .boxed()
+    }
+}
+
+/// Emit a log message as a `tracing` event.
+fn emit(log: &LogMessage, step: Option<&ExecutingStep>) {
+    let run_id = step.map(|step| tracing::field::display(step.run_id));
+    let step_id = step.map(|step| step.step_id.as_str());
+    let fields = (!log.fields.is_empty()).then(|| serde_json::Value::Object(log.fields.clone()));
+    let fields = fields.as_ref().map(tracing::field::display);
+    macro_rules! emit_at {
+        ($level:expr) => {
+            tracing::event!(
+                target: "stepflow_plugin_log",
+                $level,
+                run_id,
+                step_id,
+                fields,
+                "{}",
+                log.message
+            )
+        };
+    }
+    match log.level {
+        LogLevel::Trace => emit_at!(Level::TRACE),
+        LogLevel::Debug => emit_at!(Level::DEBUG),
+        LogLevel::Info => emit_at!(Level::INFO),
+        LogLevel::Warn => emit_at!(Level::WARN),
+        LogLevel::Error => emit_at!(Level::ERROR),
+    }
+}
@@ -12,14 +12,16 @@
 
 use futures::future::BoxFuture;
 use std::collections::HashMap;
-use std::sync::{Arc, LazyLock};
+use std::sync::Arc;
 use stepflow_plugin::Context;
 use tokio::sync::mpsc;
 
 use super::blob_handlers::{GetBlobHandler, PutBlobHandler};
 use super::flow_handlers::{EvaluateFlowHandler, GetFlowMetadataHandler};
+use super::log_handler::LogHandler;
+use super::progress_handler::ProgressHandler;
 use crate::error::TransportError;
-use crate::protocol::{Method, MethodRequest};
+use crate::protocol::{Method, MethodRequest, Notification};
 
 /// Trait for handling incoming method calls and notifications from component servers.
 ///
@@ -42,46 +44,87 @@ pub trait MethodHandler: Send + Sync {
     ) -> BoxFuture<'a, error_stack::Result<(), TransportError>>;
 }
 
+/// Trait for handling incoming notifications from component servers.
+pub trait NotificationHandler: Send + Sync {
+    /// Handle an incoming notification. No response is sent.
+    fn handle_notification<'a>(
+        &self,
+        notification: &'a Notification<'a>,
+        context: Arc<dyn Context>,
+    ) -> BoxFuture<'a, error_stack::Result<(), TransportError>>;
+}
+
 /// Registry for incoming message handlers.
 ///
 /// Maps method names to their corresponding handlers using a trait object.
+/// Each connection to a component server has its own registry, so state kept
+/// by the handlers, such as the chunks of blob uploads in progress, is not
+/// shared between connections and is released when the connection closes.
 pub struct MessageHandlerRegistry {
-    method_handlers: HashMap<Method, Box<dyn MethodHandler>>,
+    method_handlers: HashMap<Method, Arc<dyn MethodHandler>>,
+    notification_handlers: HashMap<Method, Box<dyn NotificationHandler>>,
 }
 
-static INCOMING_HANDLERS: LazyLock<MessageHandlerRegistry> = LazyLock::new(|| {
-    let mut registry = MessageHandlerRegistry::new();
-    registry.register_method(Method::BlobsGet, Box::new(GetBlobHandler));
-    registry.register_method(Method::BlobsPut, Box::new(PutBlobHandler));
-    registry.register_method(Method::FlowsEvaluate, Box::new(EvaluateFlowHandler));
-    registry.register_method(Method::FlowsGetMetadata, Box::new(GetFlowMetadataHandler));
-    registry
-});
-
 impl MessageHandlerRegistry {
     /// Create a new empty registry.
     fn new() -> Self {
         Self {
             method_handlers: HashMap::new(),
+            notification_handlers: HashMap::new(),
         }
     }
 
     /// Register a handler for a specific method.
     fn register_method(&mut self, method_name: Method, handler: Box<dyn MethodHandler>) {
-        self.method_handlers.insert(method_name, handler);
+        self.method_handlers.insert(method_name, handler.into());
     }
 
-    pub fn instance() -> &'static Self {
-        &INCOMING_HANDLERS
+    /// Register a handler for a specific notification.
+    fn register_notification(
+        &mut self,
+        method_name: Method,
+        handler: Box<dyn NotificationHandler>,
+    ) {
+        self.notification_handlers.insert(method_name, handler);
     }
 
-    pub fn get_method_handler(&self, method_name: Method) -> Option<&dyn MethodHandler> {
-        self.method_handlers.get(&method_name).map(|h| h.as_ref())
+    pub fn get_method_handler(&self, method_name: Method) -> Option<Arc<dyn MethodHandler>> {
+        self.method_handlers.get(&method_name).cloned()
+    }
+
+    /// Handle a notification from a component server, logging any failure.
+    pub async fn handle_notification(
+        &self,
+        notification: &Notification<'_>,
+        context: Arc<dyn Context>,
+    ) {
+        let Some(handler) = self.notification_handlers.get(&notification.method) else {
+            tracing::error!(
+                "Received unsupported notification for method '{}'",
+                notification.method
+            );
+            return;
+        };
+        if let Err(err) = handler.handle_notification(notification, context).await {
+            tracing::error!(
+                "Error handling notification for method '{}': {:?}",
+                notification.method,
+                err
+            );
+        }
     }
 }
 
 impl Default for MessageHandlerRegistry {
+    /// A registry with the handlers of every method component servers may call.
     fn default() -> Self {
-        Self::new()
+        let mut registry = Self::new();
+        registry.register_method(Method::BlobsGet, Box::new(GetBlobHandler));
+        registry.register_method(Method::BlobsPut, Box::new(PutBlobHandler::default()));
+        registry.register_method(Method::FlowsEvaluate, Box::new(EvaluateFlowHandler));
+        registry.register_method(Method::FlowsGetMetadata, Box::new(GetFlowMetadataHandler));
+        registry.register_notification(Method::Log, Box::new(LogHandler));
+        registry.register_notification(Method::Progress, Box::new(ProgressHandler));
+        registry
     }
 }
@@ -0,0 +1,84 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+use std::sync::Arc;
+
+use error_stack::ResultExt as _;
+use futures::future::{BoxFuture, FutureExt as _};
+use stepflow_plugin::Context;
+use stepflow_state::StepProgress;
+
+use crate::error::TransportError;
+use crate::protocol::{Notification, ProgressMessage};
+
+use super::NotificationHandler;
+use super::executing_steps::executing_step;
+
+/// Handler for progress reported by component servers.
+///
+/// The latest progress of each step is recorded in the state store.
+pub struct ProgressHandler;
+
+impl NotificationHandler for ProgressHandler {
+    fn handle_notification<'a>(
+        &self,
+        notification: &'a Notification<'a>,
+        context: Arc<dyn Context>,
+    ) -> BoxFuture<'a, error_stack::Result<(), TransportError>> {
+        async move {
+            let params = notification.params.as_ref().ok_or_else(|| {
+                TransportError::InvalidMessage("missing progress params".to_string())
+            })?;
+            let progress: ProgressMessage =
+                params
+                    .deserialize_to()
+                    .change_context(TransportError::InvalidMessage(
+                        "invalid progress params".to_string(),
+                    ))?;
+
+            // Progress sent after the execution completed is no longer relevant.
+            let Some(step) = executing_step(&progress.request_id) else {
+                tracing::debug!(
+                    "Ignoring progress for request {} that is not executing",
+                    progress.request_id
+                );
+                return Ok(());
+            };
+            tracing::debug!(
+                run_id = %step.run_id,
+                step_id = step.step_id,
+                fraction = progress.fraction,
+                "Step progress: {}",
+                progress.message.as_deref().unwrap_or_default()
+            );
+
+            let entry = StepProgress {
+                fraction: progress
+                    .fraction
+                    .filter(|fraction| fraction.is_finite())
+                    .map(|fraction| fraction.clamp(0.0, 1.0)),
+                message: progress.message,
+                completed: progress.completed,
+                total: progress.total,
+                updated_at: chrono::Utc::now(),
+            };
+            context
+                .state_store()
+                .update_step_progress(step.run_id, step.step_id, entry)
+                .await
+                .change_context(TransportError::RecvLoop)
+                .attach_printable("failed to record step progress")?;
+            Ok(())
+        }
+        .boxed()
+    }
+}
@@ -137,8 +137,10 @@ impl BidirectionalDriver {
                         }
                     }
                     Message::Notification(notification) => {
-                        tracing::debug!(method = %notification.method, "Received notification from server");
                         // Notifications are fire-and-forget, no response needed
+                        self.client_handle
+                            .handle_incoming_notification(notification)
+                            .await;
                         Ok(SseMessageEvent::Continue)
                     }
                 }
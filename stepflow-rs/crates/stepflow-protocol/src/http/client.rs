@@ -23,10 +23,13 @@ use error_stack::ResultExt as _;
 use futures::stream::StreamExt as _;
 use stepflow_plugin::Context;
 use tokio::sync::mpsc;
+use tracing::Instrument as _;
 
 use super::bidirectional_driver::BidirectionalDriver;
 use crate::error::{Result, TransportError};
-use crate::protocol::{Method, MethodRequest, Notification, ProtocolMethod, ProtocolNotification};
+use crate::protocol::{
+    Method, MethodRequest, Notification, ProtocolMethod, ProtocolNotification, TraceContext,
+};
 use crate::{LazyValue, Message, MessageHandlerRegistry, OwnedJson, RequestId};
 use serde::de::DeserializeOwned;
 
@@ -56,6 +59,7 @@ impl HttpClient {
             url,
             context,
             request_headers,
+            handlers: Arc::new(MessageHandlerRegistry::default()),
         };
 
         Ok(Self { handle })
@@ -72,6 +76,8 @@ pub struct HttpClientHandle {
     url: String,
     context: Arc<dyn Context>,
     request_headers: reqwest::header::HeaderMap,
+    /// Handlers for requests and notifications from the component server.
+    handlers: Arc<MessageHandlerRegistry>,
 }
 
 impl HttpClientHandle {
@@ -82,12 +88,21 @@ impl HttpClientHandle {
     /// Send a typed method request and return the typed response
     /// This follows the same pattern as StdioClientHandle
     pub async fn method<I>(&self, params: &I) -> Result<I::Response>
+    where
+        I: ProtocolMethod + serde::Serialize + Send + Sync + std::fmt::Debug,
+        I::Response: DeserializeOwned + Send + Sync + 'static,
+    {
+        self.method_with_id(RequestId::new_uuid(), params).await
+    }
+
+    /// Send a request with the given ID and wait for its response.
+    pub async fn method_with_id<I>(&self, id: RequestId, params: &I) -> Result<I::Response>
     where
         I: ProtocolMethod + serde::Serialize + Send + Sync + std::fmt::Debug,
         I::Response: DeserializeOwned + Send + Sync + 'static,
     {
         let response = self
-            .method_dyn(I::METHOD_NAME, LazyValue::write_ref(params))
+            .method_dyn(id, I::METHOD_NAME, LazyValue::write_ref(params))
             .await?;
 
         response
@@ -98,10 +113,10 @@ impl HttpClientHandle {
 
     async fn method_dyn(
         &self,
+        id: RequestId,
         method: Method,
         params: LazyValue<'_>,
     ) -> Result<OwnedJson<LazyValue<'static>>> {
-        let id = RequestId::new_uuid();
         let request = MethodRequest {
             jsonrpc: Default::default(),
             id: id.clone(),
@@ -112,15 +127,20 @@ impl HttpClientHandle {
         let request_str = serde_json::to_string(&request)
             .change_context(TransportError::SerializeRequest(method))?;
 
-        let response = self
-            .send_method_request(id.clone(), request_str)
-            .await?
-            .owned_response()?;
-
-        // This is an assertion since the routing should only send the response for the
-        // registered ID to the pending one-shot channel.
-        debug_assert_eq!(response.response().id(), &id);
-        response.into_success_value()
+        let span = tracing::info_span!("protocol_request", %method, request_id = %id);
+        async move {
+            let response = self
+                .send_method_request(id.clone(), request_str)
+                .await?
+                .owned_response()?;
+
+            // This is an assertion since the routing should only send the response for the
+            // registered ID to the pending one-shot channel.
+            debug_assert_eq!(response.response().id(), &id);
+            response.into_success_value()
+        }
+        .instrument(span)
+        .await
     }
 
     /// Send a typed notification (fire-and-forget)
@@ -162,11 +182,16 @@ impl HttpClientHandle {
     /// Send a JSON-RPC request and wait for a response
     /// Handles both direct JSON responses and SSE streams automatically
     async fn send_method_request(&self, id: RequestId, message: String) -> Result<OwnedJson> {
-        // Send the HTTP POST request using pre-built headers
+        // Send the HTTP POST request using pre-built headers, plus the trace
+        // context of the request span so server spans nest under it.
+        let mut headers = self.request_headers.clone();
+        if let Some(trace_context) = TraceContext::current() {
+            trace_context.insert_headers(&mut headers);
+        }
         let response = self
             .client
             .post(&self.url)
-            .headers(self.request_headers.clone())
+            .headers(headers)
             .body(message)
             .send()
             .await
@@ -284,6 +309,14 @@ impl HttpClientHandle {
     }
 
     /// Handle a bidirectional request from the server during SSE streaming with concurrent message processing
+    /// Handle a notification received from the server.
+    pub(super) async fn handle_incoming_notification(&self, notification: &Notification<'_>) {
+        tracing::debug!(method = %notification.method, "Received notification from server");
+        self.handlers
+            .handle_notification(notification, self.context.clone())
+            .await;
+    }
+
     pub(super) async fn handle_incoming_request(
         &self,
         incoming_method: Method,
@@ -296,8 +329,7 @@ impl HttpClientHandle {
             "Processing bidirectional request from server with concurrent message handling"
         );
 
-        let Some(handler) = MessageHandlerRegistry::instance().get_method_handler(incoming_method)
-        else {
+        let Some(handler) = self.handlers.get_method_handler(incoming_method) else {
             tracing::warn!(
                 method = %incoming_method,
                 request_id = %incoming_id,
@@ -507,11 +539,11 @@ mod tests {
             "id": "test-string-id"
         });
 
-        if let Some(id_value) = request_with_string_id.get("id") {
-            if let Some(s) = id_value.as_str() {
-                let request_id = RequestId::from(s.to_string());
-                assert_eq!(format!("{request_id}"), "test-string-id");
-            }
+        if let Some(id_value) = request_with_string_id.get("id")
+            && let Some(s) = id_value.as_str()
+        {
+            let request_id = RequestId::from(s.to_string());
+            assert_eq!(format!("{request_id}"), "test-string-id");
         }
 
         // Test integer ID
@@ -521,11 +553,11 @@ mod tests {
             "id": 42
         });
 
-        if let Some(id_value) = request_with_int_id.get("id") {
-            if let Some(i) = id_value.as_i64() {
-                let request_id = RequestId::from(i);
-                assert_eq!(format!("{request_id}"), "42");
-            }
+        if let Some(id_value) = request_with_int_id.get("id")
+            && let Some(i) = id_value.as_i64()
+        {
+            let request_id = RequestId::from(i);
+            assert_eq!(format!("{request_id}"), "42");
         }
     }
 }
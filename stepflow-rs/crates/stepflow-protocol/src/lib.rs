@@ -10,14 +10,19 @@
 // or implied. See the License for the specific language governing permissions and limitations under
 // the License.
 
+mod connection;
 mod error;
 mod handlers;
 pub mod http;
 mod lazy_value;
+mod metrics;
 mod owned_json;
 mod plugin;
 mod protocol;
 pub mod stdio;
+#[cfg(unix)]
+pub mod unix;
+pub mod websocket;
 
 // Re-export internal types for crate-wide access
 use handlers::*;
@@ -26,5 +31,8 @@ use owned_json::OwnedJson;
 use protocol::*;
 
 // Re-export main plugin types
+pub use connection::ReconnectPolicy;
 pub use error::{Result, TransportError};
-pub use plugin::{StepflowPluginConfig, StepflowTransport};
+pub use metrics::describe_metrics;
+pub use plugin::{BatchConfig, StepflowPluginConfig, StepflowTransport};
+pub use protocol::{Framing, TraceContext};
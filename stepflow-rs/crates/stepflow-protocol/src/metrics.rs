@@ -0,0 +1,47 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+//! Metrics recorded for requests to component servers.
+
+use metrics::{Unit, describe_counter, describe_gauge, describe_histogram};
+
+pub const PROTOCOL_REQUEST_DURATION: &str = "stepflow_protocol_request_duration_seconds";
+pub const PROTOCOL_REQUEST_ERRORS: &str = "stepflow_protocol_request_errors_total";
+pub const PLUGIN_RESTARTS: &str = "stepflow_plugin_restarts_total";
+pub const PLUGIN_PROCESSES: &str = "stepflow_plugin_processes";
+pub const PLUGIN_BATCH_SIZE: &str = "stepflow_plugin_batch_size";
+
+/// Register descriptions of the protocol metrics with the installed recorder.
+pub fn describe_metrics() {
+    describe_histogram!(
+        PROTOCOL_REQUEST_DURATION,
+        Unit::Seconds,
+        "Latency of requests to component servers, by plugin and method."
+    );
+    describe_counter!(
+        PROTOCOL_REQUEST_ERRORS,
+        "Number of failed requests to component servers, by plugin and method."
+    );
+    describe_counter!(
+        PLUGIN_RESTARTS,
+        "Number of times a plugin's component server process was restarted."
+    );
+    describe_gauge!(
+        PLUGIN_PROCESSES,
+        "Number of component server processes running for a plugin."
+    );
+    describe_histogram!(
+        PLUGIN_BATCH_SIZE,
+        Unit::Count,
+        "Number of executions sent in each batch request to a plugin's component server."
+    );
+}
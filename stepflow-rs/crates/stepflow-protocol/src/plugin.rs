@@ -12,6 +12,7 @@
 
 use std::borrow::Cow;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use error_stack::ResultExt as _;
 use indexmap::IndexMap;
@@ -22,25 +23,45 @@ use stepflow_core::{
     workflow::{Component, ValueRef},
 };
 use stepflow_plugin::{
-    Context, DynPlugin, ExecutionContext, Plugin, PluginConfig, PluginError, Result,
+    Context, DynPlugin, ExecutionContext, Plugin, PluginConfig, PluginError, PluginHealth, Result,
 };
 use tokio::sync::RwLock;
 
+use crate::connection::{ConnectionClientHandle, ConnectionConfig, ReconnectPolicy};
 use crate::error::TransportError;
+use crate::handlers::{ExecutingStep, ExecutingStepScope};
 use crate::http::{HttpClient, HttpClientHandle};
+use crate::metrics::{PLUGIN_RESTARTS, PROTOCOL_REQUEST_DURATION, PROTOCOL_REQUEST_ERRORS};
 use crate::protocol::{
-    ComponentExecuteParams, ComponentInfoParams, ComponentListParams, InitializeParams, Initialized,
-};
-use crate::stdio::{
-    client::{StdioClient, StdioClientHandle},
-    launcher::Launcher,
+    Capabilities, ComponentBatchItem, ComponentExecuteParams, ComponentInfoParams,
+    ComponentListParams, Framing, InitializeParams, Initialized, RequestId, TraceContext,
 };
+use crate::stdio::{PoolConfig, RestartPolicy, StdioPool, launcher::Launcher};
+#[cfg(unix)]
+use crate::unix::UnixSocketClient;
+use crate::websocket::WebSocketClient;
 use serde::de::DeserializeOwned;
 
+mod batcher;
+
+pub use batcher::BatchConfig;
+use batcher::Batcher;
+
 #[derive(Clone)]
-enum StepflowClientHandle {
-    Stdio(StdioClientHandle),
+enum ClientTransport {
+    Stdio(StdioPool),
     Http(HttpClientHandle),
+    /// A persistent connection, such as a WebSocket.
+    Connection(ConnectionClientHandle),
+}
+
+#[derive(Clone)]
+struct StepflowClientHandle {
+    /// Name of the plugin, used to label metrics.
+    plugin: Arc<str>,
+    transport: ClientTransport,
+    /// Optional protocol features supported by both Stepflow and the component server.
+    capabilities: Capabilities,
 }
 
 impl StepflowClientHandle {
@@ -49,39 +70,59 @@ impl StepflowClientHandle {
         I: crate::protocol::ProtocolMethod + serde::Serialize + Send + Sync + std::fmt::Debug,
         I::Response: DeserializeOwned + Send + Sync + 'static,
     {
-        match self {
-            StepflowClientHandle::Stdio(client) => client
-                .method(params)
-                .await
-                .change_context(PluginError::Execution),
-            StepflowClientHandle::Http(client) => client
-                .method(params)
-                .await
-                .change_context(PluginError::Execution),
-        }
+        self.method_with_id(RequestId::new_uuid(), params).await
     }
 
-    async fn notify<I>(&self, params: &I) -> Result<()>
+    async fn method_with_id<I>(&self, id: RequestId, params: &I) -> Result<I::Response>
     where
-        I: crate::protocol::ProtocolNotification + serde::Serialize + Send + Sync + std::fmt::Debug,
+        I: crate::protocol::ProtocolMethod + serde::Serialize + Send + Sync + std::fmt::Debug,
+        I::Response: DeserializeOwned + Send + Sync + 'static,
     {
-        match self {
-            StepflowClientHandle::Stdio(client) => client
-                .notify(params)
+        let start = Instant::now();
+        let result = match &self.transport {
+            ClientTransport::Stdio(client) => client
+                .method_with_id(id, params)
+                .await
+                .change_context(PluginError::Execution),
+            ClientTransport::Http(client) => client
+                .method_with_id(id, params)
                 .await
                 .change_context(PluginError::Execution),
-            StepflowClientHandle::Http(client) => client
-                .notify(params)
+            ClientTransport::Connection(client) => client
+                .method_with_id(id, params)
                 .await
                 .change_context(PluginError::Execution),
+        };
+
+        let labels = [
+            ("plugin", self.plugin.to_string()),
+            ("method", I::METHOD_NAME.to_string()),
+        ];
+        metrics::histogram!(PROTOCOL_REQUEST_DURATION, &labels).record(start.elapsed());
+        if result.is_err() {
+            metrics::counter!(PROTOCOL_REQUEST_ERRORS, &labels).increment(1);
         }
+        result
     }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
 pub struct StepflowPluginConfig {
     #[serde(flatten)]
     pub transport: StepflowTransport,
+    /// Pass blob references (`{"$blob": "<blob_id>"}`) to the component server
+    /// instead of the blob content. The server fetches content via `blobs/get`.
+    #[serde(default)]
+    pub blob_refs: bool,
+    /// Record log messages the component server sends while executing a step
+    /// in the state store, so they can be retrieved with the step.
+    #[serde(default)]
+    pub persist_logs: bool,
+    /// How concurrent executions of a component are sent together if the
+    /// component server supports batching.
+    #[serde(default)]
+    pub batching: BatchConfig,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -95,9 +136,43 @@ pub enum StepflowTransport {
         /// Values can contain environment variable references like ${HOME} or ${USER:-default}.
         #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
         env: IndexMap<String, String>,
+        /// How the sub-process is restarted if it exits.
+        #[serde(default)]
+        restart: RestartPolicy,
+        /// How many sub-processes are run to serve requests.
+        #[serde(default)]
+        pool: PoolConfig,
+        /// Binary framing to use for messages if the component server supports it.
+        #[serde(default)]
+        framing: Framing,
     },
     #[serde(rename = "http")]
     Http { url: String },
+    #[serde(rename = "websocket", rename_all = "camelCase")]
+    WebSocket {
+        /// URL of the component server, such as `ws://localhost:8080/ws`.
+        url: String,
+        /// How often, in milliseconds, to check that the connection is alive.
+        #[serde(default = "default_heartbeat_interval_ms")]
+        heartbeat_interval_ms: u64,
+        /// How the connection is re-established if it is lost.
+        #[serde(default)]
+        reconnect: ReconnectPolicy,
+    },
+    #[cfg(unix)]
+    #[serde(rename = "unix")]
+    Unix {
+        /// Path of the Unix domain socket the component server listens on,
+        /// relative to the working directory.
+        path: String,
+        /// How the connection is re-established if it is lost.
+        #[serde(default)]
+        reconnect: ReconnectPolicy,
+    },
+}
+
+fn default_heartbeat_interval_ms() -> u64 {
+    15_000
 }
 
 impl PluginConfig for StepflowPluginConfig {
@@ -105,39 +180,96 @@ impl PluginConfig for StepflowPluginConfig {
 
     async fn create_plugin(
         self,
+        name: &str,
         working_directory: &std::path::Path,
     ) -> error_stack::Result<Box<DynPlugin<'static>>, Self::Error> {
-        match self.transport {
-            StepflowTransport::Stdio { command, args, env } => {
+        self.batching.validate()?;
+        let state = match self.transport {
+            StepflowTransport::Stdio {
+                command,
+                args,
+                env,
+                restart,
+                pool,
+                framing,
+            } => {
+                pool.validate()?;
                 let launcher = Launcher::try_new(working_directory.to_owned(), command, args, env)?;
-
-                Ok(DynPlugin::boxed(StepflowPlugin::new(
-                    StepflowPluginState::UninitializedStdio(launcher),
-                )))
+                StepflowPluginState::UninitializedStdio(launcher, restart, framing, pool)
             }
-            StepflowTransport::Http { url } => Ok(DynPlugin::boxed(StepflowPlugin::new(
-                StepflowPluginState::UninitializedHttp(url),
-            ))),
-        }
+            StepflowTransport::Http { url } => StepflowPluginState::UninitializedHttp(url),
+            StepflowTransport::WebSocket {
+                url,
+                heartbeat_interval_ms,
+                reconnect,
+            } => {
+                error_stack::ensure!(
+                    heartbeat_interval_ms > 0,
+                    TransportError::InvalidValue {
+                        field: "heartbeatIntervalMs",
+                        expected: "a positive number of milliseconds",
+                    }
+                );
+                StepflowPluginState::UninitializedWebSocket(ConnectionConfig {
+                    address: url,
+                    heartbeat_interval: Some(Duration::from_millis(heartbeat_interval_ms)),
+                    reconnect,
+                })
+            }
+            #[cfg(unix)]
+            StepflowTransport::Unix { path, reconnect } => {
+                let path = working_directory.join(path);
+                StepflowPluginState::UninitializedUnix(ConnectionConfig {
+                    address: path.to_string_lossy().into_owned(),
+                    heartbeat_interval: None,
+                    reconnect,
+                })
+            }
+        };
+        Ok(DynPlugin::boxed(StepflowPlugin::new(
+            name.into(),
+            state,
+            self.blob_refs,
+            self.persist_logs,
+            &self.batching,
+        )))
     }
 }
 
 pub struct StepflowPlugin {
+    name: Arc<str>,
     state: RwLock<StepflowPluginState>,
+    blob_refs: bool,
+    persist_logs: bool,
+    /// Coalesces executions into batches, if enabled.
+    batcher: Option<Batcher>,
 }
 
 impl StepflowPlugin {
-    fn new(state: StepflowPluginState) -> Self {
+    fn new(
+        name: Arc<str>,
+        state: StepflowPluginState,
+        blob_refs: bool,
+        persist_logs: bool,
+        batching: &BatchConfig,
+    ) -> Self {
         Self {
+            name,
             state: RwLock::new(state),
+            blob_refs,
+            persist_logs,
+            batcher: batching.enabled().then(|| Batcher::new(batching)),
         }
     }
 }
 
 enum StepflowPluginState {
     Empty,
-    UninitializedStdio(Launcher),
+    UninitializedStdio(Launcher, RestartPolicy, Framing, PoolConfig),
     UninitializedHttp(String),
+    UninitializedWebSocket(ConnectionConfig),
+    #[cfg(unix)]
+    UninitializedUnix(ConnectionConfig),
     Initialized(StepflowClientHandle),
 }
 
@@ -153,12 +285,31 @@ impl StepflowPlugin {
     async fn create_client(&self, context: Arc<dyn Context>) -> Result<StepflowClientHandle> {
         let mut guard = self.state.write().await;
         match std::mem::replace(&mut *guard, StepflowPluginState::Empty) {
-            StepflowPluginState::UninitializedStdio(launcher) => {
-                let client = StdioClient::try_new(launcher, context)
-                    .await
-                    .change_context(PluginError::Initializing)
-                    .attach_printable("Unable to launch component server")?;
-                let handle = StepflowClientHandle::Stdio(client.handle());
+            StepflowPluginState::UninitializedStdio(
+                launcher,
+                restart_policy,
+                framing,
+                pool_config,
+            ) => {
+                // The pool initializes each process as it is launched.
+                let pool = StdioPool::try_new(
+                    self.name.clone(),
+                    launcher,
+                    restart_policy,
+                    framing,
+                    pool_config,
+                    context,
+                )
+                .await
+                .change_context(PluginError::Initializing)
+                .attach_printable("Unable to launch component server")?;
+                // Report restarts from zero so the series exists before any restart.
+                metrics::counter!(PLUGIN_RESTARTS, "plugin" => self.name.to_string()).absolute(0);
+                let handle = StepflowClientHandle {
+                    plugin: self.name.clone(),
+                    capabilities: pool.capabilities(),
+                    transport: ClientTransport::Stdio(pool),
+                };
                 *guard = StepflowPluginState::Initialized(handle.clone());
                 Ok(handle)
             }
@@ -167,7 +318,52 @@ impl StepflowPlugin {
                     .await
                     .change_context(PluginError::Initializing)
                     .attach_printable("Unable to create HTTP client")?;
-                let handle = StepflowClientHandle::Http(client.handle());
+                let client = client.handle();
+                let capabilities = client
+                    .method(&InitializeParams::default())
+                    .await
+                    .and_then(|result| result.negotiate())
+                    .change_context(PluginError::Initializing)?;
+                client
+                    .notify(&Initialized {})
+                    .await
+                    .change_context(PluginError::Initializing)?;
+                let handle = StepflowClientHandle {
+                    plugin: self.name.clone(),
+                    transport: ClientTransport::Http(client),
+                    capabilities,
+                };
+                *guard = StepflowPluginState::Initialized(handle.clone());
+                Ok(handle)
+            }
+            StepflowPluginState::UninitializedWebSocket(config) => {
+                // The client initializes each connection as it is made.
+                let client = WebSocketClient::try_new(config, context)
+                    .await
+                    .change_context(PluginError::Initializing)
+                    .attach_printable("Unable to connect to component server")?;
+                let client = client.handle();
+                let handle = StepflowClientHandle {
+                    plugin: self.name.clone(),
+                    capabilities: client.capabilities(),
+                    transport: ClientTransport::Connection(client),
+                };
+                *guard = StepflowPluginState::Initialized(handle.clone());
+                Ok(handle)
+            }
+            #[cfg(unix)]
+            StepflowPluginState::UninitializedUnix(config) => {
+                // The client initializes each connection as it is made.
+                let client = UnixSocketClient::try_new(config, context)
+                    .await
+                    .change_context(PluginError::Initializing)
+                    .attach_printable("Unable to connect to component server")?;
+                let client = client.handle();
+                let handle = StepflowClientHandle {
+                    plugin: self.name.clone(),
+                    capabilities: client.capabilities(),
+                    transport: ClientTransport::Connection(client),
+                };
                 *guard = StepflowPluginState::Initialized(handle.clone());
                 Ok(handle)
             }
@@ -180,19 +376,11 @@ impl StepflowPlugin {
 impl Plugin for StepflowPlugin {
     async fn init(&self, context: &Arc<dyn Context>) -> Result<()> {
         let client = self.create_client(context.clone()).await?;
-
-        client
-            .method(&InitializeParams {
-                runtime_protocol_version: 1,
-            })
-            .await
-            .change_context(PluginError::Initializing)?;
-
-        client
-            .notify(&Initialized {})
-            .await
-            .change_context(PluginError::Initializing)?;
-
+        tracing::info!(
+            plugin = %self.name,
+            capabilities = ?client.capabilities,
+            "Initialized component server"
+        );
         Ok(())
     }
 
@@ -219,6 +407,24 @@ impl Plugin for StepflowPlugin {
         Ok(response.info)
     }
 
+    fn supports_blob_refs(&self) -> bool {
+        self.blob_refs
+    }
+
+    fn health(&self) -> PluginHealth {
+        match self.state.try_read().as_deref() {
+            Ok(StepflowPluginState::Initialized(StepflowClientHandle {
+                transport: ClientTransport::Stdio(pool),
+                ..
+            })) => pool.health(),
+            Ok(StepflowPluginState::Initialized(StepflowClientHandle {
+                transport: ClientTransport::Connection(client),
+                ..
+            })) => client.health(),
+            _ => PluginHealth::default(),
+        }
+    }
+
     async fn execute(
         &self,
         component: &Component,
@@ -241,14 +447,45 @@ impl Plugin for StepflowPlugin {
             .clone();
 
         let client_handle = self.client_handle().await?;
-        let response = client_handle
-            .method(&ComponentExecuteParams {
-                component: component.clone(),
+        let request_id = RequestId::new_uuid();
+        // Attribute log messages and progress sent while the step executes to it.
+        let _step = ExecutingStepScope::new(
+            request_id.clone(),
+            ExecutingStep {
+                run_id,
+                step_id: step_id.clone(),
+                persist_logs: self.persist_logs,
+            },
+        );
+        let trace_context = TraceContext::current();
+        if let Some(batcher) = self
+            .batcher
+            .as_ref()
+            .filter(|_| client_handle.capabilities.batching)
+        {
+            let item = ComponentBatchItem {
+                request_id,
                 input,
                 step_id,
                 run_id: run_id.to_string(),
                 flow_id,
-            })
+                trace_context,
+            };
+            return batcher.execute(&client_handle, component, item).await;
+        }
+
+        let response = client_handle
+            .method_with_id(
+                request_id,
+                &ComponentExecuteParams {
+                    component: component.clone(),
+                    input,
+                    step_id,
+                    run_id: run_id.to_string(),
+                    flow_id,
+                    trace_context,
+                },
+            )
             .await
             .change_context(PluginError::Execution)?;
 
@@ -0,0 +1,447 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+//! Coalescing concurrent executions of a component into `components/execute_batch` requests.
+//!
+//! The first execution of a component opens a batch, which is sent once the
+//! batch window elapses or the batch is full, whichever comes first.
+//! Executions arriving while a batch is open join it.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use error_stack::ResultExt as _;
+use serde::{Deserialize, Serialize};
+use stepflow_core::{FlowError, FlowResult, workflow::Component};
+use stepflow_plugin::{PluginError, Result};
+use tokio::sync::oneshot;
+
+use super::StepflowClientHandle;
+use crate::error::{Result as TransportResult, TransportError};
+use crate::metrics::PLUGIN_BATCH_SIZE;
+use crate::protocol::{
+    ComponentBatchItem, ComponentExecuteBatchParams, ComponentExecuteBatchResult,
+};
+
+/// How concurrent executions of a component are coalesced into batches, if
+/// the component server supports batching.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct BatchConfig {
+    /// How long, in milliseconds, an execution waits for others to join its batch.
+    pub window_ms: u64,
+    /// Maximum number of executions in a batch. A full batch is sent without
+    /// waiting for the window to elapse. Set to 1 to disable batching.
+    pub max_size: usize,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            window_ms: 5,
+            max_size: 64,
+        }
+    }
+}
+
+impl BatchConfig {
+    pub fn validate(&self) -> TransportResult<()> {
+        error_stack::ensure!(
+            self.max_size >= 1,
+            TransportError::InvalidValue {
+                field: "batching",
+                expected: "maxSize >= 1",
+            }
+        );
+        Ok(())
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.max_size > 1
+    }
+}
+
+/// Sends batches to the component server.
+pub(super) trait BatchClient: Clone + Send + Sync + 'static {
+    /// Name of the plugin, used to label metrics.
+    fn plugin(&self) -> &str;
+
+    fn execute_batch(
+        &self,
+        params: ComponentExecuteBatchParams,
+    ) -> impl Future<Output = Result<ComponentExecuteBatchResult>> + Send;
+}
+
+impl BatchClient for StepflowClientHandle {
+    fn plugin(&self) -> &str {
+        &self.plugin
+    }
+
+    async fn execute_batch(
+        &self,
+        params: ComponentExecuteBatchParams,
+    ) -> Result<ComponentExecuteBatchResult> {
+        self.method(&params).await
+    }
+}
+
+/// A batch waiting to be sent.
+struct PendingBatch<C> {
+    /// Distinguishes this batch from later batches of the same component.
+    generation: u64,
+    client: C,
+    items: Vec<ComponentBatchItem>,
+    senders: Vec<oneshot::Sender<FlowResult>>,
+}
+
+struct Batches<C> {
+    next_generation: u64,
+    pending: HashMap<Component, PendingBatch<C>>,
+}
+
+impl<C> Default for Batches<C> {
+    fn default() -> Self {
+        Self {
+            next_generation: 0,
+            pending: HashMap::new(),
+        }
+    }
+}
+
+pub(super) struct Batcher<C = StepflowClientHandle> {
+    window: Duration,
+    max_size: usize,
+    batches: Arc<Mutex<Batches<C>>>,
+}
+
+impl<C: BatchClient> Batcher<C> {
+    pub fn new(config: &BatchConfig) -> Self {
+        Self {
+            window: Duration::from_millis(config.window_ms),
+            max_size: config.max_size,
+            batches: Default::default(),
+        }
+    }
+
+    /// Execute `component` on the item as part of a batch, returning its result.
+    ///
+    /// If the batch as a whole fails, each of its executions has a failed result
+    /// describing the error.
+    pub async fn execute(
+        &self,
+        client: &C,
+        component: &Component,
+        item: ComponentBatchItem,
+    ) -> Result<FlowResult> {
+        let (sender, receiver) = oneshot::channel();
+        let full = {
+            let mut batches = self.batches.lock().expect("batches poisoned");
+            let batches = &mut *batches;
+            let batch = batches.pending.entry(component.clone()).or_insert_with(|| {
+                let generation = batches.next_generation;
+                batches.next_generation += 1;
+                tokio::spawn(send_after(
+                    self.batches.clone(),
+                    component.clone(),
+                    generation,
+                    self.window,
+                ));
+                PendingBatch {
+                    generation,
+                    client: client.clone(),
+                    items: Vec::new(),
+                    senders: Vec::new(),
+                }
+            });
+            batch.items.push(item);
+            batch.senders.push(sender);
+            if batch.items.len() >= self.max_size {
+                batches.pending.remove(component)
+            } else {
+                None
+            }
+        };
+        if let Some(batch) = full {
+            // Sent in a separate task so it is not lost if this execution is dropped.
+            tokio::spawn(send(component.clone(), batch));
+        }
+
+        receiver
+            .await
+            .change_context(PluginError::Execution)
+            .attach_printable("batch was dropped")
+    }
+}
+
+/// Send the batch of `component` with the given generation once the window
+/// elapses, unless it was already sent because it was full.
+async fn send_after<C: BatchClient>(
+    batches: Arc<Mutex<Batches<C>>>,
+    component: Component,
+    generation: u64,
+    window: Duration,
+) {
+    tokio::time::sleep(window).await;
+    let batch = {
+        let mut batches = batches.lock().expect("batches poisoned");
+        match batches.pending.get(&component) {
+            Some(batch) if batch.generation == generation => batches.pending.remove(&component),
+            _ => None,
+        }
+    };
+    if let Some(batch) = batch {
+        send(component, batch).await;
+    }
+}
+
+async fn send<C: BatchClient>(component: Component, batch: PendingBatch<C>) {
+    let PendingBatch {
+        client,
+        items,
+        senders,
+        ..
+    } = batch;
+    let size = items.len();
+    metrics::histogram!(PLUGIN_BATCH_SIZE, "plugin" => client.plugin().to_string())
+        .record(size as f64);
+
+    let results = match client
+        .execute_batch(ComponentExecuteBatchParams {
+            component: component.clone(),
+            items,
+        })
+        .await
+    {
+        Ok(response) if response.results.len() == size => response.results,
+        Ok(response) => {
+            let message = format!(
+                "component server returned {} results for a batch of {size}",
+                response.results.len()
+            );
+            tracing::error!("Batch of {component} failed: {message}");
+            vec![FlowResult::Failed(FlowError::new(500, message)); size]
+        }
+        Err(error) => {
+            tracing::error!(?error, "Batch of {component} failed");
+            let error = FlowError::new(500, format!("Batch execution failed: {error}"));
+            vec![FlowResult::Failed(error); size]
+        }
+    };
+    for (sender, result) in senders.into_iter().zip(results) {
+        // The execution may have been dropped while waiting.
+        let _ = sender.send(result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::RequestId;
+    use serde_json::{Value, json};
+    use stepflow_core::{BlobId, workflow::ValueRef};
+
+    #[derive(Clone, Copy)]
+    enum Response {
+        /// Return the input of each item.
+        Echo,
+        /// Return one result fewer than there are items.
+        Truncated,
+        /// Fail the request.
+        Error,
+    }
+
+    /// Records the inputs of each batch it receives.
+    #[derive(Clone)]
+    struct MockClient {
+        batches: Arc<Mutex<Vec<Vec<Value>>>>,
+        response: Response,
+    }
+
+    impl MockClient {
+        fn new(response: Response) -> Self {
+            Self {
+                batches: Default::default(),
+                response,
+            }
+        }
+
+        fn batches(&self) -> Vec<Vec<Value>> {
+            self.batches.lock().unwrap().clone()
+        }
+    }
+
+    impl BatchClient for MockClient {
+        fn plugin(&self) -> &str {
+            "mock"
+        }
+
+        async fn execute_batch(
+            &self,
+            params: ComponentExecuteBatchParams,
+        ) -> Result<ComponentExecuteBatchResult> {
+            let inputs: Vec<ValueRef> = params.items.into_iter().map(|item| item.input).collect();
+            self.batches
+                .lock()
+                .unwrap()
+                .push(inputs.iter().map(|input| input.as_ref().clone()).collect());
+            let mut results: Vec<_> = inputs.into_iter().map(FlowResult::Success).collect();
+            match self.response {
+                Response::Echo => {}
+                Response::Truncated => {
+                    results.pop();
+                }
+                Response::Error => {
+                    return Err(PluginError::Execution).attach_printable("connection lost");
+                }
+            }
+            Ok(ComponentExecuteBatchResult { results })
+        }
+    }
+
+    fn batcher(window_ms: u64, max_size: usize) -> Arc<Batcher<MockClient>> {
+        Arc::new(Batcher::new(&BatchConfig {
+            window_ms,
+            max_size,
+        }))
+    }
+
+    /// Execute item `n` in a separate task.
+    fn spawn_execute(
+        batcher: &Arc<Batcher<MockClient>>,
+        client: &MockClient,
+        n: u64,
+    ) -> tokio::task::JoinHandle<FlowResult> {
+        let batcher = batcher.clone();
+        let client = client.clone();
+        tokio::spawn(async move {
+            let item = ComponentBatchItem {
+                request_id: RequestId::new_uuid(),
+                input: ValueRef::new(json!(n)),
+                step_id: format!("step{n}"),
+                run_id: "run".to_string(),
+                flow_id: BlobId::new("0".repeat(64)).unwrap(),
+                trace_context: None,
+            };
+            batcher
+                .execute(&client, &Component::from_string("/echo"), item)
+                .await
+                .unwrap()
+        })
+    }
+
+    fn success(n: u64) -> FlowResult {
+        FlowResult::Success(ValueRef::new(json!(n)))
+    }
+
+    #[tokio::test]
+    async fn test_window_expiry() {
+        let batcher = batcher(20, 10);
+        let client = MockClient::new(Response::Echo);
+
+        let executions: Vec<_> = (0..3)
+            .map(|n| spawn_execute(&batcher, &client, n))
+            .collect();
+        for (n, execution) in executions.into_iter().enumerate() {
+            assert_eq!(execution.await.unwrap(), success(n as u64));
+        }
+        assert_eq!(client.batches(), vec![vec![json!(0), json!(1), json!(2)]]);
+
+        // Executions after the batch was sent open a new batch.
+        assert_eq!(
+            spawn_execute(&batcher, &client, 3).await.unwrap(),
+            success(3)
+        );
+        assert_eq!(
+            client.batches(),
+            vec![vec![json!(0), json!(1), json!(2)], vec![json!(3)]]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_full_batch_sent_early() {
+        let batcher = batcher(3_600_000, 2);
+        let client = MockClient::new(Response::Echo);
+
+        let first = spawn_execute(&batcher, &client, 0);
+        let second = spawn_execute(&batcher, &client, 1);
+        let results = tokio::time::timeout(Duration::from_secs(5), async {
+            (first.await.unwrap(), second.await.unwrap())
+        })
+        .await
+        .expect("full batch should be sent without waiting for the window");
+        assert_eq!(results, (success(0), success(1)));
+        assert_eq!(client.batches(), vec![vec![json!(0), json!(1)]]);
+    }
+
+    #[tokio::test]
+    async fn test_window_of_full_batch_ignores_next_batch() {
+        let batcher = batcher(200, 3);
+        let client = MockClient::new(Response::Echo);
+
+        // The first batch is full and sent immediately, but its window keeps running.
+        let first: Vec<_> = (0..3)
+            .map(|n| spawn_execute(&batcher, &client, n))
+            .collect();
+        for execution in first {
+            execution.await.unwrap();
+        }
+
+        // The next batch opens before the window of the first one elapses, and
+        // is still open once it does.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let third = spawn_execute(&batcher, &client, 3);
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        let fourth = spawn_execute(&batcher, &client, 4);
+
+        assert_eq!(third.await.unwrap(), success(3));
+        assert_eq!(fourth.await.unwrap(), success(4));
+        assert_eq!(
+            client.batches(),
+            vec![vec![json!(0), json!(1), json!(2)], vec![json!(3), json!(4)]]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_batch_failures() {
+        let batcher = batcher(3_600_000, 2);
+
+        // A result count that does not match the batch fails every execution.
+        let client = MockClient::new(Response::Truncated);
+        for execution in [
+            spawn_execute(&batcher, &client, 0),
+            spawn_execute(&batcher, &client, 1),
+        ] {
+            let FlowResult::Failed(error) = execution.await.unwrap() else {
+                panic!("expected the execution to fail");
+            };
+            assert_eq!(error.code, 500);
+            assert_eq!(
+                error.message,
+                "component server returned 1 results for a batch of 2"
+            );
+        }
+
+        // So does a failed request.
+        let client = MockClient::new(Response::Error);
+        for execution in [
+            spawn_execute(&batcher, &client, 0),
+            spawn_execute(&batcher, &client, 1),
+        ] {
+            let FlowResult::Failed(error) = execution.await.unwrap() else {
+                panic!("expected the execution to fail");
+            };
+            assert_eq!(error.code, 500);
+            assert!(error.message.starts_with("Batch execution failed"));
+        }
+    }
+}
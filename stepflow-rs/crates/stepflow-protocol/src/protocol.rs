@@ -15,14 +15,21 @@ mod components;
 mod flows;
 mod initialization;
 mod json_rpc;
+mod logs;
 mod message_serde;
 mod messages;
 mod methods;
+mod progress;
+mod trace_context;
 
 // Re-export core protocol types
 pub(crate) use blobs::*;
 pub(crate) use components::*;
 pub(crate) use flows::*;
+pub use initialization::Framing;
 pub(crate) use initialization::*;
+pub(crate) use logs::*;
 pub(crate) use messages::*;
 pub(crate) use methods::*;
+pub(crate) use progress::*;
+pub use trace_context::TraceContext;
@@ -20,17 +20,35 @@ use crate::protocol::Method;
 use super::ProtocolMethod;
 
 /// Sent from the component server to the Stepflow to retrieve the content of a specific blob.
+///
+/// Binary blobs may be read in chunks by specifying `offset` and `length`.
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct GetBlobParams {
     /// The ID of the blob to retrieve.
     pub blob_id: BlobId,
+    /// Byte offset to start reading from (binary blobs only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+    /// Maximum number of bytes to return (binary blobs only).
+    ///
+    /// If not provided, the content from `offset` to the end of the blob is returned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub length: Option<u64>,
 }
 
 /// Sent from the Stepflow back to the component server with the blob data and metadata.
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct GetBlobResult {
+    /// The blob content. Binary content is a base64-encoded string of the requested range,
+    /// or raw bytes with a binary framing.
     pub data: ValueRef,
     pub blob_type: BlobType,
+    /// The MIME type of a binary blob.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// Total size of a binary blob in bytes, independent of the requested range.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
 }
 
 impl ProtocolMethod for GetBlobParams {
@@ -39,10 +57,33 @@ impl ProtocolMethod for GetBlobParams {
 }
 
 /// Sent from the component server to the Stepflow to store a blob with the provided content.
+///
+/// Large binary content may be uploaded in chunks: each chunk is first sent with
+/// `partial` set, and a final request lists the returned IDs in `chunks`. The stored
+/// blob is the concatenation of the listed chunks followed by `data`. Chunks are held
+/// only until the final request and are never stored as blobs themselves.
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct PutBlobParams {
+    /// The blob content. Binary content is a base64-encoded string, or raw bytes with a
+    /// binary framing.
     pub data: ValueRef,
     pub blob_type: BlobType,
+    /// The MIME type of a binary blob. Defaults to `application/octet-stream`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// Previously uploaded chunks to prepend to `data` (binary blobs only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunks: Option<Vec<BlobId>>,
+    /// Upload a chunk of a binary blob rather than storing a blob.
+    ///
+    /// The returned ID may only be listed in the `chunks` of a later request.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub partial: bool,
+    /// The ID of the run storing the blob, if any.
+    ///
+    /// When provided, the blob is readable through the API in the run's namespace.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<String>,
 }
 
 /// Sent from the Stepflow back to the component server with the ID of the stored blob.
@@ -12,12 +12,13 @@
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use stepflow_core::FlowResult;
 use stepflow_core::component::ComponentInfo;
 use stepflow_core::workflow::{Component, ValueRef};
 
-use crate::protocol::Method;
+use crate::protocol::{Method, RequestId};
 
-use super::ProtocolMethod;
+use super::{ProtocolMethod, TraceContext};
 
 /// Sent from Stepflow to the component server to execute a specific component with the provided input.
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -32,6 +33,11 @@ pub struct ComponentExecuteParams {
     pub run_id: String,
     /// The ID of the flow being executed.
     pub flow_id: stepflow_core::BlobId,
+    /// Trace context of the step being executed, if tracing is enabled.
+    ///
+    /// Component servers may use this to record their spans as children of the step.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_context: Option<TraceContext>,
 }
 
 /// Sent from the component server back to Stepflow with the result of the component execution.
@@ -46,6 +52,48 @@ impl ProtocolMethod for ComponentExecuteParams {
     type Response = ComponentExecuteResult;
 }
 
+/// Sent from Stepflow to the component server to execute a component on several inputs at once.
+///
+/// Only sent if the `batching` capability was negotiated.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ComponentExecuteBatchParams {
+    /// The component to execute.
+    pub component: Component,
+    /// The executions in the batch.
+    pub items: Vec<ComponentBatchItem>,
+}
+
+/// A single execution within a batch.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ComponentBatchItem {
+    /// Identifies this execution in `log` and `progress` notifications, in
+    /// place of the ID of the batch request.
+    pub request_id: RequestId,
+    /// The input to the component.
+    pub input: ValueRef,
+    /// The ID of the step being executed.
+    pub step_id: String,
+    /// The ID of the workflow run.
+    pub run_id: String,
+    /// The ID of the flow being executed.
+    pub flow_id: stepflow_core::BlobId,
+    /// Trace context of the step being executed, if tracing is enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_context: Option<TraceContext>,
+}
+
+/// Sent from the component server back to Stepflow with the result of each execution in a batch.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ComponentExecuteBatchResult {
+    /// The result of each execution, in the order of the items in the request.
+    pub results: Vec<FlowResult>,
+}
+
+impl ProtocolMethod for ComponentExecuteBatchParams {
+    const METHOD_NAME: Method = Method::ComponentsExecuteBatch;
+    type Response = ComponentExecuteBatchResult;
+}
+
 /// Sent from Stepflow to the component server to request information about a specific component.
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ComponentInfoParams {
@@ -17,7 +17,7 @@ use serde::{Deserialize, Serialize};
 use stepflow_core::workflow::ValueRef;
 use stepflow_core::{BlobId, FlowResult};
 
-use crate::protocol::Method;
+use crate::protocol::{Method, RequestId};
 
 use super::ProtocolMethod;
 
@@ -28,6 +28,11 @@ pub struct EvaluateFlowParams {
     pub flow_id: BlobId,
     /// The input to provide to the flow.
     pub input: ValueRef,
+    /// The ID of the `components/execute` request evaluating the flow, if any.
+    ///
+    /// While that request is handled, the flow is recorded as a run nested within its step's run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<RequestId>,
 }
 
 /// Sent from the Stepflow back to the component server with the result of the flow evaluation.
@@ -13,15 +13,112 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::error::{Result, TransportError};
 use crate::protocol::Method;
 
 use super::{ProtocolMethod, ProtocolNotification};
 
+/// Version of the protocol spoken by this Stepflow runtime.
+pub(crate) const RUNTIME_PROTOCOL_VERSION: u32 = 1;
+
+/// Oldest version of the protocol this Stepflow runtime can speak with a component server.
+pub(crate) const MIN_PROTOCOL_VERSION: u32 = 1;
+
+/// Optional protocol features supported by the Stepflow runtime.
+pub(crate) const RUNTIME_CAPABILITIES: Capabilities = Capabilities {
+    streaming: false,
+    cancellation: false,
+    batching: true,
+    binary_blobs: true,
+    progress: true,
+    logging: true,
+};
+
+/// Optional protocol features declared by each side during initialization.
+///
+/// A feature is only used when both the Stepflow runtime and the component server
+/// declare it. Features missing from the message are not supported.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct Capabilities {
+    /// Components may stream partial results.
+    pub streaming: bool,
+    /// Requests in flight may be cancelled.
+    pub cancellation: bool,
+    /// Several executions may be sent in a single request.
+    pub batching: bool,
+    /// Binary blobs may be stored and retrieved.
+    pub binary_blobs: bool,
+    /// Components may report the progress of an execution.
+    pub progress: bool,
+    /// Component servers may forward log records to the runtime.
+    pub logging: bool,
+}
+
+impl Capabilities {
+    /// The features supported by both `self` and `other`.
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self {
+            streaming: self.streaming && other.streaming,
+            cancellation: self.cancellation && other.cancellation,
+            batching: self.batching && other.batching,
+            binary_blobs: self.binary_blobs && other.binary_blobs,
+            progress: self.progress && other.progress,
+            logging: self.logging && other.logging,
+        }
+    }
+}
+
+/// How messages are encoded on the stdio pipes of a component server.
+///
+/// Messages are newline-delimited JSON until initialization completes. A binary
+/// framing prefixes each message with its length as a 4-byte big-endian integer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum Framing {
+    /// One JSON message per line.
+    #[default]
+    #[serde(rename = "json")]
+    Json,
+    /// Length-prefixed MessagePack messages, with binary blob content as raw bytes.
+    #[serde(rename = "msgpack")]
+    MessagePack,
+    /// Length-prefixed CBOR messages, with binary blob content as raw bytes.
+    #[serde(rename = "cbor")]
+    Cbor,
+}
+
+impl std::fmt::Display for Framing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Framing::Json => f.pad("json"),
+            Framing::MessagePack => f.pad("msgpack"),
+            Framing::Cbor => f.pad("cbor"),
+        }
+    }
+}
+
 /// Sent from Stepflow to the component server to begin the initialization process.
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct InitializeParams {
     /// Maximum version of the protocol being used by the Stepflow runtime.
     pub runtime_protocol_version: u32,
+    /// Optional features supported by the Stepflow runtime.
+    #[serde(default)]
+    pub runtime_capabilities: Capabilities,
+    /// Binary framings the component server may switch to after initialization,
+    /// in order of preference. Only offered over stdio.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub framings: Vec<Framing>,
+}
+
+impl Default for InitializeParams {
+    fn default() -> Self {
+        Self {
+            runtime_protocol_version: RUNTIME_PROTOCOL_VERSION,
+            runtime_capabilities: RUNTIME_CAPABILITIES,
+            framings: Vec::new(),
+        }
+    }
 }
 
 /// Sent from the component server back to Stepflow with the result of initialization.
@@ -30,6 +127,41 @@ pub struct InitializeParams {
 pub struct InitializeResult {
     /// Version of the protocol being used by the component server.
     pub server_protocol_version: u32,
+    /// Optional features supported by the component server.
+    #[serde(default)]
+    pub server_capabilities: Capabilities,
+    /// Framing chosen from those offered by the Stepflow runtime. Both sides use it
+    /// for every message after this response. Omitted to keep using JSON.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub framing: Option<Framing>,
+}
+
+impl InitializeResult {
+    /// Check that the component server speaks a supported version of the protocol,
+    /// and return the features supported by both sides.
+    pub fn negotiate(&self) -> Result<Capabilities> {
+        error_stack::ensure!(
+            (MIN_PROTOCOL_VERSION..=RUNTIME_PROTOCOL_VERSION)
+                .contains(&self.server_protocol_version),
+            TransportError::IncompatibleProtocolVersion {
+                server: self.server_protocol_version,
+                min: MIN_PROTOCOL_VERSION,
+                max: RUNTIME_PROTOCOL_VERSION,
+            }
+        );
+        Ok(RUNTIME_CAPABILITIES.intersection(&self.server_capabilities))
+    }
+
+    /// Return the framing chosen by the component server, checking it was offered.
+    pub fn negotiate_framing(&self, offered: &[Framing]) -> Result<Framing> {
+        match self.framing {
+            None | Some(Framing::Json) => Ok(Framing::Json),
+            Some(framing) if offered.contains(&framing) => Ok(framing),
+            Some(framing) => Err(error_stack::report!(TransportError::UnofferedFraming(
+                framing
+            ))),
+        }
+    }
 }
 
 impl ProtocolMethod for InitializeParams {
@@ -44,3 +176,66 @@ pub struct Initialized {}
 impl ProtocolNotification for Initialized {
     const METHOD_NAME: Method = Method::Initialized;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_negotiate() {
+        let result: InitializeResult = serde_json::from_str(
+            r#"{ "server_protocol_version": 1, "server_capabilities": { "binary_blobs": true, "streaming": true, "future": true } }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            result.negotiate().unwrap(),
+            Capabilities {
+                binary_blobs: true,
+                ..Capabilities::default()
+            }
+        );
+
+        // Older component servers do not declare any capabilities.
+        let result: InitializeResult =
+            serde_json::from_str(r#"{ "server_protocol_version": 1 }"#).unwrap();
+        assert_eq!(result.negotiate().unwrap(), Capabilities::default());
+    }
+
+    #[test]
+    fn test_negotiate_incompatible_version() {
+        let result = InitializeResult {
+            server_protocol_version: RUNTIME_PROTOCOL_VERSION + 1,
+            server_capabilities: Capabilities::default(),
+            framing: None,
+        };
+        let error = result.negotiate().unwrap_err();
+        assert_eq!(
+            error.current_context().to_string(),
+            format!(
+                "component server uses protocol version {}, but this Stepflow runtime supports versions {MIN_PROTOCOL_VERSION} to {RUNTIME_PROTOCOL_VERSION}",
+                RUNTIME_PROTOCOL_VERSION + 1
+            )
+        );
+    }
+
+    #[test]
+    fn test_negotiate_framing() {
+        let offered = [Framing::MessagePack];
+        let result: InitializeResult =
+            serde_json::from_str(r#"{ "server_protocol_version": 1 }"#).unwrap();
+        assert_eq!(result.negotiate_framing(&offered).unwrap(), Framing::Json);
+
+        let result: InitializeResult =
+            serde_json::from_str(r#"{ "server_protocol_version": 1, "framing": "msgpack" }"#)
+                .unwrap();
+        assert_eq!(
+            result.negotiate_framing(&offered).unwrap(),
+            Framing::MessagePack
+        );
+        let error = result.negotiate_framing(&[]).unwrap_err();
+        assert_eq!(
+            error.current_context().to_string(),
+            "component server chose framing 'msgpack', which was not offered"
+        );
+    }
+}
@@ -0,0 +1,63 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::protocol::{Method, RequestId};
+
+use super::ProtocolNotification;
+
+/// Severity of a log message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl From<LogLevel> for stepflow_state::LogLevel {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Trace => stepflow_state::LogLevel::Trace,
+            LogLevel::Debug => stepflow_state::LogLevel::Debug,
+            LogLevel::Info => stepflow_state::LogLevel::Info,
+            LogLevel::Warn => stepflow_state::LogLevel::Warn,
+            LogLevel::Error => stepflow_state::LogLevel::Error,
+        }
+    }
+}
+
+/// Sent from the component server to Stepflow to record a log message.
+///
+/// Messages sent with the ID of a `components/execute` request, while it is being
+/// handled, are attributed to the run and step being executed.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct LogMessage {
+    /// The severity of the message.
+    pub level: LogLevel,
+    /// The message.
+    pub message: String,
+    /// Structured fields attached to the message.
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub fields: serde_json::Map<String, serde_json::Value>,
+    /// The ID of the request being handled when the message was logged, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<RequestId>,
+}
+
+impl ProtocolNotification for LogMessage {
+    const METHOD_NAME: Method = Method::Log;
+}
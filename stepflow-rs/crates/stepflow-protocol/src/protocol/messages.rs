@@ -154,6 +154,14 @@ impl<'a> Error<'a> {
         }
     }
 
+    pub fn resource_unavailable(message: impl Into<Cow<'a, str>>) -> Self {
+        Error {
+            code: -32005, // Resource unavailable
+            message: message.into(),
+            data: None,
+        }
+    }
+
     pub fn invalid_value(field: &str, expected: &str) -> Self {
         Error {
             code: -32012,
@@ -360,10 +368,10 @@ mod tests {
             match obj {
                 serde_json::Value::Object(map) => {
                     // Check if this object has a title
-                    if let Some(serde_json::Value::String(title)) = map.get("title") {
-                        if !is_valid_python_class_name(title) {
-                            invalid_titles.push(title.clone());
-                        }
+                    if let Some(serde_json::Value::String(title)) = map.get("title")
+                        && !is_valid_python_class_name(title)
+                    {
+                        invalid_titles.push(title.clone());
                     }
 
                     // Recursively search in all values
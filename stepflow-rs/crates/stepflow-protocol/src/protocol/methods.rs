@@ -25,6 +25,8 @@ pub enum Method {
     ComponentsInfo,
     #[serde(rename = "components/execute")]
     ComponentsExecute,
+    #[serde(rename = "components/execute_batch")]
+    ComponentsExecuteBatch,
     #[serde(rename = "blobs/put")]
     BlobsPut,
     #[serde(rename = "blobs/get")]
@@ -33,6 +35,10 @@ pub enum Method {
     FlowsEvaluate,
     #[serde(rename = "flows/get_metadata")]
     FlowsGetMetadata,
+    #[serde(rename = "log")]
+    Log,
+    #[serde(rename = "progress")]
+    Progress,
 }
 
 impl std::fmt::Display for Method {
@@ -43,10 +49,13 @@ impl std::fmt::Display for Method {
             Method::ComponentsList => write!(f, "components/list"),
             Method::ComponentsInfo => write!(f, "components/info"),
             Method::ComponentsExecute => write!(f, "components/execute"),
+            Method::ComponentsExecuteBatch => write!(f, "components/execute_batch"),
             Method::BlobsPut => write!(f, "blobs/put"),
             Method::BlobsGet => write!(f, "blobs/get"),
             Method::FlowsEvaluate => write!(f, "flows/evaluate"),
             Method::FlowsGetMetadata => write!(f, "flows/get_metadata"),
+            Method::Log => write!(f, "log"),
+            Method::Progress => write!(f, "progress"),
         }
     }
 }
@@ -64,6 +73,7 @@ pub(crate) fn method_params(generator: &mut schemars::SchemaGenerator) -> Schema
     let params = vec![
         generator.subschema_for::<super::initialization::InitializeParams>(),
         generator.subschema_for::<super::components::ComponentExecuteParams>(),
+        generator.subschema_for::<super::components::ComponentExecuteBatchParams>(),
         generator.subschema_for::<super::components::ComponentInfoParams>(),
         generator.subschema_for::<super::components::ComponentListParams>(),
         generator.subschema_for::<super::blobs::GetBlobParams>(),
@@ -82,6 +92,7 @@ pub(crate) fn method_result(generator: &mut schemars::SchemaGenerator) -> Schema
     let params: Vec<Schema> = vec![
         generator.subschema_for::<super::initialization::InitializeResult>(),
         generator.subschema_for::<super::components::ComponentExecuteResult>(),
+        generator.subschema_for::<super::components::ComponentExecuteBatchResult>(),
         generator.subschema_for::<super::components::ComponentInfoResult>(),
         generator.subschema_for::<super::components::ListComponentsResult>(),
         generator.subschema_for::<super::blobs::GetBlobResult>(),
@@ -97,7 +108,11 @@ pub(crate) fn method_result(generator: &mut schemars::SchemaGenerator) -> Schema
 }
 
 pub(crate) fn notification_params(generator: &mut schemars::SchemaGenerator) -> Schema {
-    let params = vec![generator.subschema_for::<super::initialization::Initialized>()];
+    let params = vec![
+        generator.subschema_for::<super::initialization::Initialized>(),
+        generator.subschema_for::<super::logs::LogMessage>(),
+        generator.subschema_for::<super::progress::ProgressMessage>(),
+    ];
     json_schema!({
         "title": "NotificationParams",
         "description": "Parameters for the notification.",
@@ -119,6 +134,10 @@ mod tests {
             serde_json::to_string(&Method::ComponentsList).unwrap(),
             r#""components/list""#
         );
+        assert_eq!(
+            serde_json::to_string(&Method::ComponentsExecuteBatch).unwrap(),
+            r#""components/execute_batch""#
+        );
         assert_eq!(
             serde_json::to_string(&Method::FlowsEvaluate).unwrap(),
             r#""flows/evaluate""#
@@ -0,0 +1,44 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::protocol::{Method, RequestId};
+
+use super::ProtocolNotification;
+
+/// Sent from the component server to Stepflow to report the progress of a
+/// `components/execute` request while it is being handled.
+///
+/// Each notification replaces the progress previously reported for the step.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ProgressMessage {
+    /// Fraction of the work completed, from 0 to 1, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fraction: Option<f64>,
+    /// Description of the work being done.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Number of items completed, if the work is counted in items.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed: Option<u64>,
+    /// Total number of items, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+    /// The ID of the `components/execute` request whose progress is reported.
+    pub request_id: RequestId,
+}
+
+impl ProtocolNotification for ProgressMessage {
+    const METHOD_NAME: Method = Method::Progress;
+}
@@ -0,0 +1,105 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+use std::collections::HashMap;
+
+use opentelemetry::propagation::TextMapPropagator as _;
+use opentelemetry::trace::TraceContextExt as _;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing_opentelemetry::OpenTelemetrySpanExt as _;
+
+const TRACEPARENT: &str = "traceparent";
+const TRACESTATE: &str = "tracestate";
+
+/// W3C trace context identifying the span on whose behalf a request is made.
+///
+/// See <https://www.w3.org/TR/trace-context/>.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct TraceContext {
+    /// The `traceparent` value, e.g. `00-<trace-id>-<parent-id>-01`.
+    pub traceparent: String,
+    /// The `tracestate` value carrying vendor-specific trace data.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tracestate: Option<String>,
+}
+
+impl TraceContext {
+    /// The trace context of the current tracing span.
+    ///
+    /// Returns `None` if trace export is not enabled, since spans are then not
+    /// assigned OpenTelemetry trace IDs.
+    pub fn current() -> Option<Self> {
+        let context = tracing::Span::current().context();
+        if !context.span().span_context().is_valid() {
+            return None;
+        }
+
+        let mut carrier = HashMap::new();
+        TraceContextPropagator::new().inject_context(&context, &mut carrier);
+        Some(Self {
+            traceparent: carrier.remove(TRACEPARENT)?,
+            tracestate: carrier.remove(TRACESTATE).filter(|state| !state.is_empty()),
+        })
+    }
+
+    /// Add the `traceparent` and `tracestate` headers to an HTTP request.
+    pub fn insert_headers(&self, headers: &mut reqwest::header::HeaderMap) {
+        if let Ok(value) = self.traceparent.parse() {
+            headers.insert(TRACEPARENT, value);
+        }
+        if let Some(Ok(value)) = self.tracestate.as_ref().map(|state| state.parse()) {
+            headers.insert(TRACESTATE, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use tracing_subscriber::layer::SubscriberExt as _;
+
+    #[test]
+    fn test_current_trace_context() {
+        // Without an OpenTelemetry layer there is no trace context.
+        assert_eq!(TraceContext::current(), None);
+
+        let provider = SdkTracerProvider::builder().build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("step");
+            let _guard = span.enter();
+
+            let trace_context = TraceContext::current().unwrap();
+            let span_context = span.context().span().span_context().clone();
+            assert_eq!(
+                trace_context.traceparent,
+                format!(
+                    "00-{}-{}-01",
+                    span_context.trace_id(),
+                    span_context.span_id()
+                )
+            );
+
+            let mut headers = reqwest::header::HeaderMap::new();
+            trace_context.insert_headers(&mut headers);
+            assert_eq!(
+                headers.get(TRACEPARENT).unwrap(),
+                trace_context.traceparent.as_str()
+            );
+        });
+    }
+}
@@ -11,7 +11,12 @@
 // the License.
 
 pub mod client;
+mod framing;
 pub mod launcher;
+mod pool;
 pub mod recv_message_loop;
+mod supervisor;
 
 pub use client::{StdioClient, StdioClientHandle};
+pub use pool::{PoolConfig, StdioPool};
+pub use supervisor::RestartPolicy;
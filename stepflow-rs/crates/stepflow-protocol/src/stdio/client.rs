@@ -10,46 +10,59 @@
 // or implied. See the License for the specific language governing permissions and limitations under
 // the License.
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use error_stack::ResultExt as _;
 use serde::de::DeserializeOwned;
-use stepflow_plugin::Context;
+use stepflow_plugin::{Context, PluginHealth};
 
 use crate::OwnedJson;
 use crate::lazy_value::LazyValue;
-use crate::protocol::{Method, ProtocolMethod, ProtocolNotification};
+use crate::protocol::{Capabilities, Framing, Method, ProtocolMethod, ProtocolNotification};
 use crate::{MethodRequest, Notification, RequestId};
-use tokio::{
-    sync::{mpsc, oneshot},
-    task::JoinHandle,
-};
+use tokio::sync::mpsc;
 use tracing::Instrument as _;
 
-use super::{launcher::Launcher, recv_message_loop::recv_message_loop};
+use super::launcher::Launcher;
+use super::recv_message_loop::{PendingRequest, ReceiveMessageLoop, recv_message_loop};
+use super::supervisor::{RestartPolicy, Supervisor};
 use crate::error::{Result, TransportError};
 
 /// Manages a client process spawned from a command.
 ///
-/// Messages may be sent (as lines) to a channel that are sent via stdio.
+/// Messages may be sent (as lines) to a channel that are sent via stdio. If the
+/// process exits, it is restarted according to the restart policy, with its
+/// health recorded in `health`.
 pub struct StdioClient {
     outgoing_tx: mpsc::Sender<String>,
-    pending_tx: mpsc::Sender<(RequestId, oneshot::Sender<OwnedJson>)>,
-    // TODO: Use the handle. We should actually check it for errors
-    // before bubbling other (less meaningful) errors up.
-    #[allow(dead_code)]
-    loop_handle: JoinHandle<Result<()>>,
+    pending_tx: mpsc::Sender<PendingRequest>,
+    /// Capabilities negotiated with the first process.
+    capabilities: Capabilities,
 }
 
 impl StdioClient {
-    pub async fn try_new(launcher: Launcher, context: Arc<dyn Context>) -> Result<Self> {
+    pub async fn try_new(
+        plugin: Arc<str>,
+        launcher: Arc<Launcher>,
+        restart_policy: RestartPolicy,
+        framing: Framing,
+        health: Arc<Mutex<PluginHealth>>,
+        context: Arc<dyn Context>,
+    ) -> Result<Self> {
         let (outgoing_tx, outgoing_rx) = mpsc::channel(100);
         let (pending_tx, pending_rx) = mpsc::channel(100);
 
+        // Launch and initialize the first process here, so failures are reported to the caller.
+        let mut recv_loop = ReceiveMessageLoop::try_new(&launcher, framing, outgoing_tx.clone())?;
+        let capabilities = recv_loop.initialize().await?;
+        let supervisor = Supervisor::new(plugin, restart_policy, health);
+
         let recv_span = tracing::info_span!("recv_message_loop", command = ?launcher.command, args = ?launcher.args);
-        let loop_handle = tokio::spawn(
+        tokio::spawn(
             recv_message_loop(
+                recv_loop,
                 launcher,
+                supervisor,
                 outgoing_tx.clone(),
                 outgoing_rx,
                 pending_rx,
@@ -61,10 +74,15 @@ impl StdioClient {
         Ok(Self {
             outgoing_tx,
             pending_tx,
-            loop_handle,
+            capabilities,
         })
     }
 
+    /// The optional protocol features supported by both sides.
+    pub fn capabilities(&self) -> Capabilities {
+        self.capabilities
+    }
+
     pub fn handle(&self) -> StdioClientHandle {
         StdioClientHandle {
             outgoing_tx: self.outgoing_tx.clone(),
@@ -77,17 +95,26 @@ impl StdioClient {
 pub struct StdioClientHandle {
     outgoing_tx: mpsc::Sender<String>,
     /// Channel to send new pending requests to.
-    pending_tx: mpsc::Sender<(RequestId, oneshot::Sender<OwnedJson>)>,
+    pending_tx: mpsc::Sender<PendingRequest>,
 }
 
 impl StdioClientHandle {
     pub async fn method<I>(&self, params: &I) -> Result<I::Response>
+    where
+        I: ProtocolMethod + serde::Serialize + Send + Sync + std::fmt::Debug,
+        I::Response: DeserializeOwned + Send + Sync + 'static,
+    {
+        self.method_with_id(RequestId::new_uuid(), params).await
+    }
+
+    /// Send a request with the given ID and wait for its response.
+    pub async fn method_with_id<I>(&self, id: RequestId, params: &I) -> Result<I::Response>
     where
         I: ProtocolMethod + serde::Serialize + Send + Sync + std::fmt::Debug,
         I::Response: DeserializeOwned + Send + Sync + 'static,
     {
         let response = self
-            .method_dyn(I::METHOD_NAME, LazyValue::write_ref(params))
+            .method_dyn(id, I::METHOD_NAME, LazyValue::write_ref(params))
             .await?;
         response
             .value()
@@ -121,28 +148,34 @@ impl StdioClientHandle {
 
     async fn method_dyn(
         &self,
+        id: RequestId,
         method: Method,
         params: LazyValue<'_>,
     ) -> Result<OwnedJson<LazyValue<'static>>> {
-        let id = RequestId::new_uuid();
-        let (response_tx, response_rx) = tokio::sync::oneshot::channel();
-
-        self.pending_tx
-            .send((id.clone(), response_tx))
-            .await
-            .map_err(|_| TransportError::Send)?;
-
-        let request = MethodRequest::new(id.clone(), method, Some(params));
-        self.send(&request).await?;
-
-        let response = response_rx
-            .await
-            .change_context(TransportError::Recv)?
-            .owned_response()?;
-
-        // This is an assertion since the routing should only send the response for the
-        // registered ID to the pending one-shot channel.
-        debug_assert_eq!(response.response().id(), &id);
-        response.into_success_value()
+        let span = tracing::info_span!("protocol_request", %method, request_id = %id);
+        async move {
+            let (response_tx, response_rx) = tokio::sync::oneshot::channel();
+
+            self.pending_tx
+                .send((id.clone(), response_tx))
+                .await
+                .map_err(|_| TransportError::Send)?;
+
+            let request = MethodRequest::new(id.clone(), method, Some(params));
+            self.send(&request).await?;
+
+            // The loop responds with an error if the process exits first.
+            let response = response_rx
+                .await
+                .change_context(TransportError::Recv)??
+                .owned_response()?;
+
+            // This is an assertion since the routing should only send the response for the
+            // registered ID to the pending one-shot channel.
+            debug_assert_eq!(response.response().id(), &id);
+            response.into_success_value()
+        }
+        .instrument(span)
+        .await
     }
 }
@@ -0,0 +1,456 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+//! How messages are framed on the stdio pipes of a component server.
+//!
+//! Messages start out as newline-delimited JSON. A binary framing may be
+//! negotiated during `initialize`, after which each message is a 4-byte
+//! big-endian length followed by the message encoded with MessagePack or CBOR.
+//!
+//! Messages are handled as JSON within Stepflow, so binary frames are
+//! transcoded at the pipe. Binary blob content, which is a base64-encoded
+//! string in JSON, is carried as raw bytes in binary frames. Only the `data`
+//! of `blobs/put` requests and of responses to `blobs/get` requests is
+//! rewritten this way.
+
+use std::collections::HashSet;
+
+use error_stack::ResultExt as _;
+use serde::de::{self, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{SerializeMap as _, Serializer};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use stepflow_core::blob::{decode_base64_value, encode_base64};
+use tokio::io::{AsyncRead, AsyncReadExt as _};
+
+use crate::error::{Result, TransportError};
+use crate::protocol::{Framing, Method, RequestId};
+
+/// Size of the length prefix of a binary frame.
+const LENGTH_PREFIX: usize = 4;
+/// Maximum size of a frame received from a component server.
+const MAX_FRAME_LENGTH: usize = 256 * 1024 * 1024;
+/// Maximum number of bytes preallocated for a sequence based on its size hint.
+const MAX_PREALLOCATION: usize = 1024 * 1024;
+
+impl Framing {
+    /// Append the frame carrying a message, given as JSON, to `out`.
+    ///
+    /// `blob_gets` holds the IDs of `blobs/get` requests awaiting their
+    /// response. The ID is removed when the response is written.
+    pub fn write_frame(
+        self,
+        json: &str,
+        blob_gets: &mut HashSet<RequestId>,
+        out: &mut Vec<u8>,
+    ) -> Result<()> {
+        if self == Framing::Json {
+            out.extend_from_slice(json.as_bytes());
+            out.push(b'\n');
+            return Ok(());
+        }
+
+        let value: Value = serde_json::from_str(json)
+            .change_context_lazy(|| TransportError::InvalidMessage(json.to_owned()))?;
+        let content_key = match Method::deserialize(&value["method"]) {
+            Ok(Method::BlobsPut) => Some("params"),
+            Ok(_) => None,
+            Err(_) => RequestId::deserialize(&value["id"])
+                .is_ok_and(|id| blob_gets.remove(&id))
+                .then_some("result"),
+        };
+        let message = BinaryMessage {
+            message: &value,
+            content_key,
+        };
+
+        let start = out.len();
+        out.extend_from_slice(&[0; LENGTH_PREFIX]);
+        match self {
+            Framing::Json => unreachable!("handled above"),
+            Framing::MessagePack => {
+                rmp_serde::encode::write(out, &message).change_context(TransportError::Send)?
+            }
+            Framing::Cbor => {
+                ciborium::into_writer(&message, &mut *out).change_context(TransportError::Send)?
+            }
+        }
+        let length = u32::try_from(out.len() - start - LENGTH_PREFIX)
+            .change_context(TransportError::Send)
+            .attach_printable("message too large for a binary frame")?;
+        out[start..start + LENGTH_PREFIX].copy_from_slice(&length.to_be_bytes());
+        Ok(())
+    }
+
+    /// Decode the payload of a frame into the message as JSON.
+    pub fn decode(self, payload: Vec<u8>) -> Result<String> {
+        let value = match self {
+            Framing::Json => {
+                return String::from_utf8(payload)
+                    .change_context(TransportError::Recv)
+                    .attach_printable("message is not valid UTF-8");
+            }
+            Framing::MessagePack => {
+                rmp_serde::from_slice::<JsonValue>(&payload).change_context(TransportError::Recv)?
+            }
+            Framing::Cbor => ciborium::from_reader::<JsonValue, _>(payload.as_slice())
+                .change_context(TransportError::Recv)?,
+        };
+        serde_json::to_string(&value.0).change_context(TransportError::Recv)
+    }
+}
+
+/// Reads frames from a component server.
+///
+/// Reading is cancel-safe: a partially received frame is kept until the
+/// rest of it arrives.
+pub struct FrameReader<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    /// Number of bytes of `buffer` known not to contain a newline.
+    scanned: usize,
+}
+
+impl<R: AsyncRead + Unpin> FrameReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: Vec::new(),
+            scanned: 0,
+        }
+    }
+
+    /// Read the payload of the next frame, or `None` once the pipe is closed.
+    ///
+    /// Fails if a frame is longer than [`MAX_FRAME_LENGTH`].
+    pub async fn next_frame(&mut self, framing: Framing) -> Option<std::io::Result<Vec<u8>>> {
+        loop {
+            match self.take_frame(framing) {
+                Ok(Some(frame)) => return Some(Ok(frame)),
+                Ok(None) => {}
+                Err(e) => return Some(Err(e)),
+            }
+            self.buffer.reserve(8 * 1024);
+            match self.reader.read_buf(&mut self.buffer).await {
+                Ok(0) => return None,
+                Ok(_) => {}
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+
+    fn take_frame(&mut self, framing: Framing) -> std::io::Result<Option<Vec<u8>>> {
+        match framing {
+            Framing::Json => {
+                let Some(offset) = self.buffer[self.scanned..]
+                    .iter()
+                    .position(|byte| *byte == b'\n')
+                else {
+                    self.scanned = self.buffer.len();
+                    return check_frame_length(self.scanned).map(|()| None);
+                };
+                check_frame_length(self.scanned + offset)?;
+                let mut line: Vec<u8> = self.buffer.drain(..=self.scanned + offset).collect();
+                self.scanned = 0;
+                line.pop();
+                if line.last() == Some(&b'\r') {
+                    line.pop();
+                }
+                Ok(Some(line))
+            }
+            Framing::MessagePack | Framing::Cbor => {
+                let Some(prefix) = self.buffer.get(..LENGTH_PREFIX) else {
+                    return Ok(None);
+                };
+                let length = u32::from_be_bytes(prefix.try_into().expect("prefix length"));
+                check_frame_length(length as usize)?;
+                let end = LENGTH_PREFIX + length as usize;
+                if self.buffer.len() < end {
+                    return Ok(None);
+                }
+                Ok(Some(self.buffer.drain(..end).skip(LENGTH_PREFIX).collect()))
+            }
+        }
+    }
+}
+
+fn check_frame_length(length: usize) -> std::io::Result<()> {
+    if length > MAX_FRAME_LENGTH {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("frame of {length} bytes exceeds the maximum of {MAX_FRAME_LENGTH} bytes"),
+        ));
+    }
+    Ok(())
+}
+
+/// Serializes a message, writing the binary blob content of the object under
+/// `content_key` as raw bytes.
+struct BinaryMessage<'a> {
+    message: &'a Value,
+    /// The field holding blob parameters or a blob result, if any.
+    content_key: Option<&'static str>,
+}
+
+impl Serialize for BinaryMessage<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let Value::Object(fields) = self.message else {
+            return self.message.serialize(serializer);
+        };
+        let mut map = serializer.serialize_map(Some(fields.len()))?;
+        for (key, value) in fields {
+            if Some(key.as_str()) == self.content_key {
+                map.serialize_entry(key, &BlobContent(value))?;
+            } else {
+                map.serialize_entry(key, value)?;
+            }
+        }
+        map.end()
+    }
+}
+
+/// Serializes blob parameters or a blob result, writing the `data` of a
+/// binary blob as raw bytes.
+struct BlobContent<'a>(&'a Value);
+
+impl Serialize for BlobContent<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let Value::Object(fields) = self.0 else {
+            return self.0.serialize(serializer);
+        };
+        let binary = fields.get("blob_type").and_then(Value::as_str) == Some("binary");
+        let mut map = serializer.serialize_map(Some(fields.len()))?;
+        for (key, value) in fields {
+            if binary
+                && key == "data"
+                && let Ok(content) = decode_base64_value(value)
+            {
+                map.serialize_entry(key, &Bytes(&content))?;
+            } else {
+                map.serialize_entry(key, value)?;
+            }
+        }
+        map.end()
+    }
+}
+
+struct Bytes<'a>(&'a [u8]);
+
+impl Serialize for Bytes<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+/// A JSON value deserialized from a binary format, with raw bytes as base64-encoded strings.
+struct JsonValue(Value);
+
+impl<'de> Deserialize<'de> for JsonValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        deserializer
+            .deserialize_any(JsonValueVisitor)
+            .map(JsonValue)
+    }
+}
+
+struct JsonValueVisitor;
+
+impl<'de> Visitor<'de> for JsonValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("a JSON-compatible value")
+    }
+
+    fn visit_bool<E>(self, value: bool) -> std::result::Result<Value, E> {
+        Ok(Value::Bool(value))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> std::result::Result<Value, E> {
+        Ok(Value::from(value))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> std::result::Result<Value, E> {
+        Ok(Value::from(value))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> std::result::Result<Value, E> {
+        serde_json::Number::from_f64(value)
+            .map(Value::Number)
+            .ok_or_else(|| E::custom(format!("{value} is not a valid JSON number")))
+    }
+
+    fn visit_str<E>(self, value: &str) -> std::result::Result<Value, E> {
+        Ok(Value::String(value.to_owned()))
+    }
+
+    fn visit_string<E>(self, value: String) -> std::result::Result<Value, E> {
+        Ok(Value::String(value))
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> std::result::Result<Value, E> {
+        Ok(Value::String(encode_base64(value)))
+    }
+
+    fn visit_none<E>(self) -> std::result::Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_unit<E>(self) -> std::result::Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<Value, D::Error> {
+        JsonValue::deserialize(deserializer).map(|JsonValue(value)| value)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<Value, A::Error> {
+        // Like serde's own impls, don't trust the size hint for more than a bounded preallocation.
+        let max_items = MAX_PREALLOCATION / std::mem::size_of::<Value>();
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(max_items));
+        while let Some(JsonValue(item)) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(Value::Array(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> std::result::Result<Value, A::Error> {
+        let mut fields = Map::new();
+        while let Some((key, JsonValue(value))) = map.next_entry::<String, JsonValue>()? {
+            fields.insert(key, value);
+        }
+        Ok(Value::Object(fields))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    async fn round_trip(framing: Framing, messages: &[Value]) -> Vec<Value> {
+        let mut frames = Vec::new();
+        for message in messages {
+            framing
+                .write_frame(&message.to_string(), &mut HashSet::new(), &mut frames)
+                .unwrap();
+        }
+
+        // Deliver the frames a few bytes at a time.
+        let (mut writer, reader) = tokio::io::duplex(7);
+        tokio::spawn(async move {
+            use tokio::io::AsyncWriteExt as _;
+            writer.write_all(&frames).await.unwrap();
+        });
+        let mut reader = FrameReader::new(reader);
+        let mut decoded = Vec::new();
+        while let Some(frame) = reader.next_frame(framing).await {
+            let json = framing.decode(frame.unwrap()).unwrap();
+            decoded.push(serde_json::from_str(&json).unwrap());
+        }
+        decoded
+    }
+
+    #[tokio::test]
+    async fn test_round_trip() {
+        let messages = [
+            json!({
+                "jsonrpc": "2.0",
+                "id": "1",
+                "method": "components/execute",
+                "params": {
+                    "input": { "text": "héllo\nworld", "embedding": [0.25, -1.5, 3], "n": -7 },
+                    "flag": true,
+                    "missing": null,
+                },
+            }),
+            json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} }),
+        ];
+        for framing in [Framing::Json, Framing::MessagePack, Framing::Cbor] {
+            assert_eq!(round_trip(framing, &messages).await, messages, "{framing}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_binary_blobs_are_raw_bytes() {
+        let content = b"\x00\x01binary\xff".repeat(100);
+        let message = json!({
+            "jsonrpc": "2.0",
+            "id": "put",
+            "method": "blobs/put",
+            "params": { "data": encode_base64(&content), "blob_type": "binary" },
+        });
+
+        let mut frame = Vec::new();
+        Framing::MessagePack
+            .write_frame(&message.to_string(), &mut HashSet::new(), &mut frame)
+            .unwrap();
+        assert!(frame.windows(content.len()).any(|window| window == content));
+        assert!(frame.len() < message.to_string().len());
+
+        for framing in [Framing::MessagePack, Framing::Cbor] {
+            let messages = std::slice::from_ref(&message);
+            assert_eq!(round_trip(framing, messages).await, messages);
+        }
+    }
+
+    #[test]
+    fn test_only_blob_messages_carry_raw_bytes() {
+        let content = b"\x00\x01binary\xff".repeat(100);
+        let blob = json!({ "data": encode_base64(&content), "blob_type": "binary" });
+        let carries_raw_bytes = |message: &Value, blob_gets: &mut HashSet<RequestId>| {
+            let mut frame = Vec::new();
+            Framing::MessagePack
+                .write_frame(&message.to_string(), blob_gets, &mut frame)
+                .unwrap();
+            frame.windows(content.len()).any(|window| window == content)
+        };
+
+        // Component inputs and outputs keep blob-shaped values as strings.
+        let execute = json!({
+            "jsonrpc": "2.0",
+            "id": "1",
+            "method": "components/execute",
+            "params": blob,
+        });
+        assert!(!carries_raw_bytes(&execute, &mut HashSet::new()));
+
+        // Only the response to a pending `blobs/get` request is rewritten.
+        let response = json!({ "jsonrpc": "2.0", "id": "get", "result": blob });
+        let mut blob_gets = HashSet::new();
+        assert!(!carries_raw_bytes(&response, &mut blob_gets));
+        blob_gets.insert(RequestId::String("get".to_string()));
+        assert!(carries_raw_bytes(&response, &mut blob_gets));
+        assert!(blob_gets.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_frame_length_limit() {
+        let (mut writer, reader) = tokio::io::duplex(64);
+        tokio::spawn(async move {
+            use tokio::io::AsyncWriteExt as _;
+            let length = u32::try_from(MAX_FRAME_LENGTH + 1).unwrap();
+            writer.write_all(&length.to_be_bytes()).await.unwrap();
+        });
+        let mut reader = FrameReader::new(reader);
+        let error = reader
+            .next_frame(Framing::MessagePack)
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
+}
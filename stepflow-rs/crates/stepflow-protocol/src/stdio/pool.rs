@@ -0,0 +1,441 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use stepflow_plugin::{Context, PluginHealth, PluginStatus};
+
+use crate::error::{Result, TransportError};
+use crate::metrics::PLUGIN_PROCESSES;
+use crate::protocol::{Capabilities, Framing, ProtocolMethod, ProtocolNotification, RequestId};
+
+use super::client::{StdioClient, StdioClientHandle};
+use super::launcher::Launcher;
+use super::supervisor::RestartPolicy;
+
+/// How many component server processes are run for a plugin.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PoolConfig {
+    /// Number of processes started with the plugin and kept while idle.
+    pub min_instances: usize,
+    /// Maximum number of processes. Another is started when every process
+    /// is busy with a request.
+    pub max_instances: usize,
+    /// How long, in milliseconds, a process beyond `minInstances` may be
+    /// idle before it is stopped.
+    pub idle_timeout_ms: u64,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            min_instances: 1,
+            max_instances: 1,
+            idle_timeout_ms: 60_000,
+        }
+    }
+}
+
+impl PoolConfig {
+    pub fn validate(&self) -> Result<()> {
+        error_stack::ensure!(
+            self.min_instances >= 1 && self.min_instances <= self.max_instances,
+            TransportError::InvalidValue {
+                field: "pool",
+                expected: "1 <= minInstances <= maxInstances",
+            }
+        );
+        Ok(())
+    }
+}
+
+/// A component server process in the pool.
+struct Member {
+    id: u64,
+    client: StdioClientHandle,
+    health: Arc<Mutex<PluginHealth>>,
+    /// Number of requests sent to the process and awaiting their response.
+    in_flight: usize,
+    /// When the process last completed a request.
+    idle_since: Instant,
+}
+
+impl Member {
+    fn status(&self) -> PluginStatus {
+        self.health.lock().expect("plugin health poisoned").status
+    }
+}
+
+#[derive(Default)]
+struct PoolState {
+    members: Vec<Member>,
+    /// Number of processes being started.
+    starting: usize,
+    next_id: u64,
+}
+
+impl PoolState {
+    fn add(&mut self, client: StdioClientHandle, health: Arc<Mutex<PluginHealth>>) {
+        self.members.push(Member {
+            id: self.next_id,
+            client,
+            health,
+            in_flight: 0,
+            idle_since: Instant::now(),
+        });
+        self.next_id += 1;
+    }
+
+    /// Return the least loaded process, preferring those that have not failed.
+    fn least_loaded(&mut self) -> &mut Member {
+        let available = self
+            .members
+            .iter()
+            .any(|member| member.status() != PluginStatus::Failed);
+        self.members
+            .iter_mut()
+            .filter(|member| !available || member.status() != PluginStatus::Failed)
+            .min_by_key(|member| member.in_flight)
+            .expect("pool has at least one process")
+    }
+
+    /// Remove processes beyond `min` that have been idle for `idle_timeout`.
+    fn remove_idle(&mut self, min: usize, idle_timeout: Duration, now: Instant) -> Vec<Member> {
+        let mut removed = Vec::new();
+        let mut index = 0;
+        while index < self.members.len() {
+            let member = &self.members[index];
+            if self.members.len() > min
+                && member.in_flight == 0
+                && now.duration_since(member.idle_since) >= idle_timeout
+            {
+                removed.push(self.members.remove(index));
+            } else {
+                index += 1;
+            }
+        }
+        removed
+    }
+
+    fn health(&self) -> PluginHealth {
+        aggregate_health(self.members.iter().map(|member| {
+            member
+                .health
+                .lock()
+                .expect("plugin health poisoned")
+                .clone()
+        }))
+    }
+}
+
+/// Combine the health of the processes of a plugin.
+///
+/// The plugin has failed only if every process has, and is restarting if any
+/// process is not healthy.
+fn aggregate_health(processes: impl IntoIterator<Item = PluginHealth>) -> PluginHealth {
+    let mut health = PluginHealth::default();
+    let mut all_failed = true;
+    for process in processes {
+        all_failed &= process.status == PluginStatus::Failed;
+        if process.status != PluginStatus::Healthy {
+            health.status = PluginStatus::Restarting;
+        }
+        health.restarts += process.restarts;
+        health.last_error = process.last_error.or(health.last_error);
+    }
+    if all_failed && health.status != PluginStatus::Healthy {
+        health.status = PluginStatus::Failed;
+    }
+    health
+}
+
+struct PoolInner {
+    plugin: Arc<str>,
+    launcher: Arc<Launcher>,
+    restart_policy: RestartPolicy,
+    framing: Framing,
+    config: PoolConfig,
+    context: Arc<dyn Context>,
+    /// Capabilities negotiated with the processes started with the pool.
+    capabilities: Capabilities,
+    state: Mutex<PoolState>,
+}
+
+impl PoolInner {
+    fn lock(&self) -> std::sync::MutexGuard<'_, PoolState> {
+        self.state.lock().expect("pool state poisoned")
+    }
+
+    /// Launch and initialize another component server process.
+    async fn start(&self) -> Result<(StdioClientHandle, Arc<Mutex<PluginHealth>>, Capabilities)> {
+        let health = Arc::new(Mutex::new(PluginHealth::default()));
+        let client = StdioClient::try_new(
+            self.plugin.clone(),
+            self.launcher.clone(),
+            self.restart_policy.clone(),
+            self.framing,
+            health.clone(),
+            self.context.clone(),
+        )
+        .await?;
+        Ok((client.handle(), health, client.capabilities()))
+    }
+
+    fn record_size(&self, state: &PoolState) {
+        metrics::gauge!(PLUGIN_PROCESSES, "plugin" => self.plugin.to_string())
+            .set(state.members.len() as f64);
+    }
+}
+
+/// Component server processes launched from the same command.
+///
+/// Requests are sent to the least loaded process. Each process has its own
+/// receive loop, so requests a component makes back to Stepflow (such as
+/// `blobs/put` or `flows/evaluate`) are answered on the process that made them.
+#[derive(Clone)]
+pub struct StdioPool {
+    inner: Arc<PoolInner>,
+}
+
+/// A request sent to a process of the pool, released when dropped.
+struct Lease {
+    inner: Arc<PoolInner>,
+    id: u64,
+    client: StdioClientHandle,
+}
+
+impl Drop for Lease {
+    fn drop(&mut self) {
+        let mut state = self.inner.lock();
+        if let Some(member) = state.members.iter_mut().find(|member| member.id == self.id) {
+            member.in_flight -= 1;
+            member.idle_since = Instant::now();
+        }
+    }
+}
+
+impl StdioPool {
+    /// Launch and initialize the minimum number of processes.
+    pub async fn try_new(
+        plugin: Arc<str>,
+        launcher: Launcher,
+        restart_policy: RestartPolicy,
+        framing: Framing,
+        config: PoolConfig,
+        context: Arc<dyn Context>,
+    ) -> Result<Self> {
+        config.validate()?;
+        let mut inner = PoolInner {
+            plugin,
+            launcher: Arc::new(launcher),
+            restart_policy,
+            framing,
+            config,
+            context,
+            capabilities: Capabilities::default(),
+            state: Mutex::new(PoolState::default()),
+        };
+
+        let processes =
+            futures::future::try_join_all((0..inner.config.min_instances).map(|_| inner.start()))
+                .await?;
+        inner.capabilities = processes
+            .iter()
+            .map(|(_, _, capabilities)| *capabilities)
+            .reduce(|a, b| a.intersection(&b))
+            .expect("pool has at least one process");
+        let inner = Arc::new(inner);
+        {
+            let mut state = inner.lock();
+            for (client, health, _) in processes {
+                state.add(client, health);
+            }
+            inner.record_size(&state);
+        }
+
+        if inner.config.max_instances > inner.config.min_instances {
+            let idle_timeout = Duration::from_millis(inner.config.idle_timeout_ms);
+            tokio::spawn(scale_down(Arc::downgrade(&inner), idle_timeout));
+        }
+
+        Ok(Self { inner })
+    }
+
+    pub fn health(&self) -> PluginHealth {
+        self.inner.lock().health()
+    }
+
+    pub fn capabilities(&self) -> Capabilities {
+        self.inner.capabilities
+    }
+
+    pub async fn method<I>(&self, params: &I) -> Result<I::Response>
+    where
+        I: ProtocolMethod + serde::Serialize + Send + Sync + std::fmt::Debug,
+        I::Response: DeserializeOwned + Send + Sync + 'static,
+    {
+        self.method_with_id(RequestId::new_uuid(), params).await
+    }
+
+    /// Send a request with the given ID to the least loaded process.
+    pub async fn method_with_id<I>(&self, id: RequestId, params: &I) -> Result<I::Response>
+    where
+        I: ProtocolMethod + serde::Serialize + Send + Sync + std::fmt::Debug,
+        I::Response: DeserializeOwned + Send + Sync + 'static,
+    {
+        let lease = self.acquire();
+        lease.client.method_with_id(id, params).await
+    }
+
+    /// Send a notification to every process.
+    pub async fn notify<I>(&self, params: &I) -> Result<()>
+    where
+        I: ProtocolNotification + serde::Serialize + Send + Sync + std::fmt::Debug,
+    {
+        let clients: Vec<_> = self
+            .inner
+            .lock()
+            .members
+            .iter()
+            .map(|member| member.client.clone())
+            .collect();
+        for client in clients {
+            client.notify(params).await?;
+        }
+        Ok(())
+    }
+
+    /// Reserve the least loaded process for a request, starting another
+    /// process if every one is busy.
+    fn acquire(&self) -> Lease {
+        let mut state = self.inner.lock();
+        let member = state.least_loaded();
+        let busy = member.in_flight > 0;
+        member.in_flight += 1;
+        let lease = Lease {
+            inner: self.inner.clone(),
+            id: member.id,
+            client: member.client.clone(),
+        };
+
+        if busy && state.members.len() + state.starting < self.inner.config.max_instances {
+            state.starting += 1;
+            tokio::spawn(scale_up(self.inner.clone()));
+        }
+        lease
+    }
+}
+
+/// Start another process and add it to the pool.
+async fn scale_up(inner: Arc<PoolInner>) {
+    let result = inner.start().await;
+    let mut state = inner.lock();
+    state.starting -= 1;
+    match result {
+        Ok((client, health, _)) => {
+            tracing::info!(plugin = %inner.plugin, "Started another component server process");
+            state.add(client, health);
+            inner.record_size(&state);
+        }
+        Err(e) => {
+            tracing::error!(plugin = %inner.plugin, "Failed to start component server process: {e:?}");
+        }
+    }
+}
+
+/// Periodically stop processes beyond the minimum that are idle.
+async fn scale_down(inner: Weak<PoolInner>, idle_timeout: Duration) {
+    let mut interval = tokio::time::interval(idle_timeout.max(Duration::from_millis(100)) / 2);
+    loop {
+        interval.tick().await;
+        let Some(inner) = inner.upgrade() else {
+            return;
+        };
+        let removed = {
+            let mut state = inner.lock();
+            let removed =
+                state.remove_idle(inner.config.min_instances, idle_timeout, Instant::now());
+            inner.record_size(&state);
+            removed
+        };
+        if !removed.is_empty() {
+            tracing::info!(
+                plugin = %inner.plugin,
+                "Stopping {} idle component server processes",
+                removed.len()
+            );
+        }
+        // Dropping the last handle to a process stops it.
+        drop(removed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregate_health() {
+        let healthy = PluginHealth {
+            restarts: 1,
+            ..PluginHealth::default()
+        };
+        let failed = PluginHealth {
+            status: PluginStatus::Failed,
+            restarts: 5,
+            last_error: Some("exited".to_string()),
+        };
+
+        assert_eq!(aggregate_health([]), PluginHealth::default());
+        assert_eq!(
+            aggregate_health([healthy.clone(), failed.clone()]),
+            PluginHealth {
+                status: PluginStatus::Restarting,
+                restarts: 6,
+                last_error: Some("exited".to_string()),
+            }
+        );
+        assert_eq!(
+            aggregate_health([failed.clone(), failed]),
+            PluginHealth {
+                status: PluginStatus::Failed,
+                restarts: 10,
+                last_error: Some("exited".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_pool_config() {
+        let config: PoolConfig = serde_json::from_str(r#"{ "maxInstances": 4 }"#).unwrap();
+        assert_eq!(
+            config,
+            PoolConfig {
+                min_instances: 1,
+                max_instances: 4,
+                idle_timeout_ms: 60_000,
+            }
+        );
+        assert!(config.validate().is_ok());
+
+        let config = PoolConfig {
+            min_instances: 2,
+            max_instances: 1,
+            ..PoolConfig::default()
+        };
+        assert!(config.validate().is_err());
+    }
+}
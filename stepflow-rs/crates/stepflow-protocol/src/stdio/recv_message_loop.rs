@@ -10,7 +10,11 @@
 // or implied. See the License for the specific language governing permissions and limitations under
 // the License.
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use error_stack::ResultExt as _;
 use stepflow_plugin::Context;
@@ -26,27 +30,78 @@ use tokio_stream::{StreamExt as _, wrappers::LinesStream};
 
 use crate::OwnedJson;
 use crate::error::{Result, TransportError};
-use crate::{Message, MessageHandlerRegistry, RequestId};
+use crate::lazy_value::LazyValue;
+use crate::protocol::{
+    Capabilities, Framing, InitializeParams, InitializeResult, Initialized, Method, MethodResponse,
+    ProtocolMethod as _, ProtocolNotification as _,
+};
+use crate::{Message, MessageHandlerRegistry, MethodRequest, Notification, RequestId};
 
+use super::framing::FrameReader;
 use super::launcher::Launcher;
+use super::supervisor::Supervisor;
+
+/// Number of lines of a component server's stderr kept to report why it exited.
+const STDERR_TAIL_LINES: usize = 20;
+/// How long to keep reading the stderr of a component server after it exits.
+const STDERR_DRAIN_TIMEOUT: Duration = Duration::from_millis(500);
+/// How long a component server has to respond to `initialize`.
+const INITIALIZE_TIMEOUT: Duration = Duration::from_secs(30);
 
-struct ReceiveMessageLoop {
+/// A request awaiting its response, and the channel to send the response to.
+pub type PendingRequest = (RequestId, oneshot::Sender<Result<OwnedJson>>);
+
+/// A running component server process and the requests awaiting its responses.
+pub struct ReceiveMessageLoop {
     child: Child,
     to_child: ChildStdin,
-    from_child_stdout: LinesStream<BufReader<ChildStdout>>,
+    from_child_stdout: FrameReader<ChildStdout>,
     from_child_stderr: LinesStream<BufReader<ChildStderr>>,
-    pending_requests: HashMap<RequestId, oneshot::Sender<OwnedJson>>,
+    /// The last lines written to stderr, reported if the process exits.
+    stderr_tail: VecDeque<String>,
+    pending_requests: HashMap<RequestId, oneshot::Sender<Result<OwnedJson>>>,
+    /// `blobs/get` requests from the process awaiting their response.
+    blob_gets: HashSet<RequestId>,
     outgoing_tx: mpsc::Sender<String>,
+    /// Handlers for requests and notifications from the process.
+    handlers: MessageHandlerRegistry,
+    /// Framing offered to the process during initialization.
+    preferred_framing: Framing,
+    /// Framing of the messages exchanged with the process.
+    framing: Framing,
+}
+
+/// How a component server process exited.
+struct ProcessExit {
+    status: String,
+    stderr_tail: Vec<String>,
+}
+
+impl ProcessExit {
+    fn error(&self) -> error_stack::Report<TransportError> {
+        error_stack::report!(TransportError::ServerExited {
+            status: self.status.clone(),
+            stderr_tail: self.stderr_tail.clone(),
+        })
+    }
 }
 
 impl ReceiveMessageLoop {
-    fn try_new(launcher: Launcher, outgoing_tx: mpsc::Sender<String>) -> Result<Self> {
+    /// Launch the component server process.
+    ///
+    /// Messages are exchanged as JSON until the process agrees to use
+    /// `preferred_framing` during initialization.
+    pub fn try_new(
+        launcher: &Launcher,
+        preferred_framing: Framing,
+        outgoing_tx: mpsc::Sender<String>,
+    ) -> Result<Self> {
         let env: std::collections::HashMap<String, String> = std::env::vars().collect();
         let mut child = launcher.spawn(&env)?;
 
         let to_child = child.stdin.take().expect("stdin requested");
         let from_child_stdout = child.stdout.take().expect("stdout requested");
-        let from_child_stdout = LinesStream::new(BufReader::new(from_child_stdout).lines());
+        let from_child_stdout = FrameReader::new(from_child_stdout);
 
         let from_child_stderr = child.stderr.take().expect("stderr requested");
         let from_child_stderr = LinesStream::new(BufReader::new(from_child_stderr).lines());
@@ -56,32 +111,31 @@ impl ReceiveMessageLoop {
             to_child,
             from_child_stdout,
             from_child_stderr,
+            stderr_tail: VecDeque::new(),
             pending_requests: HashMap::new(),
+            blob_gets: HashSet::new(),
             outgoing_tx,
+            handlers: MessageHandlerRegistry::default(),
+            preferred_framing,
+            framing: Framing::Json,
         })
     }
 
-    fn check_child_status(&mut self) -> Result<()> {
-        if let Some(status) = self
-            .child
-            .try_wait()
-            .change_context(TransportError::Spawn)?
-            && !status.success()
-        {
-            tracing::error!("Child process exited with status {status}");
-            return Err(TransportError::Spawn.into());
+    fn record_stderr(&mut self, line: String) {
+        tracing::info!("Component stderr: {line}");
+        if self.stderr_tail.len() == STDERR_TAIL_LINES {
+            self.stderr_tail.pop_front();
         }
-        Ok(())
+        self.stderr_tail.push_back(line);
     }
 
     async fn send(&mut self, json: String) -> Result<()> {
         tracing::debug!("Sending message to child: {json}");
+        let mut frame = Vec::with_capacity(json.len() + 1);
+        self.framing
+            .write_frame(&json, &mut self.blob_gets, &mut frame)?;
         self.to_child
-            .write_all(json.as_bytes())
-            .await
-            .change_context(TransportError::Send)?;
-        self.to_child
-            .write_all(b"\n")
+            .write_all(&frame)
             .await
             .change_context(TransportError::Send)?;
         Ok(())
@@ -90,9 +144,10 @@ impl ReceiveMessageLoop {
     async fn iteration(
         &mut self,
         outgoing_rx: &mut mpsc::Receiver<String>,
-        pending_rx: &mut mpsc::Receiver<(RequestId, oneshot::Sender<OwnedJson>)>,
+        pending_rx: &mut mpsc::Receiver<PendingRequest>,
         context: &Arc<dyn Context>,
     ) -> Result<bool> {
+        let framing = self.framing;
         tokio::select! {
             child = self.child.wait() => {
                 match child {
@@ -108,17 +163,26 @@ impl ReceiveMessageLoop {
                 }
                 Ok(false)
             }
+            pending = pending_rx.recv() => {
+                let Some((id, pending)) = pending else {
+                    tracing::info!("All clients closed; stopping component server");
+                    return Ok(false);
+                };
+                self.pending_requests.insert(id, pending);
+                Ok(true)
+            }
             Some(outgoing) = outgoing_rx.recv() => {
                 self.send(outgoing).await?;
                 Ok(true)
             }
             Some(stderr_line) = self.from_child_stderr.next() => {
                 let stderr_line = stderr_line.change_context(TransportError::Recv)?;
-                tracing::info!("Component stderr: {stderr_line}");
+                self.record_stderr(stderr_line);
                 Ok(true)
             }
-            Some(line) = self.from_child_stdout.next() => {
-                let line = line.change_context(TransportError::Recv)?;
+            Some(frame) = self.from_child_stdout.next_frame(framing) => {
+                let frame = frame.change_context(TransportError::Recv)?;
+                let line = framing.decode(frame)?;
                 tracing::info!("Received line from child: {line:?}");
                 let msg = OwnedJson::try_new(line).change_context(TransportError::Recv)?;
 
@@ -126,8 +190,11 @@ impl ReceiveMessageLoop {
                 match message {
                     Message::Request(request) => {
                         tracing::info!("Received request for method '{}'", request.method);
+                        if request.method == Method::BlobsGet && framing != Framing::Json {
+                            self.blob_gets.insert(request.id.clone());
+                        }
 
-                        let Some(handler) = MessageHandlerRegistry::instance().get_method_handler(request.method) else {
+                        let Some(handler) = self.handlers.get_method_handler(request.method) else {
                             tracing::warn!("No handler found for method '{}'", request.method);
 
                             // Send an error response.
@@ -155,7 +222,9 @@ impl ReceiveMessageLoop {
                         Ok(true)
                     }
                     Message::Notification(notification) => {
-                        tracing::error!("Received unsupported notification for method '{}'", notification.method);
+                        self.handlers
+                            .handle_notification(notification, context.clone())
+                            .await;
                         Ok(true)
                     }
                     Message::Response(response) => {
@@ -163,7 +232,9 @@ impl ReceiveMessageLoop {
                         if let Some(pending) = self.get_pending(pending_rx, response.id()) {
                             // Send the response to the pending request.
                             tracing::info!("Sending response to pending request with id '{}'", response.id());
-                            pending.send(msg).map_err(|_| TransportError::Send)?;
+                            if pending.send(Ok(msg)).is_err() {
+                                tracing::debug!("Request was abandoned before its response arrived");
+                            }
                         }
                         Ok(true)
                     }
@@ -176,12 +247,123 @@ impl ReceiveMessageLoop {
         }
     }
 
+    /// Perform the initialization handshake with the component server, returning
+    /// the capabilities supported by both sides.
+    ///
+    /// Switches to the framing chosen by the process, which is used from the
+    /// `initialized` notification on.
+    pub async fn initialize(&mut self) -> Result<Capabilities> {
+        let id = RequestId::new_uuid();
+        let mut params = InitializeParams::default();
+        if self.preferred_framing != Framing::Json {
+            params.framings.push(self.preferred_framing);
+        }
+        let request = MethodRequest::new(
+            id.clone(),
+            InitializeParams::METHOD_NAME,
+            Some(LazyValue::write_ref(&params)),
+        );
+        let request = serde_json::to_string(&request)
+            .change_context(TransportError::SerializeRequest(Method::Initialize))?;
+        self.send(request).await?;
+
+        let result = tokio::time::timeout(INITIALIZE_TIMEOUT, self.wait_for_response(&id))
+            .await
+            .change_context(TransportError::Recv)
+            .attach_printable("timed out waiting for initialize response")??
+            .into_success_value()?
+            .value()
+            .deserialize_to::<InitializeResult>()
+            .change_context(TransportError::InvalidResponse(Method::Initialize))?;
+        let capabilities = result.negotiate()?;
+        self.framing = result.negotiate_framing(&params.framings)?;
+        tracing::debug!("Using {} framing with component server", self.framing);
+
+        let notification = Notification::new(
+            Initialized::METHOD_NAME,
+            Some(LazyValue::write_ref(&Initialized {})),
+        );
+        let notification = serde_json::to_string(&notification)
+            .change_context(TransportError::SerializeRequest(Method::Initialized))?;
+        self.send(notification).await?;
+        Ok(capabilities)
+    }
+
+    /// Read messages from the process until the response to `id` arrives.
+    ///
+    /// Only used before the process serves requests, so other messages are ignored.
+    async fn wait_for_response(
+        &mut self,
+        id: &RequestId,
+    ) -> Result<OwnedJson<MethodResponse<'static>>> {
+        loop {
+            tokio::select! {
+                status = self.child.wait() => {
+                    let status = status.change_context(TransportError::Recv)?;
+                    return Err(error_stack::report!(TransportError::ServerExited {
+                        status: status.to_string(),
+                        stderr_tail: self.stderr_tail.iter().cloned().collect(),
+                    }));
+                }
+                Some(stderr_line) = self.from_child_stderr.next() => {
+                    let stderr_line = stderr_line.change_context(TransportError::Recv)?;
+                    self.record_stderr(stderr_line);
+                }
+                Some(frame) = self.from_child_stdout.next_frame(self.framing) => {
+                    let line = self.framing.decode(frame.change_context(TransportError::Recv)?)?;
+                    let msg = OwnedJson::try_new(line).change_context(TransportError::Recv)?;
+                    if matches!(msg.message(), Message::Response(response) if response.id() == id) {
+                        return msg.owned_response();
+                    }
+                    tracing::warn!("Ignoring message received during initialization: {}", msg.json());
+                }
+                else => return Err(error_stack::report!(TransportError::Recv)),
+            }
+        }
+    }
+
+    /// Stop the process, failing the requests awaiting its responses.
+    ///
+    /// Requests queued but not yet sent to the process are failed as well,
+    /// since they were meant for this process.
+    async fn stop(
+        mut self,
+        outgoing_rx: &mut mpsc::Receiver<String>,
+        pending_rx: &mut mpsc::Receiver<PendingRequest>,
+    ) -> ProcessExit {
+        // The process may still be running if it broke the protocol.
+        let _ = self.child.start_kill();
+        let status = match self.child.wait().await {
+            Ok(status) => status.to_string(),
+            Err(e) => format!("unknown status ({e})"),
+        };
+
+        // Collect what the process wrote to stderr before exiting.
+        let _ = tokio::time::timeout(STDERR_DRAIN_TIMEOUT, async {
+            while let Some(Ok(line)) = self.from_child_stderr.next().await {
+                self.record_stderr(line);
+            }
+        })
+        .await;
+
+        let exit = ProcessExit {
+            status,
+            stderr_tail: self.stderr_tail.into_iter().collect(),
+        };
+        let queued = std::iter::from_fn(|| pending_rx.try_recv().ok());
+        for (_, pending) in self.pending_requests.drain().chain(queued) {
+            let _ = pending.send(Err(exit.error()));
+        }
+        while outgoing_rx.try_recv().is_ok() {}
+        exit
+    }
+
     /// Return the pending channel for the given request ID.
     fn get_pending(
         &mut self,
-        pending_rx: &mut mpsc::Receiver<(RequestId, oneshot::Sender<OwnedJson>)>,
+        pending_rx: &mut mpsc::Receiver<PendingRequest>,
         id: &RequestId,
-    ) -> Option<oneshot::Sender<OwnedJson>> {
+    ) -> Option<oneshot::Sender<Result<OwnedJson>>> {
         if let Some(pending) = self.pending_requests.remove(id) {
             Some(pending)
         } else {
@@ -218,36 +400,102 @@ impl ReceiveMessageLoop {
     }
 }
 
+/// Launch a replacement component server process and initialize it.
+async fn restart(
+    launcher: &Launcher,
+    preferred_framing: Framing,
+    outgoing_tx: &mpsc::Sender<String>,
+) -> Result<ReceiveMessageLoop> {
+    let mut recv_loop =
+        ReceiveMessageLoop::try_new(launcher, preferred_framing, outgoing_tx.clone())?;
+    recv_loop.initialize().await?;
+    Ok(recv_loop)
+}
+
+/// Fail every request once the component server will no longer be restarted.
+async fn reject_requests(
+    mut outgoing_rx: mpsc::Receiver<String>,
+    mut pending_rx: mpsc::Receiver<PendingRequest>,
+    reason: String,
+) {
+    loop {
+        tokio::select! {
+            Some((_, pending)) = pending_rx.recv() => {
+                let error = TransportError::ServerUnavailable(reason.clone());
+                let _ = pending.send(Err(error_stack::report!(error)));
+            }
+            Some(_) = outgoing_rx.recv() => {}
+            else => break,
+        }
+    }
+}
+
+/// Exchange messages with the component server, restarting it when it exits.
+///
+/// Runs until every client handle is dropped, then stops the process. Once the
+/// supervisor's restart budget is used up, requests fail without reaching a
+/// process.
 pub async fn recv_message_loop(
-    launcher: Launcher,
+    mut recv_loop: ReceiveMessageLoop,
+    launcher: Arc<Launcher>,
+    mut supervisor: Supervisor,
     outgoing_tx: mpsc::Sender<String>,
     mut outgoing_rx: mpsc::Receiver<String>,
-    mut pending_rx: mpsc::Receiver<(RequestId, oneshot::Sender<OwnedJson>)>,
+    mut pending_rx: mpsc::Receiver<PendingRequest>,
     context: Arc<dyn Context>,
-) -> Result<()> {
-    let mut recv_loop = ReceiveMessageLoop::try_new(launcher, outgoing_tx)?;
-
+) {
     loop {
-        match recv_loop
-            .iteration(&mut outgoing_rx, &mut pending_rx, &context)
-            .await
-        {
-            Ok(true) => {
-                // Continue the loop.
-            }
-            Ok(false) => {
-                // Exit the loop.
-                break;
-            }
-            Err(mut e) => {
-                tracing::info!("Error in recv loop: {e:?}. Checking child status.");
-                if let Err(child_error) = recv_loop.check_child_status() {
-                    e.extend_one(child_error);
+        loop {
+            match recv_loop
+                .iteration(&mut outgoing_rx, &mut pending_rx, &context)
+                .await
+            {
+                Ok(true) => {
+                    // Continue the loop.
+                }
+                Ok(false) => {
+                    // The process exited, or no client is left.
+                    break;
+                }
+                Err(e) => {
+                    tracing::error!("Error in recv loop: {e:?}. Stopping component server.");
+                    break;
                 }
-                return Err(TransportError::RecvLoop.into());
             }
         }
-    }
 
-    Ok(())
+        let preferred_framing = recv_loop.preferred_framing;
+        let exit = recv_loop.stop(&mut outgoing_rx, &mut pending_rx).await;
+        if pending_rx.is_closed() {
+            // No client is left to serve.
+            return;
+        }
+        tracing::error!(
+            status = %exit.status,
+            stderr = %exit.stderr_tail.join("\n"),
+            "Component server exited"
+        );
+
+        let mut reason = format!("component server exited with {}", exit.status);
+        recv_loop = loop {
+            let Some(backoff) = supervisor.restart_after(&reason, Instant::now()) else {
+                tracing::error!("Restart budget used up; component server will not be restarted");
+                drop(outgoing_tx);
+                reject_requests(outgoing_rx, pending_rx, reason).await;
+                return;
+            };
+            tokio::time::sleep(backoff).await;
+            match restart(&launcher, preferred_framing, &outgoing_tx).await {
+                Ok(recv_loop) => {
+                    tracing::info!("Restarted component server");
+                    supervisor.restarted();
+                    break recv_loop;
+                }
+                Err(e) => {
+                    tracing::error!("Failed to restart component server: {e:?}");
+                    reason = e.to_string();
+                }
+            }
+        };
+    }
 }
@@ -0,0 +1,154 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use stepflow_plugin::{PluginHealth, PluginStatus};
+
+use crate::metrics::PLUGIN_RESTARTS;
+
+/// How a component server process is restarted after it exits.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RestartPolicy {
+    /// Maximum number of restarts within `windowMs`. Once used up, the plugin
+    /// is marked as failed and requests to it fail immediately.
+    pub max_restarts: u32,
+    /// Length of the window, in milliseconds, over which restarts are counted.
+    pub window_ms: u64,
+    /// Delay before the first restart in the window. Each further restart
+    /// doubles the delay.
+    pub initial_backoff_ms: u64,
+    /// Upper bound on the delay before a restart.
+    pub max_backoff_ms: u64,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            max_restarts: 5,
+            window_ms: 60_000,
+            initial_backoff_ms: 500,
+            max_backoff_ms: 30_000,
+        }
+    }
+}
+
+/// Tracks the restarts of a component server process and reports its health.
+pub struct Supervisor {
+    plugin: Arc<str>,
+    policy: RestartPolicy,
+    /// When each restart within the current window was attempted.
+    attempts: VecDeque<Instant>,
+    health: Arc<Mutex<PluginHealth>>,
+}
+
+impl Supervisor {
+    pub fn new(plugin: Arc<str>, policy: RestartPolicy, health: Arc<Mutex<PluginHealth>>) -> Self {
+        Self {
+            plugin,
+            policy,
+            attempts: VecDeque::new(),
+            health,
+        }
+    }
+
+    /// Record that the process failed, returning how long to wait before
+    /// restarting it, or `None` if the restart budget is used up.
+    pub fn restart_after(&mut self, error: &str, now: Instant) -> Option<Duration> {
+        let window = Duration::from_millis(self.policy.window_ms);
+        while self
+            .attempts
+            .front()
+            .is_some_and(|attempt| now.duration_since(*attempt) >= window)
+        {
+            self.attempts.pop_front();
+        }
+
+        let attempts = self.attempts.len() as u32;
+        if attempts >= self.policy.max_restarts {
+            self.update(PluginStatus::Failed, error);
+            return None;
+        }
+        self.attempts.push_back(now);
+        self.update(PluginStatus::Restarting, error);
+
+        let backoff = Duration::from_millis(self.policy.initial_backoff_ms)
+            .saturating_mul(2u32.saturating_pow(attempts))
+            .min(Duration::from_millis(self.policy.max_backoff_ms));
+        Some(backoff)
+    }
+
+    /// Record that the process was restarted and is serving requests again.
+    pub fn restarted(&self) {
+        let mut health = self.health.lock().expect("plugin health poisoned");
+        health.status = PluginStatus::Healthy;
+        health.restarts += 1;
+        metrics::counter!(PLUGIN_RESTARTS, "plugin" => self.plugin.to_string()).increment(1);
+    }
+
+    fn update(&self, status: PluginStatus, error: &str) {
+        let mut health = self.health.lock().expect("plugin health poisoned");
+        health.status = status;
+        health.last_error = Some(error.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restart_budget() {
+        let health = Arc::new(Mutex::new(PluginHealth::default()));
+        let policy = RestartPolicy {
+            max_restarts: 3,
+            window_ms: 10_000,
+            initial_backoff_ms: 100,
+            max_backoff_ms: 300,
+        };
+        let mut supervisor = Supervisor::new("python".into(), policy, health.clone());
+
+        let start = Instant::now();
+        let backoffs: Vec<_> = (0..3)
+            .map(|_| supervisor.restart_after("exited", start))
+            .collect();
+        assert_eq!(
+            backoffs,
+            vec![
+                Some(Duration::from_millis(100)),
+                Some(Duration::from_millis(200)),
+                Some(Duration::from_millis(300)),
+            ]
+        );
+        assert_eq!(health.lock().unwrap().status, PluginStatus::Restarting);
+
+        supervisor.restarted();
+        let current = health.lock().unwrap().clone();
+        assert_eq!(current.status, PluginStatus::Healthy);
+        assert_eq!(current.restarts, 1);
+        assert_eq!(current.last_error.as_deref(), Some("exited"));
+
+        // The budget is used up within the window...
+        assert_eq!(supervisor.restart_after("crashed", start), None);
+        assert_eq!(health.lock().unwrap().status, PluginStatus::Failed);
+
+        // ...and replenished once earlier restarts fall out of it.
+        assert_eq!(
+            supervisor.restart_after("crashed", start + Duration::from_secs(10)),
+            Some(Duration::from_millis(100))
+        );
+    }
+}
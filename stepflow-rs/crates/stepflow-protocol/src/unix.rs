@@ -0,0 +1,73 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+//! Unix domain socket transport for Stepflow protocol.
+//!
+//! Messages are framed as over stdio, one JSON-RPC message per line.
+
+use error_stack::ResultExt as _;
+use tokio::io::{AsyncBufReadExt as _, AsyncWriteExt as _, BufReader, Lines};
+use tokio::net::UnixStream;
+use tokio::net::unix::{OwnedReadHalf, OwnedWriteHalf};
+
+use crate::connection::{ConnectionClient, Socket, SocketEvent};
+use crate::error::{Result, TransportError};
+
+/// A Unix domain socket connection to a component server.
+pub struct UnixSocket {
+    reader: Lines<BufReader<OwnedReadHalf>>,
+    writer: OwnedWriteHalf,
+}
+
+pub type UnixSocketClient = ConnectionClient<UnixSocket>;
+
+impl Socket for UnixSocket {
+    async fn connect(address: &str) -> Result<Self> {
+        let stream = UnixStream::connect(address)
+            .await
+            .change_context_lazy(|| TransportError::Connect(address.to_string()))?;
+        let (reader, writer) = stream.into_split();
+        Ok(Self {
+            reader: BufReader::new(reader).lines(),
+            writer,
+        })
+    }
+
+    async fn send(&mut self, json: String) -> Result<()> {
+        self.writer
+            .write_all(json.as_bytes())
+            .await
+            .change_context(TransportError::Send)?;
+        self.writer
+            .write_all(b"\n")
+            .await
+            .change_context(TransportError::Send)
+    }
+
+    async fn recv(&mut self) -> SocketEvent {
+        match self.reader.next_line().await {
+            Ok(Some(line)) => SocketEvent::Message(line),
+            Ok(None) => SocketEvent::Closed("closed by component server".to_string()),
+            Err(e) => SocketEvent::Closed(e.to_string()),
+        }
+    }
+
+    /// Lost connections are detected when the socket is closed, so no
+    /// heartbeats are sent.
+    async fn ping(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn close(&mut self) {
+        let _ = self.writer.shutdown().await;
+    }
+}
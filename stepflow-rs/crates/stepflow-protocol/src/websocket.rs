@@ -0,0 +1,75 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+//! WebSocket transport for Stepflow protocol.
+//!
+//! Each JSON-RPC message is sent as a text frame. Ping frames check that the
+//! connection is alive.
+
+use error_stack::ResultExt as _;
+use futures::{SinkExt as _, StreamExt as _};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::Message as Frame;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use crate::connection::{ConnectionClient, Socket, SocketEvent};
+use crate::error::{Result, TransportError};
+
+/// A WebSocket connection to a component server.
+pub struct WebSocket(WebSocketStream<MaybeTlsStream<TcpStream>>);
+
+pub type WebSocketClient = ConnectionClient<WebSocket>;
+
+impl Socket for WebSocket {
+    async fn connect(address: &str) -> Result<Self> {
+        let (socket, _) = tokio_tungstenite::connect_async(address)
+            .await
+            .change_context_lazy(|| TransportError::Connect(address.to_string()))?;
+        Ok(Self(socket))
+    }
+
+    async fn send(&mut self, json: String) -> Result<()> {
+        self.0
+            .send(Frame::Text(json))
+            .await
+            .change_context(TransportError::Send)
+    }
+
+    async fn recv(&mut self) -> SocketEvent {
+        loop {
+            return match self.0.next().await {
+                Some(Ok(Frame::Text(text))) => SocketEvent::Message(text),
+                Some(Ok(Frame::Pong(_))) => SocketEvent::Pong,
+                Some(Ok(Frame::Close(Some(frame)))) => {
+                    SocketEvent::Closed(format!("closed by component server: {}", frame.reason))
+                }
+                Some(Ok(Frame::Close(None))) | None => {
+                    SocketEvent::Closed("closed by component server".to_string())
+                }
+                // Pings are answered by the WebSocket implementation.
+                Some(Ok(_)) => continue,
+                Some(Err(e)) => SocketEvent::Closed(e.to_string()),
+            };
+        }
+    }
+
+    async fn ping(&mut self) -> Result<()> {
+        self.0
+            .send(Frame::Ping(Vec::new()))
+            .await
+            .change_context(TransportError::Send)
+    }
+
+    async fn close(&mut self) {
+        let _ = self.0.close(None).await;
+    }
+}
@@ -0,0 +1,458 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::{SinkExt as _, StreamExt as _};
+use serde_json::{Value, json};
+use stepflow_core::{
+    BlobId, FlowResult,
+    workflow::{Component, Flow, ValueRef},
+};
+use stepflow_plugin::{
+    Context, ExecutionContext, Plugin as _, PluginConfig as _, PluginError, PluginStatus,
+};
+use stepflow_protocol::{BatchConfig, ReconnectPolicy, StepflowPluginConfig, StepflowTransport};
+use stepflow_state::{InMemoryStateStore, LogLevel, StateStore};
+use tokio::io::{AsyncBufReadExt as _, AsyncWriteExt as _, BufReader};
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::Message;
+use uuid::Uuid;
+
+/// Reconnect quickly, so the tests do not wait long for the connection to be re-established.
+const RECONNECT: ReconnectPolicy = ReconnectPolicy {
+    initial_backoff_ms: 10,
+    max_backoff_ms: 10,
+};
+
+struct MockContext {
+    state_store: Arc<dyn StateStore>,
+}
+
+impl Context for MockContext {
+    fn state_store(&self) -> &Arc<dyn StateStore> {
+        &self.state_store
+    }
+
+    fn working_directory(&self) -> &Path {
+        Path::new(".")
+    }
+
+    fn submit_flow(
+        &self,
+        _flow: Arc<Flow>,
+        _flow_id: BlobId,
+        _input: ValueRef,
+    ) -> Pin<Box<dyn Future<Output = Result<Uuid, error_stack::Report<PluginError>>> + Send>> {
+        Box::pin(async { Err(PluginError::Execution.into()) })
+    }
+
+    fn flow_result(
+        &self,
+        _execution_id: Uuid,
+    ) -> Pin<Box<dyn Future<Output = Result<FlowResult, error_stack::Report<PluginError>>> + Send>>
+    {
+        Box::pin(async { Err(PluginError::Execution.into()) })
+    }
+}
+
+/// What a [`ComponentServer`] does after handling a message.
+enum Reply {
+    Send(Vec<String>),
+    None,
+    Close,
+}
+
+/// A component server that stores the input of each execution as a blob, by
+/// calling back into the runtime, and returns the blob ID. Each execution also
+/// sends a log message naming the input and reports its progress.
+///
+/// An execution with `{"close": true}` as input closes the connection instead.
+#[derive(Default)]
+struct ComponentServer {
+    /// The ID of the execute request waiting on each `blobs/put` request.
+    executions: HashMap<String, Value>,
+}
+
+impl ComponentServer {
+    fn handle(&mut self, text: &str) -> Reply {
+        let message: Value = serde_json::from_str(text).unwrap();
+        let id = message["id"].clone();
+        let reply = match message["method"].as_str() {
+            Some("initialize") => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "result": { "server_protocol_version": 1 },
+            }),
+            Some("components/execute") => {
+                let input = &message["params"]["input"];
+                if input["close"] == true {
+                    return Reply::Close;
+                }
+                let log = json!({
+                    "jsonrpc": "2.0",
+                    "method": "log",
+                    "params": {
+                        "level": "info",
+                        "message": "storing input",
+                        "fields": { "input": input },
+                        "request_id": id,
+                    },
+                });
+                let progress = json!({
+                    "jsonrpc": "2.0",
+                    "method": "progress",
+                    "params": {
+                        "fraction": 0.5,
+                        "message": "storing input",
+                        "request_id": id,
+                    },
+                });
+                let put_id = format!("put-{}", id.as_str().unwrap());
+                self.executions.insert(put_id.clone(), id);
+                let put = json!({
+                    "jsonrpc": "2.0",
+                    "id": put_id,
+                    "method": "blobs/put",
+                    "params": { "data": input, "blob_type": "data" },
+                });
+                return Reply::Send(vec![log.to_string(), progress.to_string(), put.to_string()]);
+            }
+            Some(_) => return Reply::None,
+            None => {
+                // The response to a `blobs/put` request.
+                let execution = self.executions.remove(id.as_str().unwrap()).unwrap();
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": execution,
+                    "result": { "output": message["result"] },
+                })
+            }
+        };
+        Reply::Send(vec![reply.to_string()])
+    }
+}
+
+async fn serve_websocket(listener: TcpListener) {
+    loop {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+        let mut server = ComponentServer::default();
+        while let Some(Ok(Message::Text(text))) = socket.next().await {
+            match server.handle(&text) {
+                Reply::Send(replies) => {
+                    for reply in replies {
+                        socket.send(Message::Text(reply)).await.unwrap();
+                    }
+                }
+                Reply::None => {}
+                Reply::Close => {
+                    let _ = socket.close(None).await;
+                    break;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+async fn serve_unix(listener: tokio::net::UnixListener) {
+    loop {
+        let (stream, _) = listener.accept().await.unwrap();
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let mut server = ComponentServer::default();
+        while let Ok(Some(line)) = lines.next_line().await {
+            match server.handle(&line) {
+                Reply::Send(replies) => {
+                    for reply in replies {
+                        writer.write_all(reply.as_bytes()).await.unwrap();
+                        writer.write_all(b"\n").await.unwrap();
+                    }
+                }
+                Reply::None => {}
+                Reply::Close => break,
+            }
+        }
+    }
+}
+
+/// Check that executions are multiplexed over the connection to the component
+/// server, that their log messages and progress are recorded for the step, and
+/// that the connection is re-established if lost.
+async fn check_connection_plugin(config: StepflowPluginConfig) {
+    let plugin = config
+        .create_plugin("connection", Path::new("."))
+        .await
+        .unwrap();
+    let state_store: Arc<dyn StateStore> = Arc::new(InMemoryStateStore::new());
+    let context: Arc<dyn Context> = Arc::new(MockContext {
+        state_store: state_store.clone(),
+    });
+    plugin.init(&context).await.unwrap();
+
+    let flow = Arc::new(Flow::default());
+    let flow_id = BlobId::from_flow(&flow).unwrap();
+    let component = Component::from_string("/store");
+    let run_id = Uuid::new_v4();
+    let execute = |step: usize, input: Value| {
+        let context = ExecutionContext::for_step_with_flow(
+            context.clone(),
+            run_id,
+            format!("step{step}"),
+            flow.clone(),
+            flow_id.clone(),
+        );
+        plugin.execute(&component, context, ValueRef::new(input))
+    };
+
+    // Concurrent executions share the connection, each calling back into the runtime.
+    let results = futures::future::join_all(
+        (0..4).map(|step| execute(step, json!({ "message": format!("hello {step}") }))),
+    )
+    .await;
+    for (step, result) in results.into_iter().enumerate() {
+        let FlowResult::Success(output) = result.unwrap() else {
+            panic!("execution failed");
+        };
+        let blob_id: BlobId = serde_json::from_value(output.as_ref()["blob_id"].clone()).unwrap();
+        let blob = state_store.get_blob(&blob_id).await.unwrap();
+        assert_eq!(
+            blob.data().as_ref(),
+            &json!({ "message": format!("hello {step}") })
+        );
+
+        let logs = state_store
+            .get_step_logs(run_id, &format!("step{step}"))
+            .await
+            .unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].level, LogLevel::Info);
+        assert_eq!(logs[0].message, "storing input");
+        assert_eq!(
+            logs[0].fields["input"],
+            json!({ "message": format!("hello {step}") })
+        );
+    }
+
+    let progress = state_store.get_step_progress(run_id).await.unwrap();
+    for step in 0..4 {
+        let progress = &progress[&format!("step{step}")];
+        assert_eq!(progress.fraction, Some(0.5));
+        assert_eq!(progress.message.as_deref(), Some("storing input"));
+    }
+
+    // Requests in flight fail when the connection is lost...
+    assert!(execute(4, json!({ "close": true })).await.is_err());
+
+    // ...and the connection is re-established.
+    let mut reconnected = false;
+    for _ in 0..100 {
+        if plugin.health().status == PluginStatus::Healthy
+            && execute(5, json!({ "message": "again" })).await.is_ok()
+        {
+            reconnected = true;
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert!(reconnected, "expected the connection to be re-established");
+    assert_eq!(plugin.health().restarts, 1);
+}
+
+#[tokio::test]
+async fn test_websocket_plugin() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    tokio::spawn(serve_websocket(listener));
+
+    check_connection_plugin(StepflowPluginConfig {
+        transport: StepflowTransport::WebSocket {
+            url,
+            heartbeat_interval_ms: 1_000,
+            reconnect: RECONNECT,
+        },
+        blob_refs: false,
+        persist_logs: true,
+        batching: BatchConfig::default(),
+    })
+    .await;
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_unix_socket_plugin() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join("component.sock");
+    let listener = tokio::net::UnixListener::bind(&path).unwrap();
+    tokio::spawn(serve_unix(listener));
+
+    check_connection_plugin(StepflowPluginConfig {
+        transport: StepflowTransport::Unix {
+            path: path.to_string_lossy().into_owned(),
+            reconnect: RECONNECT,
+        },
+        blob_refs: false,
+        persist_logs: true,
+        batching: BatchConfig::default(),
+    })
+    .await;
+}
+
+/// A component server supporting batching, which returns the input of each
+/// execution along with the size of its batch, and sends a log message for
+/// each execution. An execution with `{"fail": true}` as input fails.
+async fn serve_batches(listener: TcpListener) {
+    loop {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+        while let Some(Ok(Message::Text(text))) = socket.next().await {
+            let message: Value = serde_json::from_str(&text).unwrap();
+            let id = message["id"].clone();
+            let mut replies = Vec::new();
+            match message["method"].as_str() {
+                Some("initialize") => replies.push(json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "result": {
+                        "server_protocol_version": 1,
+                        "server_capabilities": { "batching": true, "logging": true },
+                    },
+                })),
+                Some("components/execute_batch") => {
+                    let items = message["params"]["items"].as_array().unwrap();
+                    let mut results = Vec::new();
+                    for item in items {
+                        replies.push(json!({
+                            "jsonrpc": "2.0",
+                            "method": "log",
+                            "params": {
+                                "level": "info",
+                                "message": "executing item",
+                                "request_id": item["request_id"],
+                            },
+                        }));
+                        results.push(if item["input"]["fail"] == true {
+                            json!({
+                                "outcome": "failed",
+                                "error": { "code": 1, "message": "item failed" },
+                            })
+                        } else {
+                            json!({
+                                "outcome": "success",
+                                "result": { "input": item["input"], "batch_size": items.len() },
+                            })
+                        });
+                    }
+                    replies.push(json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "result": { "results": results },
+                    }));
+                }
+                _ => {}
+            }
+            for reply in replies {
+                socket.send(Message::Text(reply.to_string())).await.unwrap();
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_batched_executions() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    tokio::spawn(serve_batches(listener));
+
+    let plugin = StepflowPluginConfig {
+        transport: StepflowTransport::WebSocket {
+            url,
+            heartbeat_interval_ms: 1_000,
+            reconnect: RECONNECT,
+        },
+        blob_refs: false,
+        persist_logs: true,
+        batching: BatchConfig {
+            window_ms: 50,
+            max_size: 3,
+        },
+    }
+    .create_plugin("batching", Path::new("."))
+    .await
+    .unwrap();
+    let state_store: Arc<dyn StateStore> = Arc::new(InMemoryStateStore::new());
+    let context: Arc<dyn Context> = Arc::new(MockContext {
+        state_store: state_store.clone(),
+    });
+    plugin.init(&context).await.unwrap();
+
+    let flow = Arc::new(Flow::default());
+    let flow_id = BlobId::from_flow(&flow).unwrap();
+    let component = Component::from_string("/embed");
+    let run_id = Uuid::new_v4();
+    let inputs = [
+        json!({ "text": "a" }),
+        json!({ "fail": true }),
+        json!({ "text": "c" }),
+        json!({ "text": "d" }),
+    ];
+
+    // The first three executions fill a batch, and the last is sent alone
+    // once the window elapses.
+    let results = futures::future::join_all(inputs.iter().enumerate().map(|(step, input)| {
+        let context = ExecutionContext::for_step_with_flow(
+            context.clone(),
+            run_id,
+            format!("step{step}"),
+            flow.clone(),
+            flow_id.clone(),
+        );
+        plugin.execute(&component, context, ValueRef::new(input.clone()))
+    }))
+    .await;
+    let results: Vec<_> = results.into_iter().map(|result| result.unwrap()).collect();
+    assert_eq!(
+        results[0],
+        FlowResult::Success(ValueRef::new(
+            json!({ "input": { "text": "a" }, "batch_size": 3 })
+        ))
+    );
+    assert!(matches!(&results[1], FlowResult::Failed(error) if error.message == "item failed"));
+    assert_eq!(
+        results[2],
+        FlowResult::Success(ValueRef::new(
+            json!({ "input": { "text": "c" }, "batch_size": 3 })
+        ))
+    );
+    assert_eq!(
+        results[3],
+        FlowResult::Success(ValueRef::new(
+            json!({ "input": { "text": "d" }, "batch_size": 1 })
+        ))
+    );
+
+    // Log messages are attributed to the step of each item.
+    for step in 0..inputs.len() {
+        let logs = state_store
+            .get_step_logs(run_id, &format!("step{step}"))
+            .await
+            .unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].message, "executing item");
+    }
+}
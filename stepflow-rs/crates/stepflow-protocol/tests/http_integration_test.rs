@@ -25,7 +25,7 @@ use stepflow_core::{
     workflow::{Flow, ValueRef},
 };
 use stepflow_plugin::{Context, ExecutionContext, Plugin as _, PluginConfig as _, PluginError};
-use stepflow_protocol::{StepflowPluginConfig, StepflowTransport};
+use stepflow_protocol::{BatchConfig, StepflowPluginConfig, StepflowTransport};
 use stepflow_state::{InMemoryStateStore, StateStore};
 use tokio::process::Command;
 use tokio::time::{sleep, timeout};
@@ -79,10 +79,13 @@ async fn test_http_plugin_creation_failure() {
         transport: StepflowTransport::Http {
             url: "http://127.0.0.1:18080".to_string(),
         },
+        blob_refs: false,
+        persist_logs: false,
+        batching: BatchConfig::default(),
     };
 
     let plugin = config
-        .create_plugin(std::path::Path::new("."))
+        .create_plugin("http", std::path::Path::new("."))
         .await
         .expect("Should create HTTP plugin");
 
@@ -135,10 +138,13 @@ async fn test_http_protocol_integration() {
         transport: StepflowTransport::Http {
             url: "http://127.0.0.1:18081".to_string(),
         },
+        blob_refs: false,
+        persist_logs: false,
+        batching: BatchConfig::default(),
     };
 
     let plugin = config
-        .create_plugin(std::path::Path::new("."))
+        .create_plugin("http", std::path::Path::new("."))
         .await
         .expect("Should create HTTP plugin");
 
@@ -277,10 +283,13 @@ async fn test_http_plugin_lifecycle() {
         transport: StepflowTransport::Http {
             url: "http://127.0.0.1:18082".to_string(),
         },
+        blob_refs: false,
+        persist_logs: false,
+        batching: BatchConfig::default(),
     };
 
     let plugin = config
-        .create_plugin(std::path::Path::new("."))
+        .create_plugin("http", std::path::Path::new("."))
         .await
         .expect("Should create HTTP plugin");
 
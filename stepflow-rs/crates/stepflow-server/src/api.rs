@@ -17,19 +17,24 @@ use utoipa::OpenApi;
 use utoipa_axum::router::OpenApiRouter;
 use utoipa_axum::routes;
 
+mod batches;
+mod blobs;
 mod components;
 mod debug;
 mod flows;
 mod health;
+mod labels;
 mod runs;
 
+const BLOB_TAG: &str = "Blob";
 const COMPONENT_TAG: &str = "Component";
 const FLOW_TAG: &str = "Flow";
 const RUN_TAG: &str = "Run";
 const DEBUG_TAG: &str = "Debug";
 
+pub use batches::{BatchResponse, BatchRunStatus, CreateBatchRequest};
 pub use flows::{StoreFlowRequest, StoreFlowResponse};
-pub use runs::{CreateRunRequest, CreateRunResponse};
+pub use runs::{CreateRunRequest, CreateRunResponse, RunMode};
 
 #[derive(OpenApi)]
 #[openapi(
@@ -39,6 +44,7 @@ pub use runs::{CreateRunRequest, CreateRunResponse};
         version = env!("CARGO_PKG_VERSION")
     ),
     tags(
+        (name = BLOB_TAG, description = "Blob API endpoints"),
         (name = COMPONENT_TAG, description = "Component API endpoints"),
         (name = FLOW_TAG, description = "Flow API endpoints"),
         (name = RUN_TAG, description = "Run API endpoints"),
@@ -46,20 +52,35 @@ pub use runs::{CreateRunRequest, CreateRunResponse};
     ),
     paths(
         health::health_check,
+        blobs::get_blob,
         components::list_components,
         debug::debug_execute_step,
         debug::debug_continue,
         debug::debug_get_runnable,
         runs::create_run,
+        batches::create_batch,
+        batches::get_batch,
         runs::get_run,
         runs::get_run_flow,
+        runs::get_run_tree,
         runs::list_runs,
         runs::get_run_steps,
+        runs::get_step_logs,
         runs::cancel_run,
         runs::delete_run,
+        runs::delete_runs,
         flows::store_flow,
         flows::get_flow,
         flows::delete_flow,
+        labels::list_flow_names,
+        labels::list_flow_versions,
+        labels::list_flow_labels,
+        labels::get_flow_label,
+        labels::set_flow_label,
+        labels::delete_flow_label,
+        labels::get_flow_label_history,
+        labels::rollback_flow_label,
+        labels::create_labeled_run,
     ),
     components(schemas(
         components::ListComponentsResponse,
@@ -68,17 +89,44 @@ pub use runs::{CreateRunRequest, CreateRunResponse};
         debug::DebugStepResponse,
         debug::DebugRunnableResponse,
         health::HealthResponse,
+        stepflow_plugin::PluginHealth,
+        stepflow_plugin::PluginStatus,
         runs::CreateRunRequest,
         runs::CreateRunResponse,
+        runs::RunMode,
+        batches::CreateBatchRequest,
+        batches::BatchResponse,
+        batches::BatchRunStatus,
         runs::ListRunsResponse,
+        runs::ListRunsQuery,
+        runs::DeleteRunQuery,
+        runs::DeleteRunsQuery,
+        runs::DeleteRunsResponse,
         stepflow_state::RunSummary,
         stepflow_state::RunDetails,
         runs::StepRunResponse,
         runs::ListStepRunsResponse,
+        runs::ListStepLogsResponse,
+        stepflow_state::StepLog,
+        stepflow_state::LogLevel,
+        stepflow_state::StepProgress,
         runs::RunFlowResponse,
+        runs::RunTreeNode,
         flows::StoreFlowRequest,
         flows::StoreFlowResponse,
         flows::FlowResponse,
+        flows::DeleteFlowQuery,
+        labels::ListFlowNamesResponse,
+        labels::FlowVersion,
+        labels::ListFlowVersionsResponse,
+        labels::FlowLabelResponse,
+        labels::ListFlowLabelsResponse,
+        labels::SetFlowLabelRequest,
+        labels::DeleteFlowLabelQuery,
+        labels::FlowLabelHistoryEntry,
+        labels::ListFlowLabelHistoryResponse,
+        labels::RollbackFlowLabelRequest,
+        labels::CreateLabeledRunRequest,
         stepflow_analysis::AnalysisResult,
         stepflow_analysis::Diagnostic,
         stepflow_analysis::DiagnosticLevel,
@@ -94,18 +142,33 @@ struct StepflowApi;
 pub fn create_api_router() -> OpenApiRouter<Arc<StepflowExecutor>> {
     OpenApiRouter::with_openapi(StepflowApi::openapi())
         .routes(routes!(health::health_check))
+        .routes(routes!(blobs::get_blob))
         .routes(routes!(components::list_components))
         .routes(routes!(debug::debug_execute_step))
         .routes(routes!(debug::debug_continue))
         .routes(routes!(debug::debug_get_runnable))
         .routes(routes!(runs::create_run))
+        .routes(routes!(batches::create_batch))
+        .routes(routes!(batches::get_batch))
         .routes(routes!(runs::get_run))
         .routes(routes!(runs::get_run_flow))
+        .routes(routes!(runs::get_run_tree))
         .routes(routes!(runs::list_runs))
         .routes(routes!(runs::get_run_steps))
+        .routes(routes!(runs::get_step_logs))
         .routes(routes!(runs::cancel_run))
         .routes(routes!(runs::delete_run))
+        .routes(routes!(runs::delete_runs))
         .routes(routes!(flows::store_flow))
         .routes(routes!(flows::get_flow))
         .routes(routes!(flows::delete_flow))
+        .routes(routes!(labels::list_flow_names))
+        .routes(routes!(labels::list_flow_versions))
+        .routes(routes!(labels::list_flow_labels))
+        .routes(routes!(labels::get_flow_label))
+        .routes(routes!(labels::set_flow_label))
+        .routes(routes!(labels::delete_flow_label))
+        .routes(routes!(labels::get_flow_label_history))
+        .routes(routes!(labels::rollback_flow_label))
+        .routes(routes!(labels::create_labeled_run))
 }
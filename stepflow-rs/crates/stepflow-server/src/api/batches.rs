@@ -0,0 +1,315 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+use axum::{
+    Extension,
+    extract::{Path, State},
+    http::{StatusCode, header},
+    response::{IntoResponse as _, Json, Response},
+};
+use futures::StreamExt as _;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use stepflow_core::{BlobId, status::ExecutionStatus, workflow::ValueRef};
+use stepflow_execution::StepflowExecutor;
+use stepflow_state::{BatchItem, BatchRecord, CreateRunParams, DEFAULT_NAMESPACE, RunFilters};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::api::runs::{RunWait, start_run};
+use crate::auth::Identity;
+use crate::error::{ErrorResponse, ServerError};
+use crate::namespace::Namespace;
+use crate::webhooks::WebhookDispatcher;
+
+/// Number of runs of a batch executed at once, unless requested otherwise.
+const DEFAULT_BATCH_CONCURRENCY: usize = 8;
+
+/// Request to run a flow once for each of several inputs
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateBatchRequest {
+    /// The flow hash to execute
+    pub flow_id: BlobId,
+    /// Input data for each run of the flow
+    pub inputs: Vec<ValueRef>,
+    /// Maximum number of runs to execute at once
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrency: Option<usize>,
+}
+
+/// Status of a batch and each of its runs
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchResponse {
+    /// The batch ID, recorded on each of its runs
+    pub batch_id: Uuid,
+    /// The flow executed for each input
+    pub flow_id: BlobId,
+    /// `running` until every run has finished, then `completed` if every run
+    /// completed and `failed` otherwise
+    pub status: ExecutionStatus,
+    /// Number of runs in the batch
+    pub total: usize,
+    /// Number of runs not yet finished, including runs not yet started
+    pub running: usize,
+    /// Number of runs that completed
+    pub completed: usize,
+    /// Number of runs that failed, were cancelled, or could not be started
+    pub failed: usize,
+    /// The runs, in the order of their inputs
+    pub runs: Vec<BatchRunStatus>,
+}
+
+/// Status of one run of a batch
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchRunStatus {
+    /// The ID of the run
+    pub run_id: Uuid,
+    /// The status of the run, `running` if it has not been started yet
+    pub status: ExecutionStatus,
+    /// Why the run could not be started, if it could not
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl BatchResponse {
+    fn new(batch: &BatchRecord, statuses: &HashMap<Uuid, ExecutionStatus>) -> Self {
+        let runs: Vec<_> = batch
+            .items
+            .iter()
+            .map(|item| BatchRunStatus {
+                run_id: item.run_id,
+                status: match (&item.error, statuses.get(&item.run_id)) {
+                    (Some(_), _) => ExecutionStatus::Failed,
+                    (None, Some(status)) => *status,
+                    (None, None) => ExecutionStatus::Running,
+                },
+                error: item.error.clone(),
+            })
+            .collect();
+
+        let count = |status| runs.iter().filter(|run| run.status == status).count();
+        let completed = count(ExecutionStatus::Completed);
+        let running = count(ExecutionStatus::Running) + count(ExecutionStatus::Paused);
+        let status = if running > 0 {
+            ExecutionStatus::Running
+        } else if completed == runs.len() {
+            ExecutionStatus::Completed
+        } else {
+            ExecutionStatus::Failed
+        };
+        Self {
+            batch_id: batch.batch_id,
+            flow_id: batch.flow_id.clone(),
+            status,
+            total: runs.len(),
+            running,
+            completed,
+            failed: runs.len() - running - completed,
+            runs,
+        }
+    }
+}
+
+/// Create a run of a flow for each input, executing them in the background
+#[utoipa::path(
+    post,
+    path = "/runs/batch",
+    request_body = CreateBatchRequest,
+    responses(
+        (status = 202, description = "Batch created, and its runs started in the background", body = BatchResponse,
+            headers(("Location" = String, description = "URL to poll for the batch"))),
+        (status = 400, description = "Invalid request"),
+        (status = 404, description = "Flow not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = crate::api::RUN_TAG,
+)]
+pub async fn create_batch(
+    State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
+    identity: Option<Extension<Identity>>,
+    Extension(webhooks): Extension<Arc<WebhookDispatcher>>,
+    Json(req): Json<CreateBatchRequest>,
+) -> Result<Response, ErrorResponse> {
+    let state_store = executor.state_store();
+    let flow = state_store
+        .get_namespaced_flow(&namespace, &req.flow_id)
+        .await?
+        .ok_or_else(|| error_stack::report!(ServerError::WorkflowNotFound(req.flow_id.clone())))?;
+
+    // Record the batch with the IDs of its runs before starting any of them,
+    // so the batch can be polled right away.
+    let batch = BatchRecord {
+        batch_id: Uuid::new_v4(),
+        flow_id: req.flow_id.clone(),
+        namespace: namespace.0.clone(),
+        created_by: identity.map(|Extension(identity)| identity.subject),
+        created_at: chrono::Utc::now(),
+        items: req
+            .inputs
+            .iter()
+            .map(|_| BatchItem {
+                run_id: Uuid::new_v4(),
+                error: None,
+            })
+            .collect(),
+    };
+    state_store.put_batch(batch.clone()).await?;
+
+    let max_concurrency = req
+        .max_concurrency
+        .unwrap_or(DEFAULT_BATCH_CONCURRENCY)
+        .max(1);
+    let response = BatchResponse::new(&batch, &HashMap::new());
+    tokio::spawn(run_batch(
+        executor,
+        webhooks,
+        flow,
+        batch,
+        req.inputs,
+        max_concurrency,
+    ));
+
+    let location = if namespace.0 == DEFAULT_NAMESPACE {
+        format!("/api/v1/runs/batch/{}", response.batch_id)
+    } else {
+        format!(
+            "/api/v1/namespaces/{}/runs/batch/{}",
+            namespace.0, response.batch_id
+        )
+    };
+    Ok((
+        StatusCode::ACCEPTED,
+        [(header::LOCATION, location)],
+        Json(response),
+    )
+        .into_response())
+}
+
+/// Execute the runs of a recorded batch, at most `max_concurrency` at once.
+///
+/// Runs that could not be started are recorded as failed items of the batch;
+/// the remaining runs are still executed.
+async fn run_batch(
+    executor: Arc<StepflowExecutor>,
+    webhooks: Arc<WebhookDispatcher>,
+    flow: Arc<stepflow_core::workflow::Flow>,
+    mut batch: BatchRecord,
+    inputs: Vec<ValueRef>,
+    max_concurrency: usize,
+) {
+    let state_store = executor.state_store();
+    let runs: Vec<_> = batch
+        .items
+        .iter()
+        .zip(inputs)
+        .map(|(item, input)| CreateRunParams {
+            namespace: batch.namespace.clone(),
+            batch_id: Some(batch.batch_id),
+            created_by: batch.created_by.clone(),
+            ..CreateRunParams::new(item.run_id, batch.flow_id.clone(), input)
+        })
+        .collect();
+    let mut results = futures::stream::iter(runs)
+        .map(|params| {
+            let executor = executor.clone();
+            let webhooks = webhooks.clone();
+            let flow = flow.clone();
+            async move { start_run(&executor, &webhooks, flow, params, RunWait::Finished).await }
+        })
+        .buffered(max_concurrency)
+        .enumerate();
+
+    let mut errors = Vec::new();
+    while let Some((index, result)) = results.next().await {
+        if let Err(error) = result {
+            errors.push((index, error));
+        }
+    }
+    let mut failed_to_start = false;
+    for (index, error) in errors {
+        let run_id = batch.items[index].run_id;
+        // Runs that were recorded failed while executing, and report their
+        // own status; only runs that were never recorded need an error.
+        match state_store.get_run(run_id).await {
+            Ok(None) => {
+                batch.items[index].error = Some(error.message);
+                failed_to_start = true;
+            }
+            Ok(Some(_)) => {
+                tracing::warn!(
+                    %run_id,
+                    batch_id = %batch.batch_id,
+                    message = %error.message,
+                    "Batch run failed"
+                );
+            }
+            Err(error) => {
+                tracing::error!(%run_id, ?error, "Failed to look up run of batch");
+            }
+        }
+    }
+
+    if failed_to_start && let Err(error) = state_store.put_batch(batch.clone()).await {
+        tracing::error!(
+            batch_id = %batch.batch_id,
+            ?error,
+            "Failed to record batch runs that could not be started"
+        );
+    }
+}
+
+/// Get the status of a batch and each of its runs
+#[utoipa::path(
+    get,
+    path = "/runs/batch/{batch_id}",
+    params(
+        ("batch_id" = Uuid, Path, description = "Batch ID (UUID)")
+    ),
+    responses(
+        (status = 200, description = "Batch status retrieved successfully", body = BatchResponse),
+        (status = 400, description = "Invalid batch ID format"),
+        (status = 404, description = "Batch not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = crate::api::RUN_TAG,
+)]
+pub async fn get_batch(
+    State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
+    Path(batch_id): Path<Uuid>,
+) -> Result<Json<BatchResponse>, ErrorResponse> {
+    let state_store = executor.state_store();
+    let batch = state_store
+        .get_batch(batch_id)
+        .await?
+        .filter(|batch| batch.namespace == namespace.0)
+        .ok_or_else(|| error_stack::report!(ServerError::BatchNotFound(batch_id)))?;
+
+    let filters = RunFilters {
+        namespace: Some(namespace.0.clone()),
+        batch_id: Some(batch_id),
+        ..Default::default()
+    };
+    let statuses = state_store
+        .list_runs(&filters)
+        .await?
+        .into_iter()
+        .map(|run| (run.run_id, run.status))
+        .collect();
+
+    Ok(Json(BatchResponse::new(&batch, &statuses)))
+}
@@ -0,0 +1,94 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse as _, Response},
+};
+use std::sync::Arc;
+use stepflow_core::BlobId;
+use stepflow_execution::StepflowExecutor;
+use stepflow_state::StateError;
+
+use crate::error::{ErrorResponse, ServerError};
+use crate::namespace::Namespace;
+
+/// Blobs are content-addressed, so a response for a given ID never changes.
+const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// Get the raw content of a blob
+///
+/// Binary blobs are returned with their stored content type. JSON data and flow
+/// blobs are returned as `application/json`. Only blobs readable in the
+/// namespace are returned: flows stored in it and blobs stored by its runs.
+#[utoipa::path(
+    get,
+    path = "/blobs/{blob_id}",
+    params(
+        ("blob_id" = String, Path, description = "Blob ID to retrieve")
+    ),
+    responses(
+        (status = 200, description = "Blob content", content_type = "application/octet-stream", body = Vec<u8>),
+        (status = 304, description = "Blob content has not changed"),
+        (status = 404, description = "Blob not found")
+    ),
+    tag = crate::api::BLOB_TAG,
+)]
+pub async fn get_blob(
+    State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
+    Path(blob_id): Path<BlobId>,
+    headers: HeaderMap,
+) -> Result<Response, ErrorResponse> {
+    let state_store = executor.state_store();
+    if !state_store.blob_in_namespace(&namespace, &blob_id).await? {
+        return Err(error_stack::report!(ServerError::BlobNotFound(blob_id)).into());
+    }
+
+    let etag = format!("\"{blob_id}\"");
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.split(',').any(|tag| tag.trim() == etag));
+
+    let blob_data =
+        state_store
+            .get_blob(&blob_id)
+            .await
+            .map_err(|e| match e.current_context() {
+                StateError::BlobNotFound { .. } => ErrorResponse::from(
+                    e.change_context(ServerError::BlobNotFound(blob_id.clone())),
+                ),
+                _ => ErrorResponse::from(e),
+            })?;
+
+    let mut response = if not_modified {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        blob_data.value.to_bytes().into_owned().into_response()
+    };
+
+    let response_headers = response.headers_mut();
+    if !not_modified {
+        let content_type = HeaderValue::from_str(blob_data.content_type())
+            .unwrap_or(HeaderValue::from_static("application/octet-stream"));
+        response_headers.insert(header::CONTENT_TYPE, content_type);
+    }
+    response_headers.insert(header::ETAG, HeaderValue::from_str(&etag).unwrap());
+    response_headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static(IMMUTABLE_CACHE_CONTROL),
+    );
+
+    Ok(response)
+}
@@ -22,6 +22,7 @@ use stepflow_plugin::Plugin as _;
 use utoipa::{IntoParams, ToSchema};
 
 use crate::error::ErrorResponse;
+use crate::namespace::Namespace;
 
 /// Response for listing components
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -57,6 +58,7 @@ fn default_include_schemas() -> bool {
 )]
 pub async fn list_components(
     State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
     Query(query): Query<ListComponentsQuery>,
 ) -> Result<Json<ListComponentsResponse>, ErrorResponse> {
     let include_schemas = query.include_schemas;
@@ -64,8 +66,8 @@ pub async fn list_components(
     // Get all registered plugins and query their components
     let mut all_components = Vec::new();
 
-    // Get the list of plugins from the executor
-    for plugin in executor.list_plugins().await {
+    // Get the list of plugins available to the namespace
+    for plugin in executor.plugin_router_for(&namespace).plugins() {
         // List components available from this plugin
         let mut components = plugin.list_components().await?;
         if !include_schemas {
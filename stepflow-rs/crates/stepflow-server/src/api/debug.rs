@@ -11,6 +11,7 @@
 // the License.
 
 use axum::{
+    Extension,
     extract::{Path, State},
     response::Json,
 };
@@ -23,7 +24,10 @@ use stepflow_execution::StepflowExecutor;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::api::runs::find_run;
 use crate::error::{ErrorResponse, ServerError};
+use crate::namespace::Namespace;
+use crate::webhooks::WebhookDispatcher;
 
 /// Request to execute specific steps in debug mode
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -67,9 +71,13 @@ pub struct DebugRunnableResponse {
 )]
 pub async fn debug_execute_step(
     State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
     Path(run_id): Path<Uuid>,
     Json(req): Json<DebugStepRequest>,
 ) -> Result<Json<DebugStepResponse>, ErrorResponse> {
+    // Only runs in the namespace can be debugged
+    find_run(&executor.state_store(), &namespace, run_id).await?;
+
     // Get the debug session for this run
     let mut debug_session = executor
         .debug_session(run_id)
@@ -105,8 +113,13 @@ pub async fn debug_execute_step(
 )]
 pub async fn debug_continue(
     State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
+    Extension(webhooks): Extension<Arc<WebhookDispatcher>>,
     Path(run_id): Path<Uuid>,
 ) -> Result<Json<super::runs::CreateRunResponse>, ErrorResponse> {
+    // Only runs in the namespace can be debugged
+    find_run(&executor.state_store(), &namespace, run_id).await?;
+
     // Get the debug session for this run
     let mut debug_session = executor
         .debug_session(run_id)
@@ -124,6 +137,10 @@ pub async fn debug_continue(
     };
 
     state_store.update_run_status(run_id, status, None).await?;
+    state_store
+        .record_run_callback(run_id, Some(final_result.clone()))
+        .await?;
+    webhooks.wake();
 
     Ok(Json(super::runs::CreateRunResponse {
         run_id,
@@ -150,8 +167,12 @@ pub async fn debug_continue(
 )]
 pub async fn debug_get_runnable(
     State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
     Path(run_id): Path<Uuid>,
 ) -> Result<Json<DebugRunnableResponse>, ErrorResponse> {
+    // Only runs in the namespace can be debugged
+    find_run(&executor.state_store(), &namespace, run_id).await?;
+
     // Get the debug session for this run
     let debug_session = executor
         .debug_session(run_id)
@@ -11,20 +11,19 @@
 // the License.
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     response::Json,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use stepflow_analysis::{AnalysisResult, FlowAnalysis, analyze_flow_dependencies};
-use stepflow_core::{
-    BlobId, BlobType,
-    workflow::{Flow, ValueRef},
-};
+use stepflow_analysis::{AnalysisResult, FlowAnalysis};
+use stepflow_core::{BlobId, workflow::Flow};
 use stepflow_execution::StepflowExecutor;
-use utoipa::ToSchema;
+use stepflow_state::StateError;
+use utoipa::{IntoParams, ToSchema};
 
-use crate::error::ErrorResponse;
+use crate::error::{ErrorResponse, ServerError};
+use crate::namespace::Namespace;
 
 /// Request to store a flow
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -61,6 +60,15 @@ pub struct FlowResponse {
     pub analysis: FlowAnalysis,
 }
 
+/// Query parameters for deleting a flow
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteFlowQuery {
+    /// Whether to delete the flow's finished runs along with it (default: false)
+    #[serde(default)]
+    pub delete_runs: bool,
+}
+
 /// Store a flow and return its hash
 #[utoipa::path(
     post,
@@ -74,24 +82,26 @@ pub struct FlowResponse {
 )]
 pub async fn store_flow(
     State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
     Json(req): Json<StoreFlowRequest>,
 ) -> Result<Json<StoreFlowResponse>, ErrorResponse> {
     let flow = req.flow;
 
     // First validate the workflow - we need a temporary ID for analysis
     let temp_flow_id = BlobId::from_flow(flow.as_ref()).unwrap();
-    let analysis_result = analyze_flow_dependencies(flow.clone(), temp_flow_id.clone())?;
+    let analysis_result = executor
+        .analysis_cache()
+        .get_or_analyze(&flow, &temp_flow_id)?;
 
     // Determine if we can store the flow (no fatal diagnostics)
     let stored_flow_id = if analysis_result.has_fatal_diagnostics() {
         // Validation failed: don't store the flow
         None
     } else {
-        // Store the flow as a blob
-        let state_store = executor.state_store();
-        let flow_data = ValueRef::new(serde_json::to_value(flow.as_ref()).unwrap());
-        let flow_id = state_store
-            .put_blob(flow_data, BlobType::Flow)
+        // Store the flow in the namespace
+        let flow_id = executor
+            .state_store()
+            .store_flow(&namespace, flow.clone())
             .await
             .map_err(|_| ErrorResponse {
                 code: axum::http::StatusCode::INTERNAL_SERVER_ERROR,
@@ -102,7 +112,7 @@ pub async fn store_flow(
 
     Ok(Json(StoreFlowResponse {
         flow_id: stored_flow_id,
-        analysis_result,
+        analysis_result: analysis_result.as_ref().clone(),
     }))
 }
 
@@ -121,38 +131,18 @@ pub async fn store_flow(
 )]
 pub async fn get_flow(
     State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
     Path(flow_id): Path<BlobId>,
 ) -> Result<Json<FlowResponse>, ErrorResponse> {
-    let state_store = executor.state_store();
-
-    // Retrieve the flow from the blob store
-    let blob_data = state_store
-        .get_blob(&flow_id)
-        .await
-        .map_err(|_| ErrorResponse {
-            code: axum::http::StatusCode::NOT_FOUND,
-            message: "Flow not found".to_string(),
-        })?;
-
-    // Check if it's a flow blob and deserialize
-    if blob_data.blob_type() != BlobType::Flow {
-        return Err(ErrorResponse {
-            code: axum::http::StatusCode::BAD_REQUEST,
-            message: "Blob is not a flow".to_string(),
-        });
-    }
+    // Flows stored in other namespaces are reported as not found.
+    let flow = executor
+        .state_store()
+        .get_namespaced_flow(&namespace, &flow_id)
+        .await?
+        .ok_or_else(|| error_stack::report!(ServerError::WorkflowNotFound(flow_id.clone())))?;
 
-    let flow: Flow =
-        serde_json::from_value(blob_data.data().as_ref().clone()).map_err(|_| ErrorResponse {
-            code: axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-            message: "Failed to deserialize flow".to_string(),
-        })?;
-
-    let flow = Arc::new(flow);
-
-    // Generate analysis for the flow.
-    // TODO: Cache this to avoid re-analysis.
-    let analysis_result = analyze_flow_dependencies(flow.clone(), flow_id.clone())?;
+    // Analysis is computed once per flow and cached by the executor.
+    let analysis_result = executor.analysis_cache().get_or_analyze(&flow, &flow_id)?;
 
     let analysis = match &analysis_result.analysis {
         Some(analysis) => analysis.clone(),
@@ -177,11 +167,16 @@ pub async fn get_flow(
 }
 
 /// Delete a flow by ID
+///
+/// Removes the flow from the namespace along with the namespace's labels
+/// pointing at it. Deletion is refused while the flow has running or paused
+/// runs in the namespace, or has finished runs and `deleteRuns` is not set.
 #[utoipa::path(
     delete,
     path = "/flows/{flow_id}",
     params(
-        ("flow_id" = String, Path, description = "Flow ID to delete")
+        ("flow_id" = String, Path, description = "Flow ID to delete"),
+        DeleteFlowQuery
     ),
     responses(
         (status = 204, description = "Flow deleted successfully"),
@@ -191,10 +186,28 @@ pub async fn get_flow(
     tag = crate::api::FLOW_TAG,
 )]
 pub async fn delete_flow(
-    State(_executor): State<Arc<StepflowExecutor>>,
-    Path(_flow_id): Path<BlobId>,
+    State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
+    Path(flow_id): Path<BlobId>,
+    Query(query): Query<DeleteFlowQuery>,
 ) -> Result<(), ErrorResponse> {
-    // TODO: Implement proper flow deletion with run checks
-    // For now, just return success since blobs are content-addressed and can't be easily deleted
+    let deleted = executor
+        .state_store()
+        .delete_flow(&namespace, &flow_id, query.delete_runs)
+        .await
+        .map_err(|report| match report.current_context() {
+            StateError::FlowHasActiveRuns { .. } => report
+                .change_context(ServerError::FlowHasActiveRuns(flow_id.clone()))
+                .into(),
+            StateError::FlowHasRuns { .. } => report
+                .change_context(ServerError::FlowHasRuns(flow_id.clone()))
+                .into(),
+            _ => ErrorResponse::from(report),
+        })?;
+
+    if !deleted {
+        return Err(error_stack::report!(ServerError::WorkflowNotFound(flow_id)).into());
+    }
+    executor.analysis_cache().remove(&flow_id);
     Ok(())
 }
@@ -10,20 +10,28 @@
 // or implied. See the License for the specific language governing permissions and limitations under
 // the License.
 
-use axum::response::Json;
+use std::sync::Arc;
+
+use axum::{extract::State, http::StatusCode, response::Json};
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
+use stepflow_execution::StepflowExecutor;
+use stepflow_plugin::{Plugin as _, PluginHealth, PluginStatus};
 use utoipa::ToSchema;
 
 /// Health check response
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct HealthResponse {
-    /// Service status
+    /// Service status: `healthy`, `degraded` while a plugin is restarting, or
+    /// `unhealthy` if a plugin failed and will not be restarted
     pub status: String,
     /// Timestamp when health was checked (RFC3339 format)
     pub timestamp: String,
     /// Service version
     pub version: String,
+    /// Health of each plugin, by name
+    pub plugins: IndexMap<String, PluginHealth>,
 }
 
 /// Check service health
@@ -31,13 +39,33 @@ pub struct HealthResponse {
     get,
     path = "/health",
     responses(
-        (status = 200, description = "Service is healthy", body = HealthResponse)
+        (status = 200, description = "Service is healthy or recovering", body = HealthResponse),
+        (status = 503, description = "A plugin failed and will not be restarted", body = HealthResponse)
     )
 )]
-pub async fn health_check() -> Json<HealthResponse> {
-    Json(HealthResponse {
-        status: "healthy".to_string(),
+pub async fn health_check(
+    State(executor): State<Arc<StepflowExecutor>>,
+) -> (StatusCode, Json<HealthResponse>) {
+    let plugins: IndexMap<String, PluginHealth> = executor
+        .plugin_router()
+        .plugins_with_names()
+        .map(|(name, plugin)| (name.to_string(), plugin.health()))
+        .collect();
+
+    let statuses = || plugins.values().map(|health| health.status);
+    let (code, status) = if statuses().any(|status| status == PluginStatus::Failed) {
+        (StatusCode::SERVICE_UNAVAILABLE, "unhealthy")
+    } else if statuses().any(|status| status == PluginStatus::Restarting) {
+        (StatusCode::OK, "degraded")
+    } else {
+        (StatusCode::OK, "healthy")
+    };
+
+    let response = HealthResponse {
+        status: status.to_string(),
         timestamp: chrono::Utc::now().to_rfc3339(),
         version: env!("CARGO_PKG_VERSION").to_string(),
-    })
+        plugins,
+    };
+    (code, Json(response))
 }
@@ -0,0 +1,585 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+use axum::{
+    Extension,
+    extract::{Path, Query, State},
+    response::{Json, Response},
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use stepflow_core::{BlobId, workflow::ValueRef};
+use stepflow_execution::StepflowExecutor;
+use stepflow_state::{
+    CreateRunParams, RunCallback, WorkflowLabelHistoryEntry, WorkflowLabelMetadata,
+};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::api::runs::{CreateRunResponse, RunMode, RunWait, create_run_response, start_run};
+use crate::auth::Identity;
+use crate::error::{ErrorResponse, ServerError};
+use crate::namespace::Namespace;
+use crate::webhooks::WebhookDispatcher;
+
+/// Actor recorded for label changes that do not name one.
+const DEFAULT_ACTOR: &str = "anonymous";
+
+/// The actor recorded for a label change.
+///
+/// An authenticated caller is always recorded as themselves; otherwise the
+/// actor named in the request is used.
+fn change_actor(identity: Option<Extension<Identity>>, actor: Option<String>) -> String {
+    match identity {
+        Some(Extension(identity)) => identity.subject,
+        None => actor.unwrap_or_else(|| DEFAULT_ACTOR.to_string()),
+    }
+}
+
+/// Response for listing flow names
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListFlowNamesResponse {
+    /// Names of all stored or labeled flows
+    pub names: Vec<String>,
+}
+
+/// A stored version of a named flow
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FlowVersion {
+    /// The flow ID
+    pub flow_id: BlobId,
+    /// When this version was stored
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Response for listing the versions of a named flow
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListFlowVersionsResponse {
+    /// Versions of the flow, newest first
+    pub versions: Vec<FlowVersion>,
+}
+
+/// A label pointing at a version of a named flow
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FlowLabelResponse {
+    /// The flow name
+    pub name: String,
+    /// The label (like "production" or "staging")
+    pub label: String,
+    /// The flow ID the label points at
+    pub flow_id: BlobId,
+    /// When the label was created
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// When the label was last moved
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<WorkflowLabelMetadata> for FlowLabelResponse {
+    fn from(label: WorkflowLabelMetadata) -> Self {
+        Self {
+            name: label.name,
+            label: label.label,
+            flow_id: label.flow_id,
+            created_at: label.created_at,
+            updated_at: label.updated_at,
+        }
+    }
+}
+
+/// Response for listing the labels of a named flow
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListFlowLabelsResponse {
+    /// Labels of the flow
+    pub labels: Vec<FlowLabelResponse>,
+}
+
+/// Request to set or move a label
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SetFlowLabelRequest {
+    /// The flow ID the label should point at
+    pub flow_id: BlobId,
+    /// Who is making the change, if unauthenticated (default: "anonymous")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+    /// Why the change is being made
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// Query parameters for deleting a label
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteFlowLabelQuery {
+    /// Who is making the change, if unauthenticated (default: "anonymous")
+    pub actor: Option<String>,
+    /// Why the change is being made
+    pub comment: Option<String>,
+}
+
+/// A recorded change to a label
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FlowLabelHistoryEntry {
+    /// The flow ID the label pointed at after the change (absent if it was deleted)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flow_id: Option<BlobId>,
+    /// The flow ID the label pointed at before the change (absent if it was created)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_flow_id: Option<BlobId>,
+    /// Who made the change
+    pub actor: String,
+    /// Why the change was made
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// When the change was made
+    pub changed_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<WorkflowLabelHistoryEntry> for FlowLabelHistoryEntry {
+    fn from(entry: WorkflowLabelHistoryEntry) -> Self {
+        Self {
+            flow_id: entry.flow_id,
+            previous_flow_id: entry.previous_flow_id,
+            actor: entry.actor,
+            comment: entry.comment,
+            changed_at: entry.changed_at,
+        }
+    }
+}
+
+/// Response for listing the history of a label
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListFlowLabelHistoryResponse {
+    /// Changes to the label, newest first
+    pub history: Vec<FlowLabelHistoryEntry>,
+}
+
+/// Request to move a label back to an earlier version
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RollbackFlowLabelRequest {
+    /// The earlier flow ID to move the label to (default: the version before the latest change)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flow_id: Option<BlobId>,
+    /// Who is making the change, if unauthenticated (default: "anonymous")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+    /// Why the change is being made
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// Request to run the flow a label points at
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateLabeledRunRequest {
+    /// Input data for the flow
+    pub input: ValueRef,
+    /// Whether to run in debug mode (pauses execution for step-by-step control)
+    #[serde(default)]
+    pub debug: bool,
+    /// Callback notified when the run finishes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub callback: Option<RunCallback>,
+    /// Whether to wait for the run to finish before responding
+    #[serde(default)]
+    pub mode: RunMode,
+    /// How long to wait for the run in `wait` mode, in milliseconds (default: 30000)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+}
+
+/// List the names of all flows
+#[utoipa::path(
+    get,
+    path = "/flows",
+    responses(
+        (status = 200, description = "Flow names listed successfully", body = ListFlowNamesResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = crate::api::FLOW_TAG,
+)]
+pub async fn list_flow_names(
+    State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
+) -> Result<Json<ListFlowNamesResponse>, ErrorResponse> {
+    let names = executor.state_store().list_flow_names(&namespace).await?;
+    Ok(Json(ListFlowNamesResponse { names }))
+}
+
+/// List the stored versions of a named flow
+#[utoipa::path(
+    get,
+    path = "/flows/{name}/versions",
+    params(
+        ("name" = String, Path, description = "Flow name")
+    ),
+    responses(
+        (status = 200, description = "Flow versions listed successfully", body = ListFlowVersionsResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = crate::api::FLOW_TAG,
+)]
+pub async fn list_flow_versions(
+    State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
+    Path(name): Path<String>,
+) -> Result<Json<ListFlowVersionsResponse>, ErrorResponse> {
+    let versions = executor
+        .state_store()
+        .get_flows(&namespace, &name)
+        .await?
+        .into_iter()
+        .map(|(flow_id, created_at)| FlowVersion {
+            flow_id,
+            created_at,
+        })
+        .collect();
+    Ok(Json(ListFlowVersionsResponse { versions }))
+}
+
+/// List the labels of a named flow
+#[utoipa::path(
+    get,
+    path = "/flows/{name}/labels",
+    params(
+        ("name" = String, Path, description = "Flow name")
+    ),
+    responses(
+        (status = 200, description = "Flow labels listed successfully", body = ListFlowLabelsResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = crate::api::FLOW_TAG,
+)]
+pub async fn list_flow_labels(
+    State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
+    Path(name): Path<String>,
+) -> Result<Json<ListFlowLabelsResponse>, ErrorResponse> {
+    let labels = executor
+        .state_store()
+        .list_labels_for_name(&namespace, &name)
+        .await?
+        .into_iter()
+        .map(FlowLabelResponse::from)
+        .collect();
+    Ok(Json(ListFlowLabelsResponse { labels }))
+}
+
+/// Get the flow version a label points at
+#[utoipa::path(
+    get,
+    path = "/flows/{name}/labels/{label}",
+    params(
+        ("name" = String, Path, description = "Flow name"),
+        ("label" = String, Path, description = "Label name")
+    ),
+    responses(
+        (status = 200, description = "Flow label retrieved successfully", body = FlowLabelResponse),
+        (status = 404, description = "Label not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = crate::api::FLOW_TAG,
+)]
+pub async fn get_flow_label(
+    State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
+    Path((name, label)): Path<(String, String)>,
+) -> Result<Json<FlowLabelResponse>, ErrorResponse> {
+    let label = find_label(&executor, &namespace, name, label).await?;
+    Ok(Json(label.into()))
+}
+
+/// Set a label to point at a version of the named flow
+///
+/// Creates the label if it does not exist, or moves it otherwise.
+#[utoipa::path(
+    put,
+    path = "/flows/{name}/labels/{label}",
+    params(
+        ("name" = String, Path, description = "Flow name"),
+        ("label" = String, Path, description = "Label name")
+    ),
+    request_body = SetFlowLabelRequest,
+    responses(
+        (status = 200, description = "Flow label set successfully", body = FlowLabelResponse),
+        (status = 400, description = "Flow does not have the given name"),
+        (status = 404, description = "Flow not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = crate::api::FLOW_TAG,
+)]
+pub async fn set_flow_label(
+    State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
+    Path((name, label)): Path<(String, String)>,
+    identity: Option<Extension<Identity>>,
+    Json(req): Json<SetFlowLabelRequest>,
+) -> Result<Json<FlowLabelResponse>, ErrorResponse> {
+    let state_store = executor.state_store();
+
+    // Only allow labels to point at flows with the same name
+    let flow = state_store
+        .get_namespaced_flow(&namespace, &req.flow_id)
+        .await?
+        .ok_or_else(|| error_stack::report!(ServerError::WorkflowNotFound(req.flow_id.clone())))?;
+    if flow.name() != Some(name.as_str()) {
+        return Err(error_stack::report!(ServerError::FlowNameMismatch {
+            flow_id: req.flow_id,
+            name,
+        })
+        .into());
+    }
+
+    // Labels may only point at versions stored in the namespace
+    let versions = state_store.get_flows(&namespace, &name).await?;
+    if !versions.iter().any(|(flow_id, _)| *flow_id == req.flow_id) {
+        return Err(error_stack::report!(ServerError::WorkflowNotFound(req.flow_id)).into());
+    }
+
+    state_store
+        .create_or_update_label(
+            &namespace,
+            &name,
+            &label,
+            req.flow_id,
+            &change_actor(identity, req.actor),
+            req.comment.as_deref(),
+        )
+        .await?;
+
+    let label = find_label(&executor, &namespace, name, label).await?;
+    Ok(Json(label.into()))
+}
+
+/// Delete a label from a named flow
+#[utoipa::path(
+    delete,
+    path = "/flows/{name}/labels/{label}",
+    params(
+        ("name" = String, Path, description = "Flow name"),
+        ("label" = String, Path, description = "Label name"),
+        DeleteFlowLabelQuery
+    ),
+    responses(
+        (status = 204, description = "Flow label deleted successfully"),
+        (status = 404, description = "Label not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = crate::api::FLOW_TAG,
+)]
+pub async fn delete_flow_label(
+    State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
+    Path((name, label)): Path<(String, String)>,
+    Query(query): Query<DeleteFlowLabelQuery>,
+    identity: Option<Extension<Identity>>,
+) -> Result<(), ErrorResponse> {
+    let label = find_label(&executor, &namespace, name, label).await?;
+    executor
+        .state_store()
+        .delete_label(
+            &namespace,
+            &label.name,
+            &label.label,
+            &change_actor(identity, query.actor),
+            query.comment.as_deref(),
+        )
+        .await?;
+    Ok(())
+}
+
+/// List the recorded changes to a label, newest first
+#[utoipa::path(
+    get,
+    path = "/flows/{name}/labels/{label}/history",
+    params(
+        ("name" = String, Path, description = "Flow name"),
+        ("label" = String, Path, description = "Label name")
+    ),
+    responses(
+        (status = 200, description = "Flow label history listed successfully", body = ListFlowLabelHistoryResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = crate::api::FLOW_TAG,
+)]
+pub async fn get_flow_label_history(
+    State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
+    Path((name, label)): Path<(String, String)>,
+) -> Result<Json<ListFlowLabelHistoryResponse>, ErrorResponse> {
+    let history = executor
+        .state_store()
+        .list_label_history(&namespace, &name, &label)
+        .await?
+        .into_iter()
+        .map(FlowLabelHistoryEntry::from)
+        .collect();
+    Ok(Json(ListFlowLabelHistoryResponse { history }))
+}
+
+/// Move a label back to a version it previously pointed at
+///
+/// Without a `flowId`, the label moves back to the version it pointed at
+/// before its latest change.
+#[utoipa::path(
+    post,
+    path = "/flows/{name}/labels/{label}/rollback",
+    params(
+        ("name" = String, Path, description = "Flow name"),
+        ("label" = String, Path, description = "Label name")
+    ),
+    request_body = RollbackFlowLabelRequest,
+    responses(
+        (status = 200, description = "Flow label rolled back successfully", body = FlowLabelResponse),
+        (status = 400, description = "Label never pointed at the requested version"),
+        (status = 404, description = "Label not found"),
+        (status = 409, description = "Label has no previous version"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = crate::api::FLOW_TAG,
+)]
+pub async fn rollback_flow_label(
+    State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
+    Path((name, label)): Path<(String, String)>,
+    identity: Option<Extension<Identity>>,
+    Json(req): Json<RollbackFlowLabelRequest>,
+) -> Result<Json<FlowLabelResponse>, ErrorResponse> {
+    let state_store = executor.state_store();
+
+    let history = state_store
+        .list_label_history(&namespace, &name, &label)
+        .await?;
+    let Some(latest) = history.first() else {
+        return Err(error_stack::report!(ServerError::LabelNotFound { name, label }).into());
+    };
+
+    let flow_id = match req.flow_id {
+        Some(flow_id) => {
+            if !history
+                .iter()
+                .any(|entry| entry.flow_id.as_ref() == Some(&flow_id))
+            {
+                return Err(error_stack::report!(ServerError::LabelVersionNotInHistory {
+                    name,
+                    label,
+                    flow_id,
+                })
+                .into());
+            }
+            flow_id
+        }
+        None => match &latest.previous_flow_id {
+            Some(previous_flow_id) => previous_flow_id.clone(),
+            None => {
+                return Err(error_stack::report!(ServerError::NoPreviousLabelVersion {
+                    name,
+                    label
+                })
+                .into());
+            }
+        },
+    };
+
+    let comment = req
+        .comment
+        .unwrap_or_else(|| format!("Rollback to {flow_id}"));
+    state_store
+        .create_or_update_label(
+            &namespace,
+            &name,
+            &label,
+            flow_id,
+            &change_actor(identity, req.actor),
+            Some(&comment),
+        )
+        .await?;
+
+    let label = find_label(&executor, &namespace, name, label).await?;
+    Ok(Json(label.into()))
+}
+
+/// Create and execute a run of the flow a label points at
+#[utoipa::path(
+    post,
+    path = "/flows/{name}/labels/{label}/runs",
+    params(
+        ("name" = String, Path, description = "Flow name"),
+        ("label" = String, Path, description = "Label name")
+    ),
+    request_body = CreateLabeledRunRequest,
+    responses(
+        (status = 200, description = "Flow run created successfully", body = CreateRunResponse),
+        (status = 202, description = "Flow run started but not yet finished", body = CreateRunResponse,
+            headers(("Location" = String, description = "URL to poll for the run"))),
+        (status = 400, description = "Invalid request"),
+        (status = 404, description = "Label not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = crate::api::RUN_TAG,
+)]
+pub async fn create_labeled_run(
+    State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
+    Path((name, label)): Path<(String, String)>,
+    identity: Option<Extension<Identity>>,
+    Extension(webhooks): Extension<Arc<WebhookDispatcher>>,
+    Json(req): Json<CreateLabeledRunRequest>,
+) -> Result<Response, ErrorResponse> {
+    let named_flow = executor
+        .state_store()
+        .get_named_flow(&namespace, &name, Some(&label))
+        .await?
+        .ok_or_else(|| {
+            error_stack::report!(ServerError::LabelNotFound {
+                name,
+                label: label.clone()
+            })
+        })?;
+
+    let params = CreateRunParams {
+        flow_label: Some(label),
+        namespace: namespace.0.clone(),
+        debug_mode: req.debug,
+        created_by: identity.map(|Extension(identity)| identity.subject),
+        callback: req.callback,
+        ..CreateRunParams::new(Uuid::new_v4(), named_flow.flow_id, req.input)
+    };
+    let wait = RunWait::new(req.mode, req.timeout_ms);
+    let response = start_run(&executor, &webhooks, named_flow.workflow, params, wait).await?;
+    Ok(create_run_response(&namespace, response))
+}
+
+/// Look up a label, returning a not-found error if it does not exist.
+async fn find_label(
+    executor: &StepflowExecutor,
+    namespace: &str,
+    name: String,
+    label: String,
+) -> Result<WorkflowLabelMetadata, ErrorResponse> {
+    let labels = executor
+        .state_store()
+        .list_labels_for_name(namespace, &name)
+        .await?;
+    match labels.into_iter().find(|l| l.label == label) {
+        Some(metadata) => Ok(metadata),
+        None => Err(error_stack::report!(ServerError::LabelNotFound { name, label }).into()),
+    }
+}
@@ -11,23 +11,33 @@
 // the License.
 
 use axum::{
-    extract::{Path, State},
-    response::Json,
+    Extension,
+    extract::{Path, Query, State},
+    http::{StatusCode, header},
+    response::{IntoResponse as _, Json, Response},
 };
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use stepflow_core::status::{ExecutionStatus, StepStatus};
 use stepflow_core::{
     BlobId, FlowResult,
     workflow::{Flow, ValueRef},
 };
-use stepflow_execution::StepflowExecutor;
-use stepflow_state::{RunDetails, RunSummary};
-use utoipa::ToSchema;
+use stepflow_execution::{ExecutionError, StepflowExecutor, resolve_blob_refs};
+use stepflow_state::{
+    CreateRunParams, DEFAULT_NAMESPACE, RunCallback, RunDetails, RunSummary, StateError,
+    StateStore, StepLog, StepProgress,
+};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
+use crate::auth::Identity;
 use crate::error::{ErrorResponse, ServerError};
+use crate::namespace::Namespace;
+use crate::webhooks::WebhookDispatcher;
 
 /// Request to create/execute a flow
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -40,6 +50,51 @@ pub struct CreateRunRequest {
     /// Whether to run in debug mode (pauses execution for step-by-step control)
     #[serde(default)]
     pub debug: bool,
+    /// Callback notified when the run finishes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub callback: Option<RunCallback>,
+    /// Whether to wait for the run to finish before responding
+    #[serde(default)]
+    pub mode: RunMode,
+    /// How long to wait for the run in `wait` mode, in milliseconds (default: 30000)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+}
+
+/// How long to wait for a new run to finish before responding
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum RunMode {
+    /// Respond once the run finishes
+    #[default]
+    Sync,
+    /// Respond as soon as the run is started
+    Async,
+    /// Respond once the run finishes or the timeout elapses, whichever is first
+    Wait,
+}
+
+/// Timeout of `wait` mode, unless the client specifies one.
+const DEFAULT_WAIT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long [`start_run`] waits for the run to finish.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum RunWait {
+    Finished,
+    Started,
+    Timeout(Duration),
+}
+
+impl RunWait {
+    pub(crate) fn new(mode: RunMode, timeout_ms: Option<u64>) -> Self {
+        match mode {
+            RunMode::Sync => RunWait::Finished,
+            RunMode::Async => RunWait::Started,
+            RunMode::Wait => {
+                RunWait::Timeout(timeout_ms.map_or(DEFAULT_WAIT_TIMEOUT, Duration::from_millis))
+            }
+        }
+    }
 }
 
 /// Response for create run operations
@@ -79,8 +134,13 @@ pub struct StepRunResponse {
     /// Current status of the step
     pub status: StepStatus,
     /// The result of the step execution (if completed)
+    ///
+    /// Results stored as blobs are returned with the blob content.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub result: Option<FlowResult>,
+    /// The latest progress reported by the step (if any)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<StepProgress>,
 }
 
 /// Response for listing step runs
@@ -91,6 +151,14 @@ pub struct ListStepRunsResponse {
     pub steps: IndexMap<String, StepRunResponse>,
 }
 
+/// Response for listing the log messages of a step
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListStepLogsResponse {
+    /// Log messages recorded while executing the step, oldest first
+    pub logs: Vec<StepLog>,
+}
+
 /// Response containing a flow definition and its hash for run endpoints
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -108,6 +176,8 @@ pub struct RunFlowResponse {
     request_body = CreateRunRequest,
     responses(
         (status = 200, description = "Flow run created successfully", body = CreateRunResponse),
+        (status = 202, description = "Flow run started but not yet finished", body = CreateRunResponse,
+            headers(("Location" = String, description = "URL to poll for the run"))),
         (status = 400, description = "Invalid request"),
         (status = 404, description = "Flow not found"),
         (status = 500, description = "Internal server error")
@@ -116,86 +186,174 @@ pub struct RunFlowResponse {
 )]
 pub async fn create_run(
     State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
+    identity: Option<Extension<Identity>>,
+    Extension(webhooks): Extension<Arc<WebhookDispatcher>>,
     Json(req): Json<CreateRunRequest>,
-) -> Result<Json<CreateRunResponse>, ErrorResponse> {
-    let run_id = Uuid::new_v4();
+) -> Result<Response, ErrorResponse> {
     let state_store = executor.state_store();
 
-    // Get the flow from the state store
+    // Get the flow from the state store, if it is readable in the namespace
     let flow = state_store
-        .get_flow(&req.flow_id)
+        .get_namespaced_flow(&namespace, &req.flow_id)
         .await?
         .ok_or_else(|| error_stack::report!(ServerError::WorkflowNotFound(req.flow_id.clone())))?;
 
-    // Create execution record
-    state_store
-        .create_run(
-            run_id,
-            req.flow_id.clone(),
-            flow.name(), // Use flow name if available
-            None,        // No flow label for hash-based execution
-            req.debug,
-            req.input.clone(),
-        )
-        .await?;
+    // No flow label for hash-based execution
+    let params = CreateRunParams {
+        namespace: namespace.0.clone(),
+        debug_mode: req.debug,
+        created_by: identity.map(|Extension(identity)| identity.subject),
+        callback: req.callback,
+        ..CreateRunParams::new(Uuid::new_v4(), req.flow_id, req.input)
+    };
+    let wait = RunWait::new(req.mode, req.timeout_ms);
+    let response = start_run(&executor, &webhooks, flow, params, wait).await?;
+    Ok(create_run_response(&namespace, response))
+}
 
-    let debug_mode = req.debug;
-    let input = req.input;
-    let flow_id = req.flow_id;
+/// Respond with a new run, pointing at the run for polling if it is still running.
+pub(crate) fn create_run_response(namespace: &str, response: CreateRunResponse) -> Response {
+    if response.status != ExecutionStatus::Running {
+        return Json(response).into_response();
+    }
+
+    let location = if namespace == DEFAULT_NAMESPACE {
+        format!("/api/v1/runs/{}", response.run_id)
+    } else {
+        format!("/api/v1/namespaces/{namespace}/runs/{}", response.run_id)
+    };
+    (
+        StatusCode::ACCEPTED,
+        [(header::LOCATION, location)],
+        Json(response),
+    )
+        .into_response()
+}
+
+/// Create a run of the given flow and execute it unless `params.debug_mode` is set.
+///
+/// The flow name is recorded from the flow; the other run metadata is taken
+/// from `params`. The run is finished in the background, so it is recorded
+/// and its callback notified even if the caller stops waiting per `wait`.
+pub(crate) async fn start_run(
+    executor: &Arc<StepflowExecutor>,
+    webhooks: &Arc<WebhookDispatcher>,
+    flow: Arc<Flow>,
+    mut params: CreateRunParams,
+    wait: RunWait,
+) -> Result<CreateRunResponse, ErrorResponse> {
+    if let Some(callback) = &mut params.callback {
+        webhooks
+            .prepare_callback(callback)
+            .map_err(|error| error_stack::report!(error))?;
+    }
+
+    let run_id = params.run_id;
+    let state_store = executor.state_store();
 
-    if debug_mode {
-        // In debug mode, pause execution by default
+    if params.debug_mode {
+        // In debug mode, record the run without executing it and pause it.
         // The execution will be controlled via debug endpoints
+        state_store
+            .create_run(CreateRunParams {
+                flow_name: flow.name().map(str::to_owned),
+                ..params
+            })
+            .await?;
         state_store
             .update_run_status(run_id, ExecutionStatus::Paused, None)
             .await?;
 
-        return Ok(Json(CreateRunResponse {
+        return Ok(CreateRunResponse {
             run_id,
             result: None,
             status: ExecutionStatus::Paused,
-            debug: debug_mode,
-        }));
+            debug: true,
+        });
     }
 
-    // Execute the flow using the Context trait methods
-    use stepflow_plugin::Context as _;
+    // The executor records the run under its ID. Runs refused by the
+    // namespace's quota are not recorded.
+    executor
+        .submit_run(flow, params)
+        .await
+        .map_err(|report| match report.current_context() {
+            ExecutionError::QuotaExceeded {
+                namespace,
+                max_active_runs,
+            } => {
+                let error = ServerError::NamespaceQuotaExceeded {
+                    namespace: namespace.clone(),
+                    max_active_runs: *max_active_runs,
+                };
+                report.change_context(error).into()
+            }
+            _ => ErrorResponse::from(report),
+        })?;
+
+    let finish = tokio::spawn(finish_run(executor.clone(), webhooks.clone(), run_id));
+    let finished = match wait {
+        RunWait::Finished => Some(finish.await),
+        RunWait::Started => None,
+        RunWait::Timeout(timeout) => tokio::time::timeout(timeout, finish).await.ok(),
+    };
+
+    match finished {
+        Some(finished) => finished.map_err(|error| ErrorResponse {
+            code: StatusCode::INTERNAL_SERVER_ERROR,
+            message: format!("Run '{run_id}' failed to finish: {error}"),
+        })?,
+        None => Ok(CreateRunResponse {
+            run_id,
+            result: None,
+            status: ExecutionStatus::Running,
+            debug: false,
+        }),
+    }
+}
 
-    // Submit the flow for execution
-    let submitted_run_id = executor.submit_flow(flow, flow_id, input).await?;
+/// Wait for a submitted run to finish and notify its callback.
+///
+/// Failures are logged, since the caller may no longer be waiting for them.
+async fn finish_run(
+    executor: Arc<StepflowExecutor>,
+    webhooks: Arc<WebhookDispatcher>,
+    run_id: Uuid,
+) -> Result<CreateRunResponse, ErrorResponse> {
+    let result = notify_run_result(&executor, &webhooks, run_id).await;
+    if let Err(error) = &result {
+        tracing::error!(%run_id, message = %error.message, "Failed to finish run");
+    }
+    result
+}
 
-    // Wait for the result (synchronous execution for the HTTP endpoint)
-    let flow_result = executor.flow_result(submitted_run_id).await?;
+/// Wait for the result of a submitted run and notify its callback.
+///
+/// The executor records the run's status when it finishes.
+async fn notify_run_result(
+    executor: &StepflowExecutor,
+    webhooks: &WebhookDispatcher,
+    run_id: Uuid,
+) -> Result<CreateRunResponse, ErrorResponse> {
+    // Execute the flow using the Context trait methods
+    use stepflow_plugin::Context as _;
 
-    // Check if the workflow execution was successful
-    match &flow_result {
-        FlowResult::Success(result) => {
-            // Update execution status to completed
-            state_store
-                .update_run_status(run_id, ExecutionStatus::Completed, Some(result.clone()))
-                .await?;
+    let flow_result = executor.flow_result(run_id).await?;
+    let status = match &flow_result {
+        FlowResult::Success(_) => ExecutionStatus::Completed,
+        FlowResult::Failed(_) | FlowResult::Skipped { .. } => ExecutionStatus::Failed,
+    };
 
-            Ok(Json(CreateRunResponse {
-                run_id,
-                result: Some(flow_result),
-                status: ExecutionStatus::Completed,
-                debug: debug_mode,
-            }))
-        }
-        FlowResult::Failed(_) | FlowResult::Skipped { .. } => {
-            // Update execution status to failed
-            state_store
-                .update_run_status(run_id, ExecutionStatus::Failed, None)
-                .await?;
+    // The executor records the callback delivery along with the final status.
+    webhooks.wake();
 
-            Ok(Json(CreateRunResponse {
-                run_id,
-                result: Some(flow_result),
-                status: ExecutionStatus::Failed,
-                debug: debug_mode,
-            }))
-        }
-    }
+    Ok(CreateRunResponse {
+        run_id,
+        result: Some(flow_result),
+        status,
+        debug: false,
+    })
 }
 
 /// Get execution details by ID
@@ -215,15 +373,13 @@ pub async fn create_run(
 )]
 pub async fn get_run(
     State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
     Path(run_id): Path<Uuid>,
 ) -> Result<Json<RunDetails>, ErrorResponse> {
     let state_store = executor.state_store();
 
     // Get execution details
-    let details = state_store
-        .get_run(run_id)
-        .await?
-        .ok_or_else(|| error_stack::report!(ServerError::ExecutionNotFound(run_id)))?;
+    let details = find_run(&state_store, &namespace, run_id).await?;
 
     Ok(Json(details))
 }
@@ -245,15 +401,13 @@ pub async fn get_run(
 )]
 pub async fn get_run_flow(
     State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
     Path(run_id): Path<Uuid>,
 ) -> Result<Json<RunFlowResponse>, ErrorResponse> {
     let state_store = executor.state_store();
 
     // Get execution details to retrieve the workflow hash
-    let execution = state_store
-        .get_run(run_id)
-        .await?
-        .ok_or_else(|| error_stack::report!(ServerError::ExecutionNotFound(run_id)))?;
+    let execution = find_run(&state_store, &namespace, run_id).await?;
 
     let workflow_id = execution.summary.flow_id;
     // Get the workflow from the state store
@@ -268,10 +422,91 @@ pub async fn get_run_flow(
     }))
 }
 
+/// A run and the runs nested within it
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RunTreeNode {
+    #[serde(flatten)]
+    pub summary: RunSummary,
+    /// Runs submitted by steps of this run, oldest first
+    #[schema(no_recursion)]
+    pub children: Vec<RunTreeNode>,
+}
+
+impl RunTreeNode {
+    /// Build the tree rooted at `root` from the runs of its run tree.
+    fn build(root: RunSummary, runs: Vec<RunSummary>) -> Self {
+        let mut children: HashMap<Uuid, Vec<RunSummary>> = HashMap::new();
+        for run in runs {
+            if let Some(parent_run_id) = run.parent_run_id {
+                children.entry(parent_run_id).or_default().push(run);
+            }
+        }
+        Self::with_children(root, &mut children)
+    }
+
+    fn with_children(summary: RunSummary, children: &mut HashMap<Uuid, Vec<RunSummary>>) -> Self {
+        let mut runs = children.remove(&summary.run_id).unwrap_or_default();
+        runs.sort_by_key(|run| run.created_at);
+        Self {
+            summary,
+            children: runs
+                .into_iter()
+                .map(|run| Self::with_children(run, children))
+                .collect(),
+        }
+    }
+}
+
+/// Get the tree of nested runs containing a run
+///
+/// Returns the tree rooted at the top-level run the given run is nested within.
+#[utoipa::path(
+    get,
+    path = "/runs/{run_id}/tree",
+    params(
+        ("run_id" = Uuid, Path, description = "Run ID (UUID)")
+    ),
+    responses(
+        (status = 200, description = "Run tree retrieved successfully", body = RunTreeNode),
+        (status = 400, description = "Invalid run ID format"),
+        (status = 404, description = "Run not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = crate::api::RUN_TAG,
+)]
+pub async fn get_run_tree(
+    State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
+    Path(run_id): Path<Uuid>,
+) -> Result<Json<RunTreeNode>, ErrorResponse> {
+    let state_store = executor.state_store();
+
+    let run = find_run(&state_store, &namespace, run_id).await?;
+    let root = find_run(&state_store, &namespace, run.summary.root_run_id).await?;
+    let filters = stepflow_state::RunFilters {
+        namespace: Some(namespace.0),
+        root_run_id: Some(root.summary.run_id),
+        ..Default::default()
+    };
+    let runs = state_store.list_runs(&filters).await?;
+
+    Ok(Json(RunTreeNode::build(root.summary, runs)))
+}
+
+/// Query parameters for listing runs
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ListRunsQuery {
+    /// Only list runs submitted in this batch
+    pub batch_id: Option<Uuid>,
+}
+
 /// List executions with optional filtering
 #[utoipa::path(
     get,
     path = "/runs",
+    params(ListRunsQuery),
     responses(
         (status = 200, description = "Runs listed successfully", body = ListRunsResponse),
         (status = 500, description = "Internal server error")
@@ -280,11 +515,17 @@ pub async fn get_run_flow(
 )]
 pub async fn list_runs(
     State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
+    Query(query): Query<ListRunsQuery>,
 ) -> Result<Json<ListRunsResponse>, ErrorResponse> {
     let state_store = executor.state_store();
 
     // TODO: Add query parameters for filtering (status, workflow_name, workflow_label, limit, offset)
-    let filters = stepflow_state::RunFilters::default();
+    let filters = stepflow_state::RunFilters {
+        namespace: Some(namespace.0),
+        batch_id: query.batch_id,
+        ..Default::default()
+    };
 
     let executions = state_store.list_runs(&filters).await?;
 
@@ -308,17 +549,13 @@ pub async fn list_runs(
 )]
 pub async fn get_run_steps(
     State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
     Path(run_id): Path<Uuid>,
 ) -> Result<Json<ListStepRunsResponse>, ErrorResponse> {
-    use std::collections::HashMap;
-
     let state_store = executor.state_store();
 
     // Get execution details to retrieve the workflow hash
-    let execution = state_store
-        .get_run(run_id)
-        .await?
-        .ok_or_else(|| error_stack::report!(ServerError::ExecutionNotFound(run_id)))?;
+    let execution = find_run(&state_store, &namespace, run_id).await?;
 
     let workflow_id = execution.summary.flow_id;
 
@@ -349,13 +586,23 @@ pub async fn get_run_steps(
         status_map
     };
 
+    // Get the latest progress reported by each step
+    let mut step_progress = state_store.get_step_progress(run_id).await?;
+
     // Build unified responses
     for (idx, step) in workflow.steps().iter().enumerate() {
         let status = step_statuses
             .get(&idx)
             .copied()
             .unwrap_or(StepStatus::Blocked);
-        let result = completed_steps.get(&idx).map(|sr| sr.result().clone());
+        // Offloaded results are returned with their content, as if inline.
+        let result = match completed_steps.get(&idx).map(|sr| sr.result().clone()) {
+            Some(FlowResult::Success(value)) => Some(FlowResult::Success(
+                resolve_blob_refs(value, &state_store).await?,
+            )),
+            result => result,
+        };
+        let progress = step_progress.remove(&step.id);
 
         let step_response = StepRunResponse {
             step_index: idx,
@@ -363,6 +610,7 @@ pub async fn get_run_steps(
             component: Some(step.component.to_string()),
             status,
             result,
+            progress,
         };
 
         step_responses.insert(step.id.clone(), step_response);
@@ -373,6 +621,36 @@ pub async fn get_run_steps(
     }))
 }
 
+/// Get the log messages recorded for a step of a run
+///
+/// Only plugins configured with `persistLogs` record the log messages of their steps.
+#[utoipa::path(
+    get,
+    path = "/runs/{run_id}/steps/{step_id}/logs",
+    params(
+        ("run_id" = Uuid, Path, description = "Run ID (UUID)"),
+        ("step_id" = String, Path, description = "Step ID")
+    ),
+    responses(
+        (status = 200, description = "Step logs retrieved successfully", body = ListStepLogsResponse),
+        (status = 400, description = "Invalid run ID format"),
+        (status = 404, description = "Run not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = crate::api::RUN_TAG,
+)]
+pub async fn get_step_logs(
+    State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
+    Path((run_id, step_id)): Path<(Uuid, String)>,
+) -> Result<Json<ListStepLogsResponse>, ErrorResponse> {
+    let state_store = executor.state_store();
+    find_run(&state_store, &namespace, run_id).await?;
+
+    let logs = state_store.get_step_logs(run_id, &step_id).await?;
+    Ok(Json(ListStepLogsResponse { logs }))
+}
+
 /// Cancel a running execution
 #[utoipa::path(
     post,
@@ -391,15 +669,14 @@ pub async fn get_run_steps(
 )]
 pub async fn cancel_run(
     State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
+    Extension(webhooks): Extension<Arc<WebhookDispatcher>>,
     Path(run_id): Path<Uuid>,
 ) -> Result<Json<RunSummary>, ErrorResponse> {
     let state_store = executor.state_store();
 
     // Get execution to check current status
-    let execution = state_store
-        .get_run(run_id)
-        .await?
-        .ok_or_else(|| error_stack::report!(ServerError::ExecutionNotFound(run_id)))?;
+    let execution = find_run(&state_store, &namespace, run_id).await?;
 
     // Check if execution can be cancelled
     match execution.summary.status {
@@ -416,6 +693,8 @@ pub async fn cancel_run(
             state_store
                 .update_run_status(run_id, ExecutionStatus::Cancelled, None)
                 .await?;
+            state_store.record_run_callback(run_id, None).await?;
+            webhooks.wake();
         }
     }
 
@@ -428,45 +707,136 @@ pub async fn cancel_run(
     Ok(Json(updated_execution.summary))
 }
 
-/// Delete a completed execution
+/// Query parameters for deleting a run
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteRunQuery {
+    /// Whether to also delete runs nested within the run (default: false)
+    #[serde(default)]
+    pub cascade: bool,
+}
+
+/// Query parameters selecting the runs to delete in bulk
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteRunsQuery {
+    /// Only delete runs with this status
+    pub status: Option<ExecutionStatus>,
+    /// Only delete runs of this flow
+    #[param(value_type = Option<String>)]
+    pub flow_id: Option<BlobId>,
+    /// Only delete runs of flows with this name
+    pub flow_name: Option<String>,
+    /// Only delete runs started through this label
+    pub flow_label: Option<String>,
+    /// Whether to also delete runs nested within the deleted runs (default: false)
+    #[serde(default)]
+    pub cascade: bool,
+}
+
+/// Response for bulk run deletion
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteRunsResponse {
+    /// IDs of the deleted runs, including nested runs deleted by cascading
+    pub deleted_run_ids: Vec<Uuid>,
+}
+
+/// Delete a finished execution and its step results
 #[utoipa::path(
     delete,
     path = "/runs/{run_id}",
     params(
-        ("run_id" = Uuid, Path, description = "Run ID (UUID)")
+        ("run_id" = Uuid, Path, description = "Run ID (UUID)"),
+        DeleteRunQuery
     ),
     responses(
         (status = 204, description = "Run deleted successfully"),
         (status = 400, description = "Invalid run ID format"),
         (status = 404, description = "Run not found"),
-        (status = 409, description = "Run still running"),
+        (status = 409, description = "Run, or a nested run when cascading, still running"),
         (status = 500, description = "Internal server error")
     ),
     tag = crate::api::RUN_TAG,
 )]
 pub async fn delete_run(
     State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
     Path(run_id): Path<Uuid>,
+    Query(query): Query<DeleteRunQuery>,
 ) -> Result<(), ErrorResponse> {
     let state_store = executor.state_store();
 
-    // Get execution to check current status
-    let execution = state_store
-        .get_run(run_id)
-        .await?
-        .ok_or_else(|| error_stack::report!(ServerError::ExecutionNotFound(run_id)))?;
-
-    // Check if execution can be deleted (only allow deletion of non-running executions)
-    match execution.summary.status {
-        ExecutionStatus::Running | ExecutionStatus::Paused => {
-            return Err(error_stack::report!(ServerError::ExecutionStillRunning(run_id)).into());
-        }
-        ExecutionStatus::Completed | ExecutionStatus::Failed | ExecutionStatus::Cancelled => {
-            // TODO: Implement actual execution deletion logic
-            // This should remove execution record and all associated step results
-            // For now, this is a placeholder
-        }
+    find_run(&state_store, &namespace, run_id).await?;
+
+    // Only allow deletion of runs whose tree has no running or paused runs
+    let deleted = state_store
+        .delete_run(run_id, query.cascade)
+        .await
+        .map_err(|report| match report.current_context() {
+            StateError::RunTreeActive { .. } => report
+                .change_context(ServerError::ExecutionStillRunning(run_id))
+                .into(),
+            _ => ErrorResponse::from(report),
+        })?;
+    if deleted.is_empty() {
+        return Err(error_stack::report!(ServerError::ExecutionNotFound(run_id)).into());
+    }
+    for run_id in deleted {
+        executor.forget_run(run_id).await;
     }
 
     Ok(())
 }
+
+/// Delete finished executions matching the filters
+///
+/// Runs that are still running or paused are skipped, as are runs with running
+/// or paused nested runs when cascading.
+#[utoipa::path(
+    delete,
+    path = "/runs",
+    params(DeleteRunsQuery),
+    responses(
+        (status = 200, description = "Runs deleted successfully", body = DeleteRunsResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = crate::api::RUN_TAG,
+)]
+pub async fn delete_runs(
+    State(executor): State<Arc<StepflowExecutor>>,
+    namespace: Namespace,
+    Query(query): Query<DeleteRunsQuery>,
+) -> Result<Json<DeleteRunsResponse>, ErrorResponse> {
+    let filters = stepflow_state::RunFilters {
+        status: query.status,
+        flow_id: query.flow_id,
+        flow_name: query.flow_name,
+        flow_label: query.flow_label,
+        namespace: Some(namespace.0),
+        ..Default::default()
+    };
+
+    let deleted_run_ids = executor
+        .state_store()
+        .delete_runs(&filters, query.cascade)
+        .await?;
+    for run_id in &deleted_run_ids {
+        executor.forget_run(*run_id).await;
+    }
+
+    Ok(Json(DeleteRunsResponse { deleted_run_ids }))
+}
+
+/// Look up a run in the namespace, returning a not-found error if it does not
+/// exist or belongs to another namespace.
+pub(crate) async fn find_run(
+    state_store: &Arc<dyn StateStore>,
+    namespace: &str,
+    run_id: Uuid,
+) -> Result<RunDetails, ErrorResponse> {
+    match state_store.get_run(run_id).await? {
+        Some(details) if details.summary.namespace == namespace => Ok(details),
+        _ => Err(error_stack::report!(ServerError::ExecutionNotFound(run_id)).into()),
+    }
+}
@@ -0,0 +1,613 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+//! Authentication of API callers and per-route scope enforcement.
+//!
+//! Callers authenticate with either a static API key from the configuration or
+//! an HS256 JWT signed with the configured secret. Credentials are accepted as
+//! `Authorization: Bearer <credential>` or, for API keys, `X-API-Key: <key>`.
+//! Credentials may be restricted to a set of namespaces.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, Method, header};
+use axum::middleware::Next;
+use axum::response::Response;
+use base64::Engine as _;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use hmac::{Hmac, Mac as _};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+
+use crate::error::{ErrorResponse, ServerError};
+use crate::namespace::Namespace;
+
+/// Header carrying an API key as an alternative to a bearer token.
+const API_KEY_HEADER: &str = "x-api-key";
+
+/// A permission that may be granted to an API caller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Scope {
+    /// Read flows, labels and components.
+    #[serde(rename = "flows:read")]
+    FlowsRead,
+    /// Store and delete flows and change labels.
+    #[serde(rename = "flows:write")]
+    FlowsWrite,
+    /// Read runs, their steps and blobs.
+    #[serde(rename = "runs:read")]
+    RunsRead,
+    /// Create runs.
+    #[serde(rename = "runs:create")]
+    RunsCreate,
+    /// Cancel and delete runs.
+    #[serde(rename = "runs:write")]
+    RunsWrite,
+    /// Control debug runs.
+    #[serde(rename = "debug")]
+    Debug,
+    /// Read Prometheus metrics.
+    #[serde(rename = "metrics:read")]
+    MetricsRead,
+    /// Every scope.
+    #[serde(rename = "*")]
+    All,
+}
+
+impl Scope {
+    /// The name of the scope as used in configuration and tokens.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scope::FlowsRead => "flows:read",
+            Scope::FlowsWrite => "flows:write",
+            Scope::RunsRead => "runs:read",
+            Scope::RunsCreate => "runs:create",
+            Scope::RunsWrite => "runs:write",
+            Scope::Debug => "debug",
+            Scope::MetricsRead => "metrics:read",
+            Scope::All => "*",
+        }
+    }
+
+    /// Parse a scope name, returning `None` for unknown scopes.
+    pub fn parse(name: &str) -> Option<Self> {
+        [
+            Scope::FlowsRead,
+            Scope::FlowsWrite,
+            Scope::RunsRead,
+            Scope::RunsCreate,
+            Scope::RunsWrite,
+            Scope::Debug,
+            Scope::MetricsRead,
+            Scope::All,
+        ]
+        .into_iter()
+        .find(|scope| scope.as_str() == name)
+    }
+}
+
+impl std::fmt::Display for Scope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Authentication configuration for the server.
+///
+/// When configured, every API route except the health check requires a
+/// credential granting the route's scope.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthConfig {
+    /// Static API keys.
+    #[serde(default)]
+    pub api_keys: Vec<ApiKeyConfig>,
+    /// Verification of JWT bearer tokens.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jwt: Option<JwtConfig>,
+}
+
+/// A static API key and the identity and scopes it grants.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeyConfig {
+    /// The secret key presented by the caller.
+    pub key: String,
+    /// The identity recorded for requests made with the key.
+    pub identity: String,
+    /// The scopes granted to the key.
+    pub scopes: Vec<Scope>,
+    /// If set, the namespaces the key may access. Otherwise it may access all.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespaces: Option<Vec<String>>,
+}
+
+/// Verification settings for HS256 JWT bearer tokens.
+///
+/// The token's `sub` claim is the caller's identity and its scopes are read
+/// from a space-separated `scope` claim. An optional `namespaces` array claim
+/// restricts the namespaces the caller may access. Tokens must carry an `exp`
+/// claim unless `allow_missing_exp` is set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JwtConfig {
+    /// The shared secret tokens are signed with.
+    pub secret: String,
+    /// If set, the required `iss` claim.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<String>,
+    /// If set, the required `aud` claim.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audience: Option<String>,
+    /// Accept tokens without an `exp` claim, which never expire.
+    #[serde(default)]
+    pub allow_missing_exp: bool,
+    /// Seconds of clock difference with the token issuer tolerated when
+    /// checking the `exp` and `nbf` claims.
+    #[serde(default = "default_clock_skew_secs")]
+    pub clock_skew_secs: u64,
+}
+
+fn default_clock_skew_secs() -> u64 {
+    60
+}
+
+/// An authenticated API caller.
+///
+/// Inserted into the request extensions by the auth layer, so handlers can
+/// record who made a change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Identity {
+    pub subject: String,
+    pub scopes: HashSet<Scope>,
+    /// The namespaces the caller may access, or `None` for all namespaces.
+    pub namespaces: Option<HashSet<String>>,
+}
+
+impl Identity {
+    /// Whether the caller was granted `scope`.
+    pub fn has_scope(&self, scope: Scope) -> bool {
+        self.scopes.contains(&Scope::All) || self.scopes.contains(&scope)
+    }
+
+    /// Whether the caller may access `namespace`.
+    pub fn can_access(&self, namespace: &str) -> bool {
+        self.namespaces
+            .as_ref()
+            .is_none_or(|namespaces| namespaces.contains(namespace))
+    }
+}
+
+/// Verifies credentials against an [`AuthConfig`].
+#[derive(Debug)]
+pub(crate) struct Authenticator {
+    /// SHA-256 digests of the API keys, so comparisons take the same time for any key.
+    api_keys: Vec<([u8; 32], Identity)>,
+    jwt: Option<JwtConfig>,
+}
+
+impl Authenticator {
+    pub fn new(config: &AuthConfig) -> Self {
+        let api_keys = config
+            .api_keys
+            .iter()
+            .map(|api_key| {
+                let identity = Identity {
+                    subject: api_key.identity.clone(),
+                    scopes: api_key.scopes.iter().copied().collect(),
+                    namespaces: api_key
+                        .namespaces
+                        .as_ref()
+                        .map(|namespaces| namespaces.iter().cloned().collect()),
+                };
+                (Sha256::digest(api_key.key.as_bytes()).into(), identity)
+            })
+            .collect();
+        Self {
+            api_keys,
+            jwt: config.jwt.clone(),
+        }
+    }
+
+    /// Identify the caller from the request headers.
+    pub fn authenticate(&self, headers: &HeaderMap) -> Result<Identity, ServerError> {
+        let bearer = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::trim);
+
+        if let Some(token) = bearer
+            && let Some(jwt) = &self.jwt
+            && token.split('.').count() == 3
+        {
+            return verify_jwt(jwt, token, chrono::Utc::now().timestamp());
+        }
+
+        let key = bearer.or_else(|| {
+            headers
+                .get(API_KEY_HEADER)
+                .and_then(|value| value.to_str().ok())
+        });
+        let Some(key) = key else {
+            return Err(ServerError::Unauthenticated(
+                "missing credentials".to_string(),
+            ));
+        };
+
+        let digest: [u8; 32] = Sha256::digest(key.as_bytes()).into();
+        self.api_keys
+            .iter()
+            .find(|(expected, _)| constant_time_eq(expected, &digest))
+            .map(|(_, identity)| identity.clone())
+            .ok_or_else(|| ServerError::Unauthenticated("invalid API key".to_string()))
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[derive(Deserialize)]
+struct JwtHeader {
+    alg: String,
+}
+
+#[derive(Deserialize)]
+struct JwtClaims {
+    sub: String,
+    #[serde(default)]
+    scope: String,
+    exp: Option<i64>,
+    nbf: Option<i64>,
+    iss: Option<String>,
+    #[serde(default)]
+    aud: Option<serde_json::Value>,
+    #[serde(default)]
+    namespaces: Option<HashSet<String>>,
+}
+
+/// Verify an HS256 JWT and return the identity it carries.
+fn verify_jwt(config: &JwtConfig, token: &str, now: i64) -> Result<Identity, ServerError> {
+    let invalid = |reason: &str| ServerError::Unauthenticated(format!("invalid token: {reason}"));
+
+    let mut parts = token.split('.');
+    let (Some(header), Some(payload), Some(signature)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(invalid("malformed"));
+    };
+    let decode = |part: &str| {
+        URL_SAFE_NO_PAD
+            .decode(part)
+            .map_err(|_| invalid("malformed"))
+    };
+
+    let jwt_header: JwtHeader =
+        serde_json::from_slice(&decode(header)?).map_err(|_| invalid("malformed header"))?;
+    if jwt_header.alg != "HS256" {
+        return Err(invalid("unsupported algorithm"));
+    }
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(config.secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(header.as_bytes());
+    mac.update(b".");
+    mac.update(payload.as_bytes());
+    mac.verify_slice(&decode(signature)?)
+        .map_err(|_| invalid("bad signature"))?;
+
+    let claims: JwtClaims =
+        serde_json::from_slice(&decode(payload)?).map_err(|_| invalid("malformed claims"))?;
+    let skew = i64::try_from(config.clock_skew_secs).unwrap_or(i64::MAX);
+    match claims.exp {
+        Some(exp) if now >= exp.saturating_add(skew) => return Err(invalid("expired")),
+        None if !config.allow_missing_exp => return Err(invalid("missing expiration")),
+        _ => {}
+    }
+    if claims.nbf.is_some_and(|nbf| now < nbf.saturating_sub(skew)) {
+        return Err(invalid("not yet valid"));
+    }
+    if let Some(issuer) = &config.issuer
+        && claims.iss.as_ref() != Some(issuer)
+    {
+        return Err(invalid("wrong issuer"));
+    }
+    if let Some(audience) = &config.audience {
+        let matches = match &claims.aud {
+            Some(serde_json::Value::String(aud)) => aud == audience,
+            Some(serde_json::Value::Array(auds)) => auds.iter().any(|aud| aud == audience),
+            _ => false,
+        };
+        if !matches {
+            return Err(invalid("wrong audience"));
+        }
+    }
+
+    Ok(Identity {
+        subject: claims.sub,
+        scopes: claims
+            .scope
+            .split_whitespace()
+            .filter_map(Scope::parse)
+            .collect(),
+        namespaces: claims.namespaces,
+    })
+}
+
+/// The scope required to call a route, or `None` if the route is public.
+///
+/// `path` is relative to the API base URL, except for `/metrics`, which is
+/// served outside of the API. Routes are matched on their method and exact
+/// path shape; any other request requires every scope.
+fn required_scope(method: &Method, path: &str) -> Option<Scope> {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    // GET routes also answer HEAD requests.
+    let method = if method == Method::HEAD {
+        "GET"
+    } else {
+        method.as_str()
+    };
+    let scope = match (method, segments.as_slice()) {
+        ("GET", ["health"]) => return None,
+        ("GET", ["metrics"]) => Scope::MetricsRead,
+        ("GET", ["components"]) => Scope::FlowsRead,
+        ("GET", ["blobs", _]) => Scope::RunsRead,
+
+        // Flows and their labels
+        ("GET", ["flows"] | ["flows", _] | ["flows", _, "versions" | "labels"]) => Scope::FlowsRead,
+        ("GET", ["flows", _, "labels", _] | ["flows", _, "labels", _, "history"]) => {
+            Scope::FlowsRead
+        }
+        ("POST", ["flows"]) | ("DELETE", ["flows", _]) => Scope::FlowsWrite,
+        ("PUT" | "DELETE", ["flows", _, "labels", _]) => Scope::FlowsWrite,
+        ("POST", ["flows", _, "labels", _, "rollback"]) => Scope::FlowsWrite,
+        ("POST", ["flows", _, "labels", _, "runs"]) => Scope::RunsCreate,
+
+        // Runs
+        ("POST", ["runs"] | ["runs", "batch"]) => Scope::RunsCreate,
+        ("GET", ["runs"] | ["runs", "batch", _] | ["runs", _]) => Scope::RunsRead,
+        ("GET", ["runs", _, "flow" | "tree" | "steps"] | ["runs", _, "steps", _, "logs"]) => {
+            Scope::RunsRead
+        }
+        ("DELETE", ["runs"] | ["runs", _]) | ("POST", ["runs", _, "cancel"]) => Scope::RunsWrite,
+        ("POST", ["runs", _, "debug", "step" | "continue"])
+        | ("GET", ["runs", _, "debug", "runnable"]) => Scope::Debug,
+
+        // Unknown routes and methods are only available to callers with every scope.
+        _ => Scope::All,
+    };
+    Some(scope)
+}
+
+/// Middleware authenticating the caller and enforcing the route's scope and
+/// namespace.
+pub(crate) async fn require_scope(
+    State(authenticator): State<Arc<Authenticator>>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, ErrorResponse> {
+    let Some(scope) = required_scope(request.method(), request.uri().path()) else {
+        return Ok(next.run(request).await);
+    };
+
+    let identity = authenticator.authenticate(request.headers())?;
+    if !identity.has_scope(scope) {
+        return Err(ServerError::MissingScope(scope).into());
+    }
+    if scope == Scope::MetricsRead {
+        // Metrics cover the runs of every namespace.
+        if identity.namespaces.is_some() {
+            return Err(ServerError::NamespaceForbidden("*".to_string()).into());
+        }
+    } else {
+        let namespace = request
+            .extensions()
+            .get::<Namespace>()
+            .cloned()
+            .unwrap_or_default();
+        if !identity.can_access(&namespace) {
+            return Err(ServerError::NamespaceForbidden(namespace.0).into());
+        }
+    }
+
+    request.extensions_mut().insert(identity);
+    Ok(next.run(request).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "test-secret";
+
+    fn sign(claims: serde_json::Value) -> String {
+        let header = URL_SAFE_NO_PAD.encode(r#"{"alg":"HS256","typ":"JWT"}"#);
+        let payload = URL_SAFE_NO_PAD.encode(claims.to_string());
+        let mut mac = Hmac::<Sha256>::new_from_slice(SECRET.as_bytes()).unwrap();
+        mac.update(format!("{header}.{payload}").as_bytes());
+        let signature = URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+        format!("{header}.{payload}.{signature}")
+    }
+
+    fn config() -> JwtConfig {
+        JwtConfig {
+            secret: SECRET.to_string(),
+            issuer: Some("issuer".to_string()),
+            audience: None,
+            allow_missing_exp: false,
+            clock_skew_secs: 0,
+        }
+    }
+
+    #[test]
+    fn test_verify_jwt() {
+        let token = sign(serde_json::json!({
+            "sub": "alice",
+            "scope": "runs:read runs:create unknown",
+            "iss": "issuer",
+            "exp": 200,
+        }));
+        let identity = verify_jwt(&config(), &token, 100).unwrap();
+        assert_eq!(identity.subject, "alice");
+        assert!(identity.has_scope(Scope::RunsCreate));
+        assert!(!identity.has_scope(Scope::FlowsWrite));
+        assert!(identity.can_access("default"));
+
+        let restricted = sign(serde_json::json!({
+            "sub": "bob",
+            "iss": "issuer",
+            "exp": 200,
+            "namespaces": ["team-a"],
+        }));
+        let identity = verify_jwt(&config(), &restricted, 100).unwrap();
+        assert!(identity.can_access("team-a"));
+        assert!(!identity.can_access("default"));
+
+        // Expired tokens, tampered tokens and other issuers are rejected.
+        assert!(verify_jwt(&config(), &token, 200).is_err());
+        let tampered = format!("{}x", &token[..token.len() - 1]);
+        assert!(verify_jwt(&config(), &tampered, 100).is_err());
+        let other_issuer = sign(serde_json::json!({"sub": "alice", "iss": "other", "exp": 200}));
+        assert!(verify_jwt(&config(), &other_issuer, 100).is_err());
+    }
+
+    #[test]
+    fn test_verify_jwt_validity_period() {
+        // Tokens without an expiration are only accepted when allowed.
+        let unbounded = sign(serde_json::json!({"sub": "alice", "iss": "issuer"}));
+        assert!(verify_jwt(&config(), &unbounded, 100).is_err());
+        let allow_missing_exp = JwtConfig {
+            allow_missing_exp: true,
+            ..config()
+        };
+        assert!(verify_jwt(&allow_missing_exp, &unbounded, 100).is_ok());
+
+        let token = sign(serde_json::json!({
+            "sub": "alice",
+            "iss": "issuer",
+            "nbf": 100,
+            "exp": 200,
+        }));
+        assert!(verify_jwt(&config(), &token, 99).is_err());
+        assert!(verify_jwt(&config(), &token, 100).is_ok());
+        assert!(verify_jwt(&config(), &token, 199).is_ok());
+        assert!(verify_jwt(&config(), &token, 200).is_err());
+
+        // The clock skew extends the validity period on both ends.
+        let skewed = JwtConfig {
+            clock_skew_secs: 30,
+            ..config()
+        };
+        assert!(verify_jwt(&skewed, &token, 69).is_err());
+        assert!(verify_jwt(&skewed, &token, 70).is_ok());
+        assert!(verify_jwt(&skewed, &token, 229).is_ok());
+        assert!(verify_jwt(&skewed, &token, 230).is_err());
+    }
+
+    #[test]
+    fn test_required_scope() {
+        assert_eq!(required_scope(&Method::GET, "/health"), None);
+        assert_eq!(
+            required_scope(&Method::POST, "/flows"),
+            Some(Scope::FlowsWrite)
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/flows/greeter/labels/prod/runs"),
+            Some(Scope::RunsCreate)
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/flows/greeter/labels"),
+            Some(Scope::FlowsRead)
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/runs"),
+            Some(Scope::RunsCreate)
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/runs/batch"),
+            Some(Scope::RunsCreate)
+        );
+        assert_eq!(
+            required_scope(&Method::DELETE, "/runs/abc"),
+            Some(Scope::RunsWrite)
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/runs/abc/debug/runnable"),
+            Some(Scope::Debug)
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/metrics"),
+            Some(Scope::MetricsRead)
+        );
+
+        // Methods and path shapes must match a route exactly.
+        assert_eq!(
+            required_scope(&Method::DELETE, "/flows/greeter/labels/prod/runs"),
+            Some(Scope::All)
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/flows/a/b/c/runs"),
+            Some(Scope::All)
+        );
+        assert_eq!(
+            required_scope(&Method::HEAD, "/runs/abc"),
+            Some(Scope::RunsRead)
+        );
+    }
+
+    #[test]
+    fn test_every_route_has_a_scope() {
+        let openapi = crate::api::create_api_router().into_openapi();
+        let mut routes = vec![(Method::GET, "/metrics".to_string())];
+        for (path, item) in &openapi.paths.paths {
+            // Fill in path parameters with placeholder values.
+            let path = path
+                .split('/')
+                .map(|segment| {
+                    if segment.starts_with('{') {
+                        "param"
+                    } else {
+                        segment
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("/");
+            for (method, operation) in [
+                (Method::GET, &item.get),
+                (Method::POST, &item.post),
+                (Method::PUT, &item.put),
+                (Method::DELETE, &item.delete),
+                (Method::PATCH, &item.patch),
+            ] {
+                if operation.is_some() {
+                    routes.push((method, path.clone()));
+                }
+            }
+        }
+        assert!(routes.len() > 30, "expected every route, got {routes:?}");
+
+        // No route is admin-only, and only the health check is public.
+        for (method, path) in routes {
+            let scope = required_scope(&method, &path);
+            if path == "/health" {
+                assert_eq!(scope, None);
+            } else {
+                assert!(
+                    scope.is_some_and(|scope| scope != Scope::All),
+                    "{method} {path} requires {scope:?}"
+                );
+            }
+        }
+    }
+}
@@ -16,6 +16,8 @@ use stepflow_core::BlobId;
 use stepflow_core::status::ExecutionStatus;
 use uuid::Uuid;
 
+use crate::auth::Scope;
+
 /// Error response structure.
 ///
 /// Server handlers should return this, but usually it is better to create it
@@ -36,6 +38,10 @@ pub enum ServerError {
     ExecutionNotFound(Uuid),
     #[error("Workflow '{0}' not found")]
     WorkflowNotFound(BlobId),
+    #[error("Batch '{0}' not found")]
+    BatchNotFound(Uuid),
+    #[error("Blob '{0}' not found")]
+    BlobNotFound(BlobId),
     #[error("Run '{run_id}' cannot be cancelled (status: {status:?})")]
     ExecutionNotCancellable {
         run_id: Uuid,
@@ -43,17 +49,58 @@ pub enum ServerError {
     },
     #[error("Execution '{0}' is still running and cannot be deleted")]
     ExecutionStillRunning(Uuid),
+    #[error("Flow '{0}' has running or paused runs and cannot be deleted")]
+    FlowHasActiveRuns(BlobId),
+    #[error("Flow '{0}' has runs; set deleteRuns to delete them with the flow")]
+    FlowHasRuns(BlobId),
+    #[error("Label '{label}' not found for flow '{name}'")]
+    LabelNotFound { name: String, label: String },
+    #[error("Flow '{flow_id}' is not named '{name}'")]
+    FlowNameMismatch { flow_id: BlobId, name: String },
+    #[error("Label '{label}' of flow '{name}' never pointed at '{flow_id}'")]
+    LabelVersionNotInHistory {
+        name: String,
+        label: String,
+        flow_id: BlobId,
+    },
+    #[error("Label '{label}' of flow '{name}' has no previous version")]
+    NoPreviousLabelVersion { name: String, label: String },
+    #[error("Authentication required: {0}")]
+    Unauthenticated(String),
+    #[error("Missing required scope '{0}'")]
+    MissingScope(Scope),
+    #[error("Access to namespace '{0}' is not granted")]
+    NamespaceForbidden(String),
+    #[error("Namespace '{namespace}' already has {max_active_runs} active runs")]
+    NamespaceQuotaExceeded {
+        namespace: String,
+        max_active_runs: usize,
+    },
+    #[error("Invalid callback: {0}")]
+    InvalidCallback(String),
 }
 
 impl ServerError {
     pub fn status_code(&self) -> StatusCode {
         match self {
-            ServerError::ExecutionNotFound(_) | ServerError::WorkflowNotFound(_) => {
-                StatusCode::NOT_FOUND
-            }
-            ServerError::ExecutionNotCancellable { .. } | ServerError::ExecutionStillRunning(_) => {
-                StatusCode::CONFLICT
+            ServerError::ExecutionNotFound(_)
+            | ServerError::WorkflowNotFound(_)
+            | ServerError::BatchNotFound(_)
+            | ServerError::BlobNotFound(_)
+            | ServerError::LabelNotFound { .. } => StatusCode::NOT_FOUND,
+            ServerError::FlowNameMismatch { .. }
+            | ServerError::LabelVersionNotInHistory { .. }
+            | ServerError::InvalidCallback(_) => StatusCode::BAD_REQUEST,
+            ServerError::ExecutionNotCancellable { .. }
+            | ServerError::ExecutionStillRunning(_)
+            | ServerError::FlowHasActiveRuns(_)
+            | ServerError::FlowHasRuns(_)
+            | ServerError::NoPreviousLabelVersion { .. } => StatusCode::CONFLICT,
+            ServerError::Unauthenticated(_) => StatusCode::UNAUTHORIZED,
+            ServerError::MissingScope(_) | ServerError::NamespaceForbidden(_) => {
+                StatusCode::FORBIDDEN
             }
+            ServerError::NamespaceQuotaExceeded { .. } => StatusCode::TOO_MANY_REQUESTS,
         }
     }
 }
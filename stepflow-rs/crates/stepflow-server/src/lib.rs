@@ -16,8 +16,16 @@
 //! It contains all the API endpoints, request/response types, and server startup logic.
 
 mod api;
+mod auth;
 mod error;
+mod metrics;
+mod namespace;
 mod startup;
+mod webhooks;
 
 pub use api::*;
+pub use auth::{ApiKeyConfig, AuthConfig, Identity, JwtConfig, Scope};
+pub use metrics::install_metrics_recorder;
+pub use namespace::{Namespace, is_valid_namespace};
 pub use startup::*;
+pub use webhooks::{RunCallbackPayload, WebhookConfig, WebhookOptions};
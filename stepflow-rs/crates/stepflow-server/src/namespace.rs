@@ -0,0 +1,126 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+//! Namespaces scoping API requests.
+//!
+//! Every API route is also served below `/api/v1/namespaces/{namespace}/`.
+//! Such requests are rewritten to the plain route before routing, with the
+//! namespace recorded as a request extension. Requests to the plain routes use
+//! the default namespace.
+
+use std::convert::Infallible;
+
+use axum::extract::{FromRequestParts, Request};
+use axum::http::Uri;
+use axum::http::request::Parts;
+use stepflow_state::DEFAULT_NAMESPACE;
+
+/// Prefix of API routes scoped to a namespace.
+const NAMESPACES_PREFIX: &str = "/api/v1/namespaces/";
+
+/// The namespace an API request is scoped to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Namespace(pub String);
+
+impl Namespace {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for Namespace {
+    fn default() -> Self {
+        Self(DEFAULT_NAMESPACE.to_string())
+    }
+}
+
+impl std::ops::Deref for Namespace {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for Namespace {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts
+            .extensions
+            .get::<Namespace>()
+            .cloned()
+            .unwrap_or_default())
+    }
+}
+
+/// Whether `name` may be used as a namespace.
+///
+/// Namespaces are non-empty and consist of ASCII letters, digits, `-` and `_`.
+pub fn is_valid_namespace(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+}
+
+/// Split a namespaced API path into the namespace and the plain API path.
+fn split_namespace(path: &str) -> Option<(&str, String)> {
+    let rest = path.strip_prefix(NAMESPACES_PREFIX)?;
+    let (namespace, route) = rest.split_once('/').unwrap_or((rest, ""));
+    is_valid_namespace(namespace).then(|| (namespace, format!("/api/v1/{route}")))
+}
+
+/// Rewrite requests for namespaced API routes to the plain route.
+///
+/// Requests with an invalid namespace are left unchanged, so they are not found.
+pub(crate) async fn rewrite_namespace(mut request: Request) -> Request {
+    let Some((namespace, path)) = split_namespace(request.uri().path()) else {
+        return request;
+    };
+    let namespace = Namespace(namespace.to_string());
+
+    let path_and_query = match request.uri().query() {
+        Some(query) => format!("{path}?{query}"),
+        None => path,
+    };
+    let mut parts = request.uri().clone().into_parts();
+    parts.path_and_query = match path_and_query.parse() {
+        Ok(path_and_query) => Some(path_and_query),
+        Err(_) => return request,
+    };
+    if let Ok(uri) = Uri::from_parts(parts) {
+        *request.uri_mut() = uri;
+        request.extensions_mut().insert(namespace);
+    }
+    request
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_namespace() {
+        assert_eq!(
+            split_namespace("/api/v1/namespaces/team-a/runs/abc"),
+            Some(("team-a", "/api/v1/runs/abc".to_string()))
+        );
+        assert_eq!(
+            split_namespace("/api/v1/namespaces/team_b/flows"),
+            Some(("team_b", "/api/v1/flows".to_string()))
+        );
+        assert_eq!(split_namespace("/api/v1/namespaces//runs"), None);
+        assert_eq!(split_namespace("/api/v1/namespaces/a.b/runs"), None);
+        assert_eq!(split_namespace("/api/v1/runs"), None);
+    }
+}
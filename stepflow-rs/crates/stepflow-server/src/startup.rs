@@ -10,19 +10,31 @@
 // or implied. See the License for the specific language governing permissions and limitations under
 // the License.
 
-use axum::Router;
+use axum::{Extension, Router, middleware};
 use std::sync::Arc;
 use stepflow_execution::StepflowExecutor;
+use tower::Layer as _;
 use tower::ServiceBuilder;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
 use utoipa_swagger_ui::SwaggerUi;
 
 use crate::api::create_api_router;
+use crate::auth::{AuthConfig, Authenticator, require_scope};
+use crate::metrics::{render_metrics, spawn_metrics_upkeep};
+use crate::namespace::rewrite_namespace;
+use crate::webhooks::{WebhookDispatcher, WebhookOptions};
 
 pub struct AppConfig {
     pub include_swagger: bool,
     pub include_cors: bool,
+    /// Serve Prometheus metrics at `/metrics`. If `auth` is set, reading them
+    /// requires the `metrics:read` scope.
+    pub include_metrics: bool,
+    /// Require authentication for API routes. If not set, the API is open.
+    pub auth: Option<AuthConfig>,
+    /// Retry settings for run completion callbacks.
+    pub webhooks: WebhookOptions,
 }
 
 impl Default for AppConfig {
@@ -30,12 +42,18 @@ impl Default for AppConfig {
         Self {
             include_swagger: true,
             include_cors: true,
+            include_metrics: true,
+            auth: None,
+            webhooks: WebhookOptions::default(),
         }
     }
 }
 
 impl AppConfig {
-    /// Create the application router with the current configuration
+    /// Create the application router with the current configuration.
+    ///
+    /// Must be called within a Tokio runtime, since it spawns the task
+    /// delivering run callbacks.
     pub fn create_app_router(&self, executor: Arc<StepflowExecutor>, port: u16) -> Router {
         // Create the main API router with state using utoipa-axum for consistency
         let (api_router, mut api_doc) = create_api_router().split_for_parts();
@@ -45,10 +63,36 @@ impl AppConfig {
                 .url(format!("http://localhost:{port}/api/v1"))
                 .description(Some("Localhost development server"))
                 .build(),
+            utoipa::openapi::ServerBuilder::new()
+                .url(format!(
+                    "http://localhost:{port}/api/v1/namespaces/{{namespace}}"
+                ))
+                .description(Some("Localhost development server, scoped to a namespace"))
+                .parameter(
+                    "namespace",
+                    utoipa::openapi::ServerVariableBuilder::new()
+                        .default_value(stepflow_state::DEFAULT_NAMESPACE),
+                )
+                .build(),
         ]);
 
+        // Deliver callbacks of finished runs in the background
+        let webhooks = WebhookDispatcher::start(executor.state_store(), self.webhooks.clone());
+
         // Add state to the router
-        let api_router = api_router.with_state(executor);
+        let mut api_router = api_router.with_state(executor).layer(Extension(webhooks));
+
+        // Authenticate callers of API routes if configured
+        let authenticator = self
+            .auth
+            .as_ref()
+            .map(|auth| Arc::new(Authenticator::new(auth)));
+        if let Some(authenticator) = &authenticator {
+            api_router = api_router.route_layer(middleware::from_fn_with_state(
+                authenticator.clone(),
+                require_scope,
+            ));
+        }
 
         // Create the full app router
         let mut app = Router::new().nest("/api/v1/", api_router);
@@ -58,6 +102,17 @@ impl AppConfig {
             app = app.merge(SwaggerUi::new("/swagger-ui").url("/api/v1/openapi.json", api_doc));
         }
 
+        // Add metrics if requested. They name flows, so they are authenticated as well.
+        if self.include_metrics {
+            let mut metrics_router =
+                Router::new().route("/metrics", axum::routing::get(render_metrics));
+            if let Some(authenticator) = authenticator {
+                metrics_router = metrics_router
+                    .route_layer(middleware::from_fn_with_state(authenticator, require_scope));
+            }
+            app = app.merge(metrics_router);
+        }
+
         // Setup CORS if requested.
         let cors_layer = if self.include_cors {
             Some(
@@ -76,7 +131,10 @@ impl AppConfig {
                 .layer(TraceLayer::new_for_http())
                 .option_layer(cors_layer),
         );
-        app
+
+        // Serve the API below `/api/v1/namespaces/{namespace}/` as well. The
+        // rewrite has to happen before routing, so it wraps the whole app.
+        Router::new().fallback_service(middleware::map_request(rewrite_namespace).layer(app))
     }
 }
 
@@ -84,9 +142,15 @@ impl AppConfig {
 pub async fn start_server(
     port: u16,
     executor: Arc<StepflowExecutor>,
+    app_config: AppConfig,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    // Create the app with default configuration (includes swagger and CORS)
-    let app = AppConfig::default().create_app_router(executor, port);
+    if app_config.auth.is_none() {
+        tracing::warn!("Authentication is not configured; the API is open to any caller");
+    }
+    let app = app_config.create_app_router(executor, port);
+    if app_config.include_metrics {
+        spawn_metrics_upkeep();
+    }
 
     let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{port}")).await?;
 
@@ -99,6 +163,7 @@ pub async fn start_server(
         "📄 OpenAPI spec available at http://localhost:{}/api/v1/openapi.json",
         port
     );
+    tracing::info!("📊 Metrics available at http://localhost:{}/metrics", port);
 
     axum::serve(listener, app).await?;
 
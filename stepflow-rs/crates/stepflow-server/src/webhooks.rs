@@ -0,0 +1,561 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+//! Callbacks notifying clients when their runs finish.
+//!
+//! When a run created with a callback reaches a terminal status, a delivery is
+//! recorded in the state store along with the status, and POSTed to the
+//! callback URL by a background task. Failed deliveries are retried with
+//! exponential backoff. Since deliveries are tracked in the state store,
+//! pending ones are resumed after a restart.
+//!
+//! Callbacks are only accepted when a secret key is configured. Callback
+//! secrets are encrypted with it before they are stored with the run, so the
+//! state store never holds the key used to sign deliveries.
+//!
+//! Callbacks are not delivered to loopback, link-local or private addresses,
+//! unless their host is explicitly allowed.
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::http::{HeaderName, HeaderValue};
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD;
+use chrono::Utc;
+use futures::StreamExt as _;
+use hmac::{Hmac, Mac as _};
+use ring::aead::{self, Aad, LessSafeKey, Nonce, UnboundKey};
+use ring::rand::{SecureRandom as _, SystemRandom};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+use stepflow_state::{DeliveryStatus, RunCallback, StateError, StateStore, WebhookDelivery};
+use tokio::sync::Notify;
+
+use crate::error::ServerError;
+
+/// Header carrying the HMAC-SHA256 signature of the request body.
+const SIGNATURE_HEADER: &str = "x-stepflow-signature";
+/// Header carrying the ID of the delivery, which is the same for every attempt.
+const DELIVERY_HEADER: &str = "x-stepflow-delivery";
+/// How long to wait for new deliveries when none are pending.
+const IDLE_POLL_INTERVAL: Duration = Duration::from_secs(60);
+/// Prefix of encrypted callback secrets.
+const SEALED_PREFIX: &str = "sealed:";
+
+pub use stepflow_state::RunCallbackPayload;
+
+/// Configuration of run callbacks.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookConfig {
+    /// Key used to encrypt callback secrets stored with runs.
+    ///
+    /// Runs can only be created with a callback when this is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret_key: Option<String>,
+    /// Hosts callbacks may be delivered to even if they are, or resolve to,
+    /// loopback, link-local or private addresses.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_hosts: Vec<String>,
+}
+
+/// Retry settings for callback deliveries.
+#[derive(Debug, Clone)]
+pub struct WebhookOptions {
+    /// Number of attempts after which a delivery is marked as failed.
+    pub max_attempts: u32,
+    /// Delay before the first retry. Each further retry doubles the delay.
+    pub initial_backoff: Duration,
+    /// Upper bound on the delay between retries.
+    pub max_backoff: Duration,
+    /// Timeout of each delivery request.
+    pub request_timeout: Duration,
+    /// Maximum number of deliveries sent at once.
+    pub max_concurrent_deliveries: usize,
+    /// Key used to encrypt callback secrets. See [`WebhookConfig::secret_key`].
+    pub secret_key: Option<String>,
+    /// See [`WebhookConfig::allowed_hosts`].
+    pub allowed_hosts: Vec<String>,
+}
+
+impl Default for WebhookOptions {
+    fn default() -> Self {
+        Self {
+            max_attempts: 6,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(300),
+            request_timeout: Duration::from_secs(10),
+            max_concurrent_deliveries: 16,
+            secret_key: None,
+            allowed_hosts: Vec::new(),
+        }
+    }
+}
+
+impl WebhookOptions {
+    /// The delay before retrying a delivery that has failed `attempts` times.
+    fn backoff(&self, attempts: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// Check that a callback can be delivered to a host not allowed to be internal.
+fn validate_callback(callback: &RunCallback, allowed_hosts: &[String]) -> Result<(), ServerError> {
+    let invalid = |reason: String| ServerError::InvalidCallback(reason);
+
+    let url = reqwest::Url::parse(&callback.url)
+        .map_err(|e| invalid(format!("invalid URL '{}': {e}", callback.url)))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(invalid(format!(
+            "URL '{}' must use http or https",
+            callback.url
+        )));
+    }
+    check_host(&url, allowed_hosts).map_err(invalid)?;
+    for (name, value) in &callback.headers {
+        HeaderName::from_bytes(name.as_bytes())
+            .map_err(|_| invalid(format!("invalid header name '{name}'")))?;
+        HeaderValue::from_str(value)
+            .map_err(|_| invalid(format!("invalid value for header '{name}'")))?;
+    }
+    Ok(())
+}
+
+/// Whether `host` is listed in `allowed_hosts`.
+fn is_allowed_host(allowed_hosts: &[String], host: &str) -> bool {
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    allowed_hosts
+        .iter()
+        .any(|allowed| allowed.eq_ignore_ascii_case(host))
+}
+
+/// Whether an address is loopback, link-local, private or otherwise not
+/// publicly routable.
+fn is_internal_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                // Shared address space (100.64.0.0/10)
+                || (a == 100 && (b & 0xc0) == 64)
+        }
+        IpAddr::V6(ip) => {
+            let first = ip.segments()[0];
+            ip.is_loopback()
+                || ip.is_unspecified()
+                // Unique local (fc00::/7) and link-local (fe80::/10) addresses
+                || (first & 0xfe00) == 0xfc00
+                || (first & 0xffc0) == 0xfe80
+                || ip
+                    .to_ipv4_mapped()
+                    .is_some_and(|ip| is_internal_address(IpAddr::V4(ip)))
+        }
+    }
+}
+
+/// Check that the host of a callback URL is not internal, unless allowed.
+///
+/// Hosts given by name are checked when they are resolved, by [`PublicResolver`].
+fn check_host(url: &reqwest::Url, allowed_hosts: &[String]) -> Result<(), String> {
+    let Some(host) = url.host_str() else {
+        return Err(format!("URL '{url}' has no host"));
+    };
+    if is_allowed_host(allowed_hosts, host) {
+        return Ok(());
+    }
+    // IPv6 hosts are bracketed, and IPv4 hosts are normalized to dotted decimal.
+    let internal = match host.trim_start_matches('[').trim_end_matches(']').parse() {
+        Ok(ip) => is_internal_address(ip),
+        Err(_) => {
+            let domain = host.to_ascii_lowercase();
+            domain == "localhost" || domain.ends_with(".localhost")
+        }
+    };
+    if internal {
+        return Err(format!(
+            "host '{host}' of URL '{url}' is an internal address"
+        ));
+    }
+    Ok(())
+}
+
+/// Resolves the hosts of callback URLs, dropping internal addresses unless the
+/// host is allowed.
+///
+/// Checking the addresses when connecting, rather than when the callback is
+/// created, also covers hosts whose addresses change in between.
+struct PublicResolver {
+    allowed_hosts: Arc<[String]>,
+}
+
+impl reqwest::dns::Resolve for PublicResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let allowed = is_allowed_host(&self.allowed_hosts, name.as_str());
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .filter(|addr| allowed || !is_internal_address(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("host '{host}' only resolves to internal addresses").into());
+            }
+            let addrs: reqwest::dns::Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+/// Sign a request body, returning the value of the signature header.
+fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Encrypts callback secrets while they are stored.
+struct SecretSealer {
+    key: LessSafeKey,
+    rng: SystemRandom,
+}
+
+impl SecretSealer {
+    /// Create a sealer using a key derived from `secret_key`.
+    fn new(secret_key: &str) -> Self {
+        let key_bytes: [u8; 32] = Sha256::digest(secret_key.as_bytes()).into();
+        let key = UnboundKey::new(&aead::CHACHA20_POLY1305, &key_bytes)
+            .expect("key length matches the algorithm");
+        Self {
+            key: LessSafeKey::new(key),
+            rng: SystemRandom::new(),
+        }
+    }
+
+    /// Encrypt a secret with a random nonce.
+    fn seal(&self, secret: &str) -> String {
+        let mut nonce = [0u8; aead::NONCE_LEN];
+        self.rng
+            .fill(&mut nonce)
+            .expect("system randomness is available");
+        let mut sealed = secret.as_bytes().to_vec();
+        self.key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::empty(),
+                &mut sealed,
+            )
+            .expect("secret is within the algorithm's length limit");
+        let mut encoded = nonce.to_vec();
+        encoded.extend(sealed);
+        format!("{SEALED_PREFIX}{}", STANDARD.encode(encoded))
+    }
+
+    /// Decrypt a secret sealed with the same key.
+    ///
+    /// Returns `None` if the secret was not sealed or was sealed with another key.
+    fn open(&self, sealed: &str) -> Option<String> {
+        let encoded = STANDARD.decode(sealed.strip_prefix(SEALED_PREFIX)?).ok()?;
+        if encoded.len() < aead::NONCE_LEN {
+            return None;
+        }
+        let (nonce, ciphertext) = encoded.split_at(aead::NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce).ok()?;
+        let mut ciphertext = ciphertext.to_vec();
+        let secret = self
+            .key
+            .open_in_place(nonce, Aad::empty(), &mut ciphertext)
+            .ok()?;
+        String::from_utf8(secret.to_vec()).ok()
+    }
+}
+
+/// Records and delivers callbacks of finished runs.
+pub(crate) struct WebhookDispatcher {
+    state_store: Arc<dyn StateStore>,
+    client: reqwest::Client,
+    options: WebhookOptions,
+    /// Seals callback secrets. Callbacks are only accepted if a key is configured.
+    sealer: Option<SecretSealer>,
+    /// Wakes the delivery task when a delivery is recorded.
+    wake: Notify,
+}
+
+impl WebhookDispatcher {
+    /// Create a dispatcher and spawn its delivery task.
+    ///
+    /// Must be called within a Tokio runtime. Deliveries left pending by an
+    /// earlier process are delivered as well.
+    pub fn start(state_store: Arc<dyn StateStore>, options: WebhookOptions) -> Arc<Self> {
+        let resolver = PublicResolver {
+            allowed_hosts: options.allowed_hosts.clone().into(),
+        };
+        // Redirects are not followed, since they could lead to internal addresses.
+        let client = reqwest::Client::builder()
+            .timeout(options.request_timeout)
+            .redirect(reqwest::redirect::Policy::none())
+            .dns_resolver(Arc::new(resolver))
+            .build()
+            .expect("HTTP client configuration is valid");
+        let sealer = options.secret_key.as_deref().map(SecretSealer::new);
+        let dispatcher = Arc::new(Self {
+            state_store,
+            client,
+            options,
+            sealer,
+            wake: Notify::new(),
+        });
+        tokio::spawn(dispatcher.clone().deliver_forever());
+        dispatcher
+    }
+
+    /// Check that a callback can be delivered, and encrypt its secret, if
+    /// any, before it is stored with the run.
+    pub fn prepare_callback(&self, callback: &mut RunCallback) -> Result<(), ServerError> {
+        let Some(sealer) = &self.sealer else {
+            return Err(ServerError::InvalidCallback(
+                "callbacks are not enabled, since no webhooks secret key is configured".to_string(),
+            ));
+        };
+        validate_callback(callback, &self.options.allowed_hosts)?;
+        if let Some(secret) = &mut callback.secret {
+            *secret = sealer.seal(secret);
+        }
+        Ok(())
+    }
+
+    /// Deliver deliveries recorded since the last poll without waiting for the next one.
+    ///
+    /// Deliveries are recorded in the state store when runs finish.
+    pub fn wake(&self) {
+        self.wake.notify_one();
+    }
+
+    /// Deliver pending deliveries as they become due.
+    async fn deliver_forever(self: Arc<Self>) {
+        loop {
+            let wait = match self.deliver_due().await {
+                Ok(Some(next_attempt_at)) => (next_attempt_at - Utc::now())
+                    .to_std()
+                    .unwrap_or(Duration::ZERO),
+                Ok(None) => IDLE_POLL_INTERVAL,
+                Err(error) => {
+                    tracing::warn!(?error, "Failed to load pending webhook deliveries");
+                    IDLE_POLL_INTERVAL
+                }
+            };
+            tokio::select! {
+                _ = self.wake.notified() => {}
+                _ = tokio::time::sleep(wait) => {}
+            }
+        }
+    }
+
+    /// Attempt every delivery that is due, up to `max_concurrent_deliveries` at once.
+    ///
+    /// Returns when the next pending delivery is due, if any.
+    async fn deliver_due(&self) -> error_stack::Result<Option<chrono::DateTime<Utc>>, StateError> {
+        let pending = self
+            .state_store
+            .list_webhook_deliveries(None, Some(DeliveryStatus::Pending))
+            .await?;
+
+        let now = Utc::now();
+        let (due, mut waiting): (Vec<_>, Vec<_>) = pending
+            .into_iter()
+            .partition(|delivery| delivery.next_attempt_at <= now);
+        let attempted: Vec<_> = futures::stream::iter(due)
+            .map(|mut delivery| async move {
+                self.attempt(&mut delivery).await;
+                if let Err(error) = self
+                    .state_store
+                    .put_webhook_delivery(delivery.clone())
+                    .await
+                {
+                    tracing::warn!(
+                        delivery_id = %delivery.delivery_id,
+                        ?error,
+                        "Failed to record webhook delivery attempt"
+                    );
+                }
+                delivery
+            })
+            .buffer_unordered(self.options.max_concurrent_deliveries.max(1))
+            .collect()
+            .await;
+        waiting.extend(attempted);
+
+        Ok(waiting
+            .iter()
+            .filter(|delivery| delivery.status == DeliveryStatus::Pending)
+            .map(|delivery| delivery.next_attempt_at)
+            .min())
+    }
+
+    /// Send a delivery once, updating its status with the outcome.
+    async fn attempt(&self, delivery: &mut WebhookDelivery) {
+        delivery.attempts += 1;
+        match self.send(delivery).await {
+            Ok(()) => {
+                tracing::debug!(
+                    delivery_id = %delivery.delivery_id,
+                    run_id = %delivery.run_id,
+                    "Delivered run callback"
+                );
+                delivery.status = DeliveryStatus::Delivered;
+                delivery.last_error = None;
+            }
+            Err(error) => {
+                tracing::warn!(
+                    delivery_id = %delivery.delivery_id,
+                    run_id = %delivery.run_id,
+                    attempts = delivery.attempts,
+                    %error,
+                    "Failed to deliver run callback"
+                );
+                if delivery.attempts >= self.options.max_attempts {
+                    delivery.status = DeliveryStatus::Failed;
+                } else {
+                    let backoff = self.options.backoff(delivery.attempts);
+                    delivery.next_attempt_at = Utc::now()
+                        + chrono::Duration::from_std(backoff).unwrap_or(chrono::Duration::MAX);
+                }
+                delivery.last_error = Some(error);
+            }
+        }
+    }
+
+    /// POST the delivery's payload to its callback.
+    async fn send(&self, delivery: &WebhookDelivery) -> Result<(), String> {
+        // Hosts given as addresses are not resolved, so are checked here.
+        let url = reqwest::Url::parse(&delivery.callback.url).map_err(|e| e.to_string())?;
+        check_host(&url, &self.options.allowed_hosts)?;
+        let body = serde_json::to_vec(&delivery.payload).map_err(|e| e.to_string())?;
+
+        let mut request = self
+            .client
+            .post(url)
+            .header(axum::http::header::CONTENT_TYPE, "application/json")
+            .header(DELIVERY_HEADER, delivery.delivery_id.to_string());
+        for (name, value) in &delivery.callback.headers {
+            request = request.header(name, value);
+        }
+        if let Some(sealed) = &delivery.callback.secret {
+            // Never send a delivery unsigned if its secret is unavailable.
+            let secret = self.sealer.as_ref().and_then(|sealer| sealer.open(sealed));
+            let secret = secret.ok_or_else(|| {
+                "callback secret cannot be decrypted with the configured secret key".to_string()
+            })?;
+            request = request.header(SIGNATURE_HEADER, sign(&secret, &body));
+        }
+
+        let response = request.body(body).send().await.map_err(|e| e.to_string())?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("receiver responded with {}", response.status()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        let options = WebhookOptions {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(5),
+            ..Default::default()
+        };
+        assert_eq!(options.backoff(1), Duration::from_secs(1));
+        assert_eq!(options.backoff(2), Duration::from_secs(2));
+        assert_eq!(options.backoff(3), Duration::from_secs(4));
+        assert_eq!(options.backoff(4), Duration::from_secs(5));
+        assert_eq!(options.backoff(40), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_seal_secret() {
+        let sealer = SecretSealer::new("server key");
+        let sealed = sealer.seal("callback secret");
+        assert!(sealed.starts_with(SEALED_PREFIX));
+        assert!(!sealed.contains("callback secret"));
+        assert_ne!(sealed, sealer.seal("callback secret"));
+        assert_eq!(sealer.open(&sealed).as_deref(), Some("callback secret"));
+
+        // The same configured key opens secrets sealed by an earlier process.
+        let restarted = SecretSealer::new("server key");
+        assert_eq!(restarted.open(&sealed).as_deref(), Some("callback secret"));
+
+        // Other keys, tampered values and plaintext secrets are rejected.
+        assert_eq!(SecretSealer::new("other key").open(&sealed), None);
+        let mut tampered = sealed.into_bytes();
+        let last = tampered.len() - 3;
+        tampered[last] = if tampered[last] == b'A' { b'B' } else { b'A' };
+        assert_eq!(sealer.open(&String::from_utf8(tampered).unwrap()), None);
+        assert_eq!(sealer.open("callback secret"), None);
+    }
+
+    #[test]
+    fn test_validate_callback() {
+        let callback = |url: &str| RunCallback {
+            url: url.to_string(),
+            headers: Default::default(),
+            secret: None,
+        };
+        let validate = |url: &str| validate_callback(&callback(url), &[]);
+        assert!(validate("https://example.com/hook").is_ok());
+        assert!(validate("http://93.184.216.34/hook").is_ok());
+        assert!(validate("ftp://example.com/hook").is_err());
+        assert!(validate("not a url").is_err());
+
+        // Internal addresses are rejected unless their host is allowed
+        for url in [
+            "http://localhost/hook",
+            "http://api.localhost/hook",
+            "http://127.0.0.1:8080/hook",
+            "http://10.1.2.3/hook",
+            "http://172.16.0.1/hook",
+            "http://192.168.1.1/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://100.64.0.1/hook",
+            "http://0.0.0.0/hook",
+            "http://[::1]/hook",
+            "http://[fd00::1]/hook",
+            "http://[fe80::1]/hook",
+            "http://[::ffff:127.0.0.1]/hook",
+        ] {
+            assert!(validate(url).is_err(), "{url} should be rejected");
+        }
+        let allowed = ["localhost".to_string(), "::1".to_string()];
+        assert!(validate_callback(&callback("http://localhost:3000/hook"), &allowed).is_ok());
+        assert!(validate_callback(&callback("http://[::1]/hook"), &allowed).is_ok());
+        assert!(validate_callback(&callback("http://127.0.0.1/hook"), &allowed).is_err());
+
+        let mut bad_header = callback("https://example.com/hook");
+        bad_header
+            .headers
+            .insert("bad header".to_string(), "value".to_string());
+        assert!(validate_callback(&bad_header, &[]).is_err());
+    }
+}
@@ -17,13 +17,14 @@ use serde_json::json;
 use std::sync::Arc;
 use stepflow_core::values::ValueTemplate;
 use stepflow_core::{
-    FlowResult,
+    BlobId, BlobType, FlowResult,
     workflow::{Flow, FlowBuilder, StepBuilder},
 };
 use stepflow_execution::StepflowExecutor;
 use stepflow_mock::MockPlugin;
-use stepflow_plugin::DynPlugin;
-use stepflow_state::InMemoryStateStore;
+use stepflow_plugin::{Context as _, DynPlugin};
+use stepflow_server::AuthConfig;
+use stepflow_state::{DEFAULT_NAMESPACE, InMemoryStateStore, LogLevel, StepLog, StepProgress};
 use tower::ServiceExt as _;
 use tracing_subscriber::EnvFilter;
 use tracing_subscriber::layer::SubscriberExt as _;
@@ -46,7 +47,10 @@ pub fn init_test_logging() {
 }
 
 /// Helper to create a test server with in-memory state and optional mock plugins
-async fn create_test_server(include_mocks: bool) -> (Router, Arc<StepflowExecutor>) {
+async fn create_test_server(
+    include_mocks: bool,
+    auth: Option<AuthConfig>,
+) -> (Router, Arc<StepflowExecutor>) {
     use stepflow_core::FlowError;
     use stepflow_mock::MockComponentBehavior;
 
@@ -145,6 +149,15 @@ async fn create_test_server(include_mocks: bool) -> (Router, Arc<StepflowExecuto
     let config = AppConfig {
         include_swagger: false, // Skip swagger for tests to keep them fast
         include_cors: true,     // Keep CORS for test compatibility
+        include_metrics: true,
+        auth,
+        // Retry failed callbacks quickly, and deliver them to the local test receiver
+        webhooks: stepflow_server::WebhookOptions {
+            initial_backoff: std::time::Duration::from_millis(10),
+            secret_key: Some("test key".to_string()),
+            allowed_hosts: vec!["127.0.0.1".to_string()],
+            ..Default::default()
+        },
     };
 
     let app = config.create_app_router(executor.clone(), 7837);
@@ -154,12 +167,12 @@ async fn create_test_server(include_mocks: bool) -> (Router, Arc<StepflowExecuto
 
 /// Helper to create a test server with only builtin components
 async fn create_basic_test_server() -> (Router, Arc<StepflowExecutor>) {
-    create_test_server(false).await
+    create_test_server(false, None).await
 }
 
 /// Helper to create a test server with both builtin and mock components
 async fn create_test_server_with_mocks() -> (Router, Arc<StepflowExecutor>) {
-    create_test_server(true).await
+    create_test_server(true, None).await
 }
 
 /// Helper to create a simple test workflow
@@ -199,13 +212,132 @@ async fn test_health_endpoint() {
     assert_eq!(health_response["status"], "healthy");
     assert!(health_response["timestamp"].is_string());
     assert!(health_response["version"].is_string());
+    assert_eq!(
+        health_response["plugins"]["builtin"],
+        json!({ "status": "healthy", "restarts": 0 })
+    );
+}
+
+#[tokio::test]
+async fn test_metrics_endpoint() {
+    init_test_logging();
+    stepflow_server::install_metrics_recorder();
+    stepflow_execution::describe_metrics();
+
+    let (app, executor) = create_basic_test_server().await;
+
+    let flow = Arc::new(create_test_workflow());
+    let flow_id = stepflow_core::BlobId::from_flow(&flow).unwrap();
+    executor
+        .execute_flow(flow, flow_id, json!({}).into())
+        .await
+        .unwrap();
+
+    let request = Request::builder()
+        .uri("/metrics")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/plain; version=0.0.4"
+    );
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let metrics = String::from_utf8(body.to_vec()).unwrap();
+
+    // Other tests may record metrics concurrently, so only check for presence.
+    assert!(metrics.contains("# HELP stepflow_runs_started_total"));
+    assert!(metrics.contains(r#"stepflow_runs_started_total{flow_name="test_workflow"}"#));
+    assert!(metrics.contains(r#"stepflow_runs_completed_total{flow_name="test_workflow"}"#));
+    assert!(metrics.contains(
+        r#"stepflow_step_duration_seconds_bucket{component="/builtin/create_messages",plugin="builtin",le="0.005"}"#
+    ));
+    assert!(metrics.contains("stepflow_steps_in_flight"));
+}
+
+#[tokio::test]
+async fn test_get_blob_endpoint() {
+    init_test_logging();
+
+    let (app, executor) = create_basic_test_server().await;
+
+    let content = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+    let blob_id = executor
+        .state_store()
+        .put_binary_blob(content.clone(), "image/png".to_string())
+        .await
+        .unwrap();
+    executor
+        .state_store()
+        .add_blob_to_namespace(DEFAULT_NAMESPACE, &blob_id)
+        .await
+        .unwrap();
+
+    let request = Request::builder()
+        .uri(format!("/api/v1/blobs/{blob_id}"))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let headers = response.headers();
+    assert_eq!(headers["content-type"], "image/png");
+    assert_eq!(headers["content-length"], content.len().to_string());
+    let etag = headers["etag"].clone();
+    assert_eq!(etag, format!("\"{blob_id}\""));
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(body.as_ref(), content.as_slice());
+
+    // A matching ETag short-circuits the body.
+    let request = Request::builder()
+        .uri(format!("/api/v1/blobs/{blob_id}"))
+        .header("if-none-match", etag)
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+    // JSON blobs are served as JSON.
+    let json_blob_id = executor
+        .state_store()
+        .put_blob(json!({"hello": "world"}).into(), BlobType::Data)
+        .await
+        .unwrap();
+    executor
+        .state_store()
+        .add_blob_to_namespace(DEFAULT_NAMESPACE, &json_blob_id)
+        .await
+        .unwrap();
+    let request = Request::builder()
+        .uri(format!("/api/v1/blobs/{json_blob_id}"))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/json");
+
+    // Unknown blobs are reported as not found.
+    let request = Request::builder()
+        .uri(format!("/api/v1/blobs/{}", "0".repeat(64)))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
 }
 
 #[tokio::test]
 async fn test_flow_crud_operations() {
     init_test_logging();
 
-    let (app, _executor) = create_basic_test_server().await;
+    let (app, executor) = create_basic_test_server().await;
     let workflow = create_test_workflow();
 
     // Store flow
@@ -232,6 +364,10 @@ async fn test_flow_crud_operations() {
     let flow_id = store_response["flowId"].as_str().unwrap();
     assert!(!flow_id.is_empty());
 
+    // Storing the flow computes its analysis once and caches it
+    let blob_id = BlobId::new(flow_id.to_string()).unwrap();
+    let cached = executor.analysis_cache().get(&blob_id).unwrap();
+
     // Get flow
     let get_request = Request::builder()
         .uri(format!("/api/v1/flows/{flow_id}"))
@@ -253,6 +389,10 @@ async fn test_flow_crud_operations() {
     assert!(get_response["analysis"].is_object());
     assert_eq!(get_response["analysis"]["flowId"], flow_id);
     assert!(get_response["analysis"]["steps"].is_object());
+    assert!(Arc::ptr_eq(
+        &cached,
+        &executor.analysis_cache().get(&blob_id).unwrap()
+    ));
 }
 
 #[tokio::test]
@@ -315,6 +455,1402 @@ async fn test_hash_based_execution() {
     assert!(execute_response["result"]["result"].is_null());
 }
 
+/// Helper to send a JSON request and parse the JSON response, if any
+async fn send_json(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    body: Option<serde_json::Value>,
+) -> (StatusCode, serde_json::Value) {
+    send_json_with_headers(app, method, uri, body, &[]).await
+}
+
+/// Helper to send a JSON request with extra headers and parse the JSON response, if any
+async fn send_json_with_headers(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    body: Option<serde_json::Value>,
+    headers: &[(&str, &str)],
+) -> (StatusCode, serde_json::Value) {
+    let mut request = Request::builder()
+        .uri(uri)
+        .method(method)
+        .header("content-type", "application/json");
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    let request = request
+        .body(match body {
+            Some(body) => Body::from(serde_json::to_string(&body).unwrap()),
+            None => Body::empty(),
+        })
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body = serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null);
+    (status, body)
+}
+
+#[tokio::test]
+async fn test_authentication_and_scopes() {
+    use base64::Engine as _;
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use hmac::{Hmac, Mac as _};
+
+    init_test_logging();
+
+    let auth: AuthConfig = serde_json::from_value(json!({
+        "apiKeys": [
+            {"key": "reader-key", "identity": "reader", "scopes": ["flows:read", "runs:read"]},
+            {"key": "admin-key", "identity": "admin", "scopes": ["*"]},
+            {"key": "team-key", "identity": "team", "scopes": ["*"], "namespaces": ["team-a"]},
+        ],
+        "jwt": {"secret": "jwt-secret", "issuer": "tests"},
+    }))
+    .unwrap();
+    let (app, _executor) = create_test_server(false, Some(auth)).await;
+    let reader = [("x-api-key", "reader-key")];
+    let admin = [("authorization", "Bearer admin-key")];
+
+    // The health check is public, other routes need credentials
+    let (status, _) = send_json(&app, "GET", "/api/v1/health", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send_json(&app, "GET", "/metrics", None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = send_json_with_headers(&app, "GET", "/metrics", None, &admin).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send_json_with_headers(&app, "GET", "/metrics", None, &reader).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    // Metrics cover every namespace
+    let (status, _) =
+        send_json_with_headers(&app, "GET", "/metrics", None, &[("x-api-key", "team-key")]).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send_json(&app, "GET", "/api/v1/runs", None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let bad_key = [("x-api-key", "wrong-key")];
+    let (status, _) = send_json_with_headers(&app, "GET", "/api/v1/runs", None, &bad_key).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // Routes require their scope
+    let workflow = create_test_workflow();
+    let store = json!({"flow": workflow});
+    let (status, _) =
+        send_json_with_headers(&app, "POST", "/api/v1/flows", Some(store.clone()), &reader).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, body) =
+        send_json_with_headers(&app, "POST", "/api/v1/flows", Some(store), &admin).await;
+    assert_eq!(status, StatusCode::OK);
+    let flow_id = body["flowId"].as_str().unwrap().to_string();
+
+    // The caller's identity is recorded on label changes, overriding the request
+    let (status, _) = send_json_with_headers(
+        &app,
+        "PUT",
+        "/api/v1/flows/test_workflow/labels/production",
+        Some(json!({"flowId": flow_id, "actor": "someone-else"})),
+        &admin,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, history) = send_json_with_headers(
+        &app,
+        "GET",
+        "/api/v1/flows/test_workflow/labels/production/history",
+        None,
+        &reader,
+    )
+    .await;
+    assert_eq!(history["history"][0]["actor"], "admin");
+
+    // JWT bearer tokens carry the identity and scopes of their claims
+    let sign = |claims: serde_json::Value| {
+        let header = URL_SAFE_NO_PAD.encode(r#"{"alg":"HS256","typ":"JWT"}"#);
+        let payload = URL_SAFE_NO_PAD.encode(claims.to_string());
+        let mut mac = Hmac::<sha2::Sha256>::new_from_slice(b"jwt-secret").unwrap();
+        mac.update(format!("{header}.{payload}").as_bytes());
+        let signature = URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+        format!("Bearer {header}.{payload}.{signature}")
+    };
+    let exp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        + 3600;
+    let token = sign(json!({
+        "sub": "ci-bot",
+        "scope": "runs:create runs:read",
+        "iss": "tests",
+        "exp": exp,
+    }));
+    let jwt = [("authorization", token.as_str())];
+    let (status, run) = send_json_with_headers(
+        &app,
+        "POST",
+        "/api/v1/runs",
+        Some(json!({"flowId": flow_id, "input": {}})),
+        &jwt,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let run_id = run["runId"].as_str().unwrap();
+    let (_, details) =
+        send_json_with_headers(&app, "GET", &format!("/api/v1/runs/{run_id}"), None, &jwt).await;
+    assert_eq!(details["createdBy"], "ci-bot");
+    let (status, _) = send_json_with_headers(
+        &app,
+        "DELETE",
+        &format!("/api/v1/runs/{run_id}"),
+        None,
+        &jwt,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let expired = sign(json!({"sub": "ci-bot", "scope": "*", "iss": "tests", "exp": 1}));
+    let (status, _) = send_json_with_headers(
+        &app,
+        "GET",
+        "/api/v1/runs",
+        None,
+        &[("authorization", expired.as_str())],
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // Credentials restricted to namespaces may only access those namespaces
+    let team = [("x-api-key", "team-key")];
+    let (status, _) = send_json_with_headers(&app, "GET", "/api/v1/runs", None, &team).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) =
+        send_json_with_headers(&app, "GET", "/api/v1/namespaces/team-a/runs", None, &team).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_namespace_isolation() {
+    init_test_logging();
+
+    let (app, executor) = create_basic_test_server().await;
+
+    // Store and label a flow in a namespace
+    let (status, body) = send_json(
+        &app,
+        "POST",
+        "/api/v1/namespaces/team-a/flows",
+        Some(json!({"flow": create_test_workflow()})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let flow_id = body["flowId"].as_str().unwrap().to_string();
+    let (status, _) = send_json(
+        &app,
+        "PUT",
+        "/api/v1/namespaces/team-a/flows/test_workflow/labels/production",
+        Some(json!({"flowId": flow_id})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // The flow name is only visible in its namespace
+    let (_, names) = send_json(&app, "GET", "/api/v1/namespaces/team-a/flows", None).await;
+    assert_eq!(names["names"], json!(["test_workflow"]));
+    let (_, names) = send_json(&app, "GET", "/api/v1/flows", None).await;
+    assert_eq!(names["names"], json!([]));
+    let (status, _) = send_json(
+        &app,
+        "PUT",
+        "/api/v1/flows/test_workflow/labels/production",
+        Some(json!({"flowId": flow_id})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Flows and blobs are not found by ID from other namespaces
+    let (status, _) = send_json(
+        &app,
+        "GET",
+        &format!("/api/v1/namespaces/team-a/flows/{flow_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send_json(&app, "GET", &format!("/api/v1/flows/{flow_id}"), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send_json(
+        &app,
+        "POST",
+        "/api/v1/runs",
+        Some(json!({"flowId": flow_id, "input": {}})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send_json(
+        &app,
+        "POST",
+        "/api/v1/runs/batch",
+        Some(json!({"flowId": flow_id, "inputs": [{}]})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send_json(
+        &app,
+        "GET",
+        &format!("/api/v1/namespaces/team-a/blobs/{flow_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send_json(&app, "GET", &format!("/api/v1/blobs/{flow_id}"), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let blob_id = executor
+        .state_store()
+        .put_blob(json!({"secret": "team-a"}).into(), BlobType::Data)
+        .await
+        .unwrap();
+    executor
+        .state_store()
+        .add_blob_to_namespace("team-a", &blob_id)
+        .await
+        .unwrap();
+    let (status, _) = send_json(
+        &app,
+        "GET",
+        &format!("/api/v1/namespaces/team-a/blobs/{blob_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send_json(&app, "GET", &format!("/api/v1/blobs/{blob_id}"), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Runs are only visible in the namespace they were created in
+    let (status, run) = send_json(
+        &app,
+        "POST",
+        "/api/v1/namespaces/team-a/flows/test_workflow/labels/production/runs",
+        Some(json!({"input": {}})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let run_id = run["runId"].as_str().unwrap();
+    let (status, details) = send_json(
+        &app,
+        "GET",
+        &format!("/api/v1/namespaces/team-a/runs/{run_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(details["namespace"], "team-a");
+    let (status, _) = send_json(&app, "GET", &format!("/api/v1/runs/{run_id}"), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (_, runs) = send_json(&app, "GET", "/api/v1/runs", None).await;
+    assert!(
+        !runs["runs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|run| run["runId"] == run_id)
+    );
+
+    // Invalid namespace names are not routed
+    let (status, _) = send_json(&app, "GET", "/api/v1/namespaces/team.a/flows", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_namespace_quota() {
+    use std::collections::HashMap;
+    use stepflow_execution::{ExecutorOptions, NamespaceSettings};
+
+    init_test_logging();
+
+    let plugin_router = stepflow_plugin::routing::PluginRouter::builder()
+        .register_plugin(
+            "builtin".to_string(),
+            DynPlugin::boxed(stepflow_builtins::Builtins::new()),
+        )
+        .build()
+        .unwrap();
+    let namespaces = HashMap::from([(
+        "frozen".to_string(),
+        NamespaceSettings {
+            max_active_runs: Some(0),
+            ..Default::default()
+        },
+    )]);
+    let executor = StepflowExecutor::with_namespaces(
+        Arc::new(InMemoryStateStore::new()),
+        std::path::PathBuf::from("."),
+        plugin_router,
+        ExecutorOptions::default(),
+        namespaces,
+    );
+    let app = stepflow_server::AppConfig {
+        include_swagger: false,
+        ..Default::default()
+    }
+    .create_app_router(executor.clone(), 7837);
+
+    let flow_id = executor
+        .state_store()
+        .store_flow("frozen", Arc::new(create_test_workflow()))
+        .await
+        .unwrap();
+    let (status, body) = send_json(
+        &app,
+        "POST",
+        "/api/v1/namespaces/frozen/runs",
+        Some(json!({"flowId": flow_id, "input": {}})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    assert!(body["message"].as_str().unwrap().contains("frozen"));
+
+    // Refused runs are not recorded
+    let (_, runs) = send_json(&app, "GET", "/api/v1/namespaces/frozen/runs", None).await;
+    assert_eq!(runs["runs"], json!([]));
+
+    // Runs of a batch that are refused are recorded as failed items of the batch
+    let (status, batch) = send_json(
+        &app,
+        "POST",
+        "/api/v1/namespaces/frozen/runs/batch",
+        Some(json!({"flowId": flow_id, "inputs": [{}, {}]})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let batch_id = batch["batchId"].as_str().unwrap();
+    let batch = wait_for_batch(
+        &app,
+        &format!("/api/v1/namespaces/frozen/runs/batch/{batch_id}"),
+    )
+    .await;
+    assert_eq!(batch["status"], "failed");
+    assert_eq!(batch["failed"], 2);
+    let runs = batch["runs"].as_array().unwrap();
+    assert!(runs.iter().all(|run| {
+        run["status"] == "failed" && run["error"].as_str().unwrap().contains("frozen")
+    }));
+}
+
+/// Poll the batch at `path` until none of its runs are running.
+async fn wait_for_batch(app: &Router, path: &str) -> serde_json::Value {
+    for _ in 0..100 {
+        let (status, batch) = send_json(app, "GET", path, None).await;
+        assert_eq!(status, StatusCode::OK);
+        if batch["status"] != "running" {
+            return batch;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    panic!("Batch at '{path}' did not finish");
+}
+
+#[tokio::test]
+async fn test_batch_execution() {
+    init_test_logging();
+
+    let (app, _executor) = create_basic_test_server().await;
+    let (_, stored) = send_json(
+        &app,
+        "POST",
+        "/api/v1/flows",
+        Some(json!({"flow": create_test_workflow()})),
+    )
+    .await;
+    let flow_id = stored["flowId"].as_str().unwrap();
+
+    let (status, batch) = send_json(
+        &app,
+        "POST",
+        "/api/v1/runs/batch",
+        Some(json!({
+            "flowId": flow_id,
+            "inputs": [{"n": 1}, {"n": 2}, {"n": 3}],
+            "maxConcurrency": 2
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert_eq!(batch["total"], 3);
+    let batch_id = batch["batchId"].as_str().unwrap();
+    let run_ids: Vec<_> = batch["runs"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|run| run["runId"].clone())
+        .collect();
+    assert_eq!(run_ids.len(), 3);
+
+    // The batch finishes in the background, under the run IDs returned
+    let batch = wait_for_batch(&app, &format!("/api/v1/runs/batch/{batch_id}")).await;
+    assert_eq!(batch["status"], "completed");
+    assert_eq!(batch["total"], 3);
+    assert_eq!(batch["running"], 0);
+    assert_eq!(batch["completed"], 3);
+    assert_eq!(batch["failed"], 0);
+    let runs = batch["runs"].as_array().unwrap();
+    assert!(runs.iter().all(|run| run["status"] == "completed"));
+    assert_eq!(
+        runs.iter()
+            .map(|run| run["runId"].clone())
+            .collect::<Vec<_>>(),
+        run_ids
+    );
+
+    // The batch is not found from other namespaces
+    let (status, _) = send_json(
+        &app,
+        "GET",
+        &format!("/api/v1/namespaces/other/runs/batch/{batch_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // The runs of the batch can be listed by batch ID, and record their inputs
+    let (_, listed) = send_json(
+        &app,
+        "GET",
+        &format!("/api/v1/runs?batchId={batch_id}"),
+        None,
+    )
+    .await;
+    let listed = listed["runs"].as_array().unwrap();
+    assert_eq!(listed.len(), 3);
+    assert!(listed.iter().all(|run| run["batchId"] == batch_id));
+    for (index, run) in runs.iter().enumerate() {
+        let run_id = run["runId"].as_str().unwrap();
+        let (_, details) = send_json(&app, "GET", &format!("/api/v1/runs/{run_id}"), None).await;
+        assert_eq!(details["input"], json!({"n": index + 1}));
+    }
+
+    // Other runs are not part of the batch
+    send_json(
+        &app,
+        "POST",
+        "/api/v1/runs",
+        Some(json!({"flowId": flow_id, "input": {}})),
+    )
+    .await;
+    let (_, listed) = send_json(
+        &app,
+        "GET",
+        &format!("/api/v1/runs?batchId={batch_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(listed["runs"].as_array().unwrap().len(), 3);
+
+    // Each run is recorded once, under the ID returned when it was created
+    let (_, listed) = send_json(&app, "GET", "/api/v1/runs", None).await;
+    assert_eq!(listed["runs"].as_array().unwrap().len(), 4);
+}
+
+#[tokio::test]
+async fn test_run_tree() {
+    init_test_logging();
+
+    let (app, _executor) = create_basic_test_server().await;
+    let (_, stored) = send_json(
+        &app,
+        "POST",
+        "/api/v1/flows",
+        Some(json!({"flow": create_test_workflow()})),
+    )
+    .await;
+    let inner_flow_id = stored["flowId"].as_str().unwrap();
+
+    // A flow whose step evaluates the inner flow as a nested run
+    let outer_flow = FlowBuilder::new()
+        .name("outer")
+        .step(
+            StepBuilder::builtin_step("nested", "eval")
+                .input_literal(json!({"flow_id": inner_flow_id, "input": {}}))
+                .build(),
+        )
+        .output(ValueTemplate::step_ref("nested", Default::default()))
+        .build();
+    let (_, stored) = send_json(
+        &app,
+        "POST",
+        "/api/v1/flows",
+        Some(json!({"flow": outer_flow})),
+    )
+    .await;
+    let (status, run) = send_json(
+        &app,
+        "POST",
+        "/api/v1/runs",
+        Some(json!({"flowId": stored["flowId"], "input": {}})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(run["status"], "completed");
+    let run_id = run["runId"].as_str().unwrap();
+
+    let (status, tree) = send_json(&app, "GET", &format!("/api/v1/runs/{run_id}/tree"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(tree["runId"], run_id);
+    assert_eq!(tree["rootRunId"], run_id);
+    assert!(tree.get("parentRunId").is_none());
+    let children = tree["children"].as_array().unwrap();
+    assert_eq!(children.len(), 1);
+    let child = &children[0];
+    assert_eq!(child["parentRunId"], run_id);
+    assert_eq!(child["parentStepId"], "nested");
+    assert_eq!(child["rootRunId"], run_id);
+    assert_eq!(child["flowId"], inner_flow_id);
+    assert_eq!(child["children"], json!([]));
+
+    // The tree can be fetched from any run within it
+    let child_id = child["runId"].as_str().unwrap();
+    let (_, from_child) =
+        send_json(&app, "GET", &format!("/api/v1/runs/{child_id}/tree"), None).await;
+    assert_eq!(from_child, tree);
+
+    let (status, _) = send_json(
+        &app,
+        "GET",
+        &format!("/api/v1/runs/{}/tree", uuid::Uuid::new_v4()),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+/// A plugin whose components wait for a permit before echoing their input.
+struct GatedPlugin {
+    permits: Arc<tokio::sync::Semaphore>,
+}
+
+impl stepflow_plugin::Plugin for GatedPlugin {
+    async fn init(
+        &self,
+        _context: &Arc<dyn stepflow_plugin::Context>,
+    ) -> stepflow_plugin::Result<()> {
+        Ok(())
+    }
+
+    async fn list_components(
+        &self,
+    ) -> stepflow_plugin::Result<Vec<stepflow_core::component::ComponentInfo>> {
+        Ok(vec![])
+    }
+
+    async fn component_info(
+        &self,
+        component: &stepflow_core::workflow::Component,
+    ) -> stepflow_plugin::Result<stepflow_core::component::ComponentInfo> {
+        Ok(stepflow_core::component::ComponentInfo {
+            component: component.clone(),
+            description: None,
+            input_schema: None,
+            output_schema: None,
+        })
+    }
+
+    async fn execute(
+        &self,
+        _component: &stepflow_core::workflow::Component,
+        _context: stepflow_plugin::ExecutionContext,
+        input: stepflow_core::workflow::ValueRef,
+    ) -> stepflow_plugin::Result<FlowResult> {
+        self.permits.acquire().await.unwrap().forget();
+        Ok(FlowResult::Success(input))
+    }
+}
+
+#[tokio::test]
+async fn test_run_modes() {
+    use stepflow_plugin::routing::{RouteRule, RoutingConfig};
+
+    init_test_logging();
+
+    let permits = Arc::new(tokio::sync::Semaphore::new(0));
+    let routes = std::collections::HashMap::from([(
+        "/gate/{*component}".to_string(),
+        vec![RouteRule {
+            conditions: vec![],
+            component_allow: None,
+            component_deny: None,
+            plugin: "gate".into(),
+            component: None,
+        }],
+    )]);
+    let plugin_router = stepflow_plugin::routing::PluginRouter::builder()
+        .register_plugin(
+            "gate".to_string(),
+            DynPlugin::boxed(GatedPlugin {
+                permits: permits.clone(),
+            }),
+        )
+        .with_routing_config(RoutingConfig { routes })
+        .build()
+        .unwrap();
+    let executor = StepflowExecutor::new(
+        Arc::new(InMemoryStateStore::new()),
+        std::path::PathBuf::from("."),
+        plugin_router,
+    );
+    let app = stepflow_server::AppConfig {
+        include_swagger: false,
+        ..Default::default()
+    }
+    .create_app_router(executor.clone(), 7837);
+
+    let flow = FlowBuilder::test_flow()
+        .step(
+            StepBuilder::new("gate")
+                .component("/gate/wait")
+                .input_literal(json!({"gated": true}))
+                .build(),
+        )
+        .output(ValueTemplate::step_ref("gate", Default::default()))
+        .build();
+    let (_, stored) = send_json(&app, "POST", "/api/v1/flows", Some(json!({"flow": flow}))).await;
+    let flow_id = stored["flowId"].as_str().unwrap().to_string();
+
+    // Send a run request, returning the status, Location header and body
+    let create_run = |mode: serde_json::Value| {
+        let app = app.clone();
+        let flow_id = flow_id.clone();
+        async move {
+            let mut body = json!({"flowId": flow_id, "input": {}});
+            body.as_object_mut()
+                .unwrap()
+                .extend(mode.as_object().unwrap().clone());
+            let request = Request::builder()
+                .uri("/api/v1/runs")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            let response = app.oneshot(request).await.unwrap();
+            let status = response.status();
+            let location = response
+                .headers()
+                .get("location")
+                .map(|location| location.to_str().unwrap().to_string());
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            (status, location, body)
+        }
+    };
+
+    // Poll a run until it is no longer running
+    let poll = |location: String| {
+        let app = app.clone();
+        async move {
+            for _ in 0..100 {
+                let (_, run) = send_json(&app, "GET", &location, None).await;
+                if run["status"] != "running" {
+                    return run;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            }
+            panic!("run at {location} did not finish");
+        }
+    };
+
+    // Async runs are accepted immediately
+    let (status, location, run) = create_run(json!({"mode": "async"})).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert_eq!(run["status"], "running");
+    let location = location.unwrap();
+    assert_eq!(
+        location,
+        format!("/api/v1/runs/{}", run["runId"].as_str().unwrap())
+    );
+    let (_, pending) = send_json(&app, "GET", &location, None).await;
+    assert_eq!(pending["status"], "running");
+    permits.add_permits(1);
+    let finished = poll(location).await;
+    assert_eq!(finished["status"], "completed");
+    assert_eq!(finished["result"]["result"], json!({"gated": true}));
+
+    // Waiting runs are accepted once the timeout elapses, and keep running
+    let (status, location, run) = create_run(json!({"mode": "wait", "timeoutMs": 50})).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert_eq!(run["status"], "running");
+    permits.add_permits(1);
+    let finished = poll(location.unwrap()).await;
+    assert_eq!(finished["status"], "completed");
+
+    // Waiting runs that finish in time respond with the result
+    permits.add_permits(1);
+    let (status, location, run) = create_run(json!({"mode": "wait", "timeoutMs": 5000})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(location.is_none());
+    assert_eq!(run["status"], "completed");
+    assert_eq!(run["result"]["result"], json!({"gated": true}));
+
+    // Synchronous runs wait for the result, as by default
+    permits.add_permits(1);
+    let (status, _, run) = create_run(json!({"mode": "sync"})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(run["status"], "completed");
+}
+
+#[tokio::test]
+async fn test_offloaded_step_results() {
+    use stepflow_execution::ExecutorOptions;
+    use stepflow_plugin::routing::{RouteRule, RoutingConfig};
+
+    init_test_logging();
+
+    let routes = std::collections::HashMap::from([(
+        "/gate/{*component}".to_string(),
+        vec![RouteRule {
+            conditions: vec![],
+            component_allow: None,
+            component_deny: None,
+            plugin: "gate".into(),
+            component: None,
+        }],
+    )]);
+    let plugin_router = stepflow_plugin::routing::PluginRouter::builder()
+        .register_plugin(
+            "gate".to_string(),
+            DynPlugin::boxed(GatedPlugin {
+                permits: Arc::new(tokio::sync::Semaphore::new(1)),
+            }),
+        )
+        .with_routing_config(RoutingConfig { routes })
+        .build()
+        .unwrap();
+    let executor = StepflowExecutor::with_namespaces(
+        Arc::new(InMemoryStateStore::new()),
+        std::path::PathBuf::from("."),
+        plugin_router,
+        ExecutorOptions {
+            blob_threshold: Some(64),
+            ..Default::default()
+        },
+        Default::default(),
+    );
+    let app = stepflow_server::AppConfig {
+        include_swagger: false,
+        ..Default::default()
+    }
+    .create_app_router(executor.clone(), 7837);
+
+    let flow = FlowBuilder::test_flow()
+        .step(
+            StepBuilder::new("echo")
+                .component("/gate/echo")
+                .input(ValueTemplate::workflow_input(Default::default()))
+                .build(),
+        )
+        .output(ValueTemplate::step_ref("echo", Default::default()))
+        .build();
+    let (_, stored) = send_json(&app, "POST", "/api/v1/flows", Some(json!({"flow": flow}))).await;
+    let flow_id = stored["flowId"].as_str().unwrap().to_string();
+
+    // The echoed input is large enough to be stored as a blob, and contains
+    // a value that looks like a blob reference.
+    let input = json!({
+        "lookalike": {"$blob": "0".repeat(64)},
+        "text": "x".repeat(100),
+    });
+    let (status, run) = send_json(
+        &app,
+        "POST",
+        "/api/v1/runs",
+        Some(json!({"flowId": flow_id, "input": input})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(run["result"]["result"], input);
+
+    // The steps API returns the content of the stored result
+    let run_id = run["runId"].as_str().unwrap();
+    let (status, steps) =
+        send_json(&app, "GET", &format!("/api/v1/runs/{run_id}/steps"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(steps["steps"]["echo"]["result"]["result"], input);
+}
+
+#[tokio::test]
+async fn test_run_callback() {
+    use axum::http::HeaderMap;
+    use hmac::{Hmac, Mac as _};
+    use std::sync::Mutex;
+    use stepflow_state::DeliveryStatus;
+
+    init_test_logging();
+
+    // A local receiver that fails the first delivery and accepts the rest
+    type Received = Arc<Mutex<Vec<(HeaderMap, axum::body::Bytes)>>>;
+    let received: Received = Arc::default();
+    let receiver = Router::new()
+        .route(
+            "/hook",
+            axum::routing::post(
+                |axum::extract::State(received): axum::extract::State<Received>,
+                 headers: HeaderMap,
+                 body: axum::body::Bytes| async move {
+                    let mut received = received.lock().unwrap();
+                    received.push((headers, body));
+                    if received.len() == 1 {
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    } else {
+                        StatusCode::OK
+                    }
+                },
+            ),
+        )
+        .with_state(received.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, receiver).await });
+
+    let (app, executor) = create_basic_test_server().await;
+    let (_, stored) = send_json(
+        &app,
+        "POST",
+        "/api/v1/flows",
+        Some(json!({"flow": create_test_workflow()})),
+    )
+    .await;
+    let flow_id = stored["flowId"].as_str().unwrap();
+
+    // Callbacks must be deliverable
+    let (status, _) = send_json(
+        &app,
+        "POST",
+        "/api/v1/runs",
+        Some(json!({
+            "flowId": flow_id,
+            "input": {},
+            "callback": {"url": "ftp://example.com/hook"}
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Internal addresses are rejected unless allowed
+    let (status, _) = send_json(
+        &app,
+        "POST",
+        "/api/v1/runs",
+        Some(json!({
+            "flowId": flow_id,
+            "input": {},
+            "callback": {"url": "http://169.254.169.254/latest/meta-data"}
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, run) = send_json(
+        &app,
+        "POST",
+        "/api/v1/runs",
+        Some(json!({
+            "flowId": flow_id,
+            "input": {},
+            "callback": {
+                "url": url,
+                "headers": {"x-team": "search"},
+                "secret": "hook-secret"
+            }
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let run_id = uuid::Uuid::parse_str(run["runId"].as_str().unwrap()).unwrap();
+
+    // Wait for the retry to be delivered. Deliveries are tracked in the state store.
+    let mut deliveries = vec![];
+    for _ in 0..100 {
+        deliveries = executor
+            .state_store()
+            .list_webhook_deliveries(Some(run_id), None)
+            .await
+            .unwrap();
+        if deliveries
+            .iter()
+            .any(|delivery| delivery.status == DeliveryStatus::Delivered)
+        {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    assert_eq!(deliveries.len(), 1);
+    assert_eq!(deliveries[0].status, DeliveryStatus::Delivered);
+    assert_eq!(deliveries[0].attempts, 2);
+
+    let received = received.lock().unwrap().clone();
+    assert_eq!(received.len(), 2);
+    let (headers, body) = &received[1];
+    assert_eq!(headers["x-team"], "search");
+    assert_eq!(
+        headers["x-stepflow-delivery"],
+        received[0].0["x-stepflow-delivery"]
+    );
+    let mut mac = Hmac::<sha2::Sha256>::new_from_slice(b"hook-secret").unwrap();
+    mac.update(body);
+    let signature = format!("sha256={}", hex::encode(mac.finalize().into_bytes()));
+    assert_eq!(headers["x-stepflow-signature"], signature.as_str());
+
+    let payload: serde_json::Value = serde_json::from_slice(body).unwrap();
+    assert_eq!(payload["runId"], run_id.to_string());
+    assert_eq!(payload["status"], "completed");
+    assert_eq!(payload["result"]["outcome"], "success");
+}
+
+#[tokio::test]
+async fn test_label_based_execution() {
+    init_test_logging();
+
+    let (app, executor) = create_basic_test_server().await;
+
+    // Store two versions of the same named flow
+    let v1 = executor
+        .state_store()
+        .store_flow(DEFAULT_NAMESPACE, Arc::new(create_test_workflow()))
+        .await
+        .unwrap();
+    let v2 = executor
+        .state_store()
+        .store_flow(
+            DEFAULT_NAMESPACE,
+            Arc::new(
+                FlowBuilder::test_flow()
+                    .description("Second version")
+                    .build(),
+            ),
+        )
+        .await
+        .unwrap();
+
+    let (status, names) = send_json(&app, "GET", "/api/v1/flows", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(names["names"], json!(["test_workflow"]));
+
+    let (status, versions) =
+        send_json(&app, "GET", "/api/v1/flows/test_workflow/versions", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(versions["versions"].as_array().unwrap().len(), 2);
+
+    // Point "production" at the first version, then move it to the second
+    let uri = "/api/v1/flows/test_workflow/labels/production";
+    let (status, label) = send_json(&app, "PUT", uri, Some(json!({"flowId": v1}))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(label["flowId"], json!(v1));
+
+    let (status, label) = send_json(&app, "PUT", uri, Some(json!({"flowId": v2}))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(label["flowId"], json!(v2));
+
+    let (status, labels) = send_json(&app, "GET", "/api/v1/flows/test_workflow/labels", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(labels["labels"].as_array().unwrap().len(), 1);
+
+    // Labels can only point at flows with the same name
+    let other = executor
+        .state_store()
+        .store_flow(
+            DEFAULT_NAMESPACE,
+            Arc::new(FlowBuilder::new().name("other").build()),
+        )
+        .await
+        .unwrap();
+    let (status, _) = send_json(&app, "PUT", uri, Some(json!({"flowId": other}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Run the labeled flow and check the run records the resolved flow and label
+    let (status, run) = send_json(
+        &app,
+        "POST",
+        "/api/v1/flows/test_workflow/labels/production/runs",
+        Some(json!({"input": {}})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(run["status"], "completed");
+
+    let run_id = run["runId"].as_str().unwrap();
+    let (status, details) = send_json(&app, "GET", &format!("/api/v1/runs/{run_id}"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(details["flowId"], json!(v2));
+    assert_eq!(details["flowLabel"], "production");
+
+    // Deleted labels can no longer be run
+    let (status, _) = send_json(&app, "DELETE", uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send_json(&app, "GET", uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send_json(
+        &app,
+        "POST",
+        "/api/v1/flows/test_workflow/labels/production/runs",
+        Some(json!({"input": {}})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_label_history_and_rollback() {
+    init_test_logging();
+
+    let (app, executor) = create_basic_test_server().await;
+    let v1 = executor
+        .state_store()
+        .store_flow(DEFAULT_NAMESPACE, Arc::new(create_test_workflow()))
+        .await
+        .unwrap();
+    let v2 = executor
+        .state_store()
+        .store_flow(
+            DEFAULT_NAMESPACE,
+            Arc::new(
+                FlowBuilder::test_flow()
+                    .description("Second version")
+                    .build(),
+            ),
+        )
+        .await
+        .unwrap();
+
+    let uri = "/api/v1/flows/test_workflow/labels/production";
+
+    // Rolling back a label without history fails
+    let (status, _) = send_json(&app, "POST", &format!("{uri}/rollback"), Some(json!({}))).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    send_json(
+        &app,
+        "PUT",
+        uri,
+        Some(json!({"flowId": v1, "actor": "alice"})),
+    )
+    .await;
+    let (status, _) = send_json(&app, "POST", &format!("{uri}/rollback"), Some(json!({}))).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    send_json(
+        &app,
+        "PUT",
+        uri,
+        Some(json!({"flowId": v2, "actor": "bob", "comment": "new prompt"})),
+    )
+    .await;
+
+    // One-step rollback moves the label back to the previous version
+    let (status, label) = send_json(
+        &app,
+        "POST",
+        &format!("{uri}/rollback"),
+        Some(json!({"actor": "carol"})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(label["flowId"], json!(v1));
+
+    let (status, history) = send_json(&app, "GET", &format!("{uri}/history"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let history = history["history"].as_array().unwrap();
+    assert_eq!(history.len(), 3);
+    assert_eq!(history[0]["flowId"], json!(v1));
+    assert_eq!(history[0]["previousFlowId"], json!(v2));
+    assert_eq!(history[0]["actor"], "carol");
+    assert_eq!(history[1]["comment"], "new prompt");
+    assert_eq!(history[2]["actor"], "alice");
+    assert!(history[2].get("previousFlowId").is_none());
+
+    // Rollback to a chosen version only accepts versions from the label's history
+    let (status, label) = send_json(
+        &app,
+        "POST",
+        &format!("{uri}/rollback"),
+        Some(json!({"flowId": v2})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(label["flowId"], json!(v2));
+
+    let other = executor
+        .state_store()
+        .store_flow(
+            DEFAULT_NAMESPACE,
+            Arc::new(
+                FlowBuilder::test_flow()
+                    .description("Never labeled")
+                    .build(),
+            ),
+        )
+        .await
+        .unwrap();
+    let (status, _) = send_json(
+        &app,
+        "POST",
+        &format!("{uri}/rollback"),
+        Some(json!({"flowId": other})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Deletions are recorded too
+    send_json(&app, "DELETE", &format!("{uri}?actor=dave"), None).await;
+    let (_, history) = send_json(&app, "GET", &format!("{uri}/history"), None).await;
+    assert_eq!(history["history"][0]["actor"], "dave");
+    assert!(history["history"][0].get("flowId").is_none());
+}
+
+#[tokio::test]
+async fn test_flow_deletion() {
+    init_test_logging();
+
+    let (app, executor) = create_basic_test_server().await;
+    let flow_id = executor
+        .state_store()
+        .store_flow(DEFAULT_NAMESPACE, Arc::new(create_test_workflow()))
+        .await
+        .unwrap();
+    let uri = format!("/api/v1/flows/{flow_id}");
+
+    send_json(
+        &app,
+        "PUT",
+        "/api/v1/flows/test_workflow/labels/production",
+        Some(json!({"flowId": flow_id})),
+    )
+    .await;
+
+    // A paused debug run blocks deletion, even when deleting runs
+    let (status, run) = send_json(
+        &app,
+        "POST",
+        "/api/v1/runs",
+        Some(json!({"flowId": flow_id, "input": {}, "debug": true})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send_json(&app, "DELETE", &format!("{uri}?deleteRuns=true"), None).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let run_id = run["runId"].as_str().unwrap();
+    let (status, _) = send_json(&app, "POST", &format!("/api/v1/runs/{run_id}/cancel"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send_json(
+        &app,
+        "POST",
+        "/api/v1/runs",
+        Some(json!({"flowId": flow_id, "input": {}})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Finished runs block deletion unless they are deleted too
+    let (status, _) = send_json(&app, "DELETE", &uri, None).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, _) = send_json(&app, "DELETE", &format!("{uri}?deleteRuns=true"), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = send_json(&app, "GET", &uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send_json(&app, "GET", &format!("/api/v1/runs/{run_id}"), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (_, labels) = send_json(&app, "GET", "/api/v1/flows/test_workflow/labels", None).await;
+    assert_eq!(labels["labels"], json!([]));
+
+    let (status, _) = send_json(&app, "DELETE", &uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_run_deletion() {
+    init_test_logging();
+
+    let (app, executor) = create_basic_test_server().await;
+    let flow_id = executor
+        .state_store()
+        .store_flow(DEFAULT_NAMESPACE, Arc::new(create_test_workflow()))
+        .await
+        .unwrap();
+
+    let mut run_ids = Vec::new();
+    for _ in 0..3 {
+        let (status, run) = send_json(
+            &app,
+            "POST",
+            "/api/v1/runs",
+            Some(json!({"flowId": flow_id, "input": {}})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        run_ids.push(run["runId"].as_str().unwrap().to_string());
+    }
+    let (_, debug_run) = send_json(
+        &app,
+        "POST",
+        "/api/v1/runs",
+        Some(json!({"flowId": flow_id, "input": {}, "debug": true})),
+    )
+    .await;
+    let debug_run_id = debug_run["runId"].as_str().unwrap();
+
+    // Paused runs cannot be deleted
+    let uri = format!("/api/v1/runs/{debug_run_id}");
+    let (status, _) = send_json(&app, "DELETE", &uri, None).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Deleting a finished run removes it and its steps
+    let uri = format!("/api/v1/runs/{}", run_ids[0]);
+    let (status, _) = send_json(&app, "DELETE", &uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send_json(&app, "GET", &uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send_json(&app, "GET", &format!("{uri}/steps"), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send_json(&app, "DELETE", &uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Bulk deletion removes the remaining finished runs and skips the paused one
+    let (status, body) = send_json(
+        &app,
+        "DELETE",
+        &format!("/api/v1/runs?flowId={flow_id}&cascade=true"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let deleted: Vec<String> = serde_json::from_value(body["deletedRunIds"].clone()).unwrap();
+    for run_id in &run_ids[1..] {
+        assert!(deleted.contains(run_id));
+    }
+    assert!(!deleted.iter().any(|run_id| run_id == debug_run_id));
+
+    let (_, runs) = send_json(&app, "GET", "/api/v1/runs", None).await;
+    let remaining: Vec<&str> = runs["runs"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|run| run["runId"].as_str().unwrap())
+        .collect();
+    assert_eq!(remaining, vec![debug_run_id]);
+}
+
+#[tokio::test]
+async fn test_step_logs() {
+    init_test_logging();
+
+    let (app, executor) = create_basic_test_server().await;
+    let state_store = executor.state_store();
+    let flow_id = state_store
+        .store_flow(DEFAULT_NAMESPACE, Arc::new(create_test_workflow()))
+        .await
+        .unwrap();
+    let (_, run) = send_json(
+        &app,
+        "POST",
+        "/api/v1/runs",
+        Some(json!({"flowId": flow_id, "input": {}})),
+    )
+    .await;
+    let run_id = uuid::Uuid::parse_str(run["runId"].as_str().unwrap()).unwrap();
+
+    let mut fields = serde_json::Map::new();
+    fields.insert("attempt".to_string(), json!(1));
+    state_store
+        .record_step_log(
+            run_id,
+            "test_step".to_string(),
+            StepLog {
+                level: LogLevel::Warn,
+                message: "retrying request".to_string(),
+                fields,
+                timestamp: chrono::Utc::now(),
+            },
+        )
+        .await
+        .unwrap();
+
+    let (status, body) = send_json(
+        &app,
+        "GET",
+        &format!("/api/v1/runs/{run_id}/steps/test_step/logs"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let logs = body["logs"].as_array().unwrap();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0]["level"], "warn");
+    assert_eq!(logs[0]["message"], "retrying request");
+    assert_eq!(logs[0]["fields"], json!({"attempt": 1}));
+
+    // Steps without logs have an empty list
+    let (status, body) = send_json(
+        &app,
+        "GET",
+        &format!("/api/v1/runs/{run_id}/steps/other_step/logs"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["logs"], json!([]));
+
+    // Unknown runs are not found
+    let (status, _) = send_json(
+        &app,
+        "GET",
+        &format!("/api/v1/runs/{}/steps/test_step/logs", uuid::Uuid::new_v4()),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_step_progress() {
+    init_test_logging();
+
+    let (app, executor) = create_basic_test_server().await;
+    let state_store = executor.state_store();
+    let flow_id = state_store
+        .store_flow(DEFAULT_NAMESPACE, Arc::new(create_test_workflow()))
+        .await
+        .unwrap();
+    let (_, run) = send_json(
+        &app,
+        "POST",
+        "/api/v1/runs",
+        Some(json!({"flowId": flow_id, "input": {}})),
+    )
+    .await;
+    let run_id = uuid::Uuid::parse_str(run["runId"].as_str().unwrap()).unwrap();
+
+    state_store
+        .update_step_progress(
+            run_id,
+            "test_step".to_string(),
+            StepProgress::items(2, Some(8)).with_message("processing"),
+        )
+        .await
+        .unwrap();
+
+    let (status, body) =
+        send_json(&app, "GET", &format!("/api/v1/runs/{run_id}/steps"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let progress = &body["steps"]["test_step"]["progress"];
+    assert_eq!(progress["fraction"], 0.25);
+    assert_eq!(progress["message"], "processing");
+    assert_eq!(progress["completed"], 2);
+    assert_eq!(progress["total"], 8);
+}
+
 #[tokio::test]
 async fn test_run_details() {
     init_test_logging();
@@ -15,9 +15,11 @@
 //! Provides persistent storage for blobs and execution state using SQLite.
 //! Future versions will support PostgreSQL and MySQL.
 
+mod metrics;
 mod migrations;
 mod sqlite_state_store;
 
+pub use metrics::describe_metrics;
 pub use sqlite_state_store::{SqliteStateStore, SqliteStateStoreConfig};
 
 #[cfg(test)]
@@ -27,7 +29,7 @@ mod tests {
     use stepflow_core::values::ValueTemplate;
     use stepflow_core::workflow::{FlowBuilder, StepBuilder};
     use stepflow_core::{BlobType, FlowResult, workflow::ValueRef};
-    use stepflow_state::{StateStore as _, StepResult};
+    use stepflow_state::{BlobStore as _, CreateRunParams, StateStore as _, StepResult};
     use uuid::Uuid;
 
     #[tokio::test]
@@ -50,6 +52,80 @@ mod tests {
         assert_eq!(retrieved.data().as_ref(), &test_data);
     }
 
+    #[tokio::test]
+    async fn test_binary_blob_storage() {
+        let store = SqliteStateStore::in_memory().await.unwrap();
+
+        let content = b"%PDF-1.7\n\x00\xff".to_vec();
+        let blob_id = store
+            .put_binary_blob(content.clone(), "application/pdf".to_string())
+            .await
+            .unwrap();
+
+        let retrieved = store.get_blob(&blob_id).await.unwrap();
+        assert_eq!(retrieved.blob_type(), BlobType::Binary);
+        assert_eq!(retrieved.content_type(), "application/pdf");
+        assert_eq!(retrieved.as_binary(), Some(content.as_slice()));
+
+        // Binary content equal to the serialization of a JSON blob is stored
+        // separately, rather than being ignored as a duplicate.
+        let data = json!({"hello": "world"});
+        let data_id = store
+            .put_blob(ValueRef::new(data.clone()), BlobType::Data)
+            .await
+            .unwrap();
+        let bytes_id = store
+            .put_binary_blob(serde_json::to_vec(&data).unwrap(), "text/plain".to_string())
+            .await
+            .unwrap();
+        assert_ne!(data_id, bytes_id);
+        assert_eq!(
+            store.get_blob(&data_id).await.unwrap().blob_type(),
+            BlobType::Data
+        );
+        let retrieved = store.get_blob(&bytes_id).await.unwrap();
+        assert_eq!(retrieved.blob_type(), BlobType::Binary);
+        assert_eq!(retrieved.content_type(), "text/plain");
+
+        // The same bytes under another content type are a distinct blob.
+        let png_id = store
+            .put_binary_blob(content.clone(), "image/png".to_string())
+            .await
+            .unwrap();
+        assert_ne!(png_id, blob_id);
+        assert_eq!(
+            store.get_blob(&png_id).await.unwrap().content_type(),
+            "image/png"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_blob_store_delegation() {
+        let blob_store = std::sync::Arc::new(stepflow_state::InMemoryBlobStore::new());
+        let store = SqliteStateStore::in_memory()
+            .await
+            .unwrap()
+            .with_blob_store(blob_store.clone());
+
+        // Data blobs go to the blob store.
+        let data_id = store
+            .put_blob(ValueRef::new(json!({"payload": "large"})), BlobType::Data)
+            .await
+            .unwrap();
+        assert!(blob_store.get(&data_id).await.unwrap().is_some());
+        let retrieved = store.get_blob(&data_id).await.unwrap();
+        assert_eq!(retrieved.data().as_ref(), &json!({"payload": "large"}));
+
+        // Flow blobs stay in the database.
+        let flow = FlowBuilder::new()
+            .output(ValueTemplate::empty_object())
+            .build();
+        let flow_data = ValueRef::new(serde_json::to_value(&flow).unwrap());
+        let flow_id = store.put_blob(flow_data, BlobType::Flow).await.unwrap();
+        assert!(blob_store.get(&flow_id).await.unwrap().is_none());
+        assert!(store.get_flow(&flow_id).await.unwrap().is_some());
+    }
+
     #[tokio::test]
     async fn test_step_result_storage() {
         let store = SqliteStateStore::in_memory().await.unwrap();
@@ -71,14 +147,11 @@ mod tests {
 
         // Then create the execution
         store
-            .create_run(
+            .create_run(CreateRunParams::new(
                 run_id,
                 flow_id,
-                None,                     // flow_name
-                None,                     // flow_label
-                false,                    // debug_mode
-                ValueRef::new(json!({})), // input
-            )
+                ValueRef::new(json!({})),
+            ))
             .await
             .unwrap();
 
@@ -129,4 +202,612 @@ mod tests {
         // Should get the same ID (deduplication)
         assert_eq!(blob_id1, blob_id2);
     }
+
+    #[tokio::test]
+    async fn test_delete_run() {
+        let store = SqliteStateStore::in_memory().await.unwrap();
+        let flow = std::sync::Arc::new(FlowBuilder::new().name("nested").build());
+        let flow_data = ValueRef::new(serde_json::to_value(flow.as_ref()).unwrap());
+        let flow_id = store.put_blob(flow_data, BlobType::Flow).await.unwrap();
+
+        let parent = Uuid::new_v4();
+        let child = Uuid::new_v4();
+        let grandchild = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let runs = [
+            (parent, None),
+            (child, Some(parent)),
+            (grandchild, Some(child)),
+            (other, None),
+        ];
+        for (run_id, parent_run_id) in runs {
+            store
+                .create_run(CreateRunParams {
+                    flow_name: Some("nested".to_string()),
+                    parent_run_id,
+                    ..CreateRunParams::new(run_id, flow_id.clone(), ValueRef::new(json!({})))
+                })
+                .await
+                .unwrap();
+        }
+        store
+            .queue_write(stepflow_state::StateWriteOperation::RecordStepResult {
+                run_id: grandchild,
+                step_result: StepResult::new(
+                    0,
+                    "step",
+                    FlowResult::Success(ValueRef::new(json!(1))),
+                ),
+            })
+            .unwrap();
+
+        let run = store.get_run(child).await.unwrap().unwrap();
+        assert_eq!(run.summary.parent_run_id, Some(parent));
+
+        // A run is not deleted while any run of its tree is still running.
+        for run_id in [parent, child] {
+            store
+                .update_run_status(
+                    run_id,
+                    stepflow_core::status::ExecutionStatus::Completed,
+                    None,
+                )
+                .await
+                .unwrap();
+        }
+        let error = store.delete_run(parent, true).await.unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            stepflow_state::StateError::RunTreeActive { count: 1, .. }
+        ));
+        let filters = stepflow_state::RunFilters {
+            flow_name: Some("nested".to_string()),
+            ..Default::default()
+        };
+        assert!(store.delete_runs(&filters, true).await.unwrap().is_empty());
+        assert!(store.get_run(grandchild).await.unwrap().is_some());
+        store
+            .update_run_status(
+                grandchild,
+                stepflow_core::status::ExecutionStatus::Completed,
+                None,
+            )
+            .await
+            .unwrap();
+
+        // Cascading removes every run nested beneath the deleted run.
+        let mut deleted = store.delete_run(parent, true).await.unwrap();
+        deleted.sort();
+        let mut expected = vec![parent, child, grandchild];
+        expected.sort();
+        assert_eq!(deleted, expected);
+        assert!(store.get_run(grandchild).await.unwrap().is_none());
+        assert!(
+            store
+                .list_step_results(grandchild)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(store.delete_run(parent, true).await.unwrap().is_empty());
+
+        // Bulk deletion skips running runs and removes finished ones.
+        assert!(store.delete_runs(&filters, true).await.unwrap().is_empty());
+        store
+            .update_run_status(
+                other,
+                stepflow_core::status::ExecutionStatus::Completed,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            store.delete_runs(&filters, true).await.unwrap(),
+            vec![other]
+        );
+        assert!(store.list_runs(&filters).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_namespaces_are_isolated() {
+        let store = SqliteStateStore::in_memory().await.unwrap();
+        let flow = std::sync::Arc::new(FlowBuilder::new().name("greeter").build());
+        let flow_id = store.store_flow("team-a", flow.clone()).await.unwrap();
+        store
+            .create_or_update_label(
+                "team-a",
+                "greeter",
+                "production",
+                flow_id.clone(),
+                "alice",
+                None,
+            )
+            .await
+            .unwrap();
+        let run_id = Uuid::new_v4();
+        store
+            .create_run(CreateRunParams {
+                namespace: "team-a".to_string(),
+                ..CreateRunParams::new(run_id, flow_id.clone(), ValueRef::new(json!({})))
+            })
+            .await
+            .unwrap();
+        let run = store.get_run(run_id).await.unwrap().unwrap();
+        assert_eq!(run.summary.namespace, "team-a");
+
+        // Names, labels and runs of one namespace are not visible from another.
+        assert_eq!(
+            store.list_flow_names("team-a").await.unwrap(),
+            vec!["greeter"]
+        );
+        assert!(store.list_flow_names("team-b").await.unwrap().is_empty());
+        assert!(
+            store
+                .get_flows("team-b", "greeter")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(
+            store
+                .get_named_flow("team-b", "greeter", Some("production"))
+                .await
+                .unwrap()
+                .is_none()
+        );
+        let filters = stepflow_state::RunFilters {
+            namespace: Some("team-b".to_string()),
+            ..Default::default()
+        };
+        assert!(store.list_runs(&filters).await.unwrap().is_empty());
+
+        // Flows and blobs are only readable by ID in their namespaces.
+        assert!(
+            store
+                .get_namespaced_flow("team-b", &flow_id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        let blob_id = store
+            .put_blob(ValueRef::new(json!({"secret": 1})), BlobType::Data)
+            .await
+            .unwrap();
+        assert!(!store.blob_in_namespace("team-a", &blob_id).await.unwrap());
+        store
+            .add_blob_to_run_namespace(run_id, &blob_id)
+            .await
+            .unwrap();
+        assert!(store.blob_in_namespace("team-a", &blob_id).await.unwrap());
+        assert!(!store.blob_in_namespace("team-b", &blob_id).await.unwrap());
+
+        // The same flow stored in two namespaces is only removed with the last of them.
+        assert_eq!(store.store_flow("team-b", flow).await.unwrap(), flow_id);
+        assert!(store.delete_flow("team-b", &flow_id, true).await.unwrap());
+        assert!(store.get_flow(&flow_id).await.unwrap().is_some());
+        assert!(!store.delete_flow("team-b", &flow_id, true).await.unwrap());
+        assert_eq!(
+            store
+                .list_labels_for_name("team-a", "greeter")
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            store
+                .list_label_history("team-a", "greeter", "production")
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_runs_by_batch() {
+        let store = SqliteStateStore::in_memory().await.unwrap();
+        let flow_id = store
+            .store_flow("default", std::sync::Arc::new(FlowBuilder::new().build()))
+            .await
+            .unwrap();
+        let batch_id = Uuid::new_v4();
+        for batch_id in [Some(batch_id), Some(batch_id), None] {
+            store
+                .create_run(CreateRunParams {
+                    batch_id,
+                    ..CreateRunParams::new(
+                        Uuid::new_v4(),
+                        flow_id.clone(),
+                        ValueRef::new(json!({})),
+                    )
+                })
+                .await
+                .unwrap();
+        }
+
+        let filters = stepflow_state::RunFilters {
+            batch_id: Some(batch_id),
+            ..Default::default()
+        };
+        let runs = store.list_runs(&filters).await.unwrap();
+        assert_eq!(runs.len(), 2);
+        assert!(runs.iter().all(|run| run.batch_id == Some(batch_id)));
+    }
+
+    #[tokio::test]
+    async fn test_run_lineage() {
+        let store = SqliteStateStore::in_memory().await.unwrap();
+        let flow_id = store
+            .store_flow("default", std::sync::Arc::new(FlowBuilder::new().build()))
+            .await
+            .unwrap();
+        let root = Uuid::new_v4();
+        let child = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        for (run_id, parent_run_id, root_run_id) in [
+            (root, None, root),
+            (child, Some(root), root),
+            (other, None, other),
+        ] {
+            store
+                .create_run(CreateRunParams {
+                    parent_run_id,
+                    parent_step_id: parent_run_id.map(|_| "map".to_string()),
+                    root_run_id,
+                    ..CreateRunParams::new(run_id, flow_id.clone(), ValueRef::new(json!({})))
+                })
+                .await
+                .unwrap();
+        }
+
+        let details = store.get_run(child).await.unwrap().unwrap();
+        assert_eq!(details.summary.parent_run_id, Some(root));
+        assert_eq!(details.summary.parent_step_id.as_deref(), Some("map"));
+        assert_eq!(details.summary.root_run_id, root);
+
+        let filters = stepflow_state::RunFilters {
+            root_run_id: Some(root),
+            ..Default::default()
+        };
+        let mut runs: Vec<_> = store
+            .list_runs(&filters)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|run| run.run_id)
+            .collect();
+        runs.sort();
+        let mut expected = vec![root, child];
+        expected.sort();
+        assert_eq!(runs, expected);
+    }
+
+    #[tokio::test]
+    async fn test_batches() {
+        use stepflow_state::{BatchItem, BatchRecord};
+
+        let store = SqliteStateStore::in_memory().await.unwrap();
+        let flow_id = store
+            .store_flow("default", std::sync::Arc::new(FlowBuilder::new().build()))
+            .await
+            .unwrap();
+        let batch_id = Uuid::new_v4();
+        assert_eq!(store.get_batch(batch_id).await.unwrap(), None);
+
+        let mut batch = BatchRecord {
+            batch_id,
+            flow_id,
+            namespace: "team-a".to_string(),
+            created_by: Some("ci".to_string()),
+            created_at: chrono::Utc::now(),
+            items: (0..2)
+                .map(|_| BatchItem {
+                    run_id: Uuid::new_v4(),
+                    error: None,
+                })
+                .collect(),
+        };
+        store.put_batch(batch.clone()).await.unwrap();
+        assert_eq!(
+            store.get_batch(batch_id).await.unwrap(),
+            Some(batch.clone())
+        );
+
+        // Updating a batch replaces its items
+        batch.items[1].error = Some("quota exceeded".to_string());
+        store.put_batch(batch.clone()).await.unwrap();
+        assert_eq!(store.get_batch(batch_id).await.unwrap(), Some(batch));
+    }
+
+    #[tokio::test]
+    async fn test_webhook_deliveries() {
+        use stepflow_state::{DeliveryStatus, RunCallback, WebhookDelivery};
+
+        let store = SqliteStateStore::in_memory().await.unwrap();
+        let flow = std::sync::Arc::new(FlowBuilder::new().name("notified").build());
+        let flow_data = ValueRef::new(serde_json::to_value(flow.as_ref()).unwrap());
+        let flow_id = store.put_blob(flow_data, BlobType::Flow).await.unwrap();
+
+        let callback = RunCallback {
+            url: "http://localhost:9000/hook".to_string(),
+            headers: [("x-token".to_string(), "abc".to_string())].into(),
+            secret: Some("secret".to_string()),
+        };
+        let run_id = Uuid::new_v4();
+        store
+            .create_run(CreateRunParams {
+                callback: Some(callback.clone()),
+                ..CreateRunParams::new(run_id, flow_id.clone(), ValueRef::new(json!({})))
+            })
+            .await
+            .unwrap();
+        let other_run_id = Uuid::new_v4();
+        store
+            .create_run(CreateRunParams::new(
+                other_run_id,
+                flow_id,
+                ValueRef::new(json!({})),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(
+            store.get_run_callback(run_id).await.unwrap(),
+            Some(callback.clone())
+        );
+        assert_eq!(store.get_run_callback(other_run_id).await.unwrap(), None);
+
+        let now = chrono::Utc::now();
+        let mut delivery = WebhookDelivery {
+            delivery_id: Uuid::new_v4(),
+            run_id,
+            callback,
+            payload: json!({"runId": run_id}),
+            status: DeliveryStatus::Pending,
+            attempts: 0,
+            next_attempt_at: now,
+            last_error: None,
+            created_at: now,
+        };
+        store.put_webhook_delivery(delivery.clone()).await.unwrap();
+        let pending = store
+            .list_webhook_deliveries(None, Some(DeliveryStatus::Pending))
+            .await
+            .unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].payload, delivery.payload);
+
+        // Updating a delivery replaces its status
+        delivery.status = DeliveryStatus::Delivered;
+        delivery.attempts = 2;
+        delivery.last_error = Some("connection refused".to_string());
+        store.put_webhook_delivery(delivery.clone()).await.unwrap();
+        assert!(
+            store
+                .list_webhook_deliveries(None, Some(DeliveryStatus::Pending))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        let deliveries = store
+            .list_webhook_deliveries(Some(run_id), None)
+            .await
+            .unwrap();
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].status, DeliveryStatus::Delivered);
+        assert_eq!(deliveries[0].attempts, 2);
+        assert_eq!(
+            deliveries[0].last_error.as_deref(),
+            Some("connection refused")
+        );
+
+        // Deleting the run removes its deliveries along with their payload
+        store
+            .update_run_status(
+                run_id,
+                stepflow_core::status::ExecutionStatus::Completed,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(store.delete_run(run_id, true).await.unwrap(), vec![run_id]);
+        assert!(
+            store
+                .list_webhook_deliveries(None, None)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_record_run_callback() {
+        use stepflow_core::status::ExecutionStatus;
+        use stepflow_state::{DeliveryStatus, RunCallback};
+
+        let store = SqliteStateStore::in_memory().await.unwrap();
+        let flow = std::sync::Arc::new(FlowBuilder::new().name("notified").build());
+        let flow_data = ValueRef::new(serde_json::to_value(flow.as_ref()).unwrap());
+        let flow_id = store.put_blob(flow_data, BlobType::Flow).await.unwrap();
+
+        let run_id = Uuid::new_v4();
+        store
+            .create_run(CreateRunParams {
+                callback: Some(RunCallback {
+                    url: "https://example.com/hook".to_string(),
+                    headers: Default::default(),
+                    secret: None,
+                }),
+                ..CreateRunParams::new(run_id, flow_id.clone(), ValueRef::new(json!({})))
+            })
+            .await
+            .unwrap();
+        let other_run_id = Uuid::new_v4();
+        store
+            .create_run(CreateRunParams::new(
+                other_run_id,
+                flow_id,
+                ValueRef::new(json!({})),
+            ))
+            .await
+            .unwrap();
+
+        store
+            .update_run_status(
+                run_id,
+                ExecutionStatus::Completed,
+                Some(ValueRef::new(json!({"answer": 42}))),
+            )
+            .await
+            .unwrap();
+        assert!(store.record_run_callback(run_id, None).await.unwrap());
+        // A run is only notified once, and runs without a callback are not
+        assert!(!store.record_run_callback(run_id, None).await.unwrap());
+        assert!(!store.record_run_callback(other_run_id, None).await.unwrap());
+
+        let deliveries = store.list_webhook_deliveries(None, None).await.unwrap();
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].run_id, run_id);
+        assert_eq!(deliveries[0].status, DeliveryStatus::Pending);
+        assert_eq!(deliveries[0].payload["status"], "completed");
+        assert_eq!(deliveries[0].payload["result"]["outcome"], "success");
+    }
+
+    #[tokio::test]
+    async fn test_step_logs() {
+        use stepflow_state::{LogLevel, StepLog};
+
+        let store = SqliteStateStore::in_memory().await.unwrap();
+        let flow = std::sync::Arc::new(FlowBuilder::new().name("logged").build());
+        let flow_data = ValueRef::new(serde_json::to_value(flow.as_ref()).unwrap());
+        let flow_id = store.put_blob(flow_data, BlobType::Flow).await.unwrap();
+        let run_id = Uuid::new_v4();
+        store
+            .create_run(CreateRunParams::new(
+                run_id,
+                flow_id,
+                ValueRef::new(json!({})),
+            ))
+            .await
+            .unwrap();
+
+        let first = StepLog {
+            level: LogLevel::Info,
+            message: "starting".to_string(),
+            fields: json!({"attempt": 1}).as_object().unwrap().clone(),
+            timestamp: chrono::Utc::now(),
+        };
+        let second = StepLog {
+            level: LogLevel::Warn,
+            message: "slow response".to_string(),
+            fields: serde_json::Map::new(),
+            timestamp: chrono::Utc::now(),
+        };
+        store
+            .record_step_log(run_id, "step1".to_string(), first.clone())
+            .await
+            .unwrap();
+        store
+            .record_step_log(run_id, "step1".to_string(), second.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            store.get_step_logs(run_id, "step1").await.unwrap(),
+            vec![first, second]
+        );
+        assert!(
+            store
+                .get_step_logs(run_id, "step2")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // Logs are deleted with their run
+        store
+            .update_run_status(
+                run_id,
+                stepflow_core::status::ExecutionStatus::Completed,
+                None,
+            )
+            .await
+            .unwrap();
+        store.delete_run(run_id, false).await.unwrap();
+        assert!(
+            store
+                .get_step_logs(run_id, "step1")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_step_progress() {
+        use stepflow_core::status::StepStatus;
+        use stepflow_core::workflow::Component;
+        use stepflow_state::{StepInfo, StepProgress};
+
+        let store = SqliteStateStore::in_memory().await.unwrap();
+        let flow = std::sync::Arc::new(FlowBuilder::new().name("progress").build());
+        let flow_data = ValueRef::new(serde_json::to_value(flow.as_ref()).unwrap());
+        let flow_id = store.put_blob(flow_data, BlobType::Flow).await.unwrap();
+        let run_id = Uuid::new_v4();
+        store
+            .create_run(CreateRunParams::new(
+                run_id,
+                flow_id,
+                ValueRef::new(json!({})),
+            ))
+            .await
+            .unwrap();
+        let now = chrono::Utc::now();
+        store
+            .initialize_step_info(
+                run_id,
+                &[StepInfo {
+                    run_id,
+                    step_index: 0,
+                    step_id: "step1".to_string(),
+                    component: Component::from_string("/mock/slow"),
+                    status: StepStatus::Running,
+                    created_at: now,
+                    updated_at: now,
+                    progress: None,
+                }],
+            )
+            .await
+            .unwrap();
+
+        // Later progress replaces earlier progress
+        let first = StepProgress::items(1, Some(4));
+        let second = StepProgress::items(3, Some(4)).with_message("almost done");
+        for progress in [first, second.clone()] {
+            store
+                .update_step_progress(run_id, "step1".to_string(), progress)
+                .await
+                .unwrap();
+        }
+
+        let progress = store.get_step_progress(run_id).await.unwrap();
+        assert_eq!(progress.len(), 1);
+        assert_eq!(progress["step1"], second);
+        assert_eq!(second.fraction, Some(0.75));
+
+        let step_info = store.get_step_info_for_execution(run_id).await.unwrap();
+        assert_eq!(step_info[0].progress, Some(second));
+
+        // Progress is deleted with its run
+        store
+            .update_run_status(
+                run_id,
+                stepflow_core::status::ExecutionStatus::Completed,
+                None,
+            )
+            .await
+            .unwrap();
+        store.delete_run(run_id, false).await.unwrap();
+        assert!(store.get_step_progress(run_id).await.unwrap().is_empty());
+    }
 }
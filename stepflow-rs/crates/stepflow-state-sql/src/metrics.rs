@@ -0,0 +1,31 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+//! Metrics recorded for the state store's background write queue.
+
+use metrics::{Unit, describe_gauge, describe_histogram};
+
+pub const STATE_WRITE_QUEUE_DEPTH: &str = "stepflow_state_write_queue_depth";
+pub const STATE_FLUSH_DURATION: &str = "stepflow_state_flush_duration_seconds";
+
+/// Register descriptions of the state store metrics with the installed recorder.
+pub fn describe_metrics() {
+    describe_gauge!(
+        STATE_WRITE_QUEUE_DEPTH,
+        "Number of state store writes queued and not yet applied."
+    );
+    describe_histogram!(
+        STATE_FLUSH_DURATION,
+        Unit::Seconds,
+        "Time taken to flush pending state store writes."
+    );
+}
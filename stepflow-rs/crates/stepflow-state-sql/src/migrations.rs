@@ -25,6 +25,36 @@ pub async fn run_migrations(pool: &SqlitePool) -> Result<(), StateError> {
     })
     .await?;
 
+    apply_migration(pool, "002_add_binary_blobs", || add_binary_blobs(pool)).await?;
+
+    apply_migration(pool, "003_add_flow_label_history", || {
+        add_flow_label_history(pool)
+    })
+    .await?;
+
+    apply_migration(pool, "004_add_run_parent", || add_run_parent(pool)).await?;
+
+    apply_migration(pool, "005_add_run_created_by", || add_run_created_by(pool)).await?;
+
+    apply_migration(pool, "006_add_namespaces", || add_namespaces(pool)).await?;
+
+    apply_migration(pool, "007_add_webhooks", || add_webhooks(pool)).await?;
+
+    apply_migration(pool, "008_add_run_batch", || add_run_batch(pool)).await?;
+
+    apply_migration(pool, "009_add_run_lineage", || add_run_lineage(pool)).await?;
+
+    apply_migration(pool, "010_add_step_logs", || add_step_logs(pool)).await?;
+
+    apply_migration(pool, "011_add_step_progress", || add_step_progress(pool)).await?;
+
+    apply_migration(pool, "012_add_blob_namespaces", || {
+        add_blob_namespaces(pool)
+    })
+    .await?;
+
+    apply_migration(pool, "013_add_batches", || add_batches(pool)).await?;
+
     Ok(())
 }
 
@@ -190,3 +220,303 @@ async fn create_unified_schema(pool: &SqlitePool) -> Result<(), StateError> {
 
     Ok(())
 }
+
+/// Add columns for binary blob content and its MIME type.
+///
+/// Binary blobs keep their raw bytes in `content` rather than the JSON `data` column.
+async fn add_binary_blobs(pool: &SqlitePool) -> Result<(), StateError> {
+    let commands = [
+        "ALTER TABLE blobs ADD COLUMN content BLOB",
+        "ALTER TABLE blobs ADD COLUMN content_type TEXT",
+    ];
+
+    for sql in commands {
+        sqlx::query(sql)
+            .execute(pool)
+            .await
+            .change_context(StateError::Initialization)?;
+    }
+
+    Ok(())
+}
+
+/// Add a table recording every change to a flow label.
+///
+/// A row with a null `flow_id` records that the label was deleted.
+async fn add_flow_label_history(pool: &SqlitePool) -> Result<(), StateError> {
+    let commands = [
+        r#"
+            CREATE TABLE IF NOT EXISTS flow_label_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                label TEXT NOT NULL,
+                flow_id TEXT,
+                previous_flow_id TEXT,
+                actor TEXT NOT NULL,
+                comment TEXT,
+                changed_at TEXT NOT NULL
+            )
+        "#,
+        "CREATE INDEX IF NOT EXISTS idx_flow_label_history_label ON flow_label_history(name, label)",
+    ];
+
+    for sql in commands {
+        sqlx::query(sql)
+            .execute(pool)
+            .await
+            .change_context(StateError::Initialization)?;
+    }
+
+    Ok(())
+}
+
+/// Add a column recording the run that submitted a nested run.
+async fn add_run_parent(pool: &SqlitePool) -> Result<(), StateError> {
+    let commands = [
+        "ALTER TABLE runs ADD COLUMN parent_run_id TEXT",
+        "CREATE INDEX IF NOT EXISTS idx_runs_parent_run_id ON runs(parent_run_id)",
+    ];
+
+    for sql in commands {
+        sqlx::query(sql)
+            .execute(pool)
+            .await
+            .change_context(StateError::Initialization)?;
+    }
+
+    Ok(())
+}
+
+/// Add a column recording the identity of the caller that created a run.
+async fn add_run_created_by(pool: &SqlitePool) -> Result<(), StateError> {
+    sqlx::query("ALTER TABLE runs ADD COLUMN created_by TEXT")
+        .execute(pool)
+        .await
+        .change_context(StateError::Initialization)?;
+
+    Ok(())
+}
+
+/// Add namespaces to runs and labels, and record the namespaces each flow is stored in.
+///
+/// Existing runs, labels and flows are placed in the `default` namespace. The
+/// labels table is rebuilt since its primary key gains the namespace.
+async fn add_namespaces(pool: &SqlitePool) -> Result<(), StateError> {
+    let commands = [
+        "ALTER TABLE runs ADD COLUMN namespace TEXT NOT NULL DEFAULT 'default'",
+        "CREATE INDEX IF NOT EXISTS idx_runs_namespace ON runs(namespace)",
+        "ALTER TABLE flow_label_history ADD COLUMN namespace TEXT NOT NULL DEFAULT 'default'",
+        "DROP INDEX IF EXISTS idx_flow_label_history_label",
+        "CREATE INDEX IF NOT EXISTS idx_flow_label_history_label ON flow_label_history(namespace, name, label)",
+        r#"
+            CREATE TABLE flow_labels_namespaced (
+                namespace TEXT NOT NULL DEFAULT 'default',
+                name TEXT NOT NULL,
+                label TEXT NOT NULL,
+                flow_id TEXT NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                PRIMARY KEY (namespace, name, label),
+                FOREIGN KEY (flow_id) REFERENCES blobs(id)
+            )
+        "#,
+        r#"
+            INSERT INTO flow_labels_namespaced (name, label, flow_id, created_at, updated_at)
+            SELECT name, label, flow_id, created_at, updated_at FROM flow_labels
+        "#,
+        "DROP TABLE flow_labels",
+        "ALTER TABLE flow_labels_namespaced RENAME TO flow_labels",
+        "CREATE INDEX IF NOT EXISTS idx_flow_labels_name ON flow_labels(namespace, name)",
+        "CREATE INDEX IF NOT EXISTS idx_flow_labels_flow_id ON flow_labels(flow_id)",
+        "CREATE INDEX IF NOT EXISTS idx_flow_labels_created_at ON flow_labels(created_at)",
+        r#"
+            CREATE TABLE IF NOT EXISTS flow_namespaces (
+                namespace TEXT NOT NULL,
+                flow_id TEXT NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                PRIMARY KEY (namespace, flow_id),
+                FOREIGN KEY (flow_id) REFERENCES blobs(id)
+            )
+        "#,
+        "CREATE INDEX IF NOT EXISTS idx_flow_namespaces_flow_id ON flow_namespaces(flow_id)",
+        r#"
+            INSERT OR IGNORE INTO flow_namespaces (namespace, flow_id, created_at)
+            SELECT 'default', id, created_at FROM blobs WHERE blob_type = 'flow'
+        "#,
+    ];
+
+    for sql in commands {
+        sqlx::query(sql)
+            .execute(pool)
+            .await
+            .change_context(StateError::Initialization)?;
+    }
+
+    Ok(())
+}
+
+/// Add run callbacks and the table tracking webhook deliveries.
+async fn add_webhooks(pool: &SqlitePool) -> Result<(), StateError> {
+    let commands = [
+        "ALTER TABLE runs ADD COLUMN callback TEXT",
+        r#"
+            CREATE TABLE IF NOT EXISTS webhook_deliveries (
+                id TEXT PRIMARY KEY,
+                run_id TEXT NOT NULL,
+                callback TEXT NOT NULL,
+                payload TEXT NOT NULL,
+                status TEXT NOT NULL,
+                attempts INTEGER NOT NULL DEFAULT 0,
+                next_attempt_at DATETIME NOT NULL,
+                last_error TEXT,
+                created_at DATETIME NOT NULL
+            )
+        "#,
+        "CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_run_id ON webhook_deliveries(run_id)",
+        "CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_status ON webhook_deliveries(status)",
+    ];
+
+    for sql in commands {
+        sqlx::query(sql)
+            .execute(pool)
+            .await
+            .change_context(StateError::Initialization)?;
+    }
+
+    Ok(())
+}
+
+/// Add a column recording the batch a run was submitted in.
+async fn add_run_batch(pool: &SqlitePool) -> Result<(), StateError> {
+    let commands = [
+        "ALTER TABLE runs ADD COLUMN batch_id TEXT",
+        "CREATE INDEX IF NOT EXISTS idx_runs_batch_id ON runs(batch_id)",
+    ];
+
+    for sql in commands {
+        sqlx::query(sql)
+            .execute(pool)
+            .await
+            .change_context(StateError::Initialization)?;
+    }
+
+    Ok(())
+}
+
+/// Add columns recording the step that submitted a nested run and the root of its run tree.
+async fn add_run_lineage(pool: &SqlitePool) -> Result<(), StateError> {
+    let commands = [
+        "ALTER TABLE runs ADD COLUMN parent_step_id TEXT",
+        "ALTER TABLE runs ADD COLUMN root_run_id TEXT",
+        // Existing runs belong to the tree of their top-most ancestor.
+        r#"
+            WITH RECURSIVE lineage(id, root_run_id) AS (
+                SELECT id, id FROM runs WHERE parent_run_id IS NULL
+                UNION ALL
+                SELECT r.id, l.root_run_id FROM runs r JOIN lineage l ON r.parent_run_id = l.id
+            )
+            UPDATE runs SET root_run_id = (SELECT root_run_id FROM lineage WHERE lineage.id = runs.id)
+        "#,
+        "CREATE INDEX IF NOT EXISTS idx_runs_root_run_id ON runs(root_run_id)",
+    ];
+
+    for sql in commands {
+        sqlx::query(sql)
+            .execute(pool)
+            .await
+            .change_context(StateError::Initialization)?;
+    }
+
+    Ok(())
+}
+
+/// Add the table holding log messages emitted by components while executing steps.
+async fn add_step_logs(pool: &SqlitePool) -> Result<(), StateError> {
+    let commands = [
+        r#"
+            CREATE TABLE IF NOT EXISTS step_logs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                run_id TEXT NOT NULL,
+                step_id TEXT NOT NULL,
+                level TEXT NOT NULL,
+                message TEXT NOT NULL,
+                fields TEXT,
+                timestamp DATETIME NOT NULL
+            )
+        "#,
+        "CREATE INDEX IF NOT EXISTS idx_step_logs_run_step ON step_logs(run_id, step_id)",
+    ];
+
+    for sql in commands {
+        sqlx::query(sql)
+            .execute(pool)
+            .await
+            .change_context(StateError::Initialization)?;
+    }
+
+    Ok(())
+}
+
+/// Add a table holding the latest progress reported by each step.
+async fn add_step_progress(pool: &SqlitePool) -> Result<(), StateError> {
+    let sql = r#"
+        CREATE TABLE IF NOT EXISTS step_progress (
+            run_id TEXT NOT NULL,
+            step_id TEXT NOT NULL,
+            progress TEXT NOT NULL,
+            PRIMARY KEY (run_id, step_id)
+        )
+    "#;
+
+    sqlx::query(sql)
+        .execute(pool)
+        .await
+        .change_context(StateError::Initialization)?;
+
+    Ok(())
+}
+
+/// Add a table recording the namespaces blobs are readable in.
+async fn add_blob_namespaces(pool: &SqlitePool) -> Result<(), StateError> {
+    let commands = [
+        r#"
+            CREATE TABLE IF NOT EXISTS blob_namespaces (
+                namespace TEXT NOT NULL,
+                blob_id TEXT NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                PRIMARY KEY (namespace, blob_id)
+            )
+        "#,
+        "CREATE INDEX IF NOT EXISTS idx_blob_namespaces_blob_id ON blob_namespaces(blob_id)",
+    ];
+
+    for sql in commands {
+        sqlx::query(sql)
+            .execute(pool)
+            .await
+            .change_context(StateError::Initialization)?;
+    }
+
+    Ok(())
+}
+
+/// Add a table recording batches of runs and the run of each input.
+async fn add_batches(pool: &SqlitePool) -> Result<(), StateError> {
+    let sql = r#"
+        CREATE TABLE IF NOT EXISTS batches (
+            id TEXT PRIMARY KEY,
+            flow_id TEXT NOT NULL,
+            namespace TEXT NOT NULL,
+            created_by TEXT,
+            created_at DATETIME NOT NULL,
+            items TEXT NOT NULL
+        )
+    "#;
+
+    sqlx::query(sql)
+        .execute(pool)
+        .await
+        .change_context(StateError::Initialization)?;
+
+    Ok(())
+}
@@ -10,7 +10,9 @@
 // or implied. See the License for the specific language governing permissions and limitations under
 // the License.
 
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Instant;
 
 use bit_set::BitSet;
 use error_stack::{Result, ResultExt as _};
@@ -18,17 +20,20 @@ use futures::future::{BoxFuture, FutureExt as _};
 use sqlx::{Row as _, SqlitePool, sqlite::SqlitePoolOptions};
 use stepflow_core::status::{ExecutionStatus, StepStatus};
 use stepflow_core::{
-    BlobData, BlobId, BlobType, FlowResult,
+    BlobData, BlobId, BlobType, BlobValue, FlowResult,
     workflow::{Component, Flow, ValueRef},
 };
 use stepflow_state::{
-    RunDetails, RunFilters, RunSummary, StateError, StateStore, StateWriteOperation, StepInfo,
-    StepResult, WorkflowLabelMetadata, WorkflowWithMetadata,
+    BatchRecord, BlobStore, CreateRunParams, DeliveryStatus, RunCallback, RunDetails, RunFilters,
+    RunSummary, SYSTEM_ACTOR, StateError, StateStore, StateWriteOperation, StepInfo, StepLog,
+    StepProgress, StepResult, WebhookDelivery, WorkflowLabelHistoryEntry, WorkflowLabelMetadata,
+    WorkflowWithMetadata,
 };
 use tokio::sync::{mpsc, oneshot};
 use tokio::task::JoinHandle;
 use uuid::Uuid;
 
+use crate::metrics::{STATE_FLUSH_DURATION, STATE_WRITE_QUEUE_DEPTH};
 use crate::migrations;
 
 /// Configuration for SqliteStateStore
@@ -53,6 +58,10 @@ fn default_auto_migrate() -> bool {
 /// SQLite-based StateStore implementation with async write queuing
 pub struct SqliteStateStore {
     pool: SqlitePool,
+    /// Optional external storage for data and binary blobs.
+    ///
+    /// Flow blobs are always kept in the database since runs reference them.
+    blob_store: Option<Arc<dyn BlobStore>>,
     write_queue: mpsc::UnboundedSender<StateWriteOperation>,
     _background_task: JoinHandle<()>,
 }
@@ -78,11 +87,43 @@ impl SqliteStateStore {
 
         Ok(Self {
             pool,
+            blob_store: None,
             write_queue,
             _background_task: background_task,
         })
     }
 
+    /// Delegate storage of data and binary blobs to the given blob store.
+    pub fn with_blob_store(mut self, blob_store: Arc<dyn BlobStore>) -> Self {
+        self.blob_store = Some(blob_store);
+        self
+    }
+
+    /// Queue a write operation for the background worker.
+    fn send_write(
+        &self,
+        operation: StateWriteOperation,
+    ) -> std::result::Result<(), mpsc::error::SendError<StateWriteOperation>> {
+        // Count the write before sending so the worker never decrements first.
+        metrics::gauge!(STATE_WRITE_QUEUE_DEPTH).increment(1);
+        self.write_queue
+            .send(operation)
+            .inspect_err(|_| metrics::gauge!(STATE_WRITE_QUEUE_DEPTH).decrement(1))
+    }
+
+    /// Wait for all queued writes to be applied.
+    async fn flush_all_pending_writes(&self) -> Result<(), StateError> {
+        let (tx, rx) = oneshot::channel();
+        self.send_write(StateWriteOperation::Flush {
+            run_id: None,
+            completion_notify: tx,
+        })
+        .map_err(|_| error_stack::report!(StateError::Internal))?;
+        rx.await
+            .map_err(|_| error_stack::report!(StateError::Internal))?
+            .map_err(|e| error_stack::report!(e))
+    }
+
     /// Create SqliteStateStore directly from a database URL
     pub async fn from_url(database_url: &str) -> Result<Self, StateError> {
         let config = SqliteStateStoreConfig {
@@ -99,6 +140,7 @@ impl SqliteStateStore {
         pool: SqlitePool,
     ) {
         while let Some(operation) = receiver.recv().await {
+            metrics::gauge!(STATE_WRITE_QUEUE_DEPTH).decrement(1);
             match operation {
                 StateWriteOperation::RecordStepResult {
                     run_id,
@@ -132,26 +174,31 @@ impl SqliteStateStore {
     }
 
     /// Synchronous version of create_run for background worker
-    async fn create_run_sync(
-        pool: &SqlitePool,
-        run_id: Uuid,
-        flow_id: BlobId,
-        flow_name: Option<String>,
-        flow_label: Option<String>,
-        debug_mode: bool,
-        input: ValueRef,
-    ) -> Result<(), StateError> {
-        let input_json =
-            serde_json::to_string(input.as_ref()).change_context(StateError::Serialization)?;
-        let sql = "INSERT INTO runs (id, flow_id, flow_name, flow_label, status, debug_mode, input_json) VALUES (?, ?, ?, ?, 'running', ?, ?)";
+    async fn create_run_sync(pool: &SqlitePool, params: CreateRunParams) -> Result<(), StateError> {
+        let input_json = serde_json::to_string(params.input.as_ref())
+            .change_context(StateError::Serialization)?;
+        let callback_json = params
+            .callback
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .change_context(StateError::Serialization)?;
+        let sql = "INSERT INTO runs (id, flow_id, flow_name, flow_label, namespace, status, debug_mode, input_json, parent_run_id, parent_step_id, root_run_id, batch_id, created_by, callback) VALUES (?, ?, ?, ?, ?, 'running', ?, ?, ?, ?, ?, ?, ?, ?)";
 
         sqlx::query(sql)
-            .bind(run_id.to_string())
-            .bind(flow_id.to_string())
-            .bind(flow_name)
-            .bind(flow_label)
-            .bind(debug_mode)
+            .bind(params.run_id.to_string())
+            .bind(params.flow_id.to_string())
+            .bind(params.flow_name)
+            .bind(params.flow_label)
+            .bind(params.namespace)
+            .bind(params.debug_mode)
             .bind(&input_json)
+            .bind(params.parent_run_id.map(|id| id.to_string()))
+            .bind(params.parent_step_id)
+            .bind(params.root_run_id.to_string())
+            .bind(params.batch_id.map(|id| id.to_string()))
+            .bind(params.created_by)
+            .bind(callback_json)
             .execute(pool)
             .await
             .change_context(StateError::Internal)?;
@@ -255,6 +302,126 @@ impl SqliteStateStore {
     }
 }
 
+/// Parse a timestamp stored by SQLite.
+///
+/// Columns defaulting to `CURRENT_TIMESTAMP` hold UTC times formatted as
+/// `YYYY-MM-DD HH:MM:SS`, while values written by the store are RFC 3339.
+fn parse_timestamp(value: &str) -> Result<chrono::DateTime<chrono::Utc>, StateError> {
+    if let Ok(timestamp) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Ok(timestamp.with_timezone(&chrono::Utc));
+    }
+    chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f")
+        .map(|timestamp| timestamp.and_utc())
+        .change_context(StateError::Internal)
+        .attach_printable_lazy(|| format!("invalid timestamp: {value}"))
+}
+
+/// Parse the JSON stored in the `progress` column of `step_progress`.
+fn parse_progress(value: &str) -> Result<StepProgress, StateError> {
+    serde_json::from_str(value).change_context(StateError::Serialization)
+}
+
+/// Read an optional UUID column, such as `parent_run_id`, of a run row.
+fn parse_optional_uuid(
+    row: &sqlx::sqlite::SqliteRow,
+    column: &str,
+) -> Result<Option<Uuid>, StateError> {
+    row.get::<Option<String>, _>(column)
+        .map(|id| Uuid::parse_str(&id).change_context(StateError::Internal))
+        .transpose()
+}
+
+/// Select the ID and status of the runs matching `roots` and of every run
+/// nested beneath them.
+fn run_tree_sql(roots: &str) -> String {
+    format!(
+        r#"
+            WITH RECURSIVE tree(id) AS (
+                SELECT id FROM runs WHERE {roots}
+                UNION SELECT r.id FROM runs r JOIN tree t ON r.parent_run_id = t.id
+            )
+            SELECT r.id, r.status FROM runs r JOIN tree t ON r.id = t.id
+        "#
+    )
+}
+
+/// Count the runs of a run tree that are running or paused.
+fn count_active(tree: &[(String, String)]) -> usize {
+    tree.iter()
+        .filter(|(_, status)| status == "running" || status == "paused")
+        .count()
+}
+
+/// Delete runs and everything recorded for them.
+async fn delete_run_rows(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    tree: &[(String, String)],
+) -> Result<(), StateError> {
+    for table in [
+        "step_results",
+        "step_info",
+        "step_logs",
+        "step_progress",
+        "webhook_deliveries",
+    ] {
+        for (id, _) in tree {
+            sqlx::query(&format!("DELETE FROM {table} WHERE run_id = ?"))
+                .bind(id)
+                .execute(&mut **tx)
+                .await
+                .change_context(StateError::Internal)?;
+        }
+    }
+    for (id, _) in tree {
+        sqlx::query("DELETE FROM runs WHERE id = ?")
+            .bind(id)
+            .execute(&mut **tx)
+            .await
+            .change_context(StateError::Internal)?;
+    }
+    Ok(())
+}
+
+/// A flow label within a namespace.
+struct LabelKey<'a> {
+    namespace: &'a str,
+    name: &'a str,
+    label: &'a str,
+}
+
+/// Append a label change to `flow_label_history`.
+///
+/// A `flow_id` of `None` records that the label was deleted.
+async fn record_label_change(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    key: LabelKey<'_>,
+    flow_id: Option<&str>,
+    previous_flow_id: Option<&str>,
+    actor: &str,
+    comment: Option<&str>,
+) -> Result<(), StateError> {
+    let sql = r#"
+        INSERT INTO flow_label_history
+            (namespace, name, label, flow_id, previous_flow_id, actor, comment, changed_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+    "#;
+
+    sqlx::query(sql)
+        .bind(key.namespace)
+        .bind(key.name)
+        .bind(key.label)
+        .bind(flow_id)
+        .bind(previous_flow_id)
+        .bind(actor)
+        .bind(comment)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .execute(&mut **tx)
+        .await
+        .change_context(StateError::Internal)?;
+
+    Ok(())
+}
+
 impl StateStore for SqliteStateStore {
     fn put_blob(
         &self,
@@ -262,16 +429,33 @@ impl StateStore for SqliteStateStore {
         blob_type: BlobType,
     ) -> BoxFuture<'_, error_stack::Result<BlobId, StateError>> {
         async move {
+            if blob_type == BlobType::Binary {
+                let content = stepflow_core::blob::decode_base64_value(data.as_ref())
+                    .change_context(StateError::Serialization)?;
+                return self
+                    .put_binary_blob(
+                        content,
+                        stepflow_core::blob::DEFAULT_BINARY_CONTENT_TYPE.to_string(),
+                    )
+                    .await;
+            }
+
             // Generate content-based ID
             let blob_id = BlobId::from_content(&data).change_context(StateError::Internal)?;
 
+            if let Some(blob_store) = &self.blob_store
+                && blob_type != BlobType::Flow
+            {
+                let blob_data = BlobData::from_value_ref(data, blob_type, blob_id.clone())
+                    .change_context(StateError::Internal)?;
+                blob_store.put(blob_data).await?;
+                return Ok(blob_id);
+            }
+
             // Serialize data to JSON string
             let json_str =
                 serde_json::to_string(data.as_ref()).change_context(StateError::Serialization)?;
-            let type_str = match blob_type {
-                BlobType::Flow => "flow",
-                BlobType::Data => "data",
-            };
+            let type_str = blob_type.as_str();
 
             // Store blob with type information
             let sql = "INSERT OR IGNORE INTO blobs (id, data, blob_type) VALUES (?, ?, ?)";
@@ -289,13 +473,44 @@ impl StateStore for SqliteStateStore {
         .boxed()
     }
 
+    fn put_binary_blob(
+        &self,
+        content: Vec<u8>,
+        content_type: String,
+    ) -> BoxFuture<'_, error_stack::Result<BlobId, StateError>> {
+        async move {
+            let blob_id = BlobId::from_bytes(&content, &content_type).change_context(StateError::Internal)?;
+
+            if let Some(blob_store) = &self.blob_store {
+                let blob_data =
+                    BlobData::new(BlobValue::binary(content, content_type), blob_id.clone());
+                blob_store.put(blob_data).await?;
+                return Ok(blob_id);
+            }
+
+            // The JSON `data` column is not used for binary blobs.
+            let sql = "INSERT OR IGNORE INTO blobs (id, data, blob_type, content, content_type) VALUES (?, 'null', 'binary', ?, ?)";
+
+            sqlx::query(sql)
+                .bind(blob_id.as_str())
+                .bind(&content)
+                .bind(&content_type)
+                .execute(&self.pool)
+                .await
+                .change_context(StateError::Internal)?;
+
+            Ok(blob_id)
+        }
+        .boxed()
+    }
+
     fn get_blob(
         &self,
         blob_id: &BlobId,
     ) -> BoxFuture<'_, error_stack::Result<BlobData, StateError>> {
         let blob_id = blob_id.clone();
         async move {
-            let sql = "SELECT data, blob_type FROM blobs WHERE id = ?";
+            let sql = "SELECT data, blob_type, content, content_type FROM blobs WHERE id = ?";
 
             let row = sqlx::query(sql)
                 .bind(blob_id.as_str())
@@ -303,18 +518,36 @@ impl StateStore for SqliteStateStore {
                 .await
                 .change_context(StateError::Internal)?;
 
-            let row = row.ok_or_else(|| {
-                error_stack::report!(StateError::BlobNotFound {
-                    blob_id: blob_id.as_str().to_string(),
-                })
-            })?;
+            let Some(row) = row else {
+                let blob_data = match &self.blob_store {
+                    Some(blob_store) => blob_store.get(&blob_id).await?,
+                    None => None,
+                };
+                return blob_data.ok_or_else(|| {
+                    error_stack::report!(StateError::BlobNotFound {
+                        blob_id: blob_id.as_str().to_string(),
+                    })
+                });
+            };
+
+            // Get blob type from database
+            let type_str: String = row.get("blob_type");
+            if type_str == "binary" {
+                let content: Vec<u8> = row.get("content");
+                let content_type: Option<String> = row.get("content_type");
+                let content_type = content_type.unwrap_or_else(|| {
+                    stepflow_core::blob::DEFAULT_BINARY_CONTENT_TYPE.to_string()
+                });
+                return Ok(BlobData::new(
+                    BlobValue::binary(content, content_type),
+                    blob_id,
+                ));
+            }
 
             let json_str: String = row.get("data");
             let value: serde_json::Value =
                 serde_json::from_str(&json_str).change_context(StateError::Serialization)?;
 
-            // Get blob type from database
-            let type_str: String = row.get("blob_type");
             let blob_type = match type_str.as_str() {
                 "flow" => BlobType::Flow,
                 "data" => BlobType::Data,
@@ -332,6 +565,52 @@ impl StateStore for SqliteStateStore {
         .boxed()
     }
 
+    fn add_blob_to_namespace(
+        &self,
+        namespace: &str,
+        blob_id: &BlobId,
+    ) -> BoxFuture<'_, error_stack::Result<(), StateError>> {
+        let namespace = namespace.to_string();
+        let blob_id = blob_id.clone();
+        async move {
+            sqlx::query("INSERT OR IGNORE INTO blob_namespaces (namespace, blob_id) VALUES (?, ?)")
+                .bind(&namespace)
+                .bind(blob_id.as_str())
+                .execute(&self.pool)
+                .await
+                .change_context(StateError::Internal)?;
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn blob_in_namespace(
+        &self,
+        namespace: &str,
+        blob_id: &BlobId,
+    ) -> BoxFuture<'_, error_stack::Result<bool, StateError>> {
+        let namespace = namespace.to_string();
+        let blob_id = blob_id.clone();
+        async move {
+            let sql = r#"
+                SELECT 1 FROM blob_namespaces WHERE namespace = ? AND blob_id = ?
+                UNION ALL
+                SELECT 1 FROM flow_namespaces WHERE namespace = ? AND flow_id = ?
+                LIMIT 1
+            "#;
+            let found: Option<i64> = sqlx::query_scalar(sql)
+                .bind(&namespace)
+                .bind(blob_id.as_str())
+                .bind(&namespace)
+                .bind(blob_id.as_str())
+                .fetch_optional(&self.pool)
+                .await
+                .change_context(StateError::Internal)?;
+            Ok(found.is_some())
+        }
+        .boxed()
+    }
+
     fn get_step_result(
         &self,
         run_id: Uuid,
@@ -402,14 +681,25 @@ impl StateStore for SqliteStateStore {
 
     fn store_flow(
         &self,
+        namespace: &str,
         workflow: Arc<Flow>,
     ) -> BoxFuture<'_, error_stack::Result<BlobId, StateError>> {
         // Use the unified blob storage
         let workflow_json = serde_json::to_value(workflow.as_ref());
+        let namespace = namespace.to_string();
         async move {
             let flow_data = workflow_json.change_context(StateError::Serialization)?;
             let flow_value = ValueRef::new(flow_data);
-            self.put_blob(flow_value, BlobType::Flow).await
+            let flow_id = self.put_blob(flow_value, BlobType::Flow).await?;
+
+            sqlx::query("INSERT OR IGNORE INTO flow_namespaces (namespace, flow_id) VALUES (?, ?)")
+                .bind(&namespace)
+                .bind(flow_id.as_str())
+                .execute(&self.pool)
+                .await
+                .change_context(StateError::Internal)?;
+
+            Ok(flow_id)
         }
         .boxed()
     }
@@ -434,10 +724,12 @@ impl StateStore for SqliteStateStore {
 
     fn get_flows(
         &self,
+        namespace: &str,
         name: &str,
     ) -> BoxFuture<'_, error_stack::Result<Vec<(BlobId, chrono::DateTime<chrono::Utc>)>, StateError>>
     {
         let pool = self.pool.clone();
+        let namespace = namespace.to_string();
         let name = name.to_string();
 
         async move {
@@ -445,16 +737,19 @@ impl StateStore for SqliteStateStore {
             let sql = r#"
                 SELECT DISTINCT b.id, b.created_at 
                 FROM blobs b 
-                LEFT JOIN flow_labels l ON l.flow_id = b.id 
+                LEFT JOIN flow_labels l ON l.flow_id = b.id AND l.namespace = ?
+                LEFT JOIN flow_namespaces n ON n.flow_id = b.id AND n.namespace = ?
                 WHERE b.blob_type = 'flow' 
                 AND (
                     l.name = ? 
-                    OR json_extract(b.data, '$.name') = ?
+                    OR (n.flow_id IS NOT NULL AND json_extract(b.data, '$.name') = ?)
                 )
                 ORDER BY b.created_at DESC
             "#;
 
             let rows = sqlx::query(sql)
+                .bind(&namespace)
+                .bind(&namespace)
                 .bind(&name)
                 .bind(&name)
                 .fetch_all(&pool)
@@ -465,10 +760,7 @@ impl StateStore for SqliteStateStore {
             for row in rows {
                 let flow_id =
                     BlobId::new(row.get::<String, _>("id")).change_context(StateError::Internal)?;
-                let created_at =
-                    chrono::DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
-                        .change_context(StateError::Internal)?
-                        .with_timezone(&chrono::Utc);
+                let created_at = parse_timestamp(&row.get::<String, _>("created_at"))?;
                 results.push((flow_id, created_at));
             }
 
@@ -479,10 +771,12 @@ impl StateStore for SqliteStateStore {
 
     fn get_named_flow(
         &self,
+        namespace: &str,
         name: &str,
         label: Option<&str>,
     ) -> BoxFuture<'_, error_stack::Result<Option<WorkflowWithMetadata>, StateError>> {
         let pool = self.pool.clone();
+        let namespace = namespace.to_string();
         let name = name.to_string();
         let label = label.map(|s| s.to_string());
 
@@ -495,10 +789,11 @@ impl StateStore for SqliteStateStore {
                                b.data, b.created_at as flow_created_at
                         FROM flow_labels l 
                         JOIN blobs b ON l.flow_id = b.id 
-                        WHERE l.name = ? AND l.label = ? AND b.blob_type = 'flow'
+                        WHERE l.namespace = ? AND l.name = ? AND l.label = ? AND b.blob_type = 'flow'
                     "#;
 
                     let row = sqlx::query(sql)
+                        .bind(&namespace)
                         .bind(&name)
                         .bind(&label_str)
                         .fetch_optional(&pool)
@@ -516,22 +811,11 @@ impl StateStore for SqliteStateStore {
                                 name: row.get("name"),
                                 label: row.get("label"),
                                 flow_id: flow_id.clone(),
-                                created_at: chrono::DateTime::parse_from_rfc3339(
-                                    &row.get::<String, _>("created_at"),
-                                )
-                                .change_context(StateError::Internal)?
-                                .with_timezone(&chrono::Utc),
-                                updated_at: chrono::DateTime::parse_from_rfc3339(
-                                    &row.get::<String, _>("updated_at"),
-                                )
-                                .change_context(StateError::Internal)?
-                                .with_timezone(&chrono::Utc),
+                                created_at: parse_timestamp(&row.get::<String, _>("created_at"))?,
+                                updated_at: parse_timestamp(&row.get::<String, _>("updated_at"))?,
                             };
-                            let created_at = chrono::DateTime::parse_from_rfc3339(
-                                &row.get::<String, _>("flow_created_at"),
-                            )
-                            .change_context(StateError::Internal)?
-                            .with_timezone(&chrono::Utc);
+                            let created_at =
+                                parse_timestamp(&row.get::<String, _>("flow_created_at"))?;
 
                             Ok(Some(WorkflowWithMetadata {
                                 workflow: Arc::new(workflow),
@@ -548,17 +832,20 @@ impl StateStore for SqliteStateStore {
                     let sql = r#"
                         SELECT DISTINCT b.id, b.data, b.created_at 
                         FROM blobs b 
-                        LEFT JOIN flow_labels l ON l.flow_id = b.id 
+                        LEFT JOIN flow_labels l ON l.flow_id = b.id AND l.namespace = ?
+                        LEFT JOIN flow_namespaces n ON n.flow_id = b.id AND n.namespace = ?
                         WHERE b.blob_type = 'flow' 
                         AND (
                             l.name = ? 
-                            OR json_extract(b.data, '$.name') = ?
+                            OR (n.flow_id IS NOT NULL AND json_extract(b.data, '$.name') = ?)
                         )
                         ORDER BY b.created_at DESC 
                         LIMIT 1
                     "#;
 
                     let row = sqlx::query(sql)
+                        .bind(&namespace)
+                        .bind(&namespace)
                         .bind(&name)
                         .bind(&name)
                         .fetch_optional(&pool)
@@ -572,11 +859,7 @@ impl StateStore for SqliteStateStore {
                                 .change_context(StateError::Internal)?;
                             let flow_id = BlobId::new(row.get::<String, _>("id"))
                                 .change_context(StateError::Internal)?;
-                            let created_at = chrono::DateTime::parse_from_rfc3339(
-                                &row.get::<String, _>("created_at"),
-                            )
-                            .change_context(StateError::Internal)?
-                            .with_timezone(&chrono::Utc);
+                            let created_at = parse_timestamp(&row.get::<String, _>("created_at"))?;
 
                             Ok(Some(WorkflowWithMetadata {
                                 workflow: Arc::new(workflow),
@@ -593,42 +876,238 @@ impl StateStore for SqliteStateStore {
         .boxed()
     }
 
+    fn delete_flow(
+        &self,
+        namespace: &str,
+        flow_id: &BlobId,
+        delete_runs: bool,
+    ) -> BoxFuture<'_, error_stack::Result<bool, StateError>> {
+        let pool = self.pool.clone();
+        let namespace = namespace.to_string();
+        let flow_id = flow_id.clone();
+
+        async move {
+            // Apply queued step writes so they cannot recreate deleted runs.
+            self.flush_all_pending_writes().await?;
+
+            let mut tx = pool.begin().await.change_context(StateError::Internal)?;
+
+            let exists: Option<i64> =
+                sqlx::query_scalar("SELECT 1 FROM flow_namespaces WHERE namespace = ? AND flow_id = ?")
+                    .bind(&namespace)
+                    .bind(flow_id.as_str())
+                    .fetch_optional(&mut *tx)
+                    .await
+                    .change_context(StateError::Internal)?;
+            if exists.is_none() {
+                return Ok(false);
+            }
+
+            // The runs of the flow and the runs nested within them.
+            let tree: Vec<(String, String)> =
+                sqlx::query_as(&run_tree_sql("flow_id = ? AND namespace = ?"))
+                    .bind(flow_id.as_str())
+                    .bind(&namespace)
+                    .fetch_all(&mut *tx)
+                    .await
+                    .change_context(StateError::Internal)?;
+
+            let active = count_active(&tree);
+            if active > 0 {
+                return Err(error_stack::report!(StateError::FlowHasActiveRuns {
+                    flow_id: flow_id.to_string(),
+                    count: active,
+                }));
+            }
+            if !tree.is_empty() && !delete_runs {
+                return Err(error_stack::report!(StateError::FlowHasRuns {
+                    flow_id: flow_id.to_string(),
+                    count: tree.len(),
+                }));
+            }
+
+            delete_run_rows(&mut tx, &tree).await?;
+            sqlx::query("DELETE FROM flow_namespaces WHERE flow_id = ? AND namespace = ?")
+                .bind(flow_id.as_str())
+                .bind(&namespace)
+                .execute(&mut *tx)
+                .await
+                .change_context(StateError::Internal)?;
+
+            // Detach labels pointing at the flow, recording the change in their history.
+            let detached = sqlx::query(
+                "DELETE FROM flow_labels WHERE flow_id = ? AND namespace = ? RETURNING name, label",
+            )
+            .bind(flow_id.as_str())
+            .bind(&namespace)
+            .fetch_all(&mut *tx)
+            .await
+            .change_context(StateError::Internal)?;
+            let comment = format!("Flow {flow_id} deleted");
+            for row in detached {
+                record_label_change(
+                    &mut tx,
+                    LabelKey {
+                        namespace: &namespace,
+                        name: row.get("name"),
+                        label: row.get("label"),
+                    },
+                    None,
+                    Some(flow_id.as_str()),
+                    SYSTEM_ACTOR,
+                    Some(&comment),
+                )
+                .await?;
+            }
+
+            // Only remove the flow itself once no namespace holds it.
+            sqlx::query(
+                "DELETE FROM blobs WHERE id = ? AND NOT EXISTS (SELECT 1 FROM flow_namespaces WHERE flow_id = ?)",
+            )
+            .bind(flow_id.as_str())
+            .bind(flow_id.as_str())
+            .execute(&mut *tx)
+            .await
+            .change_context(StateError::Internal)?;
+
+            tx.commit().await.change_context(StateError::Internal)?;
+            Ok(true)
+        }
+        .boxed()
+    }
+
     fn create_or_update_label(
         &self,
+        namespace: &str,
         name: &str,
         label: &str,
         flow_id: BlobId,
+        actor: &str,
+        comment: Option<&str>,
     ) -> BoxFuture<'_, error_stack::Result<(), StateError>> {
         let pool = self.pool.clone();
+        let namespace = namespace.to_string();
         let name = name.to_string();
         let label = label.to_string();
+        let actor = actor.to_string();
+        let comment = comment.map(|s| s.to_string());
 
         async move {
-            let sql = "INSERT OR REPLACE INTO flow_labels (name, label, flow_id, updated_at) VALUES (?, ?, ?, CURRENT_TIMESTAMP)";
+            let mut tx = pool.begin().await.change_context(StateError::Internal)?;
+
+            let previous_flow_id: Option<String> = sqlx::query_scalar(
+                "SELECT flow_id FROM flow_labels WHERE namespace = ? AND name = ? AND label = ?",
+            )
+            .bind(&namespace)
+            .bind(&name)
+            .bind(&label)
+            .fetch_optional(&mut *tx)
+            .await
+            .change_context(StateError::Internal)?;
+
+            // Moving an existing label keeps its original creation time.
+            let sql = r#"
+                INSERT INTO flow_labels (namespace, name, label, flow_id, updated_at)
+                VALUES (?, ?, ?, ?, CURRENT_TIMESTAMP)
+                ON CONFLICT (namespace, name, label)
+                DO UPDATE SET flow_id = excluded.flow_id, updated_at = CURRENT_TIMESTAMP
+            "#;
 
             sqlx::query(sql)
+                .bind(&namespace)
                 .bind(&name)
                 .bind(&label)
                 .bind(flow_id.to_string())
-                .execute(&pool)
+                .execute(&mut *tx)
                 .await
                 .change_context(StateError::Internal)?;
 
+            record_label_change(
+                &mut tx,
+                LabelKey {
+                    namespace: &namespace,
+                    name: &name,
+                    label: &label,
+                },
+                Some(flow_id.as_str()),
+                previous_flow_id.as_deref(),
+                &actor,
+                comment.as_deref(),
+            )
+            .await?;
+
+            tx.commit().await.change_context(StateError::Internal)?;
             Ok(())
-        }.boxed()
+        }
+        .boxed()
+    }
+
+    fn list_label_history(
+        &self,
+        namespace: &str,
+        name: &str,
+        label: &str,
+    ) -> BoxFuture<'_, error_stack::Result<Vec<WorkflowLabelHistoryEntry>, StateError>> {
+        let pool = self.pool.clone();
+        let namespace = namespace.to_string();
+        let name = name.to_string();
+        let label = label.to_string();
+
+        async move {
+            let sql = r#"
+                SELECT name, label, flow_id, previous_flow_id, actor, comment, changed_at
+                FROM flow_label_history
+                WHERE namespace = ? AND name = ? AND label = ?
+                ORDER BY id DESC
+            "#;
+
+            let rows = sqlx::query(sql)
+                .bind(&namespace)
+                .bind(&name)
+                .bind(&label)
+                .fetch_all(&pool)
+                .await
+                .change_context(StateError::Internal)?;
+
+            let parse_flow_id = |value: Option<String>| {
+                value
+                    .map(BlobId::new)
+                    .transpose()
+                    .change_context(StateError::Internal)
+            };
+
+            let mut history = Vec::new();
+            for row in rows {
+                history.push(WorkflowLabelHistoryEntry {
+                    name: row.get("name"),
+                    label: row.get("label"),
+                    flow_id: parse_flow_id(row.get("flow_id"))?,
+                    previous_flow_id: parse_flow_id(row.get("previous_flow_id"))?,
+                    actor: row.get("actor"),
+                    comment: row.get("comment"),
+                    changed_at: parse_timestamp(&row.get::<String, _>("changed_at"))?,
+                });
+            }
+
+            Ok(history)
+        }
+        .boxed()
     }
 
     fn list_labels_for_name(
         &self,
+        namespace: &str,
         name: &str,
     ) -> BoxFuture<'_, error_stack::Result<Vec<WorkflowLabelMetadata>, StateError>> {
         let pool = self.pool.clone();
+        let namespace = namespace.to_string();
         let name = name.to_string();
 
         async move {
-            let sql = "SELECT name, label, flow_id, created_at, updated_at FROM flow_labels WHERE name = ? ORDER BY created_at DESC";
+            let sql = "SELECT name, label, flow_id, created_at, updated_at FROM flow_labels WHERE namespace = ? AND name = ? ORDER BY created_at DESC";
 
             let rows = sqlx::query(sql)
+                .bind(&namespace)
                 .bind(&name)
                 .fetch_all(&pool)
                 .await
@@ -640,16 +1119,8 @@ impl StateStore for SqliteStateStore {
                     name: row.get("name"),
                     label: row.get("label"),
                     flow_id: BlobId::new(row.get::<String, _>("flow_id")).change_context(StateError::Internal)?,
-                    created_at: chrono::DateTime::parse_from_rfc3339(
-                        &row.get::<String, _>("created_at"),
-                    )
-                    .change_context(StateError::Internal)?
-                    .with_timezone(&chrono::Utc),
-                    updated_at: chrono::DateTime::parse_from_rfc3339(
-                        &row.get::<String, _>("updated_at"),
-                    )
-                    .change_context(StateError::Internal)?
-                    .with_timezone(&chrono::Utc),
+                    created_at: parse_timestamp(&row.get::<String, _>("created_at"))?,
+                    updated_at: parse_timestamp(&row.get::<String, _>("updated_at"))?,
                 };
 
                 labels.push(workflow_label);
@@ -659,21 +1130,28 @@ impl StateStore for SqliteStateStore {
         }.boxed()
     }
 
-    fn list_flow_names(&self) -> BoxFuture<'_, error_stack::Result<Vec<String>, StateError>> {
+    fn list_flow_names(
+        &self,
+        namespace: &str,
+    ) -> BoxFuture<'_, error_stack::Result<Vec<String>, StateError>> {
         let pool = self.pool.clone();
+        let namespace = namespace.to_string();
 
         async move {
             let sql = r#"
-                SELECT DISTINCT name FROM flow_labels 
+                SELECT DISTINCT name FROM flow_labels WHERE namespace = ?
                 UNION 
-                SELECT DISTINCT json_extract(data, '$.name') as name 
-                FROM blobs 
-                WHERE blob_type = 'flow' 
-                AND json_extract(data, '$.name') IS NOT NULL 
+                SELECT DISTINCT json_extract(b.data, '$.name') as name 
+                FROM blobs b
+                JOIN flow_namespaces n ON n.flow_id = b.id AND n.namespace = ?
+                WHERE b.blob_type = 'flow' 
+                AND json_extract(b.data, '$.name') IS NOT NULL 
                 ORDER BY name
             "#;
 
             let rows = sqlx::query(sql)
+                .bind(&namespace)
+                .bind(&namespace)
                 .fetch_all(&pool)
                 .await
                 .change_context(StateError::Internal)?;
@@ -691,23 +1169,49 @@ impl StateStore for SqliteStateStore {
 
     fn delete_label(
         &self,
+        namespace: &str,
         name: &str,
         label: &str,
+        actor: &str,
+        comment: Option<&str>,
     ) -> BoxFuture<'_, error_stack::Result<(), StateError>> {
         let pool = self.pool.clone();
+        let namespace = namespace.to_string();
         let name = name.to_string();
         let label = label.to_string();
+        let actor = actor.to_string();
+        let comment = comment.map(|s| s.to_string());
 
         async move {
-            let sql = "DELETE FROM flow_labels WHERE name = ? AND label = ?";
+            let mut tx = pool.begin().await.change_context(StateError::Internal)?;
 
-            sqlx::query(sql)
+            let sql = "DELETE FROM flow_labels WHERE namespace = ? AND name = ? AND label = ? RETURNING flow_id";
+
+            let previous_flow_id: Option<String> = sqlx::query_scalar(sql)
+                .bind(&namespace)
                 .bind(&name)
                 .bind(&label)
-                .execute(&pool)
+                .fetch_optional(&mut *tx)
                 .await
                 .change_context(StateError::Internal)?;
 
+            if let Some(previous_flow_id) = previous_flow_id {
+                record_label_change(
+                    &mut tx,
+                    LabelKey {
+                        namespace: &namespace,
+                        name: &name,
+                        label: &label,
+                    },
+                    None,
+                    Some(&previous_flow_id),
+                    &actor,
+                    comment.as_deref(),
+                )
+                .await?;
+            }
+
+            tx.commit().await.change_context(StateError::Internal)?;
             Ok(())
         }
         .boxed()
@@ -715,31 +1219,12 @@ impl StateStore for SqliteStateStore {
 
     fn create_run(
         &self,
-        run_id: Uuid,
-        flow_id: BlobId,
-        flow_name: Option<&str>,
-        flow_label: Option<&str>,
-        debug_mode: bool,
-        input: ValueRef,
+        params: CreateRunParams,
     ) -> BoxFuture<'_, error_stack::Result<(), StateError>> {
         // Execute synchronously to avoid race condition with step results
         let pool = self.pool.clone();
-        let workflow_name = flow_name.map(|s| s.to_string());
-        let workflow_label = flow_label.map(|s| s.to_string());
 
-        async move {
-            Self::create_run_sync(
-                &pool,
-                run_id,
-                flow_id,
-                workflow_name,
-                workflow_label,
-                debug_mode,
-                input,
-            )
-            .await
-        }
-        .boxed()
+        async move { Self::create_run_sync(&pool, params).await }.boxed()
     }
 
     fn update_run_status(
@@ -759,9 +1244,9 @@ impl StateStore for SqliteStateStore {
 
             let sql = match status {
                 ExecutionStatus::Completed | ExecutionStatus::Failed | ExecutionStatus::Cancelled => {
-                    "UPDATE executions SET status = ?, result_json = ?, completed_at = CURRENT_TIMESTAMP WHERE id = ?"
+                    "UPDATE runs SET status = ?, result_json = ?, completed_at = CURRENT_TIMESTAMP WHERE id = ?"
                 }
-                _ => "UPDATE executions SET status = ?, result_json = ? WHERE id = ?",
+                _ => "UPDATE runs SET status = ?, result_json = ? WHERE id = ?",
             };
 
             sqlx::query(sql)
@@ -783,7 +1268,7 @@ impl StateStore for SqliteStateStore {
         let pool = self.pool.clone();
 
         async move {
-            let sql = "SELECT id, flow_name, flow_label, flow_id, status, debug_mode, input_json, result_json, created_at, completed_at FROM runs WHERE id = ?";
+            let sql = "SELECT id, flow_name, flow_label, flow_id, namespace, status, debug_mode, parent_run_id, parent_step_id, root_run_id, batch_id, created_by, input_json, result_json, created_at, completed_at FROM runs WHERE id = ?";
 
             let row = sqlx::query(sql)
                 .bind(run_id.to_string())
@@ -799,6 +1284,7 @@ impl StateStore for SqliteStateStore {
                         "completed" => ExecutionStatus::Completed,
                         "failed" => ExecutionStatus::Failed,
                         "paused" => ExecutionStatus::Paused,
+                        "cancelled" => ExecutionStatus::Cancelled,
                         _ => {
                             tracing::warn!("Unrecognized execution status: {status_str}");
                             ExecutionStatus::Running
@@ -824,18 +1310,12 @@ impl StateStore for SqliteStateStore {
                         None
                     };
 
-                    let created_at = chrono::DateTime::parse_from_rfc3339(
-                        &row.get::<String, _>("created_at"),
-                    )
-                    .change_context(StateError::Internal)?
-                    .with_timezone(&chrono::Utc);
+                    let created_at = parse_timestamp(&row.get::<String, _>("created_at"))?;
 
                     let completed_at = row
                         .get::<Option<String>, _>("completed_at")
                         .map(|s| {
-                            chrono::DateTime::parse_from_rfc3339(&s)
-                                .change_context(StateError::Internal)
-                                .map(|dt| dt.with_timezone(&chrono::Utc))
+                            parse_timestamp(&s)
                         })
                         .transpose()?;
 
@@ -845,8 +1325,14 @@ impl StateStore for SqliteStateStore {
                             flow_name,
                             flow_label,
                             flow_id,
+                            namespace: row.get("namespace"),
                             status,
                             debug_mode: row.get("debug_mode"),
+                            parent_run_id: parse_optional_uuid(&row, "parent_run_id")?,
+                            parent_step_id: row.get("parent_step_id"),
+                            root_run_id: parse_optional_uuid(&row, "root_run_id")?.unwrap_or(run_id),
+                            batch_id: parse_optional_uuid(&row, "batch_id")?,
+                            created_by: row.get("created_by"),
                             created_at,
                             completed_at,
                         },
@@ -869,7 +1355,7 @@ impl StateStore for SqliteStateStore {
         let filters = filters.clone();
 
         async move {
-            let mut sql = "SELECT id, flow_name, flow_label, flow_id, status, debug_mode, created_at, completed_at FROM runs".to_string();
+            let mut sql = "SELECT id, flow_name, flow_label, flow_id, namespace, status, debug_mode, parent_run_id, parent_step_id, root_run_id, batch_id, created_by, created_at, completed_at FROM runs".to_string();
             let mut conditions = Vec::new();
             let mut bind_values: Vec<String> = Vec::new();
 
@@ -885,11 +1371,36 @@ impl StateStore for SqliteStateStore {
                 bind_values.push(status_str.to_string());
             }
 
+            if let Some(ref flow_id) = filters.flow_id {
+                conditions.push("flow_id = ?".to_string());
+                bind_values.push(flow_id.to_string());
+            }
+
             if let Some(ref flow_name) = filters.flow_name {
                 conditions.push("flow_name = ?".to_string());
                 bind_values.push(flow_name.clone());
             }
 
+            if let Some(ref flow_label) = filters.flow_label {
+                conditions.push("flow_label = ?".to_string());
+                bind_values.push(flow_label.clone());
+            }
+
+            if let Some(ref namespace) = filters.namespace {
+                conditions.push("namespace = ?".to_string());
+                bind_values.push(namespace.clone());
+            }
+
+            if let Some(batch_id) = filters.batch_id {
+                conditions.push("batch_id = ?".to_string());
+                bind_values.push(batch_id.to_string());
+            }
+
+            if let Some(root_run_id) = filters.root_run_id {
+                conditions.push("root_run_id = ?".to_string());
+                bind_values.push(root_run_id.to_string());
+            }
+
             if !conditions.is_empty() {
                 sql.push_str(" WHERE ");
                 sql.push_str(&conditions.join(" AND "));
@@ -929,11 +1440,12 @@ impl StateStore for SqliteStateStore {
                     "completed" => ExecutionStatus::Completed,
                     "failed" => ExecutionStatus::Failed,
                     "paused" => ExecutionStatus::Paused,
+                    "cancelled" => ExecutionStatus::Cancelled,
                     _ => ExecutionStatus::Running,
                 };
 
-                let flow_name = row.get::<Option<String>, _>("workflow_name");
-                let flow_label = row.get::<Option<String>, _>("workflow_label");
+                let flow_name = row.get::<Option<String>, _>("flow_name");
+                let flow_label = row.get::<Option<String>, _>("flow_label");
                 let flow_id = BlobId::new(row.get::<String, _>("flow_id")).change_context(StateError::Internal)?;
 
                 let summary = RunSummary {
@@ -941,19 +1453,19 @@ impl StateStore for SqliteStateStore {
                     flow_name,
                     flow_label,
                     flow_id,
+                    namespace: row.get("namespace"),
                     status,
                     debug_mode: row.get("debug_mode"),
-                    created_at: chrono::DateTime::parse_from_rfc3339(
-                        &row.get::<String, _>("created_at"),
-                    )
-                    .change_context(StateError::Internal)?
-                    .with_timezone(&chrono::Utc),
+                    parent_run_id: parse_optional_uuid(&row, "parent_run_id")?,
+                    parent_step_id: row.get("parent_step_id"),
+                    root_run_id: parse_optional_uuid(&row, "root_run_id")?.unwrap_or(run_id),
+                    batch_id: parse_optional_uuid(&row, "batch_id")?,
+                    created_by: row.get("created_by"),
+                    created_at: parse_timestamp(&row.get::<String, _>("created_at"))?,
                     completed_at: row
                         .get::<Option<String>, _>("completed_at")
                         .map(|s| {
-                            chrono::DateTime::parse_from_rfc3339(&s)
-                                .change_context(StateError::Internal)
-                                .map(|dt| dt.with_timezone(&chrono::Utc))
+                            parse_timestamp(&s)
                         })
                         .transpose()?,
                 };
@@ -965,8 +1477,338 @@ impl StateStore for SqliteStateStore {
         }.boxed()
     }
 
+    fn delete_run(
+        &self,
+        run_id: Uuid,
+        cascade: bool,
+    ) -> BoxFuture<'_, error_stack::Result<Vec<Uuid>, StateError>> {
+        let pool = self.pool.clone();
+
+        async move {
+            // Apply queued step writes so they cannot recreate deleted runs.
+            self.flush_all_pending_writes().await?;
+
+            let mut tx = pool.begin().await.change_context(StateError::Internal)?;
+
+            let tree_sql = if cascade {
+                run_tree_sql("id = ?")
+            } else {
+                "SELECT id, status FROM runs WHERE id = ?".to_string()
+            };
+            let tree: Vec<(String, String)> = sqlx::query_as(&tree_sql)
+                .bind(run_id.to_string())
+                .fetch_all(&mut *tx)
+                .await
+                .change_context(StateError::Internal)?;
+
+            let active = count_active(&tree);
+            if active > 0 {
+                return Err(error_stack::report!(StateError::RunTreeActive {
+                    run_id,
+                    count: active,
+                }));
+            }
+
+            delete_run_rows(&mut tx, &tree).await?;
+            tx.commit().await.change_context(StateError::Internal)?;
+
+            tree.iter()
+                .map(|(id, _)| Uuid::parse_str(id).change_context(StateError::Internal))
+                .collect()
+        }
+        .boxed()
+    }
+
+    fn get_run_callback(
+        &self,
+        run_id: Uuid,
+    ) -> BoxFuture<'_, error_stack::Result<Option<RunCallback>, StateError>> {
+        async move {
+            let callback: Option<Option<String>> =
+                sqlx::query_scalar("SELECT callback FROM runs WHERE id = ?")
+                    .bind(run_id.to_string())
+                    .fetch_optional(&self.pool)
+                    .await
+                    .change_context(StateError::Internal)?;
+
+            callback
+                .flatten()
+                .map(|callback| {
+                    serde_json::from_str(&callback).change_context(StateError::Serialization)
+                })
+                .transpose()
+        }
+        .boxed()
+    }
+
+    fn put_webhook_delivery(
+        &self,
+        delivery: WebhookDelivery,
+    ) -> BoxFuture<'_, error_stack::Result<(), StateError>> {
+        async move {
+            let callback_json = serde_json::to_string(&delivery.callback)
+                .change_context(StateError::Serialization)?;
+            let payload_json = serde_json::to_string(&delivery.payload)
+                .change_context(StateError::Serialization)?;
+
+            let sql = r#"
+                INSERT INTO webhook_deliveries
+                    (id, run_id, callback, payload, status, attempts, next_attempt_at, last_error, created_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT (id) DO UPDATE SET
+                    status = excluded.status,
+                    attempts = excluded.attempts,
+                    next_attempt_at = excluded.next_attempt_at,
+                    last_error = excluded.last_error
+            "#;
+            sqlx::query(sql)
+                .bind(delivery.delivery_id.to_string())
+                .bind(delivery.run_id.to_string())
+                .bind(callback_json)
+                .bind(payload_json)
+                .bind(delivery.status.as_str())
+                .bind(delivery.attempts as i64)
+                .bind(delivery.next_attempt_at.to_rfc3339())
+                .bind(delivery.last_error)
+                .bind(delivery.created_at.to_rfc3339())
+                .execute(&self.pool)
+                .await
+                .change_context(StateError::Internal)?;
+
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn list_webhook_deliveries(
+        &self,
+        run_id: Option<Uuid>,
+        status: Option<DeliveryStatus>,
+    ) -> BoxFuture<'_, error_stack::Result<Vec<WebhookDelivery>, StateError>> {
+        async move {
+            let mut sql = "SELECT id, run_id, callback, payload, status, attempts, next_attempt_at, last_error, created_at FROM webhook_deliveries WHERE 1=1".to_string();
+            if run_id.is_some() {
+                sql.push_str(" AND run_id = ?");
+            }
+            if status.is_some() {
+                sql.push_str(" AND status = ?");
+            }
+            sql.push_str(" ORDER BY created_at, rowid");
+
+            let mut query = sqlx::query(&sql);
+            if let Some(run_id) = run_id {
+                query = query.bind(run_id.to_string());
+            }
+            if let Some(status) = status {
+                query = query.bind(status.as_str());
+            }
+            let rows = query
+                .fetch_all(&self.pool)
+                .await
+                .change_context(StateError::Internal)?;
+
+            rows.iter()
+                .map(|row| {
+                    let status: String = row.get("status");
+                    Ok(WebhookDelivery {
+                        delivery_id: Uuid::parse_str(&row.get::<String, _>("id"))
+                            .change_context(StateError::Internal)?,
+                        run_id: Uuid::parse_str(&row.get::<String, _>("run_id"))
+                            .change_context(StateError::Internal)?,
+                        callback: serde_json::from_str(&row.get::<String, _>("callback"))
+                            .change_context(StateError::Serialization)?,
+                        payload: serde_json::from_str(&row.get::<String, _>("payload"))
+                            .change_context(StateError::Serialization)?,
+                        status: status
+                            .parse()
+                            .map_err(|_| error_stack::report!(StateError::Internal))
+                            .attach_printable(status)?,
+                        attempts: row.get::<i64, _>("attempts") as u32,
+                        next_attempt_at: parse_timestamp(&row.get::<String, _>("next_attempt_at"))?,
+                        last_error: row.get("last_error"),
+                        created_at: parse_timestamp(&row.get::<String, _>("created_at"))?,
+                    })
+                })
+                .collect()
+        }
+        .boxed()
+    }
+
+    fn put_batch(&self, batch: BatchRecord) -> BoxFuture<'_, error_stack::Result<(), StateError>> {
+        async move {
+            let items_json =
+                serde_json::to_string(&batch.items).change_context(StateError::Serialization)?;
+
+            let sql = r#"
+                INSERT INTO batches (id, flow_id, namespace, created_by, created_at, items)
+                VALUES (?, ?, ?, ?, ?, ?)
+                ON CONFLICT (id) DO UPDATE SET items = excluded.items
+            "#;
+            sqlx::query(sql)
+                .bind(batch.batch_id.to_string())
+                .bind(batch.flow_id.to_string())
+                .bind(batch.namespace)
+                .bind(batch.created_by)
+                .bind(batch.created_at.to_rfc3339())
+                .bind(items_json)
+                .execute(&self.pool)
+                .await
+                .change_context(StateError::Internal)?;
+
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn get_batch(
+        &self,
+        batch_id: Uuid,
+    ) -> BoxFuture<'_, error_stack::Result<Option<BatchRecord>, StateError>> {
+        async move {
+            let row = sqlx::query(
+                "SELECT flow_id, namespace, created_by, created_at, items FROM batches WHERE id = ?",
+            )
+            .bind(batch_id.to_string())
+            .fetch_optional(&self.pool)
+            .await
+            .change_context(StateError::Internal)?;
+
+            row.map(|row| {
+                Ok(BatchRecord {
+                    batch_id,
+                    flow_id: BlobId::new(row.get::<String, _>("flow_id"))
+                        .change_context(StateError::Internal)?,
+                    namespace: row.get("namespace"),
+                    created_by: row.get("created_by"),
+                    created_at: parse_timestamp(&row.get::<String, _>("created_at"))?,
+                    items: serde_json::from_str(&row.get::<String, _>("items"))
+                        .change_context(StateError::Serialization)?,
+                })
+            })
+            .transpose()
+        }
+        .boxed()
+    }
+
     // Step Status Management
 
+    fn record_step_log(
+        &self,
+        run_id: Uuid,
+        step_id: String,
+        log: StepLog,
+    ) -> BoxFuture<'_, error_stack::Result<(), StateError>> {
+        async move {
+            let fields_json = if log.fields.is_empty() {
+                None
+            } else {
+                Some(serde_json::to_string(&log.fields).change_context(StateError::Serialization)?)
+            };
+
+            let sql = "INSERT INTO step_logs (run_id, step_id, level, message, fields, timestamp) VALUES (?, ?, ?, ?, ?, ?)";
+            sqlx::query(sql)
+                .bind(run_id.to_string())
+                .bind(step_id)
+                .bind(log.level.as_str())
+                .bind(log.message)
+                .bind(fields_json)
+                .bind(log.timestamp.to_rfc3339())
+                .execute(&self.pool)
+                .await
+                .change_context(StateError::Internal)?;
+
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn get_step_logs(
+        &self,
+        run_id: Uuid,
+        step_id: &str,
+    ) -> BoxFuture<'_, error_stack::Result<Vec<StepLog>, StateError>> {
+        let step_id = step_id.to_owned();
+        async move {
+            let sql = "SELECT level, message, fields, timestamp FROM step_logs WHERE run_id = ? AND step_id = ? ORDER BY id";
+            let rows = sqlx::query(sql)
+                .bind(run_id.to_string())
+                .bind(step_id)
+                .fetch_all(&self.pool)
+                .await
+                .change_context(StateError::Internal)?;
+
+            rows.iter()
+                .map(|row| {
+                    let level: String = row.get("level");
+                    let fields: Option<String> = row.get("fields");
+                    Ok(StepLog {
+                        level: level
+                            .parse()
+                            .map_err(|_| error_stack::report!(StateError::Internal))
+                            .attach_printable(level)?,
+                        message: row.get("message"),
+                        fields: fields
+                            .map(|fields| serde_json::from_str(&fields))
+                            .transpose()
+                            .change_context(StateError::Serialization)?
+                            .unwrap_or_default(),
+                        timestamp: parse_timestamp(&row.get::<String, _>("timestamp"))?,
+                    })
+                })
+                .collect()
+        }
+        .boxed()
+    }
+
+    fn update_step_progress(
+        &self,
+        run_id: Uuid,
+        step_id: String,
+        progress: StepProgress,
+    ) -> BoxFuture<'_, error_stack::Result<(), StateError>> {
+        async move {
+            let progress_json =
+                serde_json::to_string(&progress).change_context(StateError::Serialization)?;
+
+            let sql = r#"
+                INSERT INTO step_progress (run_id, step_id, progress) VALUES (?, ?, ?)
+                ON CONFLICT (run_id, step_id) DO UPDATE SET progress = excluded.progress
+            "#;
+            sqlx::query(sql)
+                .bind(run_id.to_string())
+                .bind(step_id)
+                .bind(progress_json)
+                .execute(&self.pool)
+                .await
+                .change_context(StateError::Internal)?;
+
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn get_step_progress(
+        &self,
+        run_id: Uuid,
+    ) -> BoxFuture<'_, error_stack::Result<HashMap<String, StepProgress>, StateError>> {
+        async move {
+            let sql = "SELECT step_id, progress FROM step_progress WHERE run_id = ?";
+            let rows = sqlx::query(sql)
+                .bind(run_id.to_string())
+                .fetch_all(&self.pool)
+                .await
+                .change_context(StateError::Internal)?;
+
+            rows.iter()
+                .map(|row| {
+                    let progress = parse_progress(&row.get::<String, _>("progress"))?;
+                    Ok((row.get("step_id"), progress))
+                })
+                .collect()
+        }
+        .boxed()
+    }
+
     fn initialize_step_info(
         &self,
         run_id: Uuid,
@@ -1024,14 +1866,11 @@ impl StateStore for SqliteStateStore {
         let mut step_indices = BitSet::new();
         step_indices.insert(step_index);
 
-        if let Err(e) = self
-            .write_queue
-            .send(StateWriteOperation::UpdateStepStatuses {
-                run_id,
-                status,
-                step_indices,
-            })
-        {
+        if let Err(e) = self.send_write(StateWriteOperation::UpdateStepStatuses {
+            run_id,
+            status,
+            step_indices,
+        }) {
             tracing::error!("Failed to queue step status update: {:?}", e);
         }
     }
@@ -1042,7 +1881,8 @@ impl StateStore for SqliteStateStore {
     ) -> BoxFuture<'_, error_stack::Result<(), StateError>> {
         let (tx, rx) = oneshot::channel();
 
-        if let Err(e) = self.write_queue.send(StateWriteOperation::Flush {
+        let start = Instant::now();
+        if let Err(e) = self.send_write(StateWriteOperation::Flush {
             run_id: Some(run_id),
             completion_notify: tx,
         }) {
@@ -1051,17 +1891,18 @@ impl StateStore for SqliteStateStore {
         }
 
         async move {
-            match rx.await {
+            let result = match rx.await {
                 Ok(result) => result.map_err(|e| error_stack::report!(e)),
                 Err(_) => Err(error_stack::report!(StateError::Internal)),
-            }
+            };
+            metrics::histogram!(STATE_FLUSH_DURATION).record(start.elapsed());
+            result
         }
         .boxed()
     }
 
     fn queue_write(&self, operation: StateWriteOperation) -> error_stack::Result<(), StateError> {
-        self.write_queue
-            .send(operation)
+        self.send_write(operation)
             .map_err(|_| error_stack::report!(StateError::Internal))
     }
 
@@ -1072,7 +1913,13 @@ impl StateStore for SqliteStateStore {
         let pool = self.pool.clone();
 
         async move {
-            let sql = "SELECT step_index, step_id, component, status, created_at, updated_at FROM step_info WHERE run_id = ? ORDER BY step_index";
+            let sql = r#"
+                SELECT i.step_index, i.step_id, i.component, i.status, i.created_at, i.updated_at, p.progress
+                FROM step_info i
+                LEFT JOIN step_progress p ON p.run_id = i.run_id AND p.step_id = i.step_id
+                WHERE i.run_id = ?
+                ORDER BY i.step_index
+            "#;
 
             let rows = sqlx::query(sql)
                 .bind(run_id.to_string())
@@ -1102,16 +1949,12 @@ impl StateStore for SqliteStateStore {
                     step_id: row.get("step_id"),
                     component,
                     status,
-                    created_at: chrono::DateTime::parse_from_rfc3339(
-                        &row.get::<String, _>("created_at"),
-                    )
-                    .change_context(StateError::Internal)?
-                    .with_timezone(&chrono::Utc),
-                    updated_at: chrono::DateTime::parse_from_rfc3339(
-                        &row.get::<String, _>("updated_at"),
-                    )
-                    .change_context(StateError::Internal)?
-                    .with_timezone(&chrono::Utc),
+                    created_at: parse_timestamp(&row.get::<String, _>("created_at"))?,
+                    updated_at: parse_timestamp(&row.get::<String, _>("updated_at"))?,
+                    progress: row
+                        .get::<Option<String>, _>("progress")
+                        .map(|progress| parse_progress(&progress))
+                        .transpose()?,
                 };
 
                 step_infos.push(step_info);
@@ -1153,16 +1996,10 @@ impl StateStore for SqliteStateStore {
                     step_id: row.get("step_id"),
                     component,
                     status: stepflow_core::status::StepStatus::Runnable, // These are now runnable
-                    created_at: chrono::DateTime::parse_from_rfc3339(
-                        &row.get::<String, _>("created_at"),
-                    )
-                    .change_context(StateError::Internal)?
-                    .with_timezone(&chrono::Utc),
-                    updated_at: chrono::DateTime::parse_from_rfc3339(
-                        &row.get::<String, _>("updated_at"),
-                    )
-                    .change_context(StateError::Internal)?
-                    .with_timezone(&chrono::Utc),
+                    created_at: parse_timestamp(&row.get::<String, _>("created_at"))?,
+                    updated_at: parse_timestamp(&row.get::<String, _>("updated_at"))?,
+                    // Runnable steps have not started, so have not reported progress
+                    progress: None,
                 };
 
                 runnable_steps.push(step_info);
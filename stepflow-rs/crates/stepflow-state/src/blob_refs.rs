@@ -0,0 +1,66 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+//! Resolution of blob references (`{"$blob": "<blob_id>"}`) within values.
+//!
+//! Large step results are passed between steps as blob references. Whoever
+//! needs the content resolves them: the executor for plugins that cannot
+//! fetch blobs themselves, and plugins that can when they read their input.
+
+use std::sync::Arc;
+
+use serde_json::Value;
+use stepflow_core::BlobId;
+use stepflow_core::blob::{contains_blob_ref_keys, unescape_blob_ref};
+use stepflow_core::workflow::ValueRef;
+
+use crate::{Result, StateStore};
+
+/// Replace all blob references within `value` by the blob content, and
+/// unescape values that look like blob references.
+pub async fn resolve_blob_refs(
+    value: ValueRef,
+    state_store: &Arc<dyn StateStore>,
+) -> Result<ValueRef> {
+    if !contains_blob_ref_keys(value.as_ref()) {
+        return Ok(value);
+    }
+
+    let mut resolved = value.as_ref().clone();
+    resolve_blob_refs_rec(&mut resolved, state_store).await?;
+    Ok(ValueRef::new(resolved))
+}
+
+async fn resolve_blob_refs_rec(value: &mut Value, state_store: &Arc<dyn StateStore>) -> Result<()> {
+    if let Some(blob_id) = BlobId::from_blob_ref(value) {
+        let blob = state_store.get_blob(&blob_id).await?;
+        // The content is an offloaded step result, which may hold escaped values.
+        *value = blob.data().as_ref().clone();
+        return Box::pin(resolve_blob_refs_rec(value, state_store)).await;
+    }
+
+    unescape_blob_ref(value);
+    match value {
+        Value::Object(object) => {
+            for item in object.values_mut() {
+                Box::pin(resolve_blob_refs_rec(item, state_store)).await?;
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                Box::pin(resolve_blob_refs_rec(item, state_store)).await?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
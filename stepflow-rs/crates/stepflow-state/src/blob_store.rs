@@ -0,0 +1,183 @@
+// Copyright 2025 DataStax Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License
+// is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express
+// or implied. See the License for the specific language governing permissions and limitations under
+// the License.
+
+use std::{collections::HashMap, sync::Arc};
+
+use error_stack::ResultExt as _;
+use futures::future::{BoxFuture, FutureExt as _};
+use stepflow_core::{
+    BlobData, BlobId, BlobType, BlobValue, blob::DEFAULT_BINARY_CONTENT_TYPE, workflow::ValueRef,
+};
+use tokio::sync::RwLock;
+
+use crate::StateError;
+
+/// Storage backend for blob content.
+///
+/// Blob stores hold content-addressed payloads separately from run metadata.
+/// [`crate::StateStore`] implementations may delegate blob storage to a `BlobStore`
+/// so that large payloads do not end up in the metadata database.
+pub trait BlobStore: Send + Sync {
+    /// Store blob data under its blob ID.
+    ///
+    /// Since blobs are content-addressed, storing a blob that already exists is a no-op.
+    ///
+    /// # Arguments
+    /// * `blob` - The blob data to store
+    fn put(&self, blob: BlobData) -> BoxFuture<'_, error_stack::Result<(), StateError>>;
+
+    /// Retrieve blob data by ID.
+    ///
+    /// # Arguments
+    /// * `blob_id` - The blob ID to retrieve
+    ///
+    /// # Returns
+    /// The blob data, or None if the blob does not exist
+    fn get(
+        &self,
+        blob_id: &BlobId,
+    ) -> BoxFuture<'_, error_stack::Result<Option<BlobData>, StateError>>;
+
+    /// Delete a blob by ID.
+    ///
+    /// # Arguments
+    /// * `blob_id` - The blob ID to delete
+    ///
+    /// # Returns
+    /// Whether the blob existed
+    fn delete(&self, blob_id: &BlobId) -> BoxFuture<'_, error_stack::Result<bool, StateError>>;
+}
+
+/// Blob content in the byte-oriented form persisted by file and object stores.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredBlob {
+    /// The type of the blob
+    pub blob_type: BlobType,
+    /// The MIME type of the content
+    pub content_type: String,
+    /// The serialized content (JSON for data and flow blobs, raw bytes for binary blobs)
+    pub content: Vec<u8>,
+}
+
+impl StoredBlob {
+    /// Convert blob data into its persisted form.
+    pub fn from_blob_data(blob: &BlobData) -> Self {
+        Self {
+            blob_type: blob.blob_type(),
+            content_type: blob.content_type().to_string(),
+            content: blob.value.to_bytes().into_owned(),
+        }
+    }
+
+    /// Convert the persisted form back into blob data.
+    pub fn into_blob_data(self, blob_id: BlobId) -> error_stack::Result<BlobData, StateError> {
+        match self.blob_type {
+            BlobType::Binary => {
+                let content_type = if self.content_type.is_empty() {
+                    DEFAULT_BINARY_CONTENT_TYPE.to_string()
+                } else {
+                    self.content_type
+                };
+                Ok(BlobData::new(
+                    BlobValue::binary(self.content, content_type),
+                    blob_id,
+                ))
+            }
+            blob_type => {
+                let value: serde_json::Value = serde_json::from_slice(&self.content)
+                    .change_context(StateError::Serialization)?;
+                BlobData::from_value_ref(ValueRef::new(value), blob_type, blob_id)
+                    .change_context(StateError::Serialization)
+            }
+        }
+    }
+}
+
+/// In-memory implementation of [`BlobStore`].
+#[derive(Default)]
+pub struct InMemoryBlobStore {
+    /// Map from blob ID (SHA-256 hash) to stored data
+    blobs: Arc<RwLock<HashMap<BlobId, BlobData>>>,
+}
+
+impl InMemoryBlobStore {
+    /// Create a new, empty in-memory blob store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl BlobStore for InMemoryBlobStore {
+    fn put(&self, blob: BlobData) -> BoxFuture<'_, error_stack::Result<(), StateError>> {
+        async move {
+            // Keep the first stored copy so metadata is stable for a given ID.
+            let mut blobs = self.blobs.write().await;
+            blobs.entry(blob.blob_id.clone()).or_insert(blob);
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn get(
+        &self,
+        blob_id: &BlobId,
+    ) -> BoxFuture<'_, error_stack::Result<Option<BlobData>, StateError>> {
+        let blob_id = blob_id.clone();
+        async move {
+            let blobs = self.blobs.read().await;
+            Ok(blobs.get(&blob_id).cloned())
+        }
+        .boxed()
+    }
+
+    fn delete(&self, blob_id: &BlobId) -> BoxFuture<'_, error_stack::Result<bool, StateError>> {
+        let blob_id = blob_id.clone();
+        async move {
+            let mut blobs = self.blobs.write().await;
+            Ok(blobs.remove(&blob_id).is_some())
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_stored_blob_roundtrip() {
+        let json_blob = BlobData::from_value_ref(
+            ValueRef::new(json!({"a": [1, 2, 3]})),
+            BlobType::Data,
+            BlobId::new("a".repeat(64)).unwrap(),
+        )
+        .unwrap();
+        let stored = StoredBlob::from_blob_data(&json_blob);
+        assert_eq!(stored.content_type, "application/json");
+        assert_eq!(
+            stored.into_blob_data(json_blob.blob_id.clone()).unwrap(),
+            json_blob
+        );
+
+        let binary_blob = BlobData::new(
+            BlobValue::binary(vec![0u8, 1, 2, 255], "image/png"),
+            BlobId::from_bytes(&[0u8, 1, 2, 255], "image/png").unwrap(),
+        );
+        let stored = StoredBlob::from_blob_data(&binary_blob);
+        assert_eq!(stored.content, vec![0u8, 1, 2, 255]);
+        assert_eq!(
+            stored.into_blob_data(binary_blob.blob_id.clone()).unwrap(),
+            binary_blob
+        );
+    }
+}
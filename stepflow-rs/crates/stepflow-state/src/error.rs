@@ -37,6 +37,15 @@ pub enum StateError {
 
     #[error("Serialization error")]
     Serialization,
+
+    #[error("Flow {flow_id} has {count} running or paused runs")]
+    FlowHasActiveRuns { flow_id: String, count: usize },
+
+    #[error("Flow {flow_id} has {count} runs")]
+    FlowHasRuns { flow_id: String, count: usize },
+
+    #[error("Run {run_id} has {count} running or paused runs in its tree")]
+    RunTreeActive { run_id: Uuid, count: usize },
 }
 
 pub type Result<T, E = error_stack::Report<StateError>> = std::result::Result<T, E>;
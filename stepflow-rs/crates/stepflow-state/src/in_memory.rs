@@ -12,19 +12,23 @@
 
 use error_stack::ResultExt as _;
 use futures::future::{BoxFuture, FutureExt as _};
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 use stepflow_core::status::ExecutionStatus;
 
 use crate::{
-    StateStore,
+    BlobStore, InMemoryBlobStore, SYSTEM_ACTOR, StateStore,
     state_store::{
-        RunDetails, RunFilters, RunSummary, StepInfo, StepResult, WorkflowLabelMetadata,
-        WorkflowWithMetadata,
+        BatchRecord, CreateRunParams, DeliveryStatus, RunCallback, RunDetails, RunFilters,
+        RunSummary, StepInfo, StepLog, StepProgress, StepResult, WebhookDelivery,
+        WorkflowLabelHistoryEntry, WorkflowLabelMetadata, WorkflowWithMetadata,
     },
 };
 use stepflow_core::{
     FlowResult,
-    blob::{BlobData, BlobId, BlobType},
+    blob::{BlobData, BlobId, BlobType, BlobValue},
     workflow::{Flow, ValueRef},
 };
 use uuid::Uuid;
@@ -32,7 +36,18 @@ use uuid::Uuid;
 use crate::StateError;
 use tokio::sync::RwLock;
 
-type WorkflowLabelsMap = Arc<RwLock<HashMap<(String, String), WorkflowLabelMetadata>>>;
+type WorkflowLabelsMap = Arc<RwLock<HashMap<(String, String, String), WorkflowLabelMetadata>>>;
+type StepLogsMap = Arc<RwLock<HashMap<Uuid, HashMap<String, Vec<StepLog>>>>>;
+type StepProgressMap = Arc<RwLock<HashMap<Uuid, HashMap<String, StepProgress>>>>;
+type BlobNamespacesMap = Arc<RwLock<HashMap<BlobId, HashSet<String>>>>;
+
+/// A stored flow, when it was first stored and the namespaces it was stored in.
+struct StoredFlow {
+    flow: Arc<Flow>,
+    created_at: chrono::DateTime<chrono::Utc>,
+    namespaces: HashSet<String>,
+}
+
 /// Execution-specific state storage for a single workflow execution.
 #[derive(Debug)]
 struct ExecutionState {
@@ -70,30 +85,59 @@ impl Default for ExecutionState {
 /// single-process execution. In the future, this can be extended with
 /// persistent storage backends for distributed or long-running flows.
 pub struct InMemoryStateStore {
-    /// Map from blob ID (SHA-256 hash) to stored JSON data
-    blobs: Arc<RwLock<HashMap<String, BlobData>>>,
+    /// Storage for blob content other than flows
+    blob_store: Arc<dyn BlobStore>,
     /// Map from run_id to execution-specific state
     executions: Arc<RwLock<HashMap<Uuid, ExecutionState>>>,
-    /// Map from flow hash to flow content
-    flows: Arc<RwLock<HashMap<String, Arc<Flow>>>>,
-    /// Map from (flow_name, label) to flow label metadata
+    /// Map from flow ID to flow content. Flows are kept here rather than in
+    /// the blob store.
+    flows: Arc<RwLock<HashMap<BlobId, StoredFlow>>>,
+    /// Map from (namespace, flow_name, label) to flow label metadata
     flow_labels: WorkflowLabelsMap,
+    /// Changes to flow labels and the namespace of the label, oldest first
+    label_history: Arc<RwLock<Vec<(String, WorkflowLabelHistoryEntry)>>>,
     /// Map from run_id to execution details
     execution_metadata: Arc<RwLock<HashMap<Uuid, RunDetails>>>,
     /// Map from run_id to step info
     step_info: Arc<RwLock<HashMap<Uuid, HashMap<usize, StepInfo>>>>,
+    /// Map from run_id to the callback notified when the run finishes
+    run_callbacks: Arc<RwLock<HashMap<Uuid, RunCallback>>>,
+    /// Webhook deliveries, oldest first
+    webhook_deliveries: Arc<RwLock<Vec<WebhookDelivery>>>,
+    /// Map from batch_id to the batch
+    batches: Arc<RwLock<HashMap<Uuid, BatchRecord>>>,
+    /// Map from run_id to the log messages of each step, oldest first
+    step_logs: StepLogsMap,
+    /// Map from run_id to the latest progress reported by each step
+    step_progress: StepProgressMap,
+    /// Map from blob ID to the namespaces the blob was added to
+    blob_namespaces: BlobNamespacesMap,
 }
 
 impl InMemoryStateStore {
     /// Create a new in-memory state store.
     pub fn new() -> Self {
+        Self::with_blob_store(Arc::new(InMemoryBlobStore::new()))
+    }
+
+    /// Create a new in-memory state store that delegates blob storage to `blob_store`.
+    ///
+    /// As with the SQLite store, flow blobs are kept in the state store itself.
+    pub fn with_blob_store(blob_store: Arc<dyn BlobStore>) -> Self {
         Self {
-            blobs: Arc::new(RwLock::new(HashMap::new())),
+            blob_store,
             executions: Arc::new(RwLock::new(HashMap::new())),
             flows: Arc::new(RwLock::new(HashMap::new())),
             flow_labels: Arc::new(RwLock::new(HashMap::new())),
+            label_history: Arc::new(RwLock::new(Vec::new())),
             execution_metadata: Arc::new(RwLock::new(HashMap::new())),
             step_info: Arc::new(RwLock::new(HashMap::new())),
+            run_callbacks: Arc::new(RwLock::new(HashMap::new())),
+            webhook_deliveries: Arc::new(RwLock::new(Vec::new())),
+            batches: Arc::new(RwLock::new(HashMap::new())),
+            step_logs: Arc::new(RwLock::new(HashMap::new())),
+            step_progress: Arc::new(RwLock::new(HashMap::new())),
+            blob_namespaces: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -111,6 +155,10 @@ impl InMemoryStateStore {
 
         let mut step_info = self.step_info.write().await;
         step_info.remove(&run_id);
+
+        self.run_callbacks.write().await.remove(&run_id);
+        self.step_logs.write().await.remove(&run_id);
+        self.step_progress.write().await.remove(&run_id);
     }
 
     /// Record the result of a step execution (private implementation method).
@@ -158,6 +206,60 @@ impl InMemoryStateStore {
             }
         }
     }
+
+    /// Remove the given runs and everything recorded for them.
+    ///
+    /// Takes the run metadata already locked by the caller, so that no run can
+    /// be created beneath the removed runs while they are collected.
+    async fn remove_runs(&self, metadata: &mut HashMap<Uuid, RunDetails>, run_ids: &[Uuid]) {
+        let mut executions = self.executions.write().await;
+        let mut step_info = self.step_info.write().await;
+        let mut run_callbacks = self.run_callbacks.write().await;
+        let mut step_logs = self.step_logs.write().await;
+        let mut step_progress = self.step_progress.write().await;
+        for run_id in run_ids {
+            metadata.remove(run_id);
+            executions.remove(run_id);
+            step_info.remove(run_id);
+            run_callbacks.remove(run_id);
+            step_logs.remove(run_id);
+            step_progress.remove(run_id);
+        }
+        self.webhook_deliveries
+            .write()
+            .await
+            .retain(|delivery| !run_ids.contains(&delivery.run_id));
+    }
+}
+
+/// Collect `run_ids` along with every run nested beneath them.
+fn with_nested_runs(metadata: &HashMap<Uuid, RunDetails>, mut run_ids: Vec<Uuid>) -> Vec<Uuid> {
+    let mut next = 0;
+    while next < run_ids.len() {
+        let parent = run_ids[next];
+        run_ids.extend(
+            metadata
+                .values()
+                .filter(|run| run.summary.parent_run_id == Some(parent))
+                .map(|run| run.summary.run_id),
+        );
+        next += 1;
+    }
+    run_ids
+}
+
+/// Count the runs that are running or paused.
+fn count_active(metadata: &HashMap<Uuid, RunDetails>, run_ids: &[Uuid]) -> usize {
+    run_ids
+        .iter()
+        .filter_map(|run_id| metadata.get(run_id))
+        .filter(|run| {
+            matches!(
+                run.summary.status,
+                ExecutionStatus::Running | ExecutionStatus::Paused
+            )
+        })
+        .count()
 }
 
 impl Default for InMemoryStateStore {
@@ -172,17 +274,34 @@ impl StateStore for InMemoryStateStore {
         data: ValueRef,
         blob_type: BlobType,
     ) -> BoxFuture<'_, error_stack::Result<BlobId, StateError>> {
-        let blobs = self.blobs.clone();
-
         async move {
+            if blob_type == BlobType::Binary {
+                let content = stepflow_core::blob::decode_base64_value(data.as_ref())
+                    .change_context(StateError::Serialization)?;
+                return self
+                    .put_binary_blob(
+                        content,
+                        stepflow_core::blob::DEFAULT_BINARY_CONTENT_TYPE.to_string(),
+                    )
+                    .await;
+            }
+
             let blob_id = BlobId::from_content(&data).change_context(StateError::Internal)?;
             let blob_data = BlobData::from_value_ref(data, blob_type, blob_id.clone())
                 .change_context(StateError::Internal)?;
-
-            // Store the data (overwrites are fine since content is identical)
-            {
-                let mut blobs = blobs.write().await;
-                blobs.insert(blob_id.as_str().to_string(), blob_data);
+            match blob_data.value {
+                BlobValue::Flow(flow) => {
+                    self.flows
+                        .write()
+                        .await
+                        .entry(blob_id.clone())
+                        .or_insert_with(|| StoredFlow {
+                            flow,
+                            created_at: chrono::Utc::now(),
+                            namespaces: HashSet::new(),
+                        });
+                }
+                _ => self.blob_store.put(blob_data).await?,
             }
 
             Ok(blob_id)
@@ -190,24 +309,89 @@ impl StateStore for InMemoryStateStore {
         .boxed()
     }
 
+    fn put_binary_blob(
+        &self,
+        content: Vec<u8>,
+        content_type: String,
+    ) -> BoxFuture<'_, error_stack::Result<BlobId, StateError>> {
+        async move {
+            let blob_id =
+                BlobId::from_bytes(&content, &content_type).change_context(StateError::Internal)?;
+            let blob_data =
+                BlobData::new(BlobValue::binary(content, content_type), blob_id.clone());
+
+            self.blob_store.put(blob_data).await?;
+
+            Ok(blob_id)
+        }
+        .boxed()
+    }
+
     fn get_blob(
         &self,
         blob_id: &BlobId,
     ) -> BoxFuture<'_, error_stack::Result<BlobData, StateError>> {
-        let blobs = self.blobs.clone();
-        let blob_id_str = blob_id.as_str().to_string();
+        let blob_id = blob_id.clone();
 
         async move {
-            let blobs = blobs.read().await;
-            blobs.get(&blob_id_str).cloned().ok_or_else(|| {
+            if let Some(stored) = self.flows.read().await.get(&blob_id) {
+                return Ok(BlobData::new(BlobValue::Flow(stored.flow.clone()), blob_id));
+            }
+            self.blob_store.get(&blob_id).await?.ok_or_else(|| {
                 error_stack::report!(StateError::BlobNotFound {
-                    blob_id: blob_id_str.clone()
+                    blob_id: blob_id.to_string()
                 })
             })
         }
         .boxed()
     }
 
+    fn add_blob_to_namespace(
+        &self,
+        namespace: &str,
+        blob_id: &BlobId,
+    ) -> BoxFuture<'_, error_stack::Result<(), StateError>> {
+        let namespace = namespace.to_string();
+        let blob_id = blob_id.clone();
+
+        async move {
+            self.blob_namespaces
+                .write()
+                .await
+                .entry(blob_id)
+                .or_default()
+                .insert(namespace);
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn blob_in_namespace(
+        &self,
+        namespace: &str,
+        blob_id: &BlobId,
+    ) -> BoxFuture<'_, error_stack::Result<bool, StateError>> {
+        let namespace = namespace.to_string();
+        let blob_id = blob_id.clone();
+
+        async move {
+            let added = self
+                .blob_namespaces
+                .read()
+                .await
+                .get(&blob_id)
+                .is_some_and(|namespaces| namespaces.contains(&namespace));
+            let stored_flow = self
+                .flows
+                .read()
+                .await
+                .get(&blob_id)
+                .is_some_and(|stored| stored.namespaces.contains(&namespace));
+            Ok(added || stored_flow)
+        }
+        .boxed()
+    }
+
     fn get_step_result(
         &self,
         run_id: Uuid,
@@ -269,14 +453,21 @@ impl StateStore for InMemoryStateStore {
 
     fn store_flow(
         &self,
+        namespace: &str,
         workflow: Arc<Flow>,
     ) -> BoxFuture<'_, error_stack::Result<BlobId, StateError>> {
+        let namespace = namespace.to_string();
+
         async move {
             // Serialize the workflow as blob data
             let flow_data = ValueRef::new(serde_json::to_value(workflow.as_ref()).unwrap());
 
             // Store as a blob with Flow type
-            self.put_blob(flow_data, BlobType::Flow).await
+            let flow_id = self.put_blob(flow_data, BlobType::Flow).await?;
+            if let Some(stored) = self.flows.write().await.get_mut(&flow_id) {
+                stored.namespaces.insert(namespace);
+            }
+            Ok(flow_id)
         }
         .boxed()
     }
@@ -286,46 +477,52 @@ impl StateStore for InMemoryStateStore {
         flow_id: &BlobId,
     ) -> BoxFuture<'_, error_stack::Result<Option<Arc<Flow>>, StateError>> {
         let flow_id = flow_id.clone();
-        let blobs = self.blobs.clone();
-
-        async move {
-            // Try to get the blob data directly
-            let blobs = blobs.read().await;
-            match blobs.get(flow_id.as_str()) {
-                Some(blob_data) => {
-                    // Use the typed accessor
-                    if let Some(flow) = blob_data.as_flow() {
-                        Ok(Some(flow.clone()))
-                    } else {
-                        Ok(None) // Not a flow blob
-                    }
-                }
-                None => Ok(None), // Blob not found
-            }
+
+        async move {
+            let flows = self.flows.read().await;
+            Ok(flows.get(&flow_id).map(|stored| stored.flow.clone()))
         }
         .boxed()
     }
 
     fn get_flows(
         &self,
-        _name: &str,
+        namespace: &str,
+        name: &str,
     ) -> BoxFuture<'_, error_stack::Result<Vec<(BlobId, chrono::DateTime<chrono::Utc>)>, StateError>>
     {
+        let namespace = namespace.to_string();
+        let name = name.to_string();
+
         async move {
-            // TODO: Implement workflow name tracking in blob-based storage
-            // For now, return empty list since we no longer track workflow names separately
-            Ok(Vec::new())
+            let labels = self.flow_labels.read().await;
+            let flows = self.flows.read().await;
+
+            // Include flows with the given name as well as flows labeled under it.
+            let mut results: Vec<(BlobId, chrono::DateTime<chrono::Utc>)> = flows
+                .iter()
+                .filter(|(flow_id, stored)| {
+                    (stored.namespaces.contains(&namespace)
+                        && stored.flow.name() == Some(name.as_str()))
+                        || labels.iter().any(|((ns, n, _), label)| {
+                            ns == &namespace && n == &name && &label.flow_id == *flow_id
+                        })
+                })
+                .map(|(flow_id, stored)| (flow_id.clone(), stored.created_at))
+                .collect();
+            results.sort_by_key(|(_, created_at)| std::cmp::Reverse(*created_at));
+            Ok(results)
         }
         .boxed()
     }
 
     fn get_named_flow(
         &self,
+        namespace: &str,
         name: &str,
         label: Option<&str>,
     ) -> BoxFuture<'_, error_stack::Result<Option<WorkflowWithMetadata>, StateError>> {
-        let flows = self.flows.clone();
-        let flow_labels = self.flow_labels.clone();
+        let namespace = namespace.to_string();
         let name = name.to_string();
         let label = label.map(|s| s.to_string());
 
@@ -333,91 +530,262 @@ impl StateStore for InMemoryStateStore {
             match label {
                 Some(label_str) => {
                     // Get workflow by label
-                    let labels = flow_labels.read().await;
-                    let key = (name, label_str);
-
-                    if let Some(label_metadata) = labels.get(&key) {
-                        let flows = flows.read().await;
-                        if let Some(workflow) = flows.get(&label_metadata.flow_id.to_string()) {
-                            return Ok(Some(WorkflowWithMetadata {
-                                workflow: workflow.clone(),
-                                flow_id: label_metadata.flow_id.clone(),
-                                created_at: label_metadata.created_at,
-                                label_info: Some(label_metadata.clone()),
-                            }));
+                    let label_metadata = {
+                        let labels = self.flow_labels.read().await;
+                        match labels.get(&(namespace, name, label_str)) {
+                            Some(label_metadata) => label_metadata.clone(),
+                            None => return Ok(None),
                         }
-                    }
-                    Ok(None)
+                    };
+
+                    let Some(workflow) = self.get_flow(&label_metadata.flow_id).await? else {
+                        return Ok(None);
+                    };
+                    let created_at = self
+                        .flows
+                        .read()
+                        .await
+                        .get(&label_metadata.flow_id)
+                        .map(|stored| stored.created_at)
+                        .unwrap_or(label_metadata.created_at);
+
+                    Ok(Some(WorkflowWithMetadata {
+                        workflow,
+                        flow_id: label_metadata.flow_id.clone(),
+                        created_at,
+                        label_info: Some(label_metadata),
+                    }))
                 }
                 None => {
-                    // TODO: Implement name-based workflow lookup with blob storage
-                    // For now, return None since we no longer maintain a separate workflow index
-                    Ok(None)
+                    // Get the most recently stored workflow with the name
+                    let flows = self.flows.read().await;
+                    let latest = flows
+                        .iter()
+                        .filter(|(_, stored)| {
+                            stored.namespaces.contains(&namespace)
+                                && stored.flow.name() == Some(name.as_str())
+                        })
+                        .max_by_key(|(_, stored)| stored.created_at);
+
+                    Ok(latest.map(|(flow_id, stored)| WorkflowWithMetadata {
+                        workflow: stored.flow.clone(),
+                        flow_id: flow_id.clone(),
+                        created_at: stored.created_at,
+                        label_info: None,
+                    }))
+                }
+            }
+        }
+        .boxed()
+    }
+
+    fn delete_flow(
+        &self,
+        namespace: &str,
+        flow_id: &BlobId,
+        delete_runs: bool,
+    ) -> BoxFuture<'_, error_stack::Result<bool, StateError>> {
+        let namespace = namespace.to_string();
+        let flow_id = flow_id.clone();
+
+        async move {
+            let in_namespace = self
+                .flows
+                .read()
+                .await
+                .get(&flow_id)
+                .is_some_and(|stored| stored.namespaces.contains(&namespace));
+            if !in_namespace {
+                return Ok(false);
+            }
+
+            // Hold the run metadata lock so no run of the flow starts while deleting.
+            let mut metadata = self.execution_metadata.write().await;
+            let flow_runs = metadata
+                .values()
+                .filter(|run| run.summary.flow_id == flow_id && run.summary.namespace == namespace)
+                .map(|run| run.summary.run_id)
+                .collect();
+            let runs = with_nested_runs(&metadata, flow_runs);
+
+            let active = count_active(&metadata, &runs);
+            if active > 0 {
+                return Err(error_stack::report!(StateError::FlowHasActiveRuns {
+                    flow_id: flow_id.to_string(),
+                    count: active,
+                }));
+            }
+            if !runs.is_empty() && !delete_runs {
+                return Err(error_stack::report!(StateError::FlowHasRuns {
+                    flow_id: flow_id.to_string(),
+                    count: runs.len(),
+                }));
+            }
+
+            self.remove_runs(&mut metadata, &runs).await;
+
+            // Detach labels pointing at the flow, recording the change in their history.
+            let mut labels = self.flow_labels.write().await;
+            let mut history = self.label_history.write().await;
+            labels.retain(|(ns, name, label), label_metadata| {
+                if ns != &namespace || label_metadata.flow_id != flow_id {
+                    return true;
                 }
+                history.push((
+                    namespace.clone(),
+                    WorkflowLabelHistoryEntry {
+                        name: name.clone(),
+                        label: label.clone(),
+                        flow_id: None,
+                        previous_flow_id: Some(flow_id.clone()),
+                        actor: SYSTEM_ACTOR.to_string(),
+                        comment: Some(format!("Flow {flow_id} deleted")),
+                        changed_at: chrono::Utc::now(),
+                    },
+                ));
+                false
+            });
+
+            // Only remove the flow itself once no namespace holds it.
+            let mut flows = self.flows.write().await;
+            let unused = flows.get_mut(&flow_id).is_some_and(|stored| {
+                stored.namespaces.remove(&namespace);
+                stored.namespaces.is_empty()
+            });
+            if unused {
+                flows.remove(&flow_id);
             }
+
+            Ok(true)
         }
         .boxed()
     }
 
     fn create_or_update_label(
         &self,
+        namespace: &str,
         name: &str,
         label: &str,
         flow_id: BlobId,
+        actor: &str,
+        comment: Option<&str>,
     ) -> BoxFuture<'_, error_stack::Result<(), StateError>> {
         let workflow_labels = self.flow_labels.clone();
+        let namespace = namespace.to_string();
         let name = name.to_string();
         let label = label.to_string();
+        let actor = actor.to_string();
+        let comment = comment.map(|s| s.to_string());
 
         async move {
             let now = chrono::Utc::now();
-            let workflow_label = WorkflowLabelMetadata {
-                name: name.clone(),
-                label: label.clone(),
-                flow_id,
-                created_at: now,
-                updated_at: now,
-            };
-
             let mut labels = workflow_labels.write().await;
-            labels.insert((name, label), workflow_label);
+            let mut previous_flow_id = None;
+            labels
+                .entry((namespace.clone(), name.clone(), label.clone()))
+                .and_modify(|existing| {
+                    previous_flow_id = Some(existing.flow_id.clone());
+                    existing.flow_id = flow_id.clone();
+                    existing.updated_at = now;
+                })
+                .or_insert_with(|| WorkflowLabelMetadata {
+                    name: name.clone(),
+                    label: label.clone(),
+                    flow_id: flow_id.clone(),
+                    created_at: now,
+                    updated_at: now,
+                });
+
+            // Record the change while still holding the label lock so history stays ordered.
+            self.label_history.write().await.push((
+                namespace,
+                WorkflowLabelHistoryEntry {
+                    name,
+                    label,
+                    flow_id: Some(flow_id),
+                    previous_flow_id,
+                    actor,
+                    comment,
+                    changed_at: now,
+                },
+            ));
 
             Ok(())
         }
         .boxed()
     }
 
+    fn list_label_history(
+        &self,
+        namespace: &str,
+        name: &str,
+        label: &str,
+    ) -> BoxFuture<'_, error_stack::Result<Vec<WorkflowLabelHistoryEntry>, StateError>> {
+        let label_history = self.label_history.clone();
+        let namespace = namespace.to_string();
+        let name = name.to_string();
+        let label = label.to_string();
+
+        async move {
+            let history = label_history.read().await;
+            Ok(history
+                .iter()
+                .rev()
+                .filter(|(ns, entry)| {
+                    ns == &namespace && entry.name == name && entry.label == label
+                })
+                .map(|(_, entry)| entry.clone())
+                .collect())
+        }
+        .boxed()
+    }
+
     fn list_labels_for_name(
         &self,
+        namespace: &str,
         name: &str,
     ) -> BoxFuture<'_, error_stack::Result<Vec<WorkflowLabelMetadata>, StateError>> {
         let workflow_labels = self.flow_labels.clone();
+        let namespace = namespace.to_string();
         let name = name.to_string();
 
         async move {
             let labels = workflow_labels.read().await;
-            let results: Vec<WorkflowLabelMetadata> = labels
+            let mut results: Vec<WorkflowLabelMetadata> = labels
                 .iter()
-                .filter(|((n, _label), _workflow_label)| n == &name)
-                .map(|((_name, _label), workflow_label)| workflow_label.clone())
+                .filter(|((ns, n, _label), _workflow_label)| ns == &namespace && n == &name)
+                .map(|(_key, workflow_label)| workflow_label.clone())
                 .collect();
+            results.sort_by_key(|label| std::cmp::Reverse(label.created_at));
             Ok(results)
         }
         .boxed()
     }
 
-    fn list_flow_names(&self) -> BoxFuture<'_, error_stack::Result<Vec<String>, StateError>> {
+    fn list_flow_names(
+        &self,
+        namespace: &str,
+    ) -> BoxFuture<'_, error_stack::Result<Vec<String>, StateError>> {
         let flows = self.flows.clone();
+        let flow_labels = self.flow_labels.clone();
+        let namespace = namespace.to_string();
 
         async move {
             let flows = flows.read().await;
-            let mut names = std::collections::HashSet::new();
+            let mut names = HashSet::new();
 
-            for flow in flows.values() {
-                if let Some(name) = flow.name() {
+            for stored in flows.values() {
+                if let Some(name) = stored.flow.name()
+                    && stored.namespaces.contains(&namespace)
+                {
                     names.insert(name.to_owned());
                 }
             }
+            for (ns, name, _label) in flow_labels.read().await.keys() {
+                if ns == &namespace {
+                    names.insert(name.clone());
+                }
+            }
 
             let mut result: Vec<String> = names.into_iter().collect();
             result.sort();
@@ -428,17 +796,37 @@ impl StateStore for InMemoryStateStore {
 
     fn delete_label(
         &self,
+        namespace: &str,
         name: &str,
         label: &str,
+        actor: &str,
+        comment: Option<&str>,
     ) -> BoxFuture<'_, error_stack::Result<(), StateError>> {
         let workflow_labels = self.flow_labels.clone();
+        let namespace = namespace.to_string();
         let name = name.to_string();
         let label = label.to_string();
+        let actor = actor.to_string();
+        let comment = comment.map(|s| s.to_string());
 
         async move {
             let mut labels = workflow_labels.write().await;
-            let key = (name, label);
-            labels.remove(&key);
+            let key = (namespace, name, label);
+            if let Some(removed) = labels.remove(&key) {
+                let (namespace, name, label) = key;
+                self.label_history.write().await.push((
+                    namespace,
+                    WorkflowLabelHistoryEntry {
+                        name,
+                        label,
+                        flow_id: None,
+                        previous_flow_id: Some(removed.flow_id),
+                        actor,
+                        comment,
+                        changed_at: chrono::Utc::now(),
+                    },
+                ));
+            }
 
             Ok(())
         }
@@ -447,33 +835,39 @@ impl StateStore for InMemoryStateStore {
 
     fn create_run(
         &self,
-        run_id: Uuid,
-        flow_id: BlobId,
-        workflow_name: Option<&str>,
-        workflow_label: Option<&str>,
-        debug_mode: bool,
-        input: ValueRef,
+        params: CreateRunParams,
     ) -> BoxFuture<'_, error_stack::Result<(), StateError>> {
         let metadata = self.execution_metadata.clone();
+        let run_callbacks = self.run_callbacks.clone();
+        let callback = params.callback;
         let now = chrono::Utc::now();
         let execution_details = RunDetails {
             summary: RunSummary {
-                run_id,
-                flow_id,
-                flow_name: workflow_name.map(|s| s.to_string()),
-                flow_label: workflow_label.map(|s| s.to_string()),
+                run_id: params.run_id,
+                flow_id: params.flow_id,
+                flow_name: params.flow_name,
+                flow_label: params.flow_label,
+                namespace: params.namespace,
                 status: ExecutionStatus::Running,
-                debug_mode,
+                debug_mode: params.debug_mode,
+                parent_run_id: params.parent_run_id,
+                parent_step_id: params.parent_step_id,
+                root_run_id: params.root_run_id,
+                batch_id: params.batch_id,
+                created_by: params.created_by,
                 created_at: now,
                 completed_at: None,
             },
-            input,
+            input: params.input,
             result: None,
         };
 
         async move {
             let mut metadata = metadata.write().await;
-            metadata.insert(run_id, execution_details);
+            metadata.insert(params.run_id, execution_details);
+            if let Some(callback) = callback {
+                run_callbacks.write().await.insert(params.run_id, callback);
+            }
             Ok(())
         }
         .boxed()
@@ -539,6 +933,13 @@ impl StateStore for InMemoryStateStore {
                         return false;
                     }
 
+                    // Apply workflow ID filter
+                    if let Some(ref flow_id) = filters.flow_id
+                        && &exec.summary.flow_id != flow_id
+                    {
+                        return false;
+                    }
+
                     // Apply workflow name filter
                     if let Some(ref workflow_name) = filters.flow_name
                         && exec.summary.flow_name.as_ref() != Some(workflow_name)
@@ -553,13 +954,33 @@ impl StateStore for InMemoryStateStore {
                         return false;
                     }
 
+                    // Apply namespace filter
+                    if let Some(ref namespace) = filters.namespace
+                        && &exec.summary.namespace != namespace
+                    {
+                        return false;
+                    }
+
+                    // Apply batch filter
+                    if filters.batch_id.is_some() && exec.summary.batch_id != filters.batch_id {
+                        return false;
+                    }
+
+                    // Apply run tree filter
+                    if filters
+                        .root_run_id
+                        .is_some_and(|root| exec.summary.root_run_id != root)
+                    {
+                        return false;
+                    }
+
                     true
                 })
                 .map(|exec| exec.summary.clone())
                 .collect();
 
             // Sort by creation time (newest first)
-            results.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+            results.sort_by_key(|run| std::cmp::Reverse(run.created_at));
 
             // Apply pagination
             if let Some(offset) = filters.offset {
@@ -653,6 +1074,170 @@ impl StateStore for InMemoryStateStore {
         }
     }
 
+    fn delete_run(
+        &self,
+        run_id: Uuid,
+        cascade: bool,
+    ) -> BoxFuture<'_, error_stack::Result<Vec<Uuid>, StateError>> {
+        async move {
+            let mut metadata = self.execution_metadata.write().await;
+            if !metadata.contains_key(&run_id) {
+                return Ok(Vec::new());
+            }
+
+            // Collect the run and, when cascading, every run nested beneath it.
+            let deleted = if cascade {
+                with_nested_runs(&metadata, vec![run_id])
+            } else {
+                vec![run_id]
+            };
+
+            let active = count_active(&metadata, &deleted);
+            if active > 0 {
+                return Err(error_stack::report!(StateError::RunTreeActive {
+                    run_id,
+                    count: active,
+                }));
+            }
+
+            self.remove_runs(&mut metadata, &deleted).await;
+            Ok(deleted)
+        }
+        .boxed()
+    }
+
+    fn get_run_callback(
+        &self,
+        run_id: Uuid,
+    ) -> BoxFuture<'_, error_stack::Result<Option<RunCallback>, StateError>> {
+        async move { Ok(self.run_callbacks.read().await.get(&run_id).cloned()) }.boxed()
+    }
+
+    fn put_webhook_delivery(
+        &self,
+        delivery: WebhookDelivery,
+    ) -> BoxFuture<'_, error_stack::Result<(), StateError>> {
+        async move {
+            let mut deliveries = self.webhook_deliveries.write().await;
+            match deliveries
+                .iter_mut()
+                .find(|existing| existing.delivery_id == delivery.delivery_id)
+            {
+                Some(existing) => *existing = delivery,
+                None => deliveries.push(delivery),
+            }
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn list_webhook_deliveries(
+        &self,
+        run_id: Option<Uuid>,
+        status: Option<DeliveryStatus>,
+    ) -> BoxFuture<'_, error_stack::Result<Vec<WebhookDelivery>, StateError>> {
+        async move {
+            Ok(self
+                .webhook_deliveries
+                .read()
+                .await
+                .iter()
+                .filter(|delivery| run_id.is_none_or(|run_id| delivery.run_id == run_id))
+                .filter(|delivery| status.is_none_or(|status| delivery.status == status))
+                .cloned()
+                .collect())
+        }
+        .boxed()
+    }
+
+    fn put_batch(&self, batch: BatchRecord) -> BoxFuture<'_, error_stack::Result<(), StateError>> {
+        async move {
+            self.batches.write().await.insert(batch.batch_id, batch);
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn get_batch(
+        &self,
+        batch_id: Uuid,
+    ) -> BoxFuture<'_, error_stack::Result<Option<BatchRecord>, StateError>> {
+        async move { Ok(self.batches.read().await.get(&batch_id).cloned()) }.boxed()
+    }
+
+    fn record_step_log(
+        &self,
+        run_id: Uuid,
+        step_id: String,
+        log: StepLog,
+    ) -> BoxFuture<'_, error_stack::Result<(), StateError>> {
+        async move {
+            self.step_logs
+                .write()
+                .await
+                .entry(run_id)
+                .or_default()
+                .entry(step_id)
+                .or_default()
+                .push(log);
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn get_step_logs(
+        &self,
+        run_id: Uuid,
+        step_id: &str,
+    ) -> BoxFuture<'_, error_stack::Result<Vec<StepLog>, StateError>> {
+        let step_id = step_id.to_owned();
+        async move {
+            Ok(self
+                .step_logs
+                .read()
+                .await
+                .get(&run_id)
+                .and_then(|steps| steps.get(&step_id))
+                .cloned()
+                .unwrap_or_default())
+        }
+        .boxed()
+    }
+
+    fn update_step_progress(
+        &self,
+        run_id: Uuid,
+        step_id: String,
+        progress: StepProgress,
+    ) -> BoxFuture<'_, error_stack::Result<(), StateError>> {
+        async move {
+            self.step_progress
+                .write()
+                .await
+                .entry(run_id)
+                .or_default()
+                .insert(step_id, progress);
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn get_step_progress(
+        &self,
+        run_id: Uuid,
+    ) -> BoxFuture<'_, error_stack::Result<HashMap<String, StepProgress>, StateError>> {
+        async move {
+            Ok(self
+                .step_progress
+                .read()
+                .await
+                .get(&run_id)
+                .cloned()
+                .unwrap_or_default())
+        }
+        .boxed()
+    }
+
     fn flush_pending_writes(
         &self,
         _run_id: uuid::Uuid,
@@ -669,11 +1254,22 @@ impl StateStore for InMemoryStateStore {
 
         async move {
             let step_info_guard = step_info_map.read().await;
+            let step_progress = self.step_progress.read().await;
+            let step_progress = step_progress.get(&run_id);
 
             let step_infos = step_info_guard
                 .get(&run_id)
                 .map(|execution_steps| {
-                    let mut steps: Vec<StepInfo> = execution_steps.values().cloned().collect();
+                    let mut steps: Vec<StepInfo> = execution_steps
+                        .values()
+                        .cloned()
+                        .map(|mut step| {
+                            step.progress = step_progress
+                                .and_then(|progress| progress.get(&step.step_id))
+                                .cloned();
+                            step
+                        })
+                        .collect();
                     // Sort by step_index for consistent ordering
                     steps.sort_by_key(|step| step.step_index);
                     steps
@@ -718,6 +1314,7 @@ impl StateStore for InMemoryStateStore {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::DEFAULT_NAMESPACE;
     use serde_json::json;
 
     #[tokio::test]
@@ -755,6 +1352,63 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_blob_store_delegation() {
+        let blob_store = Arc::new(InMemoryBlobStore::new());
+        let store = InMemoryStateStore::with_blob_store(blob_store.clone());
+
+        // Data blobs go to the blob store.
+        let data_id = store
+            .put_blob(ValueRef::new(json!({"payload": "large"})), BlobType::Data)
+            .await
+            .unwrap();
+        assert!(blob_store.get(&data_id).await.unwrap().is_some());
+
+        // Flow blobs stay in the state store.
+        let flow = stepflow_core::workflow::FlowBuilder::new()
+            .name("kept")
+            .build();
+        let flow_data = ValueRef::new(serde_json::to_value(&flow).unwrap());
+        let flow_id = store.put_blob(flow_data, BlobType::Flow).await.unwrap();
+        assert!(blob_store.get(&flow_id).await.unwrap().is_none());
+        assert!(store.get_flow(&flow_id).await.unwrap().is_some());
+        let retrieved = store.get_blob(&flow_id).await.unwrap();
+        assert_eq!(retrieved.blob_type(), BlobType::Flow);
+    }
+
+    #[tokio::test]
+    async fn test_binary_blob_storage() {
+        let store = InMemoryStateStore::new();
+        let content = vec![0x89, b'P', b'N', b'G', 0x00, 0xff];
+
+        let blob_id = store
+            .put_binary_blob(content.clone(), "image/png".to_string())
+            .await
+            .unwrap();
+        assert_eq!(blob_id, BlobId::from_bytes(&content, "image/png").unwrap());
+
+        let retrieved = store.get_blob(&blob_id).await.unwrap();
+        assert_eq!(retrieved.blob_type(), BlobType::Binary);
+        assert_eq!(retrieved.content_type(), "image/png");
+        assert_eq!(retrieved.as_binary(), Some(content.as_slice()));
+
+        // Storing the same bytes as base64 through `put_blob` yields the same
+        // ID for the same content type.
+        let encoded = ValueRef::new(json!(stepflow_core::blob::encode_base64(&content)));
+        let blob_id2 = store.put_blob(encoded, BlobType::Binary).await.unwrap();
+        assert_eq!(
+            blob_id2,
+            store
+                .put_binary_blob(
+                    content.clone(),
+                    stepflow_core::blob::DEFAULT_BINARY_CONTENT_TYPE.to_string()
+                )
+                .await
+                .unwrap()
+        );
+        assert_ne!(blob_id, blob_id2);
+    }
+
     #[tokio::test]
     async fn test_blob_id_validation() {
         // Valid blob ID
@@ -902,4 +1556,399 @@ mod tests {
         let all_results = store.list_step_results(run_id).await.unwrap();
         assert!(all_results.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_named_flows_and_labels() {
+        use stepflow_core::workflow::FlowBuilder;
+
+        let store = InMemoryStateStore::new();
+        let v1 = store
+            .store_flow(
+                DEFAULT_NAMESPACE,
+                Arc::new(FlowBuilder::new().name("greeter").build()),
+            )
+            .await
+            .unwrap();
+        let v2 = store
+            .store_flow(
+                DEFAULT_NAMESPACE,
+                Arc::new(FlowBuilder::new().name("greeter").version("2").build()),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            store.list_flow_names(DEFAULT_NAMESPACE).await.unwrap(),
+            vec!["greeter"]
+        );
+        let versions: Vec<BlobId> = store
+            .get_flows(DEFAULT_NAMESPACE, "greeter")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(flow_id, _)| flow_id)
+            .collect();
+        assert_eq!(versions.len(), 2);
+        assert!(versions.contains(&v1) && versions.contains(&v2));
+
+        // Labels resolve to the flow they point at, and keep their creation time when moved.
+        store
+            .create_or_update_label(
+                DEFAULT_NAMESPACE,
+                "greeter",
+                "production",
+                v1.clone(),
+                "alice",
+                None,
+            )
+            .await
+            .unwrap();
+        let labeled = store
+            .get_named_flow(DEFAULT_NAMESPACE, "greeter", Some("production"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(labeled.flow_id, v1);
+        let created_at = labeled.label_info.unwrap().created_at;
+
+        store
+            .create_or_update_label(
+                DEFAULT_NAMESPACE,
+                "greeter",
+                "production",
+                v2.clone(),
+                "bob",
+                Some("v2"),
+            )
+            .await
+            .unwrap();
+        let labels = store
+            .list_labels_for_name(DEFAULT_NAMESPACE, "greeter")
+            .await
+            .unwrap();
+        assert_eq!(labels.len(), 1);
+        assert_eq!(labels[0].flow_id, v2);
+        assert_eq!(labels[0].created_at, created_at);
+
+        store
+            .delete_label(DEFAULT_NAMESPACE, "greeter", "production", "alice", None)
+            .await
+            .unwrap();
+        assert!(
+            store
+                .get_named_flow(DEFAULT_NAMESPACE, "greeter", Some("production"))
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            store
+                .get_named_flow(DEFAULT_NAMESPACE, "greeter", None)
+                .await
+                .unwrap()
+                .is_some()
+        );
+
+        // Every change is recorded, newest first.
+        let history = store
+            .list_label_history(DEFAULT_NAMESPACE, "greeter", "production")
+            .await
+            .unwrap();
+        let changes: Vec<_> = history
+            .iter()
+            .map(|entry| {
+                (
+                    entry.flow_id.clone(),
+                    entry.previous_flow_id.clone(),
+                    entry.actor.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                (None, Some(v2.clone()), "alice"),
+                (Some(v2), Some(v1.clone()), "bob"),
+                (Some(v1), None, "alice"),
+            ]
+        );
+        assert_eq!(history[1].comment.as_deref(), Some("v2"));
+    }
+
+    #[tokio::test]
+    async fn test_delete_flow() {
+        use stepflow_core::workflow::FlowBuilder;
+
+        let store = InMemoryStateStore::new();
+        let flow_id = store
+            .store_flow(
+                DEFAULT_NAMESPACE,
+                Arc::new(FlowBuilder::new().name("greeter").build()),
+            )
+            .await
+            .unwrap();
+        store
+            .create_or_update_label(
+                DEFAULT_NAMESPACE,
+                "greeter",
+                "production",
+                flow_id.clone(),
+                "alice",
+                None,
+            )
+            .await
+            .unwrap();
+        let inner_flow_id = store
+            .store_flow(
+                DEFAULT_NAMESPACE,
+                Arc::new(FlowBuilder::new().name("inner").build()),
+            )
+            .await
+            .unwrap();
+        let run_id = Uuid::new_v4();
+        let nested_run_id = Uuid::new_v4();
+        store
+            .create_run(CreateRunParams {
+                flow_name: Some("greeter".to_string()),
+                ..CreateRunParams::new(run_id, flow_id.clone(), ValueRef::new(json!({})))
+            })
+            .await
+            .unwrap();
+        store
+            .create_run(CreateRunParams {
+                flow_name: Some("inner".to_string()),
+                parent_run_id: Some(run_id),
+                ..CreateRunParams::new(nested_run_id, inner_flow_id, ValueRef::new(json!({})))
+            })
+            .await
+            .unwrap();
+
+        // Running runs, including nested runs of other flows, and finished runs
+        // block deletion unless runs are deleted too.
+        store
+            .update_run_status(run_id, ExecutionStatus::Completed, None)
+            .await
+            .unwrap();
+        let err = store
+            .delete_flow(DEFAULT_NAMESPACE, &flow_id, true)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.current_context(),
+            StateError::FlowHasActiveRuns { count: 1, .. }
+        ));
+        store
+            .update_run_status(nested_run_id, ExecutionStatus::Completed, None)
+            .await
+            .unwrap();
+        let err = store
+            .delete_flow(DEFAULT_NAMESPACE, &flow_id, false)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.current_context(),
+            StateError::FlowHasRuns { count: 2, .. }
+        ));
+
+        assert!(
+            store
+                .delete_flow(DEFAULT_NAMESPACE, &flow_id, true)
+                .await
+                .unwrap()
+        );
+        assert!(store.get_flow(&flow_id).await.unwrap().is_none());
+        assert!(store.get_run(run_id).await.unwrap().is_none());
+        assert!(store.get_run(nested_run_id).await.unwrap().is_none());
+        assert!(
+            store
+                .list_labels_for_name(DEFAULT_NAMESPACE, "greeter")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        let history = store
+            .list_label_history(DEFAULT_NAMESPACE, "greeter", "production")
+            .await
+            .unwrap();
+        assert_eq!(history[0].actor, SYSTEM_ACTOR);
+        assert_eq!(history[0].previous_flow_id, Some(flow_id.clone()));
+
+        // Deleting again reports that the flow no longer exists.
+        assert!(
+            !store
+                .delete_flow(DEFAULT_NAMESPACE, &flow_id, true)
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delete_run() {
+        use stepflow_core::workflow::FlowBuilder;
+
+        let store = InMemoryStateStore::new();
+        let flow_id = store
+            .store_flow(
+                DEFAULT_NAMESPACE,
+                Arc::new(FlowBuilder::new().name("greeter").build()),
+            )
+            .await
+            .unwrap();
+
+        let parent = Uuid::new_v4();
+        let child = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        for (run_id, parent_run_id) in [(parent, None), (child, Some(parent)), (other, None)] {
+            store
+                .create_run(CreateRunParams {
+                    flow_name: Some("greeter".to_string()),
+                    parent_run_id,
+                    ..CreateRunParams::new(run_id, flow_id.clone(), ValueRef::new(json!({})))
+                })
+                .await
+                .unwrap();
+        }
+        store.record_step_result(
+            child,
+            StepResult::new(0, "step", FlowResult::Success(ValueRef::new(json!(42)))),
+        );
+        let now = chrono::Utc::now();
+        store
+            .put_webhook_delivery(WebhookDelivery {
+                delivery_id: Uuid::new_v4(),
+                run_id: child,
+                callback: RunCallback {
+                    url: "http://localhost:9000/hook".to_string(),
+                    headers: Default::default(),
+                    secret: None,
+                },
+                payload: json!({"runId": child}),
+                status: DeliveryStatus::Delivered,
+                attempts: 1,
+                next_attempt_at: now,
+                last_error: None,
+                created_at: now,
+            })
+            .await
+            .unwrap();
+
+        // A run is not deleted while a run nested within it is still running.
+        store
+            .update_run_status(parent, ExecutionStatus::Completed, None)
+            .await
+            .unwrap();
+        let err = store.delete_run(parent, true).await.unwrap_err();
+        assert!(matches!(
+            err.current_context(),
+            StateError::RunTreeActive { count: 1, .. }
+        ));
+        assert!(
+            store
+                .delete_runs(&RunFilters::default(), true)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        store
+            .update_run_status(child, ExecutionStatus::Completed, None)
+            .await
+            .unwrap();
+
+        // Deleting the parent with cascade removes the nested run too.
+        let mut deleted = store.delete_run(parent, true).await.unwrap();
+        deleted.sort();
+        let mut expected = vec![parent, child];
+        expected.sort();
+        assert_eq!(deleted, expected);
+        assert!(store.get_run(child).await.unwrap().is_none());
+        assert!(store.list_step_results(child).await.unwrap().is_empty());
+        assert!(
+            store
+                .list_webhook_deliveries(Some(child), None)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(store.delete_run(parent, true).await.unwrap().is_empty());
+
+        // Bulk deletion skips runs that are still running.
+        let filters = RunFilters::default();
+        assert!(store.delete_runs(&filters, true).await.unwrap().is_empty());
+        store
+            .update_run_status(other, ExecutionStatus::Completed, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            store.delete_runs(&filters, true).await.unwrap(),
+            vec![other]
+        );
+        assert!(store.list_runs(&filters).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_namespaces_are_isolated() {
+        use stepflow_core::workflow::FlowBuilder;
+
+        let store = InMemoryStateStore::new();
+        let flow = Arc::new(FlowBuilder::new().name("greeter").build());
+        let flow_id = store.store_flow("team-a", flow.clone()).await.unwrap();
+        store
+            .create_or_update_label(
+                "team-a",
+                "greeter",
+                "production",
+                flow_id.clone(),
+                "alice",
+                None,
+            )
+            .await
+            .unwrap();
+        store
+            .create_run(CreateRunParams {
+                namespace: "team-a".to_string(),
+                ..CreateRunParams::new(Uuid::new_v4(), flow_id.clone(), ValueRef::new(json!({})))
+            })
+            .await
+            .unwrap();
+
+        // Names, labels and runs of one namespace are not visible from another.
+        assert_eq!(
+            store.list_flow_names("team-a").await.unwrap(),
+            vec!["greeter"]
+        );
+        assert!(store.list_flow_names("team-b").await.unwrap().is_empty());
+        assert!(
+            store
+                .get_named_flow("team-b", "greeter", None)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            store
+                .get_named_flow("team-b", "greeter", Some("production"))
+                .await
+                .unwrap()
+                .is_none()
+        );
+        let filters = RunFilters {
+            namespace: Some("team-b".to_string()),
+            ..RunFilters::default()
+        };
+        assert!(store.list_runs(&filters).await.unwrap().is_empty());
+
+        // The same flow stored in two namespaces is only removed with the last of them.
+        assert_eq!(store.store_flow("team-b", flow).await.unwrap(), flow_id);
+        assert!(store.delete_flow("team-b", &flow_id, true).await.unwrap());
+        assert!(store.get_flow(&flow_id).await.unwrap().is_some());
+        assert!(!store.delete_flow("team-b", &flow_id, true).await.unwrap());
+        assert_eq!(
+            store
+                .list_labels_for_name("team-a", "greeter")
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
 }
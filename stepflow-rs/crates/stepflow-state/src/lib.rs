@@ -10,13 +10,20 @@
 // or implied. See the License for the specific language governing permissions and limitations under
 // the License.
 
+mod blob_refs;
+mod blob_store;
 mod error;
 mod in_memory;
 mod state_store;
 
+pub use blob_refs::resolve_blob_refs;
+pub use blob_store::{BlobStore, InMemoryBlobStore, StoredBlob};
 pub use error::{Result, StateError};
 pub use in_memory::InMemoryStateStore;
 pub use state_store::{
-    DebugSessionData, RunDetails, RunFilters, RunStepDetails, RunSummary, RunWithBlobs, StateStore,
-    StateWriteOperation, StepInfo, StepResult, WorkflowLabelMetadata, WorkflowWithMetadata,
+    BatchItem, BatchRecord, CreateRunParams, DEFAULT_NAMESPACE, DebugSessionData, DeliveryStatus,
+    LogLevel, RunCallback, RunCallbackPayload, RunDetails, RunFilters, RunStepDetails, RunSummary,
+    RunWithBlobs, SYSTEM_ACTOR, StateStore, StateWriteOperation, StepInfo, StepLog, StepProgress,
+    StepResult, WebhookDelivery, WorkflowLabelHistoryEntry, WorkflowLabelMetadata,
+    WorkflowWithMetadata,
 };
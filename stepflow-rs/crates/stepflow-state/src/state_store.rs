@@ -10,9 +10,11 @@
 // or implied. See the License for the specific language governing permissions and limitations under
 // the License.
 
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
 use bit_set::BitSet;
+use error_stack::ResultExt as _;
 use futures::future::{BoxFuture, FutureExt as _};
 use stepflow_core::status::{ExecutionStatus, StepStatus};
 use stepflow_core::{
@@ -84,12 +86,32 @@ pub trait StateStore: Send + Sync {
     ///
     /// # Returns
     /// The blob ID for the stored data
+    ///
+    /// Binary blobs may be stored through this method by passing the content as a
+    /// base64-encoded string; they are given the default binary content type.
     fn put_blob(
         &self,
         data: ValueRef,
         blob_type: BlobType,
     ) -> BoxFuture<'_, error_stack::Result<BlobId, StateError>>;
 
+    /// Store raw binary content as a blob and return its content-based ID.
+    ///
+    /// The blob ID is generated as a SHA-256 hash of the raw bytes, so the
+    /// same content always produces the same ID regardless of content type.
+    ///
+    /// # Arguments
+    /// * `content` - The raw bytes to store
+    /// * `content_type` - The MIME type of the content
+    ///
+    /// # Returns
+    /// The blob ID for the stored content
+    fn put_binary_blob(
+        &self,
+        content: Vec<u8>,
+        content_type: String,
+    ) -> BoxFuture<'_, error_stack::Result<BlobId, StateError>>;
+
     /// Retrieve blob data with type information by blob ID.
     ///
     /// # Arguments
@@ -135,6 +157,60 @@ pub trait StateStore: Send + Sync {
         .boxed()
     }
 
+    /// Make a blob readable in a namespace.
+    ///
+    /// Blobs are content-addressed and shared between namespaces, so storing a
+    /// blob does not say which namespaces may read it. Blobs stored by a run are
+    /// added to the run's namespace.
+    ///
+    /// # Arguments
+    /// * `namespace` - The namespace to add the blob to
+    /// * `blob_id` - The blob ID to add
+    fn add_blob_to_namespace(
+        &self,
+        namespace: &str,
+        blob_id: &BlobId,
+    ) -> BoxFuture<'_, error_stack::Result<(), StateError>>;
+
+    /// Check whether a blob is readable in a namespace.
+    ///
+    /// A blob is readable in the namespaces it was added to and, for workflows,
+    /// the namespaces the workflow was stored in.
+    ///
+    /// # Arguments
+    /// * `namespace` - The namespace to check
+    /// * `blob_id` - The blob ID to check
+    fn blob_in_namespace(
+        &self,
+        namespace: &str,
+        blob_id: &BlobId,
+    ) -> BoxFuture<'_, error_stack::Result<bool, StateError>>;
+
+    /// Make a blob stored by a run readable in the run's namespace.
+    ///
+    /// Does nothing if the run does not exist.
+    ///
+    /// # Arguments
+    /// * `run_id` - The run that stored the blob
+    /// * `blob_id` - The blob ID stored by the run
+    fn add_blob_to_run_namespace(
+        &self,
+        run_id: Uuid,
+        blob_id: &BlobId,
+    ) -> BoxFuture<'_, error_stack::Result<(), StateError>> {
+        let blob_id = blob_id.clone();
+        async move {
+            match self.get_run(run_id).await? {
+                Some(run) => {
+                    self.add_blob_to_namespace(&run.summary.namespace, &blob_id)
+                        .await
+                }
+                None => Ok(()),
+            }
+        }
+        .boxed()
+    }
+
     /// Retrieve the result of a step execution by step index.
     ///
     /// # Arguments
@@ -168,13 +244,18 @@ pub trait StateStore: Send + Sync {
 
     /// Store a workflow as a blob and return its blob ID.
     ///
+    /// Workflows are content-addressed and shared between namespaces, but a
+    /// workflow is only found by name within the namespaces it was stored in.
+    ///
     /// # Arguments
+    /// * `namespace` - The namespace the workflow is stored in
     /// * `workflow` - The workflow to store
     ///
     /// # Returns
     /// The blob ID of the stored workflow
     fn store_flow(
         &self,
+        namespace: &str,
         workflow: Arc<Flow>,
     ) -> BoxFuture<'_, error_stack::Result<BlobId, StateError>>;
 
@@ -190,9 +271,34 @@ pub trait StateStore: Send + Sync {
         flow_id: &BlobId,
     ) -> BoxFuture<'_, error_stack::Result<Option<Arc<Flow>>, StateError>>;
 
+    /// Retrieve a workflow by its blob ID if it is readable in a namespace.
+    ///
+    /// # Arguments
+    /// * `namespace` - The namespace the workflow must be readable in
+    /// * `flow_id` - The blob ID of the workflow
+    ///
+    /// # Returns
+    /// The workflow if found in the namespace, or `None` otherwise
+    fn get_namespaced_flow(
+        &self,
+        namespace: &str,
+        flow_id: &BlobId,
+    ) -> BoxFuture<'_, error_stack::Result<Option<Arc<Flow>>, StateError>> {
+        let namespace = namespace.to_string();
+        let flow_id = flow_id.clone();
+        async move {
+            if !self.blob_in_namespace(&namespace, &flow_id).await? {
+                return Ok(None);
+            }
+            self.get_flow(&flow_id).await
+        }
+        .boxed()
+    }
+
     /// Get all flows with a specific name, ordered by creation time (newest first).
     ///
     /// # Arguments
+    /// * `namespace` - The namespace to search
     /// * `name` - The flow name to search for
     ///
     /// # Returns
@@ -200,6 +306,7 @@ pub trait StateStore: Send + Sync {
     #[allow(clippy::type_complexity)]
     fn get_flows(
         &self,
+        namespace: &str,
         name: &str,
     ) -> BoxFuture<'_, error_stack::Result<Vec<(BlobId, chrono::DateTime<chrono::Utc>)>, StateError>>;
 
@@ -210,6 +317,7 @@ pub trait StateStore: Send + Sync {
     /// If label is Some, returns the workflow with that specific label.
     ///
     /// # Arguments
+    /// * `namespace` - The namespace to search
     /// * `name` - The workflow name
     /// * `label` - Optional label name. If None, returns latest workflow.
     ///
@@ -217,78 +325,133 @@ pub trait StateStore: Send + Sync {
     /// The workflow with metadata if found
     fn get_named_flow(
         &self,
+        namespace: &str,
         name: &str,
         label: Option<&str>,
     ) -> BoxFuture<'_, error_stack::Result<Option<WorkflowWithMetadata>, StateError>>;
 
+    /// Delete a workflow from a namespace and detach the namespace's labels pointing at it.
+    ///
+    /// Fails with [`StateError::FlowHasActiveRuns`] while any run of the flow in
+    /// the namespace, or any run nested within one, is running or paused.
+    /// Finished runs and their nested runs are deleted along with the flow if
+    /// `delete_runs` is true; otherwise their presence fails the deletion with
+    /// [`StateError::FlowHasRuns`]. The workflow blob itself is
+    /// removed once no namespace holds the workflow. Blobs produced by runs are
+    /// content-addressed and may be shared, so they are left in place.
+    ///
+    /// # Arguments
+    /// * `namespace` - The namespace to delete the workflow from
+    /// * `flow_id` - The blob ID of the workflow
+    /// * `delete_runs` - Whether to delete the finished runs of the workflow
+    ///
+    /// # Returns
+    /// Whether the workflow existed in the namespace
+    fn delete_flow(
+        &self,
+        namespace: &str,
+        flow_id: &BlobId,
+        delete_runs: bool,
+    ) -> BoxFuture<'_, error_stack::Result<bool, StateError>>;
+
     /// Create or update a workflow label.
     ///
+    /// The change is recorded in the label's history.
+    ///
     /// # Arguments
+    /// * `namespace` - The namespace of the label
     /// * `name` - The workflow name (from workflow.name field)
     /// * `label` - The label name (like "production", "staging")
     /// * `flow_id` - The blob ID of the workflow
+    /// * `actor` - Who made the change
+    /// * `comment` - Optional reason for the change
     ///
     /// # Returns
     /// Success if the label was created/updated
     fn create_or_update_label(
         &self,
+        namespace: &str,
         name: &str,
         label: &str,
         flow_id: BlobId,
+        actor: &str,
+        comment: Option<&str>,
     ) -> BoxFuture<'_, error_stack::Result<(), StateError>>;
 
+    /// List the recorded changes to a workflow label, newest first.
+    ///
+    /// # Arguments
+    /// * `namespace` - The namespace of the label
+    /// * `name` - The workflow name
+    /// * `label` - The label name
+    ///
+    /// # Returns
+    /// A vector of label changes, including deletions
+    fn list_label_history(
+        &self,
+        namespace: &str,
+        name: &str,
+        label: &str,
+    ) -> BoxFuture<'_, error_stack::Result<Vec<WorkflowLabelHistoryEntry>, StateError>>;
+
     /// List all labels for a specific workflow name.
     ///
     /// # Arguments
+    /// * `namespace` - The namespace of the labels
     /// * `name` - The workflow name
     ///
     /// # Returns
     /// A vector of workflow labels with metadata
     fn list_labels_for_name(
         &self,
+        namespace: &str,
         name: &str,
     ) -> BoxFuture<'_, error_stack::Result<Vec<WorkflowLabelMetadata>, StateError>>;
 
-    /// List all workflow names.
+    /// List all workflow names in a namespace.
+    ///
+    /// # Arguments
+    /// * `namespace` - The namespace to list
     ///
     /// # Returns
-    /// A vector of all unique workflow names in the system
-    fn list_flow_names(&self) -> BoxFuture<'_, error_stack::Result<Vec<String>, StateError>>;
+    /// A vector of all unique workflow names in the namespace
+    fn list_flow_names(
+        &self,
+        namespace: &str,
+    ) -> BoxFuture<'_, error_stack::Result<Vec<String>, StateError>>;
 
     /// Delete a workflow label.
     ///
+    /// Deleting an existing label is recorded in the label's history.
+    ///
     /// # Arguments
+    /// * `namespace` - The namespace of the label
     /// * `name` - The workflow name
     /// * `label` - The label name
+    /// * `actor` - Who made the change
+    /// * `comment` - Optional reason for the change
     ///
     /// # Returns
     /// Success if the label was deleted
     fn delete_label(
         &self,
+        namespace: &str,
         name: &str,
         label: &str,
+        actor: &str,
+        comment: Option<&str>,
     ) -> BoxFuture<'_, error_stack::Result<(), StateError>>;
 
     /// Create a new run record.
     ///
     /// # Arguments
-    /// * `run_id` - The unique identifier for the run
-    /// * `flow_id` - Workflow blob ID
-    /// * `workflow_name` - Optional workflow name (from workflow.name field)
-    /// * `workflow_label` - Optional workflow label used for execution
-    /// * `debug_mode` - Whether run is in debug mode
-    /// * `input` - Input data as JSON
+    /// * `params` - The identity, flow, input and provenance of the run
     ///
     /// # Returns
     /// Success if the run was created
     fn create_run(
         &self,
-        run_id: Uuid,
-        flow_id: BlobId,
-        workflow_name: Option<&str>,
-        workflow_label: Option<&str>,
-        debug_mode: bool,
-        input: ValueRef,
+        params: CreateRunParams,
     ) -> BoxFuture<'_, error_stack::Result<(), StateError>>;
 
     /// Update run status.
@@ -331,6 +494,239 @@ pub trait StateStore: Send + Sync {
         filters: &RunFilters,
     ) -> BoxFuture<'_, error_stack::Result<Vec<RunSummary>, StateError>>;
 
+    /// Delete a run along with its step results and step info.
+    ///
+    /// If `cascade` is true, runs nested within the run (such as those
+    /// submitted by `/eval` or `/map`) are deleted as well. Blobs produced by
+    /// the run are content-addressed and may be shared, so they are left in place.
+    ///
+    /// Fails with [`StateError::RunTreeActive`] if the run, or when cascading
+    /// any run nested within it, is running or paused.
+    ///
+    /// # Arguments
+    /// * `run_id` - The run identifier
+    /// * `cascade` - Whether to also delete nested runs
+    ///
+    /// # Returns
+    /// The IDs of the deleted runs, empty if the run did not exist
+    fn delete_run(
+        &self,
+        run_id: Uuid,
+        cascade: bool,
+    ) -> BoxFuture<'_, error_stack::Result<Vec<Uuid>, StateError>>;
+
+    /// Delete all finished runs matching the filters.
+    ///
+    /// Runs that are still running or paused are skipped, as are runs with
+    /// running or paused nested runs when cascading. The `limit` and `offset`
+    /// of the filters apply to the runs considered for deletion.
+    ///
+    /// # Arguments
+    /// * `filters` - Filters selecting the runs to delete
+    /// * `cascade` - Whether to also delete nested runs
+    ///
+    /// # Returns
+    /// The IDs of the deleted runs
+    fn delete_runs(
+        &self,
+        filters: &RunFilters,
+        cascade: bool,
+    ) -> BoxFuture<'_, error_stack::Result<Vec<Uuid>, StateError>> {
+        let filters = filters.clone();
+        async move {
+            let runs = self.list_runs(&filters).await?;
+            let mut deleted = Vec::new();
+            for run in runs {
+                if matches!(
+                    run.status,
+                    ExecutionStatus::Running | ExecutionStatus::Paused
+                ) || deleted.contains(&run.run_id)
+                {
+                    continue;
+                }
+                match self.delete_run(run.run_id, cascade).await {
+                    Ok(run_ids) => deleted.extend(run_ids),
+                    Err(e) if matches!(e.current_context(), StateError::RunTreeActive { .. }) => {}
+                    Err(e) => return Err(e),
+                }
+            }
+            Ok(deleted)
+        }
+        .boxed()
+    }
+
+    /// Get the callback to notify when a run finishes.
+    ///
+    /// # Arguments
+    /// * `run_id` - The run identifier
+    ///
+    /// # Returns
+    /// The callback given when the run was created, if any
+    fn get_run_callback(
+        &self,
+        run_id: Uuid,
+    ) -> BoxFuture<'_, error_stack::Result<Option<RunCallback>, StateError>>;
+
+    /// Record a webhook delivery, or update the status of a recorded one.
+    ///
+    /// # Arguments
+    /// * `delivery` - The delivery and its current status
+    ///
+    /// # Returns
+    /// Success if the delivery was recorded
+    fn put_webhook_delivery(
+        &self,
+        delivery: WebhookDelivery,
+    ) -> BoxFuture<'_, error_stack::Result<(), StateError>>;
+
+    /// List webhook deliveries, oldest first.
+    ///
+    /// # Arguments
+    /// * `run_id` - If set, only list deliveries for this run
+    /// * `status` - If set, only list deliveries with this status
+    ///
+    /// # Returns
+    /// The matching deliveries
+    fn list_webhook_deliveries(
+        &self,
+        run_id: Option<Uuid>,
+        status: Option<DeliveryStatus>,
+    ) -> BoxFuture<'_, error_stack::Result<Vec<WebhookDelivery>, StateError>>;
+
+    /// Record a pending delivery to the callback of a finished run.
+    ///
+    /// Called once the run's terminal status has been recorded. Does nothing if
+    /// the run has no callback, or a delivery was already recorded for it.
+    ///
+    /// # Arguments
+    /// * `run_id` - The finished run
+    /// * `result` - The run's result, or `None` to send the result recorded with the run
+    ///
+    /// # Returns
+    /// Whether a delivery was recorded
+    fn record_run_callback(
+        &self,
+        run_id: Uuid,
+        result: Option<FlowResult>,
+    ) -> BoxFuture<'_, error_stack::Result<bool, StateError>> {
+        async move {
+            let Some(callback) = self.get_run_callback(run_id).await? else {
+                return Ok(false);
+            };
+            if !self
+                .list_webhook_deliveries(Some(run_id), None)
+                .await?
+                .is_empty()
+            {
+                return Ok(false);
+            }
+            let Some(details) = self.get_run(run_id).await? else {
+                return Ok(false);
+            };
+
+            let payload = RunCallbackPayload {
+                summary: details.summary,
+                result: result.or(details.result),
+            };
+            let now = chrono::Utc::now();
+            let delivery = WebhookDelivery {
+                delivery_id: Uuid::new_v4(),
+                run_id,
+                callback,
+                payload: serde_json::to_value(payload).change_context(StateError::Serialization)?,
+                status: DeliveryStatus::Pending,
+                attempts: 0,
+                next_attempt_at: now,
+                last_error: None,
+                created_at: now,
+            };
+            self.put_webhook_delivery(delivery).await?;
+            Ok(true)
+        }
+        .boxed()
+    }
+
+    /// Record a batch of runs, or update the items of a recorded one.
+    ///
+    /// # Arguments
+    /// * `batch` - The batch and the current state of its items
+    ///
+    /// # Returns
+    /// Success if the batch was recorded
+    fn put_batch(&self, batch: BatchRecord) -> BoxFuture<'_, error_stack::Result<(), StateError>>;
+
+    /// Get a recorded batch of runs.
+    ///
+    /// # Arguments
+    /// * `batch_id` - The batch identifier
+    ///
+    /// # Returns
+    /// The batch if found
+    fn get_batch(
+        &self,
+        batch_id: Uuid,
+    ) -> BoxFuture<'_, error_stack::Result<Option<BatchRecord>, StateError>>;
+
+    /// Record a log message emitted by a component while executing a step.
+    ///
+    /// # Arguments
+    /// * `run_id` - The run the step belongs to
+    /// * `step_id` - The step that emitted the log message
+    /// * `log` - The log message
+    ///
+    /// # Returns
+    /// Success if the log message was recorded
+    fn record_step_log(
+        &self,
+        run_id: Uuid,
+        step_id: String,
+        log: StepLog,
+    ) -> BoxFuture<'_, error_stack::Result<(), StateError>>;
+
+    /// List the log messages recorded for a step, oldest first.
+    ///
+    /// # Arguments
+    /// * `run_id` - The run the step belongs to
+    /// * `step_id` - The step to list log messages for
+    ///
+    /// # Returns
+    /// The recorded log messages
+    fn get_step_logs(
+        &self,
+        run_id: Uuid,
+        step_id: &str,
+    ) -> BoxFuture<'_, error_stack::Result<Vec<StepLog>, StateError>>;
+
+    /// Record the latest progress reported while executing a step.
+    ///
+    /// Replaces any progress previously recorded for the step.
+    ///
+    /// # Arguments
+    /// * `run_id` - The run the step belongs to
+    /// * `step_id` - The step that reported progress
+    /// * `progress` - The reported progress
+    ///
+    /// # Returns
+    /// Success if the progress was recorded
+    fn update_step_progress(
+        &self,
+        run_id: Uuid,
+        step_id: String,
+        progress: StepProgress,
+    ) -> BoxFuture<'_, error_stack::Result<(), StateError>>;
+
+    /// Get the latest progress reported by each step of a run.
+    ///
+    /// # Arguments
+    /// * `run_id` - The run to get progress for
+    ///
+    /// # Returns
+    /// The latest progress, keyed by step ID, for steps that reported progress
+    fn get_step_progress(
+        &self,
+        run_id: Uuid,
+    ) -> BoxFuture<'_, error_stack::Result<HashMap<String, StepProgress>, StateError>>;
+
     /// Flush any pending write operations to persistent storage.
     ///
     /// This method ensures that all queued write operations are completed before returning.
@@ -469,6 +865,30 @@ pub struct WorkflowLabelMetadata {
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// Actor recorded for label changes made by the state store itself.
+pub const SYSTEM_ACTOR: &str = "system";
+
+/// Namespace of runs, flows and labels that were not given one.
+pub const DEFAULT_NAMESPACE: &str = "default";
+
+fn default_namespace() -> String {
+    DEFAULT_NAMESPACE.to_string()
+}
+
+/// A recorded change to a workflow label.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkflowLabelHistoryEntry {
+    pub name: String,
+    pub label: String,
+    /// The flow the label points at after the change, or `None` if it was deleted.
+    pub flow_id: Option<BlobId>,
+    /// The flow the label pointed at before the change, or `None` if it did not exist.
+    pub previous_flow_id: Option<BlobId>,
+    pub actor: String,
+    pub comment: Option<String>,
+    pub changed_at: chrono::DateTime<chrono::Utc>,
+}
+
 /// Summary information about a flow run.
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -477,12 +897,302 @@ pub struct RunSummary {
     pub flow_id: BlobId,
     pub flow_name: Option<String>,
     pub flow_label: Option<String>,
+    /// The namespace the run belongs to.
+    #[serde(default = "default_namespace")]
+    pub namespace: String,
     pub status: ExecutionStatus,
     pub debug_mode: bool,
+    /// The run that submitted this run as a nested flow, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_run_id: Option<Uuid>,
+    /// The step of the parent run that submitted this run, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_step_id: Option<String>,
+    /// The top-level run this run is nested within, or the run itself if it is not nested.
+    pub root_run_id: Uuid,
+    /// The batch the run was submitted in, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_id: Option<Uuid>,
+    /// Identity of the caller that created the run, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<String>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+/// Parameters for creating a run record.
+#[derive(Debug, Clone)]
+pub struct CreateRunParams {
+    /// The unique identifier for the run
+    pub run_id: Uuid,
+    /// Workflow blob ID
+    pub flow_id: BlobId,
+    /// Workflow name (from workflow.name field)
+    pub flow_name: Option<String>,
+    /// Workflow label used for execution
+    pub flow_label: Option<String>,
+    /// The namespace the run belongs to
+    pub namespace: String,
+    /// Whether the run is in debug mode
+    pub debug_mode: bool,
+    /// Input data for the run
+    pub input: ValueRef,
+    /// The run that submitted this one as a nested flow, if any
+    pub parent_run_id: Option<Uuid>,
+    /// The step of the parent run that submitted this one, if known
+    pub parent_step_id: Option<String>,
+    /// The top-level run this one is nested within, or `run_id` if it is not nested
+    pub root_run_id: Uuid,
+    /// The batch the run was submitted in, if any
+    pub batch_id: Option<Uuid>,
+    /// Identity of the caller that created the run, if known
+    pub created_by: Option<String>,
+    /// Callback to notify when the run finishes, if any
+    pub callback: Option<RunCallback>,
+}
+
+impl CreateRunParams {
+    /// Parameters for a top-level, non-debug run in the default namespace with
+    /// no label or creator.
+    pub fn new(run_id: Uuid, flow_id: BlobId, input: ValueRef) -> Self {
+        Self {
+            run_id,
+            flow_id,
+            flow_name: None,
+            flow_label: None,
+            namespace: DEFAULT_NAMESPACE.to_string(),
+            debug_mode: false,
+            input,
+            parent_run_id: None,
+            parent_step_id: None,
+            root_run_id: run_id,
+            batch_id: None,
+            created_by: None,
+            callback: None,
+        }
+    }
+}
+
+/// A URL notified with a run's summary and result when the run finishes.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RunCallback {
+    /// The URL the run's summary and result are POSTed to.
+    pub url: String,
+    /// Headers added to the callback request.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    /// If set, the request body is signed with HMAC-SHA256 using this secret.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+/// Status of a webhook delivery.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, utoipa::ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum DeliveryStatus {
+    /// Not yet delivered; will be attempted at `next_attempt_at`.
+    Pending,
+    /// Accepted by the receiver.
+    Delivered,
+    /// Given up on after the maximum number of attempts.
+    Failed,
+}
+
+impl DeliveryStatus {
+    /// The name of the status as stored.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeliveryStatus::Pending => "pending",
+            DeliveryStatus::Delivered => "delivered",
+            DeliveryStatus::Failed => "failed",
+        }
+    }
+}
+
+impl std::str::FromStr for DeliveryStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(DeliveryStatus::Pending),
+            "delivered" => Ok(DeliveryStatus::Delivered),
+            "failed" => Ok(DeliveryStatus::Failed),
+            _ => Err(format!("unknown delivery status '{s}'")),
+        }
+    }
+}
+
+/// The body POSTed to a run's callback.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RunCallbackPayload {
+    #[serde(flatten)]
+    pub summary: RunSummary,
+    /// The result of the run, if it produced one.
+    pub result: Option<FlowResult>,
+}
+
+/// A notification of a finished run sent to its callback.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebhookDelivery {
+    pub delivery_id: Uuid,
+    pub run_id: Uuid,
+    pub callback: RunCallback,
+    /// The request body.
+    pub payload: serde_json::Value,
+    pub status: DeliveryStatus,
+    /// Number of failed or successful attempts so far.
+    pub attempts: u32,
+    pub next_attempt_at: chrono::DateTime<chrono::Utc>,
+    /// The error of the latest failed attempt, if any.
+    pub last_error: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A batch of runs of one flow, submitted together.
+///
+/// The runs record their own status. The batch records the run ID assigned to
+/// each input, in input order, and why a run could not be started.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchRecord {
+    pub batch_id: Uuid,
+    pub flow_id: BlobId,
+    /// The namespace the runs belong to.
+    pub namespace: String,
+    /// Identity of the caller that submitted the batch, if known.
+    pub created_by: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub items: Vec<BatchItem>,
+}
+
+/// The run of one input of a batch.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchItem {
+    /// The ID of the run. No run is recorded under it if it failed to start.
+    pub run_id: Uuid,
+    /// Why the run could not be started, if it failed to start.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Severity of a log message.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+    utoipa::ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    /// The name of the level as stored.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogLevel::Trace => "trace",
+            LogLevel::Debug => "debug",
+            LogLevel::Info => "info",
+            LogLevel::Warn => "warn",
+            LogLevel::Error => "error",
+        }
+    }
+}
+
+impl std::fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl std::str::FromStr for LogLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "trace" => Ok(LogLevel::Trace),
+            "debug" => Ok(LogLevel::Debug),
+            "info" => Ok(LogLevel::Info),
+            "warn" => Ok(LogLevel::Warn),
+            "error" => Ok(LogLevel::Error),
+            _ => Err(format!("unknown log level '{s}'")),
+        }
+    }
+}
+
+/// A log message emitted by a component while executing a step.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StepLog {
+    pub level: LogLevel,
+    pub message: String,
+    /// Structured fields attached to the message.
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    #[schema(value_type = Object)]
+    pub fields: serde_json::Map<String, serde_json::Value>,
+    /// When the runtime received the message.
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+/// Progress reported by a component while executing a step.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StepProgress {
+    /// Fraction of the work completed, from 0 to 1, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fraction: Option<f64>,
+    /// Description of the work being done.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Number of items completed, if the work is counted in items.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed: Option<u64>,
+    /// Total number of items, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+    /// When the progress was reported.
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl StepProgress {
+    /// Progress through a number of items, of which `completed` are done.
+    ///
+    /// The fraction is computed if the `total` is known.
+    pub fn items(completed: u64, total: Option<u64>) -> Self {
+        let fraction = total
+            .filter(|total| *total > 0)
+            .map(|total| (completed as f64 / total as f64).min(1.0));
+        Self {
+            fraction,
+            message: None,
+            completed: Some(completed),
+            total,
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    /// Set the message describing the work being done.
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+}
+
 /// Detailed flow run information including input and result.
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -497,8 +1207,13 @@ pub struct RunDetails {
 #[derive(Debug, Clone, Default)]
 pub struct RunFilters {
     pub status: Option<ExecutionStatus>,
+    pub flow_id: Option<BlobId>,
     pub flow_name: Option<String>,
     pub flow_label: Option<String>,
+    pub namespace: Option<String>,
+    pub batch_id: Option<Uuid>,
+    /// Only runs within the run tree rooted at this run, including the root itself.
+    pub root_run_id: Option<Uuid>,
     pub limit: Option<usize>,
     pub offset: Option<usize>,
 }
@@ -546,6 +1261,8 @@ pub struct StepInfo {
     pub created_at: chrono::DateTime<chrono::Utc>,
     /// When the step was last updated
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    /// The latest progress reported by the step, if any
+    pub progress: Option<StepProgress>,
 }
 
 #[cfg(test)]
@@ -567,8 +1284,14 @@ mod tests {
                 flow_id: flow_id.clone(),
                 flow_name: Some("test-workflow".to_string()),
                 flow_label: Some("production".to_string()),
+                namespace: DEFAULT_NAMESPACE.to_string(),
                 status: ExecutionStatus::Completed,
                 debug_mode: false,
+                parent_run_id: None,
+                parent_step_id: None,
+                root_run_id: run_id,
+                batch_id: None,
+                created_by: None,
                 created_at: now,
                 completed_at: Some(now),
             },